@@ -21,6 +21,7 @@ use scale_codec::{Decode, Encode};
 use sp_core::{H160, H256};
 use sp_runtime::traits::Block as BlockT;
 // Frontier
+use fp_evm::CallFrame;
 use fp_storage::EthereumStorageSchema;
 
 #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
@@ -48,6 +49,9 @@ pub trait Backend<Block: BlockT>: Send + Sync {
 	/// Returns reference to log indexer backend.
 	fn log_indexer(&self) -> &dyn LogIndexerBackend<Block>;
 
+	/// Returns reference to trace indexer backend.
+	fn trace_indexer(&self) -> &dyn TraceIndexerBackend<Block>;
+
 	/// Indicate whether the log indexing feature is supported.
 	fn is_indexed(&self) -> bool {
 		self.log_indexer().is_indexed()
@@ -82,3 +86,66 @@ pub trait LogIndexerBackend<Block: BlockT>: Send + Sync {
 		topics: Vec<Vec<Option<H256>>>,
 	) -> Result<Vec<FilteredLog<Block>>, String>;
 }
+
+/// A call of a transaction, flattened out of its call tree.
+#[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
+pub struct FlatCallFrame {
+	/// Indexes of the call and its ancestors among their siblings, empty for the top level call.
+	pub trace_address: Vec<u32>,
+	/// Number of direct sub calls.
+	pub subtraces: u32,
+	/// The call, without its sub calls.
+	pub call: CallFrame,
+}
+
+impl FlatCallFrame {
+	/// Flatten a call tree, parents coming before their sub calls.
+	pub fn flatten(frame: CallFrame) -> Vec<Self> {
+		fn visit(mut frame: CallFrame, trace_address: Vec<u32>, out: &mut Vec<FlatCallFrame>) {
+			let calls = core::mem::take(&mut frame.calls);
+			out.push(FlatCallFrame {
+				trace_address: trace_address.clone(),
+				subtraces: calls.len() as u32,
+				call: frame,
+			});
+			for (index, call) in calls.into_iter().enumerate() {
+				let mut trace_address = trace_address.clone();
+				trace_address.push(index as u32);
+				visit(call, trace_address, out);
+			}
+		}
+
+		let mut out = Vec::new();
+		visit(frame, Vec::new(), &mut out);
+		out
+	}
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct FilteredTrace<Block: BlockT> {
+	pub substrate_block_hash: Block::Hash,
+	pub ethereum_block_hash: H256,
+	pub block_number: u32,
+	pub transaction_hash: H256,
+	pub transaction_index: u32,
+	pub trace: FlatCallFrame,
+}
+
+/// The trace indexer backend interface.
+#[async_trait::async_trait]
+pub trait TraceIndexerBackend<Block: BlockT>: Send + Sync {
+	/// Indicate whether the trace indexing feature is supported.
+	fn is_indexed(&self) -> bool;
+
+	/// Filter the calls by the parameters. An empty address list matches any address, the
+	/// first `after` matching calls are skipped and at most `count` calls are returned.
+	async fn filter_traces(
+		&self,
+		from_block: u64,
+		to_block: u64,
+		from_addresses: Vec<H160>,
+		to_addresses: Vec<H160>,
+		after: u32,
+		count: u32,
+	) -> Result<Vec<FilteredTrace<Block>>, String>;
+}
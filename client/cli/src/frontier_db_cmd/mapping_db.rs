@@ -21,7 +21,7 @@ use std::sync::Arc;
 use ethereum_types::H256;
 use serde::Deserialize;
 // Substrate
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::Block as BlockT;
 // Frontier
@@ -83,19 +83,8 @@ where
 						.block_hash(ethereum_block_hash)?
 						.is_none()
 					{
-						let existing_transaction_hashes: Vec<H256> = if let Some(statuses) = self
-							.client
-							.runtime_api()
-							.current_transaction_statuses(*substrate_block_hash)
-							.map_err(|e| format!("{:?}", e))?
-						{
-							statuses
-								.iter()
-								.map(|t| t.transaction_hash)
-								.collect::<Vec<H256>>()
-						} else {
-							vec![]
-						};
+						let existing_transaction_hashes =
+							self.transaction_hashes(*substrate_block_hash)?;
 
 						let commitment = fc_db::kv::MappingCommitment::<B> {
 							block_hash: *substrate_block_hash,
@@ -141,19 +130,8 @@ where
 						.block_hash(ethereum_block_hash)?
 						.is_some()
 					{
-						let existing_transaction_hashes: Vec<H256> = if let Some(statuses) = self
-							.client
-							.runtime_api()
-							.current_transaction_statuses(*substrate_block_hash)
-							.map_err(|e| format!("{:?}", e))?
-						{
-							statuses
-								.iter()
-								.map(|t| t.transaction_hash)
-								.collect::<Vec<H256>>()
-						} else {
-							vec![]
-						};
+						let existing_transaction_hashes =
+							self.transaction_hashes(*substrate_block_hash)?;
 
 						let commitment = fc_db::kv::MappingCommitment::<B> {
 							block_hash: *substrate_block_hash,
@@ -174,6 +152,33 @@ where
 		}
 		Ok(())
 	}
+
+	fn transaction_hashes(&self, substrate_block_hash: B::Hash) -> sc_cli::Result<Vec<H256>> {
+		let api = self.client.runtime_api();
+		let api_version = api
+			.api_version::<dyn EthereumRuntimeRPCApi<B>>(substrate_block_hash)
+			.map_err(|e| format!("{:?}", e))?
+			.ok_or("Runtime api version call failed".to_string())?;
+		let transaction_hashes = if api_version < 9 {
+			#[allow(deprecated)]
+			let statuses = api
+				.current_transaction_statuses_before_version_9(substrate_block_hash)
+				.map_err(|e| format!("{:?}", e))?;
+			statuses
+				.unwrap_or_default()
+				.iter()
+				.map(|t| t.transaction_hash)
+				.collect()
+		} else {
+			api.current_transaction_statuses(substrate_block_hash)
+				.map_err(|e| format!("{:?}", e))?
+				.unwrap_or_default()
+				.iter()
+				.map(|t| t.transaction_hash)
+				.collect()
+		};
+		Ok(transaction_hashes)
+	}
 }
 
 impl<'a, B: BlockT, C: HeaderBackend<B>> FrontierDbMessage for MappingDb<'a, B, C> {}
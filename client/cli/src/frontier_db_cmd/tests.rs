@@ -540,7 +540,7 @@ fn commitment_create() {
 	let client = Arc::new(c);
 
 	// Get some transaction status.
-	let t1 = fp_rpc::TransactionStatus::default();
+	let t1 = fp_rpc::TransactionStatusV2::default();
 	let t1_hash = t1.transaction_hash;
 	let statuses = vec![t1];
 
@@ -615,8 +615,8 @@ fn commitment_update() {
 	let client = Arc::new(c);
 
 	// Get some transaction status.
-	let t1 = fp_rpc::TransactionStatus::default();
-	let t2 = fp_rpc::TransactionStatus {
+	let t1 = fp_rpc::TransactionStatusV2::default();
+	let t2 = fp_rpc::TransactionStatusV2 {
 		transaction_hash: H256::from_str(
 			"0x2200000000000000000000000000000000000000000000000000000000000000",
 		)
@@ -751,7 +751,7 @@ fn mapping_read_works() {
 	let client = Arc::new(c);
 
 	// Get some transaction status.
-	let t1 = fp_rpc::TransactionStatus::default();
+	let t1 = fp_rpc::TransactionStatusV2::default();
 	let t1_hash = t1.transaction_hash;
 	let statuses = vec![t1];
 
@@ -35,7 +35,7 @@ use sp_core::{H160, H256};
 pub use sp_database::Database;
 use sp_runtime::traits::Block as BlockT;
 // Frontier
-use fc_api::{FilteredLog, TransactionMetadata};
+use fc_api::{FilteredLog, FilteredTrace, TransactionMetadata};
 use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA_CACHE};
 
 const DB_HASH_LEN: usize = 32;
@@ -67,6 +67,7 @@ pub struct Backend<Block, C> {
 	meta: Arc<MetaDb<Block>>,
 	mapping: Arc<MappingDb<Block>>,
 	log_indexer: LogIndexerBackend<Block>,
+	trace_indexer: TraceIndexerBackend<Block>,
 }
 
 #[async_trait::async_trait]
@@ -90,6 +91,10 @@ impl<Block: BlockT, C: HeaderBackend<Block>> fc_api::Backend<Block> for Backend<
 		&self.log_indexer
 	}
 
+	fn trace_indexer(&self) -> &dyn fc_api::TraceIndexerBackend<Block> {
+		&self.trace_indexer
+	}
+
 	async fn latest_block_hash(&self) -> Result<Block::Hash, String> {
 		Ok(self.client.info().best_hash)
 	}
@@ -115,6 +120,28 @@ impl<Block: BlockT> fc_api::LogIndexerBackend<Block> for LogIndexerBackend<Block
 	}
 }
 
+#[derive(Clone, Default)]
+pub struct TraceIndexerBackend<Block>(PhantomData<Block>);
+
+#[async_trait::async_trait]
+impl<Block: BlockT> fc_api::TraceIndexerBackend<Block> for TraceIndexerBackend<Block> {
+	fn is_indexed(&self) -> bool {
+		false
+	}
+
+	async fn filter_traces(
+		&self,
+		_from_block: u64,
+		_to_block: u64,
+		_from_addresses: Vec<H160>,
+		_to_addresses: Vec<H160>,
+		_after: u32,
+		_count: u32,
+	) -> Result<Vec<FilteredTrace<Block>>, String> {
+		Err("KeyValue db does not index traces".into())
+	}
+}
+
 /// Returns the frontier database directory.
 pub fn frontier_database_dir(db_config_dir: &Path, db_path: &str) -> PathBuf {
 	db_config_dir.join("frontier").join(db_path)
@@ -168,6 +195,7 @@ impl<Block: BlockT, C: HeaderBackend<Block>> Backend<Block, C> {
 				_marker: PhantomData,
 			}),
 			log_indexer: LogIndexerBackend(PhantomData),
+			trace_indexer: TraceIndexerBackend(PhantomData),
 		})
 	}
 
@@ -28,7 +28,10 @@ use sqlx::{
 	ConnectOptions, Error, Execute, QueryBuilder, Row, Sqlite,
 };
 // Substrate
-use sc_client_api::backend::{Backend as BackendT, StorageProvider};
+use sc_client_api::{
+	backend::{Backend as BackendT, StorageProvider},
+	BlockBackend,
+};
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::{H160, H256};
@@ -37,10 +40,11 @@ use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto, Zero},
 };
 // Frontier
-use fc_api::{FilteredLog, TransactionMetadata};
+use fc_api::{FilteredLog, FilteredTrace, FlatCallFrame, TransactionMetadata};
 use fc_storage::{StorageOverride, StorageQuerier};
 use fp_consensus::{FindLogError, Hashes, Log as ConsensusLog, PostLog, PreLog};
-use fp_rpc::EthereumRuntimeRPCApi;
+use fp_evm::{CallTracerConfig, TracerConfig, TransactionTrace};
+use fp_rpc::{DebugRuntimeApi, EthereumRuntimeRPCApi};
 use fp_storage::EthereumStorageSchema;
 
 /// Maximum number to topics allowed to be filtered upon
@@ -59,6 +63,19 @@ pub struct Log {
 	pub substrate_block_hash: Vec<u8>,
 }
 
+/// Represents a call of a transaction.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Trace {
+	pub from_address: Vec<u8>,
+	pub to_address: Vec<u8>,
+	pub transaction_hash: Vec<u8>,
+	pub transaction_index: i32,
+	pub trace_index: i32,
+	/// SCALE encoded `FlatCallFrame`.
+	pub data: Vec<u8>,
+	pub substrate_block_hash: Vec<u8>,
+}
+
 /// Represents the block metadata.
 #[derive(Eq, PartialEq)]
 struct BlockMetadata {
@@ -77,6 +94,8 @@ pub struct SqliteBackendConfig<'a> {
 	pub create_if_missing: bool,
 	pub thread_count: u32,
 	pub cache_size: u64,
+	/// Index the calls of every transaction, which requires replaying the blocks.
+	pub index_traces: bool,
 }
 
 /// Represents the indexed status of a block and if it's canon or not.
@@ -102,6 +121,9 @@ pub struct Backend<Block> {
 	/// The number of allowed operations for the Sqlite filter call.
 	/// A value of `0` disables the timeout.
 	num_ops_timeout: i32,
+
+	/// Whether the calls of the transactions are indexed.
+	index_traces: bool,
 }
 
 impl<Block> Backend<Block>
@@ -115,6 +137,9 @@ where
 		num_ops_timeout: Option<NonZeroU32>,
 		storage_override: Arc<dyn StorageOverride<Block>>,
 	) -> Result<Self, Error> {
+		let index_traces = match &config {
+			BackendConfig::Sqlite(config) => config.index_traces,
+		};
 		let any_pool = SqlitePoolOptions::new()
 			.max_connections(pool_size)
 			.connect_lazy_with(Self::connect_options(&config)?.disable_statement_logging());
@@ -128,6 +153,7 @@ where
 				.unwrap_or(0)
 				.try_into()
 				.unwrap_or(i32::MAX),
+			index_traces,
 		})
 	}
 
@@ -232,7 +258,7 @@ where
 
 				let schema = StorageQuerier::new(client)
 					.storage_schema(substrate_genesis_hash)
-					.unwrap_or(EthereumStorageSchema::V3)
+					.unwrap_or(EthereumStorageSchema::V4)
 					.encode();
 				let ethereum_block_hash = ethereum_block.header.hash().as_bytes().to_owned();
 				let substrate_block_hash = substrate_genesis_hash.as_bytes();
@@ -278,7 +304,7 @@ where
 				Ok(log) => {
 					let schema = StorageQuerier::new(client.clone())
 						.storage_schema(hash)
-						.unwrap_or(EthereumStorageSchema::V3);
+						.unwrap_or(EthereumStorageSchema::V4);
 					let log_hashes = match log {
 						ConsensusLog::Post(PostLog::Hashes(post_hashes)) => post_hashes,
 						ConsensusLog::Post(PostLog::Block(block)) => Hashes::from_block(block),
@@ -517,9 +543,10 @@ where
 		transaction_count += receipts.len();
 		for (transaction_index, receipt) in receipts.iter().enumerate() {
 			let receipt_logs = match receipt {
-				ethereum::ReceiptV3::Legacy(d)
-				| ethereum::ReceiptV3::EIP2930(d)
-				| ethereum::ReceiptV3::EIP1559(d) => &d.logs,
+				ethereum::ReceiptV4::Legacy(d)
+				| ethereum::ReceiptV4::EIP2930(d)
+				| ethereum::ReceiptV4::EIP1559(d)
+				| ethereum::ReceiptV4::EIP7702(d) => &d.logs,
 			};
 			let transaction_index = transaction_index as i32;
 			log_count += receipt_logs.len();
@@ -544,6 +571,172 @@ where
 		logs
 	}
 
+	/// Index the calls of the transactions of a block, replaying it through the
+	/// `DebugRuntimeApi`. Does nothing unless trace indexing is enabled.
+	pub async fn index_block_traces<Client>(&self, client: Arc<Client>, block_hash: Block::Hash)
+	where
+		Client: ProvideRuntimeApi<Block> + BlockBackend<Block> + Send + Sync + 'static,
+		Client::Api: DebugRuntimeApi<Block>,
+	{
+		if !self.index_traces {
+			return;
+		}
+		let pool = self.pool().clone();
+		let storage_override = self.storage_override.clone();
+		let _ = async {
+			let is_traced =
+				sqlx::query("SELECT 1 FROM trace_sync_status WHERE substrate_block_hash = ?")
+					.bind(block_hash.as_bytes())
+					.fetch_optional(&pool)
+					.await?
+					.is_some();
+			if is_traced {
+				return Ok(());
+			}
+
+			// Spawn a blocking task to replay the block.
+			let traces = tokio::task::spawn_blocking(move || {
+				Self::get_traces(client, storage_override, block_hash)
+			})
+			.await
+			.map_err(|_| Error::Protocol("tokio blocking task failed".to_string()))?;
+			Self::insert_block_traces(&pool, block_hash, traces).await
+		}
+		.await
+		.map_err(|e: Error| {
+			log::error!(target: "frontier-sql", "[Traces] {e}");
+		});
+	}
+
+	/// Store the traces of a block and mark it as traced. A block that could not be replayed, for
+	/// instance by a runtime built without tracing, is marked as failed so it isn't picked again.
+	async fn insert_block_traces(
+		pool: &SqlitePool,
+		block_hash: H256,
+		traces: Result<Vec<Trace>, Error>,
+	) -> Result<(), Error> {
+		let (traces, status) = match traces {
+			Ok(traces) => (traces, 1i32),
+			Err(e) => {
+				log::warn!(target: "frontier-sql", "[Traces] Skipping block {block_hash:?}: {e}");
+				(vec![], 0i32)
+			}
+		};
+
+		let mut tx = pool.begin().await?;
+		for trace in traces {
+			let _ = sqlx::query(
+				"INSERT OR IGNORE INTO traces(
+					from_address,
+					to_address,
+					transaction_hash,
+					transaction_index,
+					trace_index,
+					data,
+					substrate_block_hash)
+				VALUES (?, ?, ?, ?, ?, ?, ?)",
+			)
+			.bind(trace.from_address)
+			.bind(trace.to_address)
+			.bind(trace.transaction_hash)
+			.bind(trace.transaction_index)
+			.bind(trace.trace_index)
+			.bind(trace.data)
+			.bind(trace.substrate_block_hash)
+			.execute(&mut *tx)
+			.await?;
+		}
+		sqlx::query(
+			"INSERT OR IGNORE INTO trace_sync_status(substrate_block_hash, status) VALUES (?, ?)",
+		)
+		.bind(block_hash.as_bytes())
+		.bind(status)
+		.execute(&mut *tx)
+		.await?;
+		tx.commit().await
+	}
+
+	fn get_traces<Client>(
+		client: Arc<Client>,
+		storage_override: Arc<dyn StorageOverride<Block>>,
+		substrate_block_hash: H256,
+	) -> Result<Vec<Trace>, Error>
+	where
+		Client: ProvideRuntimeApi<Block> + BlockBackend<Block>,
+		Client::Api: DebugRuntimeApi<Block>,
+	{
+		let transaction_hashes = storage_override
+			.current_block(substrate_block_hash)
+			.map(|block| {
+				block
+					.transactions
+					.iter()
+					.map(|transaction| transaction.hash())
+					.collect::<Vec<_>>()
+			})
+			.unwrap_or_default();
+		if transaction_hashes.is_empty() {
+			return Ok(vec![]);
+		}
+
+		let (header, extrinsics) = client
+			.block(substrate_block_hash)
+			.map_err(|e| Error::Protocol(format!("[Traces] {e:?}")))?
+			.ok_or_else(|| {
+				Error::Protocol(format!(
+					"[Traces] Missing block body for hash {substrate_block_hash:?}"
+				))
+			})?
+			.block
+			.deconstruct();
+		let parent_hash = *header.parent_hash();
+		let api = client.runtime_api();
+		let has_api = api
+			.has_api::<dyn DebugRuntimeApi<Block>>(parent_hash)
+			.map_err(|e| Error::Protocol(format!("[Traces] {e}")))?;
+		if !has_api {
+			log::debug!(target: "frontier-sql", "[Traces] Block {substrate_block_hash:?} cannot be replayed");
+			return Ok(vec![]);
+		}
+		let transaction_traces = api
+			.trace_block(
+				parent_hash,
+				&header,
+				extrinsics,
+				TracerConfig::CallTracer(CallTracerConfig::default()),
+			)
+			.map_err(|e| Error::Protocol(format!("[Traces] {e}")))?
+			.map_err(|e| Error::Protocol(format!("[Traces] Replay failed: {e:?}")))?;
+
+		let mut traces = vec![];
+		for (transaction_index, (transaction_hash, transaction_trace)) in transaction_hashes
+			.into_iter()
+			.zip(transaction_traces)
+			.enumerate()
+		{
+			let TransactionTrace::CallTracer(frame) = transaction_trace else {
+				continue;
+			};
+			for (trace_index, trace) in FlatCallFrame::flatten(frame).into_iter().enumerate() {
+				traces.push(Trace {
+					from_address: trace.call.from.as_bytes().to_owned(),
+					to_address: trace.call.to.as_bytes().to_owned(),
+					transaction_hash: transaction_hash.as_bytes().to_owned(),
+					transaction_index: transaction_index as i32,
+					trace_index: trace_index as i32,
+					data: trace.encode(),
+					substrate_block_hash: substrate_block_hash.as_bytes().to_owned(),
+				});
+			}
+		}
+		log::debug!(
+			target: "frontier-sql",
+			"Ready to commit {} traces of block {substrate_block_hash:?}",
+			traces.len(),
+		);
+		Ok(traces)
+	}
+
 	/// Retrieves the status if a block has been already indexed.
 	pub async fn is_block_indexed(&self, block_hash: Block::Hash) -> bool {
 		sqlx::query("SELECT substrate_block_hash FROM sync_status WHERE substrate_block_hash = ?")
@@ -651,6 +844,40 @@ where
 		None
 	}
 
+	/// Retrieves the first canonical block hash in decreasing order that had its logs indexed
+	/// but not its traces. Always `None` when trace indexing is disabled.
+	pub async fn get_first_untraced_canon_block(&self) -> Option<H256> {
+		if !self.index_traces {
+			return None;
+		}
+		match sqlx::query(
+			"SELECT s.substrate_block_hash FROM sync_status AS s
+			INNER JOIN blocks as b
+			ON s.substrate_block_hash = b.substrate_block_hash
+			WHERE b.is_canon = 1 AND s.status = 1 AND NOT EXISTS (
+				SELECT 1 FROM trace_sync_status AS t
+				WHERE t.substrate_block_hash = s.substrate_block_hash
+			)
+			ORDER BY b.block_number LIMIT 1",
+		)
+		.fetch_optional(self.pool())
+		.await
+		{
+			Ok(result) => {
+				if let Some(row) = result {
+					let block_hash_bytes: Vec<u8> = row.get(0);
+					let block_hash = H256::from_slice(&block_hash_bytes[..]);
+					return Some(block_hash);
+				}
+			}
+			Err(err) => {
+				log::debug!(target: "frontier-sql", "Failed retrieving untraced block {err:?}");
+			}
+		}
+
+		None
+	}
+
 	/// Retrieve the block hash for the last indexed canon block.
 	pub async fn last_indexed_canon_block(&self) -> Result<H256, Error> {
 		let row = sqlx::query(
@@ -718,6 +945,30 @@ where
 					substrate_block_hash
 				)
 			);
+			CREATE TABLE IF NOT EXISTS traces (
+				id INTEGER PRIMARY KEY,
+				from_address BLOB NOT NULL,
+				to_address BLOB NOT NULL,
+				transaction_hash BLOB NOT NULL,
+				transaction_index INTEGER NOT NULL,
+				trace_index INTEGER NOT NULL,
+				data BLOB NOT NULL,
+				substrate_block_hash BLOB NOT NULL,
+				UNIQUE (
+					trace_index,
+					transaction_index,
+					substrate_block_hash
+				)
+			);
+			CREATE TABLE IF NOT EXISTS trace_sync_status (
+				id INTEGER PRIMARY KEY,
+				substrate_block_hash BLOB NOT NULL,
+				-- 1 when traced, 0 when the replay failed.
+				status INTEGER DEFAULT 1 NOT NULL,
+				UNIQUE (
+					substrate_block_hash
+				)
+			);
 			COMMIT;",
 		)
 		.execute(pool)
@@ -754,6 +1005,15 @@ where
 				ethereum_block_hash,
 				ethereum_transaction_index
 			);
+			CREATE INDEX IF NOT EXISTS traces_from_idx ON traces (
+				from_address
+			);
+			CREATE INDEX IF NOT EXISTS traces_to_idx ON traces (
+				to_address
+			);
+			CREATE INDEX IF NOT EXISTS traces_substrate_index ON traces (
+				substrate_block_hash
+			);
 			COMMIT;",
 		)
 		.execute(pool)
@@ -820,6 +1080,10 @@ impl<Block: BlockT<Hash = H256>> fc_api::Backend<Block> for Backend<Block> {
 		self
 	}
 
+	fn trace_indexer(&self) -> &dyn fc_api::TraceIndexerBackend<Block> {
+		self
+	}
+
 	async fn latest_block_hash(&self) -> Result<Block::Hash, String> {
 		// Retrieves the block hash for the latest indexed block, maybe it's not canon.
 		sqlx::query("SELECT substrate_block_hash FROM blocks ORDER BY block_number DESC LIMIT 1")
@@ -933,6 +1197,161 @@ impl<Block: BlockT<Hash = H256>> fc_api::LogIndexerBackend<Block> for Backend<Bl
 	}
 }
 
+#[async_trait::async_trait]
+impl<Block: BlockT<Hash = H256>> fc_api::TraceIndexerBackend<Block> for Backend<Block> {
+	fn is_indexed(&self) -> bool {
+		self.index_traces
+	}
+
+	async fn filter_traces(
+		&self,
+		from_block: u64,
+		to_block: u64,
+		from_addresses: Vec<H160>,
+		to_addresses: Vec<H160>,
+		after: u32,
+		count: u32,
+	) -> Result<Vec<FilteredTrace<Block>>, String> {
+		if !self.index_traces {
+			return Err("Trace indexing is disabled".to_string());
+		}
+
+		let trace_key =
+			format!("{from_block}-{to_block}-{from_addresses:?}-{to_addresses:?}-{after}-{count}");
+		let mut qb = QueryBuilder::new("");
+		let query = build_trace_query(
+			&mut qb,
+			from_block,
+			to_block,
+			from_addresses,
+			to_addresses,
+			after,
+			count,
+		);
+		let sql = query.sql();
+
+		let mut conn = self
+			.pool()
+			.acquire()
+			.await
+			.map_err(|err| format!("failed acquiring sqlite connection: {}", err))?;
+		let trace_key2 = trace_key.clone();
+		conn.lock_handle()
+			.await
+			.map_err(|err| format!("{:?}", err))?
+			.set_progress_handler(self.num_ops_timeout, move || {
+				log::debug!(target: "frontier-sql", "Sqlite progress_handler triggered for {trace_key2}");
+				false
+			});
+		log::debug!(target: "frontier-sql", "Query: {sql:?} - {trace_key}");
+
+		let mut out: Vec<FilteredTrace<Block>> = vec![];
+		let mut rows = query.fetch(&mut *conn);
+		let maybe_err = loop {
+			match rows.try_next().await {
+				Ok(Some(row)) => {
+					// Substrate block hash
+					let substrate_block_hash =
+						H256::from_slice(&row.try_get::<Vec<u8>, _>(0).unwrap_or_default()[..]);
+					// Ethereum block hash
+					let ethereum_block_hash =
+						H256::from_slice(&row.try_get::<Vec<u8>, _>(1).unwrap_or_default()[..]);
+					// Block number
+					let block_number = row.try_get::<i32, _>(2).unwrap_or_default() as u32;
+					// Transaction hash
+					let transaction_hash =
+						H256::from_slice(&row.try_get::<Vec<u8>, _>(3).unwrap_or_default()[..]);
+					// Transaction index
+					let transaction_index = row.try_get::<i32, _>(4).unwrap_or_default() as u32;
+					// Flattened call
+					let trace: FlatCallFrame =
+						Decode::decode(&mut &row.try_get::<Vec<u8>, _>(5).unwrap_or_default()[..])
+							.map_err(|_| "Cannot decode trace".to_string())?;
+					out.push(FilteredTrace {
+						substrate_block_hash,
+						ethereum_block_hash,
+						block_number,
+						transaction_hash,
+						transaction_index,
+						trace,
+					});
+				}
+				Ok(None) => break None, // no more rows
+				Err(err) => break Some(err),
+			};
+		};
+		drop(rows);
+		conn.lock_handle()
+			.await
+			.map_err(|err| format!("{:?}", err))?
+			.remove_progress_handler();
+
+		if let Some(err) = maybe_err {
+			log::error!(target: "frontier-sql", "Failed to query sql db: {err:?} - {trace_key}");
+			return Err("Failed to query sql db with statement".to_string());
+		}
+
+		Ok(out)
+	}
+}
+
+/// Build a SQL query to retrieve a list of traces given certain constraints.
+fn build_trace_query<'a>(
+	qb: &'a mut QueryBuilder<Sqlite>,
+	from_block: u64,
+	to_block: u64,
+	from_addresses: Vec<H160>,
+	to_addresses: Vec<H160>,
+	after: u32,
+	count: u32,
+) -> Query<'a, Sqlite, SqliteArguments<'a>> {
+	qb.push(
+		"
+SELECT
+	t.substrate_block_hash,
+	b.ethereum_block_hash,
+	b.block_number,
+	t.transaction_hash,
+	t.transaction_index,
+	t.data
+FROM traces AS t
+INNER JOIN blocks AS b
+ON (b.block_number BETWEEN ",
+	);
+	qb.separated(" AND ")
+		.push_bind(from_block as i64)
+		.push_bind(to_block as i64)
+		.push_unseparated(")");
+	qb.push(" AND b.substrate_block_hash = t.substrate_block_hash")
+		.push(" AND b.is_canon = 1")
+		.push("\nWHERE 1");
+
+	for (column, addresses) in [
+		("from_address", from_addresses),
+		("to_address", to_addresses),
+	] {
+		if !addresses.is_empty() {
+			qb.push(format!(" AND t.{column} IN ("));
+			let mut qb_addr = qb.separated(", ");
+			addresses.iter().for_each(|addr| {
+				qb_addr.push_bind(addr.as_bytes().to_owned());
+			});
+			qb_addr.push_unseparated(")");
+		}
+	}
+
+	qb.push(
+		"
+ORDER BY b.block_number ASC, t.transaction_index ASC, t.trace_index ASC
+LIMIT ",
+	)
+	.push_bind(i64::from(count))
+	.push(" OFFSET ")
+	.push_bind(after as i64);
+
+	qb.build()
+}
+
 /// Build a SQL query to retrieve a list of logs given certain constraints.
 fn build_query<'a>(
 	qb: &'a mut QueryBuilder<Sqlite>,
@@ -1115,6 +1534,7 @@ mod test {
 				create_if_missing: true,
 				cache_size: 20480,
 				thread_count: 4,
+				index_traces: false,
 			}),
 			1,
 			None,
@@ -1773,6 +2193,139 @@ mod test {
 		.await;
 	}
 
+	#[tokio::test]
+	async fn filter_traces_works() {
+		use fc_api::TraceIndexerBackend;
+
+		let TestData {
+			mut backend,
+			alice,
+			bob,
+			substrate_hash_1,
+			substrate_hash_2,
+			ethereum_hash_1,
+			ethereum_hash_2,
+			..
+		} = prepare().await;
+		backend.index_traces = true;
+
+		let carol = H160::repeat_byte(0x03);
+		let call = |from: H160, to: H160, calls: Vec<fp_evm::CallFrame>| fp_evm::CallFrame {
+			call_type: fp_evm::CallType::Call,
+			from,
+			to,
+			value: Some(Default::default()),
+			gas: 21_000,
+			gas_used: 21_000,
+			input: vec![],
+			output: vec![],
+			exit_reason: None,
+			logs: vec![],
+			calls,
+		};
+		let transaction_hash = H256::repeat_byte(0x0b);
+		let traces = [
+			(
+				substrate_hash_1,
+				call(alice, bob, vec![call(bob, carol, vec![])]),
+			),
+			(substrate_hash_2, call(bob, carol, vec![])),
+		];
+		for (substrate_block_hash, frame) in traces {
+			for (trace_index, trace) in FlatCallFrame::flatten(frame).into_iter().enumerate() {
+				sqlx::query(
+					"INSERT INTO traces(
+						from_address,
+						to_address,
+						transaction_hash,
+						transaction_index,
+						trace_index,
+						data,
+						substrate_block_hash)
+					VALUES (?, ?, ?, ?, ?, ?, ?)",
+				)
+				.bind(trace.call.from.as_bytes())
+				.bind(trace.call.to.as_bytes())
+				.bind(transaction_hash.as_bytes())
+				.bind(0i32)
+				.bind(trace_index as i32)
+				.bind(trace.encode())
+				.bind(substrate_block_hash.as_bytes())
+				.execute(backend.pool())
+				.await
+				.expect("sql query must succeed");
+			}
+		}
+
+		let all = backend
+			.filter_traces(1, 3, vec![], vec![], 0, 10)
+			.await
+			.expect("must succeed");
+		assert_eq!(
+			all.iter()
+				.map(|t| (t.block_number, t.trace.trace_address.clone()))
+				.collect::<Vec<_>>(),
+			vec![(1, vec![]), (1, vec![0]), (2, vec![])]
+		);
+		assert_eq!(all[0].ethereum_block_hash, ethereum_hash_1);
+		assert_eq!(all[0].trace.subtraces, 1);
+		assert_eq!(all[2].ethereum_block_hash, ethereum_hash_2);
+		assert_eq!(all[2].transaction_hash, transaction_hash);
+
+		let from_bob = backend
+			.filter_traces(1, 3, vec![bob], vec![], 0, 10)
+			.await
+			.expect("must succeed");
+		assert_eq!(from_bob, &all[1..]);
+
+		let to_carol = backend
+			.filter_traces(1, 3, vec![], vec![carol], 1, 10)
+			.await
+			.expect("must succeed");
+		assert_eq!(to_carol, &all[2..]);
+
+		let paged = backend
+			.filter_traces(1, 3, vec![], vec![], 1, 1)
+			.await
+			.expect("must succeed");
+		assert_eq!(paged, &all[1..2]);
+	}
+
+	#[tokio::test]
+	async fn failed_trace_replay_is_not_retried() {
+		let TestData {
+			mut backend,
+			substrate_hash_1,
+			substrate_hash_2,
+			..
+		} = prepare().await;
+		backend.index_traces = true;
+		for substrate_block_hash in [substrate_hash_1, substrate_hash_2] {
+			sqlx::query("INSERT INTO sync_status(substrate_block_hash, status) VALUES (?, 1)")
+				.bind(substrate_block_hash.as_bytes())
+				.execute(backend.pool())
+				.await
+				.expect("sql query must succeed");
+		}
+		assert_eq!(
+			backend.get_first_untraced_canon_block().await,
+			Some(substrate_hash_1)
+		);
+
+		Backend::<OpaqueBlock>::insert_block_traces(
+			backend.pool(),
+			substrate_hash_1,
+			Err(Error::Protocol("Replay failed".to_string())),
+		)
+		.await
+		.expect("must succeed");
+
+		assert_eq!(
+			backend.get_first_untraced_canon_block().await,
+			Some(substrate_hash_2)
+		);
+	}
+
 	#[test]
 	fn test_query_should_be_generated_correctly() {
 		use sqlx::Execute;
@@ -20,14 +20,17 @@ use std::{ops::DerefMut, sync::Arc, time::Duration};
 
 use futures::prelude::*;
 // Substrate
-use sc_client_api::backend::{Backend as BackendT, StorageProvider};
+use sc_client_api::{
+	backend::{Backend as BackendT, StorageProvider},
+	BlockBackend,
+};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{Backend, HeaderBackend};
 use sp_consensus::SyncOracle;
 use sp_core::H256;
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
 // Frontier
-use fp_rpc::EthereumRuntimeRPCApi;
+use fp_rpc::{DebugRuntimeApi, EthereumRuntimeRPCApi};
 
 use crate::{EthereumBlockNotification, EthereumBlockNotificationSinks, SyncStrategy};
 
@@ -67,8 +70,8 @@ impl<Block, Backend, Client> SyncWorker<Block, Backend, Client>
 where
 	Block: BlockT<Hash = H256>,
 	Client: ProvideRuntimeApi<Block>,
-	Client::Api: EthereumRuntimeRPCApi<Block>,
-	Client: HeaderBackend<Block> + StorageProvider<Block, Backend> + 'static,
+	Client::Api: EthereumRuntimeRPCApi<Block> + DebugRuntimeApi<Block>,
+	Client: HeaderBackend<Block> + BlockBackend<Block> + StorageProvider<Block, Backend> + 'static,
 	Backend: BackendT<Block> + 'static,
 {
 	/// Spawn the indexing worker. The worker can be given commands via the sender channel.
@@ -155,6 +158,16 @@ where
 							indexer_backend.index_block_logs(block_hash).await;
 						}
 
+						// Index the calls of a canonical block whose traces were not indexed yet
+						if let Some(block_hash) =
+							indexer_backend.get_first_untraced_canon_block().await
+						{
+							log::debug!(target: "frontier-sql", "Indexing traces of canonical block {block_hash:?}");
+							indexer_backend
+								.index_block_traces(client.clone(), block_hash)
+								.await;
+						}
+
 						// Fix any missing blocks
 						index_missing_blocks(
 							client.clone(),
@@ -274,8 +287,8 @@ async fn index_block_and_ancestors<Block, Backend, Client>(
 ) where
 	Block: BlockT<Hash = H256>,
 	Client: ProvideRuntimeApi<Block>,
-	Client::Api: EthereumRuntimeRPCApi<Block>,
-	Client: HeaderBackend<Block> + StorageProvider<Block, Backend> + 'static,
+	Client::Api: EthereumRuntimeRPCApi<Block> + DebugRuntimeApi<Block>,
+	Client: HeaderBackend<Block> + BlockBackend<Block> + StorageProvider<Block, Backend> + 'static,
 	Backend: BackendT<Block> + 'static,
 {
 	let blockchain_backend = substrate_backend.blockchain();
@@ -301,6 +314,9 @@ async fn index_block_and_ancestors<Block, Backend, Client>(
 			});
 		log::debug!(target: "frontier-sql", "Inserted block metadata");
 		indexer_backend.index_block_logs(hash).await;
+		indexer_backend
+			.index_block_traces(client.clone(), hash)
+			.await;
 
 		if let Ok(Some(header)) = blockchain_backend.header(hash) {
 			let parent_hash = header.parent_hash();
@@ -321,8 +337,8 @@ async fn index_canonical_block_and_ancestors<Block, Backend, Client>(
 ) where
 	Block: BlockT<Hash = H256>,
 	Client: ProvideRuntimeApi<Block>,
-	Client::Api: EthereumRuntimeRPCApi<Block>,
-	Client: HeaderBackend<Block> + StorageProvider<Block, Backend> + 'static,
+	Client::Api: EthereumRuntimeRPCApi<Block> + DebugRuntimeApi<Block>,
+	Client: HeaderBackend<Block> + BlockBackend<Block> + StorageProvider<Block, Backend> + 'static,
 	Backend: BackendT<Block> + 'static,
 {
 	let blockchain_backend = substrate_backend.blockchain();
@@ -368,6 +384,9 @@ async fn index_canonical_block_and_ancestors<Block, Backend, Client>(
 			});
 		log::debug!(target: "frontier-sql", "Inserted block metadata  {hash:?}");
 		indexer_backend.index_block_logs(hash).await;
+		indexer_backend
+			.index_block_traces(client.clone(), hash)
+			.await;
 
 		if let Ok(Some(header)) = blockchain_backend.header(hash) {
 			let parent_hash = header.parent_hash();
@@ -405,8 +424,8 @@ async fn index_missing_blocks<Block, Client, Backend>(
 ) where
 	Block: BlockT<Hash = H256>,
 	Client: ProvideRuntimeApi<Block>,
-	Client::Api: EthereumRuntimeRPCApi<Block>,
-	Client: HeaderBackend<Block> + StorageProvider<Block, Backend> + 'static,
+	Client::Api: EthereumRuntimeRPCApi<Block> + DebugRuntimeApi<Block>,
+	Client: HeaderBackend<Block> + BlockBackend<Block> + StorageProvider<Block, Backend> + 'static,
 	Backend: BackendT<Block> + 'static,
 {
 	if let Some(block_number) = indexer_backend.get_first_missing_canon_block().await {
@@ -442,8 +461,8 @@ async fn index_genesis_block<Block, Client, Backend>(
 ) where
 	Block: BlockT<Hash = H256>,
 	Client: ProvideRuntimeApi<Block>,
-	Client::Api: EthereumRuntimeRPCApi<Block>,
-	Client: HeaderBackend<Block> + StorageProvider<Block, Backend> + 'static,
+	Client::Api: EthereumRuntimeRPCApi<Block> + DebugRuntimeApi<Block>,
+	Client: HeaderBackend<Block> + BlockBackend<Block> + StorageProvider<Block, Backend> + 'static,
 	Backend: BackendT<Block> + 'static,
 {
 	log::info!(
@@ -525,7 +544,7 @@ mod test {
 			mix_hash: H256::default(),
 			nonce: ethereum_types::H64::default(),
 		};
-		let ethereum_transactions: Vec<ethereum::TransactionV2> = vec![];
+		let ethereum_transactions: Vec<ethereum::TransactionV3> = vec![];
 		let ethereum_block = ethereum::Block::new(partial_header, ethereum_transactions, vec![]);
 		DigestItem::Consensus(
 			fp_consensus::FRONTIER_ENGINE_ID,
@@ -561,6 +580,7 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				index_traces: false,
 			}),
 			100,
 			None,
@@ -596,7 +616,7 @@ mod test {
 			let topics_2_4 = H256::repeat_byte(0x06);
 
 			let receipts = Encode::encode(&vec![
-				ethereum::ReceiptV3::EIP1559(ethereum::EIP1559ReceiptData {
+				ethereum::ReceiptV4::EIP1559(ethereum::EIP1559ReceiptData {
 					status_code: 0u8,
 					used_gas: U256::zero(),
 					logs_bloom: ethereum_types::Bloom::zero(),
@@ -606,7 +626,7 @@ mod test {
 						data: vec![],
 					}],
 				}),
-				ethereum::ReceiptV3::EIP1559(ethereum::EIP1559ReceiptData {
+				ethereum::ReceiptV4::EIP1559(ethereum::EIP1559ReceiptData {
 					status_code: 0u8,
 					used_gas: U256::zero(),
 					logs_bloom: ethereum_types::Bloom::zero(),
@@ -764,6 +784,7 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				index_traces: false,
 			}),
 			100,
 			None,
@@ -829,7 +850,7 @@ mod test {
 			let topics_2_4 = H256::random();
 
 			let receipts = Encode::encode(&vec![
-				ethereum::ReceiptV3::EIP1559(ethereum::EIP1559ReceiptData {
+				ethereum::ReceiptV4::EIP1559(ethereum::EIP1559ReceiptData {
 					status_code: 0u8,
 					used_gas: U256::zero(),
 					logs_bloom: ethereum_types::Bloom::zero(),
@@ -839,7 +860,7 @@ mod test {
 						data: vec![],
 					}],
 				}),
-				ethereum::ReceiptV3::EIP1559(ethereum::EIP1559ReceiptData {
+				ethereum::ReceiptV4::EIP1559(ethereum::EIP1559ReceiptData {
 					status_code: 0u8,
 					used_gas: U256::zero(),
 					logs_bloom: ethereum_types::Bloom::zero(),
@@ -968,6 +989,7 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				index_traces: false,
 			}),
 			100,
 			None,
@@ -1134,6 +1156,7 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				index_traces: false,
 			}),
 			100,
 			None,
@@ -1278,6 +1301,7 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				index_traces: false,
 			}),
 			100,
 			None,
@@ -1379,6 +1403,7 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				index_traces: false,
 			}),
 			100,
 			None,
@@ -1494,6 +1519,7 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				index_traces: false,
 			}),
 			100,
 			None,
@@ -1595,6 +1621,7 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				index_traces: false,
 			}),
 			100,
 			None,
@@ -1710,6 +1737,7 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				index_traces: false,
 			}),
 			100,
 			None,
@@ -1811,6 +1839,7 @@ mod test {
 				create_if_missing: true,
 				cache_size: 204800,
 				thread_count: 4,
+				index_traces: false,
 			}),
 			100,
 			None,
@@ -181,6 +181,16 @@ pub trait EthApi {
 		number_or_hash: Option<BlockNumberOrHash>,
 	) -> RpcResult<Bytes>;
 
+	/// Returns the account and storage values of the given address, along with the storage
+	/// proofs of the entries holding them.
+	#[method(name = "eth_getProof")]
+	async fn proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<H256>,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<EthAccount>;
+
 	// ########################################################################
 	// Execute
 	// ########################################################################
@@ -192,6 +202,7 @@ pub trait EthApi {
 		request: TransactionRequest,
 		number_or_hash: Option<BlockNumberOrHash>,
 		state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<Bytes>;
 
 	/// Estimate gas needed for execution of given contract.
@@ -200,8 +211,26 @@ pub trait EthApi {
 		&self,
 		request: TransactionRequest,
 		number_or_hash: Option<BlockNumberOrHash>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<U256>;
 
+	/// Generate an access list for a transaction, along with the gas it uses with the list.
+	#[method(name = "eth_createAccessList")]
+	async fn create_access_list(
+		&self,
+		request: TransactionRequest,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<AccessListResult>;
+
+	/// Simulate a sequence of blocks of calls on top of the given block, each block and call
+	/// seeing the state changes of the previous ones.
+	#[method(name = "eth_simulateV1")]
+	async fn simulate_v1(
+		&self,
+		payload: SimulatePayload,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<Vec<SimulatedBlock>>;
+
 	// ########################################################################
 	// Fee
 	// ########################################################################
@@ -0,0 +1,44 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Trace rpc interface.
+
+use ethereum_types::H256;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+use crate::types::{BlockNumberOrHash, LocalizedTrace, TraceFilter};
+
+/// Trace rpc interface, in the format of OpenEthereum.
+#[rpc(server)]
+#[async_trait]
+pub trait TraceApi {
+	/// Replays the block with the given number or hash and returns the calls of its transactions.
+	#[method(name = "trace_block")]
+	async fn trace_block(
+		&self,
+		number: BlockNumberOrHash,
+	) -> RpcResult<Option<Vec<LocalizedTrace>>>;
+
+	/// Replays the transaction with the given hash and returns its calls.
+	#[method(name = "trace_transaction")]
+	async fn trace_transaction(&self, hash: H256) -> RpcResult<Option<Vec<LocalizedTrace>>>;
+
+	/// Returns the indexed calls matching the filter.
+	#[method(name = "trace_filter")]
+	async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTrace>>;
+}
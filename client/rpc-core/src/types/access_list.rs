@@ -0,0 +1,56 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum::AccessListItem;
+use ethereum_types::U256;
+use serde::Serialize;
+
+/// Result of `eth_createAccessList`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+	/// Addresses and storage slots accessed by the transaction.
+	pub access_list: Vec<AccessListItem>,
+	/// Gas used by the transaction with the access list applied.
+	pub gas_used: U256,
+	/// Why the transaction failed, if it did.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum_types::{H160, H256};
+
+	#[test]
+	fn test_serialize_access_list_result() {
+		let result = AccessListResult {
+			access_list: vec![AccessListItem {
+				address: H160::repeat_byte(0xaa),
+				storage_keys: vec![H256::from_low_u64_be(1)],
+			}],
+			gas_used: U256::from(23_400),
+			error: None,
+		};
+		assert_eq!(
+			serde_json::to_string(&result).unwrap(),
+			r#"{"accessList":[{"address":"0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","storageKeys":["0x0000000000000000000000000000000000000000000000000000000000000001"]}],"gasUsed":"0x5b68"}"#
+		);
+	}
+}
@@ -0,0 +1,68 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{H160, H256, U256, U64};
+use serde::{Deserialize, Serialize};
+
+/// EIP-7702 authorization tuple.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizationListItem {
+	/// Chain the authorization is valid on, zero for any chain
+	pub chain_id: U64,
+	/// Address the authority delegates its code to
+	pub address: H160,
+	/// Nonce of the authority
+	pub nonce: U256,
+	/// The parity (0 for even, 1 for odd) of the y-value of the secp256k1 signature.
+	pub y_parity: U64,
+	/// The R field of the signature.
+	pub r: U256,
+	/// The S field of the signature.
+	pub s: U256,
+}
+
+impl From<ethereum::AuthorizationListItem> for AuthorizationListItem {
+	fn from(item: ethereum::AuthorizationListItem) -> Self {
+		Self {
+			chain_id: U64::from(item.chain_id),
+			address: item.address,
+			nonce: item.nonce,
+			y_parity: U64::from(item.y_parity as u8),
+			r: U256::from(item.r.as_bytes()),
+			s: U256::from(item.s.as_bytes()),
+		}
+	}
+}
+
+impl From<AuthorizationListItem> for ethereum::AuthorizationListItem {
+	fn from(item: AuthorizationListItem) -> Self {
+		let mut r = [0u8; 32];
+		let mut s = [0u8; 32];
+		item.r.to_big_endian(&mut r);
+		item.s.to_big_endian(&mut s);
+		Self {
+			chain_id: item.chain_id.as_u64(),
+			address: item.address,
+			nonce: item.nonce,
+			y_parity: !item.y_parity.is_zero(),
+			r: H256(r),
+			s: H256(s),
+		}
+	}
+}
@@ -18,7 +18,7 @@
 
 use std::collections::BTreeMap;
 
-use ethereum_types::{H256, U256};
+use ethereum_types::{H160, H256, U256, U64};
 use serde::Deserialize;
 
 use crate::types::Bytes;
@@ -41,3 +41,26 @@ pub struct CallStateOverride {
 	/// executing the call.
 	pub state_diff: Option<BTreeMap<H256, H256>>,
 }
+
+/// Block override
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOverrides {
+	/// Fake block number.
+	pub number: Option<U256>,
+	/// Fake block timestamp, in seconds.
+	pub time: Option<U64>,
+	/// Fake block gas limit.
+	pub gas_limit: Option<U64>,
+	/// Fake block author.
+	pub fee_recipient: Option<H160>,
+	/// Fake block randomness.
+	pub prev_randao: Option<H256>,
+	/// Fake block base fee.
+	pub base_fee_per_gas: Option<U256>,
+	/// Fake blob base fee.
+	pub blob_base_fee: Option<U256>,
+	/// Fake hashes of the previous blocks, by number.
+	pub block_hash: Option<BTreeMap<U64, H256>>,
+}
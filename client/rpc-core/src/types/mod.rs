@@ -18,7 +18,9 @@
 
 //! RPC types
 
+mod access_list;
 mod account_info;
+mod authorization;
 mod block;
 mod block_number;
 mod bytes;
@@ -28,7 +30,10 @@ mod filter;
 mod index;
 mod log;
 mod receipt;
+mod simulate;
 mod sync;
+mod trace;
+mod trace_filter;
 mod transaction;
 mod transaction_request;
 #[cfg(feature = "txpool")]
@@ -37,17 +42,19 @@ mod work;
 
 pub mod pubsub;
 
-use ethereum::TransactionV2 as EthereumTransaction;
+use ethereum::TransactionV3 as EthereumTransaction;
 use ethereum_types::H160;
 
 #[cfg(feature = "txpool")]
 pub use self::txpool::{Summary, TransactionMap, TxPoolResult};
 pub use self::{
+	access_list::AccessListResult,
 	account_info::{AccountInfo, EthAccount, ExtAccountInfo, RecoveredAccount, StorageProof},
+	authorization::AuthorizationListItem,
 	block::{Block, BlockTransactions, Header, Rich, RichBlock, RichHeader},
 	block_number::BlockNumberOrHash,
 	bytes::Bytes,
-	call_request::CallStateOverride,
+	call_request::{BlockOverrides, CallStateOverride},
 	fee::{FeeHistory, FeeHistoryCache, FeeHistoryCacheItem, FeeHistoryCacheLimit},
 	filter::{
 		Filter, FilterAddress, FilterChanges, FilterPool, FilterPoolItem, FilterType,
@@ -56,10 +63,21 @@ pub use self::{
 	index::Index,
 	log::Log,
 	receipt::Receipt,
+	simulate::{
+		SimulateBlock, SimulateCallError, SimulateCallResult, SimulatePayload, SimulatedBlock,
+	},
 	sync::{
 		ChainStatus, EthProtocolInfo, PeerCount, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
 		Peers, PipProtocolInfo, SyncInfo, SyncStatus, TransactionStats,
 	},
+	trace::{
+		opcode_name, BlockTransactionTrace, CallFrame, CallLog, PrestateAccount, PrestateResult,
+		StructLog, StructLoggerResult, TraceParams, TracerConfig, TransactionTrace,
+	},
+	trace_filter::{
+		CallAction, CreateAction, LocalizedTrace, SuicideAction, TraceAction, TraceFilter,
+		TraceResult, TraceType,
+	},
 	transaction::{LocalTransactionStatus, RichRawTransaction, Transaction},
 	transaction_request::{TransactionMessage, TransactionRequest},
 	work::Work,
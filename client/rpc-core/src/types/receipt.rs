@@ -19,7 +19,7 @@
 use ethereum_types::{Bloom as H2048, H160, H256, U256, U64};
 use serde::Serialize;
 
-use crate::types::Log;
+use crate::types::{Bytes, Log};
 
 /// Receipt
 #[derive(Clone, Debug, Serialize)]
@@ -60,4 +60,7 @@ pub struct Receipt {
 	/// EIP-2718 type
 	#[serde(rename = "type")]
 	pub transaction_type: U256,
+	/// Output of the reverted transaction, possibly truncated
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub revert_reason: Option<Bytes>,
 }
@@ -0,0 +1,182 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use ethereum_types::{H160, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{BlockOverrides, Bytes, CallStateOverride, Log, RichBlock, TransactionRequest};
+
+/// Parameters of `eth_simulateV1`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+	/// Blocks to simulate, in order, each on top of the previous one.
+	pub block_state_calls: Vec<SimulateBlock>,
+	/// Add an ERC-20 like `Transfer` log for every value transfer.
+	#[serde(default)]
+	pub trace_transfers: bool,
+	/// Validate the calls as transactions: nonces, balances and fees.
+	#[serde(default)]
+	pub validation: bool,
+	/// Return the full transactions in the simulated blocks rather than their hashes.
+	#[serde(default)]
+	pub return_full_transactions: bool,
+}
+
+/// A block to simulate.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlock {
+	/// Overrides of the block context.
+	pub block_overrides: Option<BlockOverrides>,
+	/// Overrides of the state, applied before the calls of the block.
+	pub state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
+	/// Calls of the block, executed in order.
+	#[serde(default)]
+	pub calls: Vec<TransactionRequest>,
+}
+
+/// A simulated block, along with the results of its calls.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+	/// The block.
+	#[serde(flatten)]
+	pub block: RichBlock,
+	/// Results of the calls, in order.
+	pub calls: Vec<SimulateCallResult>,
+}
+
+/// Result of a simulated call.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateCallResult {
+	/// Data returned by the call.
+	pub return_data: Bytes,
+	/// Logs emitted by the call.
+	pub logs: Vec<Log>,
+	/// Gas used by the call.
+	pub gas_used: U64,
+	/// 1 if the call succeeded, 0 otherwise.
+	pub status: U64,
+	/// Why the call failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<SimulateCallError>,
+}
+
+/// Error of a failed simulated call.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateCallError {
+	/// 3 for a revert, -32015 for any other failure of the EVM.
+	pub code: i32,
+	/// Description of the failure.
+	pub message: String,
+	/// Data returned by a reverted call.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub data: Option<Bytes>,
+}
+
+impl SimulateCallError {
+	/// Error code of a reverted call.
+	pub const REVERTED: i32 = 3;
+	/// Error code of a call halted by the EVM.
+	pub const VM_ERROR: i32 = -32015;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum_types::U256;
+
+	#[test]
+	fn test_deserialize_simulate_payload() {
+		let payload: SimulatePayload = serde_json::from_str(
+			r#"{
+				"blockStateCalls": [
+					{
+						"blockOverrides": {"number": "0x10", "time": "0x64", "baseFeePerGas": "0x0"},
+						"stateOverrides": {
+							"0x0000000000000000000000000000000000000001": {"balance": "0x3e8"}
+						},
+						"calls": [
+							{"from": "0x0000000000000000000000000000000000000001", "value": "0x1"}
+						]
+					},
+					{}
+				],
+				"traceTransfers": true
+			}"#,
+		)
+		.unwrap();
+
+		assert!(payload.trace_transfers);
+		assert!(!payload.validation);
+		assert!(!payload.return_full_transactions);
+		assert_eq!(payload.block_state_calls.len(), 2);
+
+		let block = &payload.block_state_calls[0];
+		assert_eq!(
+			block.block_overrides,
+			Some(BlockOverrides {
+				number: Some(U256::from(16)),
+				time: Some(U64::from(100)),
+				base_fee_per_gas: Some(U256::zero()),
+				..Default::default()
+			})
+		);
+		assert_eq!(
+			block.state_overrides.as_ref().unwrap()[&H160::from_low_u64_be(1)].balance,
+			Some(U256::from(1000))
+		);
+		assert_eq!(block.calls.len(), 1);
+		assert_eq!(block.calls[0].value, Some(U256::one()));
+		assert_eq!(payload.block_state_calls[1], SimulateBlock::default());
+	}
+
+	#[test]
+	fn test_deserialize_simulate_payload_rejects_unknown_fields() {
+		assert!(serde_json::from_str::<SimulatePayload>(
+			r#"{"blockStateCalls": [{"blockOverrides": {"difficulty": "0x1"}}]}"#
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn test_serialize_simulate_call_result() {
+		let result = SimulateCallResult {
+			return_data: Bytes(vec![0xab]),
+			logs: vec![],
+			gas_used: U64::from(21_000),
+			status: U64::zero(),
+			error: Some(SimulateCallError {
+				code: SimulateCallError::REVERTED,
+				message: "execution reverted".into(),
+				data: Some(Bytes(vec![0xab])),
+			}),
+		};
+		assert_eq!(
+			serde_json::to_string(&result).unwrap(),
+			r#"{"returnData":"0xab","logs":[],"gasUsed":"0x5208","status":"0x0","error":{"code":3,"message":"execution reverted","data":"0xab"}}"#
+		);
+	}
+}
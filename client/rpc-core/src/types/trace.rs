@@ -0,0 +1,352 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use ethereum_types::{H160, H256, U256, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::types::Bytes;
+
+/// Options of the `debug_trace*` methods.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TraceParams {
+	/// Do not record the stack.
+	pub disable_stack: bool,
+	/// Do not record the memory.
+	pub disable_memory: bool,
+	/// Do not record the storage.
+	pub disable_storage: bool,
+	/// Name of a built-in tracer, the struct logger being used when unset.
+	pub tracer: Option<String>,
+	/// Options of the built-in tracer.
+	pub tracer_config: TracerConfig,
+}
+
+/// Options of the built-in tracers, each tracer ignoring the options of the others.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TracerConfig {
+	/// `callTracer`: only record the top level call.
+	pub only_top_call: bool,
+	/// `callTracer`: record the logs emitted by each call.
+	pub with_log: bool,
+	/// `prestateTracer`: return the state before and after the transaction.
+	pub diff_mode: bool,
+}
+
+/// Trace of a transaction, in the format of the requested tracer.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TransactionTrace {
+	StructLogger(StructLoggerResult),
+	CallTracer(CallFrame),
+	PrestateTracer(PrestateResult),
+}
+
+/// Trace of a transaction of a block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTransactionTrace {
+	/// Hash of the transaction.
+	pub tx_hash: H256,
+	/// Trace of the transaction.
+	pub result: TransactionTrace,
+}
+
+/// Output of geth's default struct logger.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLoggerResult {
+	/// Gas used by the transaction.
+	pub gas: u64,
+	/// Whether the transaction failed.
+	pub failed: bool,
+	/// Hex encoded return data, without `0x` prefix.
+	pub return_value: String,
+	/// One entry per executed opcode.
+	pub struct_logs: Vec<StructLog>,
+}
+
+/// State of the machine right before an opcode is executed.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+	/// Program counter.
+	pub pc: u64,
+	/// Opcode name.
+	pub op: String,
+	/// Gas left before executing the opcode.
+	pub gas: u64,
+	/// Gas cost of the opcode.
+	pub gas_cost: u64,
+	/// Call depth.
+	pub depth: u32,
+	/// Stack, bottom first.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<U256>>,
+	/// Memory, as hex encoded 32 bytes words without `0x` prefix.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub memory: Option<Vec<String>>,
+	/// Accessed storage slots of the current contract, hex encoded without `0x` prefix.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<String, String>>,
+}
+
+/// A call frame, as output by geth's `callTracer`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+	/// Kind of call, e.g. `CALL` or `CREATE2`.
+	#[serde(rename = "type")]
+	pub call_type: String,
+	pub from: H160,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub to: Option<H160>,
+	/// Transferred value, unset for delegate and static calls.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub value: Option<U256>,
+	pub gas: U64,
+	pub gas_used: U64,
+	pub input: Bytes,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub output: Option<Bytes>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// Decoded `Error(string)` message of reverted calls.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub revert_reason: Option<String>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub logs: Vec<CallLog>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub calls: Vec<CallFrame>,
+}
+
+/// A log emitted by a call frame.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallLog {
+	pub address: H160,
+	pub topics: Vec<H256>,
+	pub data: Bytes,
+	/// Number of sub calls of the frame made before the log was emitted.
+	pub position: U64,
+}
+
+/// Output of geth's `prestateTracer`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum PrestateResult {
+	/// State of the touched accounts before the transaction.
+	Prestate(BTreeMap<H160, PrestateAccount>),
+	/// Modified fields of the touched accounts, before and after the transaction.
+	Diff {
+		pre: BTreeMap<H160, PrestateAccount>,
+		post: BTreeMap<H160, PrestateAccount>,
+	},
+}
+
+/// State of an account recorded by the prestate tracer, unset fields being omitted.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateAccount {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<u64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub code: Option<Bytes>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// Name of an opcode, as displayed by geth.
+pub fn opcode_name(opcode: u8) -> String {
+	let name = match opcode {
+		0x00 => "STOP",
+		0x01 => "ADD",
+		0x02 => "MUL",
+		0x03 => "SUB",
+		0x04 => "DIV",
+		0x05 => "SDIV",
+		0x06 => "MOD",
+		0x07 => "SMOD",
+		0x08 => "ADDMOD",
+		0x09 => "MULMOD",
+		0x0a => "EXP",
+		0x0b => "SIGNEXTEND",
+		0x10 => "LT",
+		0x11 => "GT",
+		0x12 => "SLT",
+		0x13 => "SGT",
+		0x14 => "EQ",
+		0x15 => "ISZERO",
+		0x16 => "AND",
+		0x17 => "OR",
+		0x18 => "XOR",
+		0x19 => "NOT",
+		0x1a => "BYTE",
+		0x1b => "SHL",
+		0x1c => "SHR",
+		0x1d => "SAR",
+		0x20 => "KECCAK256",
+		0x30 => "ADDRESS",
+		0x31 => "BALANCE",
+		0x32 => "ORIGIN",
+		0x33 => "CALLER",
+		0x34 => "CALLVALUE",
+		0x35 => "CALLDATALOAD",
+		0x36 => "CALLDATASIZE",
+		0x37 => "CALLDATACOPY",
+		0x38 => "CODESIZE",
+		0x39 => "CODECOPY",
+		0x3a => "GASPRICE",
+		0x3b => "EXTCODESIZE",
+		0x3c => "EXTCODECOPY",
+		0x3d => "RETURNDATASIZE",
+		0x3e => "RETURNDATACOPY",
+		0x3f => "EXTCODEHASH",
+		0x40 => "BLOCKHASH",
+		0x41 => "COINBASE",
+		0x42 => "TIMESTAMP",
+		0x43 => "NUMBER",
+		0x44 => "DIFFICULTY",
+		0x45 => "GASLIMIT",
+		0x46 => "CHAINID",
+		0x47 => "SELFBALANCE",
+		0x48 => "BASEFEE",
+		0x49 => "BLOBHASH",
+		0x4a => "BLOBBASEFEE",
+		0x50 => "POP",
+		0x51 => "MLOAD",
+		0x52 => "MSTORE",
+		0x53 => "MSTORE8",
+		0x54 => "SLOAD",
+		0x55 => "SSTORE",
+		0x56 => "JUMP",
+		0x57 => "JUMPI",
+		0x58 => "PC",
+		0x59 => "MSIZE",
+		0x5a => "GAS",
+		0x5b => "JUMPDEST",
+		0x5c => "TLOAD",
+		0x5d => "TSTORE",
+		0x5e => "MCOPY",
+		0x5f => "PUSH0",
+		0x60..=0x7f => return format!("PUSH{}", opcode - 0x5f),
+		0x80..=0x8f => return format!("DUP{}", opcode - 0x7f),
+		0x90..=0x9f => return format!("SWAP{}", opcode - 0x8f),
+		0xa0..=0xa4 => return format!("LOG{}", opcode - 0xa0),
+		0xf0 => "CREATE",
+		0xf1 => "CALL",
+		0xf2 => "CALLCODE",
+		0xf3 => "RETURN",
+		0xf4 => "DELEGATECALL",
+		0xf5 => "CREATE2",
+		0xfa => "STATICCALL",
+		0xfd => "REVERT",
+		0xfe => "INVALID",
+		0xff => "SELFDESTRUCT",
+		_ => return format!("opcode {:#x} not defined", opcode),
+	};
+	name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_opcode_name() {
+		assert_eq!(opcode_name(0x00), "STOP");
+		assert_eq!(opcode_name(0x60), "PUSH1");
+		assert_eq!(opcode_name(0x7f), "PUSH32");
+		assert_eq!(opcode_name(0x8f), "DUP16");
+		assert_eq!(opcode_name(0x90), "SWAP1");
+		assert_eq!(opcode_name(0xa4), "LOG4");
+		assert_eq!(opcode_name(0x0c), "opcode 0xc not defined");
+	}
+
+	#[test]
+	fn test_deserialize_trace_params() {
+		let params: TraceParams = serde_json::from_str(r#"{"disableStorage":true}"#).unwrap();
+		assert_eq!(
+			params,
+			TraceParams {
+				disable_storage: true,
+				..Default::default()
+			}
+		);
+
+		let params: TraceParams =
+			serde_json::from_str(r#"{"tracer":"callTracer","tracerConfig":{"onlyTopCall":true}}"#)
+				.unwrap();
+		assert_eq!(params.tracer.as_deref(), Some("callTracer"));
+		assert!(params.tracer_config.only_top_call);
+		assert!(!params.tracer_config.with_log);
+	}
+
+	#[test]
+	fn test_serialize_call_frame() {
+		let frame = CallFrame {
+			call_type: "CALL".to_string(),
+			from: H160::repeat_byte(0xaa),
+			to: Some(H160::repeat_byte(0xbb)),
+			value: Some(U256::zero()),
+			gas: U64::from(0x5208),
+			gas_used: U64::from(0x5208),
+			input: Bytes(vec![]),
+			output: None,
+			error: Some("execution reverted".to_string()),
+			revert_reason: None,
+			logs: vec![],
+			calls: vec![],
+		};
+		assert_eq!(
+			serde_json::to_string(&frame).unwrap(),
+			r#"{"type":"CALL","from":"0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","to":"0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb","value":"0x0","gas":"0x5208","gasUsed":"0x5208","input":"0x","error":"execution reverted"}"#
+		);
+	}
+
+	#[test]
+	fn test_serialize_prestate_diff() {
+		let account = H160::repeat_byte(0xaa);
+		let result = PrestateResult::Diff {
+			pre: BTreeMap::from([(
+				account,
+				PrestateAccount {
+					balance: Some(U256::from(2)),
+					nonce: Some(1),
+					..Default::default()
+				},
+			)]),
+			post: BTreeMap::from([(
+				account,
+				PrestateAccount {
+					nonce: Some(2),
+					..Default::default()
+				},
+			)]),
+		};
+		assert_eq!(
+			serde_json::to_string(&result).unwrap(),
+			r#"{"pre":{"0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa":{"balance":"0x2","nonce":1}},"post":{"0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa":{"nonce":2}}}"#
+		);
+	}
+}
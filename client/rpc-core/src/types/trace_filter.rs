@@ -0,0 +1,179 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{H160, H256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{BlockNumberOrHash, Bytes};
+
+/// Filter of the `trace_filter` method.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TraceFilter {
+	/// First block to search, the latest block when unset.
+	pub from_block: Option<BlockNumberOrHash>,
+	/// Last block to search, the latest block when unset.
+	pub to_block: Option<BlockNumberOrHash>,
+	/// Senders of the calls, matching any sender when empty.
+	pub from_address: Vec<H160>,
+	/// Recipients of the calls, matching any recipient when empty.
+	pub to_address: Vec<H160>,
+	/// Number of matching calls to skip.
+	pub after: Option<u32>,
+	/// Maximum number of calls to return.
+	pub count: Option<u32>,
+}
+
+/// A call of a transaction, in the format of OpenEthereum's `trace_*` methods.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedTrace {
+	pub action: TraceAction,
+	/// `None` for failed calls and self destructs.
+	pub result: Option<TraceResult>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// Number of direct sub calls.
+	pub subtraces: u32,
+	/// Indexes of the call and its ancestors among their siblings, empty for the top level call.
+	pub trace_address: Vec<u32>,
+	pub block_hash: H256,
+	pub block_number: u64,
+	pub transaction_hash: H256,
+	pub transaction_position: u32,
+	#[serde(rename = "type")]
+	pub trace_type: TraceType,
+}
+
+/// Kind of a traced call.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceType {
+	Call,
+	Create,
+	Suicide,
+}
+
+/// Parameters of a traced call.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TraceAction {
+	Call(CallAction),
+	Create(CreateAction),
+	Suicide(SuicideAction),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallAction {
+	pub from: H160,
+	pub to: H160,
+	pub value: U256,
+	pub gas: U256,
+	pub input: Bytes,
+	/// One of `call`, `callcode`, `delegatecall` and `staticcall`.
+	pub call_type: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAction {
+	pub from: H160,
+	pub value: U256,
+	pub gas: U256,
+	pub init: Bytes,
+	/// Either `create` or `create2`.
+	pub creation_method: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuicideAction {
+	pub address: H160,
+	pub refund_address: H160,
+	pub balance: U256,
+}
+
+/// Outcome of a successful call.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TraceResult {
+	Call {
+		#[serde(rename = "gasUsed")]
+		gas_used: U256,
+		output: Bytes,
+	},
+	Create {
+		#[serde(rename = "gasUsed")]
+		gas_used: U256,
+		code: Bytes,
+		address: H160,
+	},
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_deserialize_trace_filter() {
+		let filter: TraceFilter = serde_json::from_str(
+			r#"{"fromBlock":"0x1","toAddress":["0x0000000000000000000000000000000000000001"],"count":10}"#,
+		)
+		.unwrap();
+		assert_eq!(
+			filter,
+			TraceFilter {
+				from_block: Some(BlockNumberOrHash::Num(1)),
+				to_address: vec![H160::from_low_u64_be(1)],
+				count: Some(10),
+				..Default::default()
+			}
+		);
+	}
+
+	#[test]
+	fn test_serialize_localized_trace() {
+		let trace = LocalizedTrace {
+			action: TraceAction::Call(CallAction {
+				from: H160::zero(),
+				to: H160::from_low_u64_be(1),
+				value: U256::zero(),
+				gas: U256::from(21_000),
+				input: Bytes(vec![]),
+				call_type: "call".to_string(),
+			}),
+			result: None,
+			error: Some("Reverted".to_string()),
+			subtraces: 0,
+			trace_address: vec![0],
+			block_hash: H256::zero(),
+			block_number: 1,
+			transaction_hash: H256::zero(),
+			transaction_position: 0,
+			trace_type: TraceType::Call,
+		};
+		let value = serde_json::to_value(trace).unwrap();
+		assert_eq!(value["type"], "call");
+		assert_eq!(value["action"]["callType"], "call");
+		assert_eq!(value["action"]["gas"], "0x5208");
+		assert_eq!(value["result"], serde_json::Value::Null);
+		assert_eq!(value["error"], "Reverted");
+		assert_eq!(value["traceAddress"], serde_json::json!([0]));
+	}
+}
@@ -16,11 +16,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use ethereum::{AccessListItem, TransactionAction, TransactionV2 as EthereumTransaction};
+use ethereum::{AccessListItem, TransactionAction, TransactionV3 as EthereumTransaction};
 use ethereum_types::{H160, H256, U256, U64};
 use serde::{ser::SerializeStruct, Serialize, Serializer};
 
-use crate::types::{BuildFrom, Bytes};
+use crate::types::{AuthorizationListItem, BuildFrom, Bytes};
 
 /// Transaction
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
@@ -66,6 +66,9 @@ pub struct Transaction {
 	/// Pre-pay to warm storage access.
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub access_list: Option<Vec<AccessListItem>>,
+	/// EIP-7702 authorizations.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub authorization_list: Option<Vec<AuthorizationListItem>>,
 	/// The parity (0 for even, 1 for odd) of the y-value of the secp256k1 signature.
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub y_parity: Option<U256>,
@@ -106,6 +109,7 @@ impl BuildFrom for Transaction {
 				creates: None,
 				chain_id: t.signature.chain_id().map(U64::from),
 				access_list: None,
+				authorization_list: None,
 				y_parity: None,
 				v: Some(U256::from(t.signature.v())),
 				r: U256::from(t.signature.r().as_bytes()),
@@ -132,6 +136,7 @@ impl BuildFrom for Transaction {
 				creates: None,
 				chain_id: Some(U64::from(t.chain_id)),
 				access_list: Some(t.access_list.clone()),
+				authorization_list: None,
 				y_parity: Some(U256::from(t.odd_y_parity as u8)),
 				v: Some(U256::from(t.odd_y_parity as u8)),
 				r: U256::from(t.r.as_bytes()),
@@ -159,6 +164,41 @@ impl BuildFrom for Transaction {
 				creates: None,
 				chain_id: Some(U64::from(t.chain_id)),
 				access_list: Some(t.access_list.clone()),
+				authorization_list: None,
+				y_parity: Some(U256::from(t.odd_y_parity as u8)),
+				v: Some(U256::from(t.odd_y_parity as u8)),
+				r: U256::from(t.r.as_bytes()),
+				s: U256::from(t.s.as_bytes()),
+			},
+			EthereumTransaction::EIP7702(t) => Self {
+				transaction_type: U256::from(4),
+				hash,
+				nonce: t.nonce,
+				block_hash: None,
+				block_number: None,
+				transaction_index: None,
+				from,
+				to: match t.action {
+					TransactionAction::Call(to) => Some(to),
+					TransactionAction::Create => None,
+				},
+				value: t.value,
+				gas: t.gas_limit,
+				// If transaction is not mined yet, gas price is considered just max fee per gas.
+				gas_price: Some(t.max_fee_per_gas),
+				max_fee_per_gas: Some(t.max_fee_per_gas),
+				max_priority_fee_per_gas: Some(t.max_priority_fee_per_gas),
+				input: Bytes(t.input.clone()),
+				creates: None,
+				chain_id: Some(U64::from(t.chain_id)),
+				access_list: Some(t.access_list.clone()),
+				authorization_list: Some(
+					t.authorization_list
+						.iter()
+						.cloned()
+						.map(Into::into)
+						.collect(),
+				),
 				y_parity: Some(U256::from(t.odd_y_parity as u8)),
 				v: Some(U256::from(t.odd_y_parity as u8)),
 				r: U256::from(t.r.as_bytes()),
@@ -17,13 +17,13 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use ethereum::{
-	AccessListItem, EIP1559TransactionMessage, EIP2930TransactionMessage, LegacyTransactionMessage,
-	TransactionAction,
+	AccessListItem, EIP1559TransactionMessage, EIP2930TransactionMessage,
+	EIP7702TransactionMessage, LegacyTransactionMessage, TransactionAction,
 };
 use ethereum_types::{H160, U256, U64};
 use serde::{Deserialize, Deserializer};
 
-use crate::types::Bytes;
+use crate::types::{AuthorizationListItem, Bytes};
 
 /// Transaction request from the RPC.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
@@ -54,6 +54,8 @@ pub struct TransactionRequest {
 
 	/// EIP-2930 access list
 	pub access_list: Option<Vec<AccessListItem>>,
+	/// EIP-7702 authorization list
+	pub authorization_list: Option<Vec<AuthorizationListItem>>,
 	/// Chain ID that this transaction is valid on
 	pub chain_id: Option<U64>,
 
@@ -134,10 +136,37 @@ pub enum TransactionMessage {
 	Legacy(LegacyTransactionMessage),
 	EIP2930(EIP2930TransactionMessage),
 	EIP1559(EIP1559TransactionMessage),
+	EIP7702(EIP7702TransactionMessage),
 }
 
 impl From<TransactionRequest> for Option<TransactionMessage> {
 	fn from(req: TransactionRequest) -> Self {
+		// EIP7702
+		// Set-code transactions cannot create contracts and must carry at least one
+		// authorization.
+		if let Some(authorization_list) = req.authorization_list {
+			if authorization_list.is_empty() {
+				return None;
+			}
+
+			return Some(TransactionMessage::EIP7702(EIP7702TransactionMessage {
+				action: TransactionAction::Call(req.to?),
+				nonce: req.nonce.unwrap_or_default(),
+				max_priority_fee_per_gas: req.max_priority_fee_per_gas.unwrap_or_default(),
+				max_fee_per_gas: req.max_fee_per_gas.unwrap_or_default(),
+				gas_limit: req.gas.unwrap_or_default(),
+				value: req.value.unwrap_or_default(),
+				input: req
+					.data
+					.into_bytes()
+					.map(|bytes| bytes.into_vec())
+					.unwrap_or_default(),
+				access_list: req.access_list.unwrap_or_default(),
+				authorization_list: authorization_list.into_iter().map(Into::into).collect(),
+				chain_id: req.chain_id.map(|id| id.as_u64()).unwrap_or_default(),
+			}));
+		}
+
 		match (req.max_fee_per_gas, &req.access_list, req.gas_price) {
 			// EIP1559
 			// Empty fields fall back to the canonical transaction schema.
@@ -308,4 +337,43 @@ mod tests {
 			}
 		);
 	}
+
+	#[test]
+	fn test_authorization_list_builds_eip7702_message() {
+		let data = json!({
+			"from": "0x60be2d1d3665660d22ff9624b7be0551ee1ac91b",
+			"to": "0x13fe2d1d3665660d22ff9624b7be0551ee1ac91b",
+			"maxFeePerGas": "0x20",
+			"gas": "0x40",
+			"authorizationList": [{
+				"chainId": "0x1",
+				"address": "0x60be2d1d3665660d22ff9624b7be0551ee1ac91b",
+				"nonce": "0x2",
+				"yParity": "0x1",
+				"r": "0x3",
+				"s": "0x4"
+			}]
+		});
+
+		let args = serde_json::from_value::<TransactionRequest>(data).unwrap();
+		match Option::<TransactionMessage>::from(args) {
+			Some(TransactionMessage::EIP7702(message)) => {
+				assert_eq!(message.authorization_list.len(), 1);
+				let item = &message.authorization_list[0];
+				assert_eq!(item.chain_id, 1);
+				assert_eq!(item.nonce, U256::from(2));
+				assert!(item.y_parity);
+			}
+			_ => panic!("expected an EIP-7702 message"),
+		}
+
+		// An empty authorization list is invalid.
+		let data = json!({
+			"from": "0x60be2d1d3665660d22ff9624b7be0551ee1ac91b",
+			"to": "0x13fe2d1d3665660d22ff9624b7be0551ee1ac91b",
+			"authorizationList": []
+		});
+		let args = serde_json::from_value::<TransactionRequest>(data).unwrap();
+		assert!(Option::<TransactionMessage>::from(args).is_none());
+	}
 }
@@ -18,7 +18,7 @@
 
 use std::collections::HashMap;
 
-use ethereum::{TransactionAction, TransactionV2 as EthereumTransaction};
+use ethereum::{TransactionAction, TransactionV3 as EthereumTransaction};
 use ethereum_types::{H160, U256};
 use serde::{Serialize, Serializer};
 
@@ -70,6 +70,7 @@ impl BuildFrom for Summary {
 			EthereumTransaction::Legacy(t) => (t.action, t.value, t.gas_price, t.gas_limit),
 			EthereumTransaction::EIP2930(t) => (t.action, t.value, t.gas_price, t.gas_limit),
 			EthereumTransaction::EIP1559(t) => (t.action, t.value, t.max_fee_per_gas, t.gas_limit),
+			EthereumTransaction::EIP7702(t) => (t.action, t.value, t.max_fee_per_gas, t.gas_limit),
 		};
 		Self {
 			to: match action {
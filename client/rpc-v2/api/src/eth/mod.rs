@@ -25,7 +25,7 @@ use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 pub use self::pubsub::*;
 use crate::types::{
 	access_list::AccessListResult,
-	block::Block,
+	block::{Block, BlockOverrides},
 	block_id::{BlockNumberOrTag, BlockNumberOrTagOrHash},
 	bytes::Bytes,
 	fee::FeeHistoryResult,
@@ -175,7 +175,7 @@ pub trait EthExecuteApi {
 		request: TransactionRequest,
 		number_or_hash: Option<BlockNumberOrTagOrHash>,
 		state_overrides: Option<StateOverrides>,
-		// block_overrides: Option<BlockOverrides>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<Bytes>;
 
 	/// Generates and returns an estimate of hou much gas is necessary to allow the transaction to complete.
@@ -185,6 +185,7 @@ pub trait EthExecuteApi {
 		request: TransactionRequest,
 		number_or_hash: Option<BlockNumberOrTag>,
 		state_overrides: Option<StateOverrides>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<U256>;
 
 	/// Generates an access list for a transaction.
@@ -308,7 +309,7 @@ pub trait EthStateApi {
 	async fn proof(
 		&self,
 		address: Address,
-		storage_keys: H256,
+		storage_keys: Vec<H256>,
 		block: Option<BlockNumberOrTagOrHash>,
 	) -> RpcResult<AccountProof>;
 }
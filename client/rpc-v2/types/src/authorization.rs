@@ -0,0 +1,38 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{Address, U256, U64};
+use serde::{Deserialize, Serialize};
+
+/// A list of signed authorizations.
+///
+/// Each authorization sets the code of its signer to a delegation designator pointing at
+/// `address` (introduced in [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702)).
+pub type AuthorizationList = Vec<SignedAuthorization>;
+
+/// The item of authorization list.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedAuthorization {
+	pub chain_id: U64,
+	pub address: Address,
+	pub nonce: U64,
+	pub y_parity: U64,
+	pub r: U256,
+	pub s: U256,
+}
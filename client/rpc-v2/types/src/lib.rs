@@ -21,6 +21,7 @@
 #![warn(unused_crate_dependencies)]
 
 pub mod access_list;
+pub mod authorization;
 pub mod block;
 pub mod block_id;
 pub mod bytes;
@@ -36,7 +37,7 @@ pub mod transaction;
 pub mod txpool;
 
 pub use self::{
-	access_list::*, block::*, block_id::*, bytes::Bytes, fee::*, filter::*, index::Index, log::Log,
-	proof::*, pubsub::*, state::*, sync::*, transaction::*, txpool::*,
+	access_list::*, authorization::*, block::*, block_id::*, bytes::Bytes, fee::*, filter::*,
+	index::Index, log::Log, proof::*, pubsub::*, state::*, sync::*, transaction::*, txpool::*,
 };
 pub use ethereum_types::{Address, Bloom, H256, U128, U256, U64};
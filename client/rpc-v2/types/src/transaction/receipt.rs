@@ -19,7 +19,7 @@
 use ethereum_types::{Address, Bloom, H256, U256, U64};
 use serde::{Deserialize, Serialize};
 
-use crate::{log::Log, transaction::TxType};
+use crate::{bytes::Bytes, log::Log, transaction::TxType};
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -64,4 +64,8 @@ pub struct TransactionReceipt {
 	/// The post-transaction state root (pre Byzantium).
 	#[serde(rename = "root", skip_serializing_if = "Option::is_none")]
 	pub state_root: Option<H256>,
+
+	/// The (possibly truncated) output of a reverted transaction.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub revert_reason: Option<Bytes>,
 }
@@ -23,7 +23,9 @@ use serde::{
 	Deserialize, Serialize,
 };
 
-use crate::{access_list::AccessList, bytes::Bytes, transaction::TxType};
+use crate::{
+	access_list::AccessList, authorization::AuthorizationList, bytes::Bytes, transaction::TxType,
+};
 
 /// Transaction request from the RPC.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
@@ -71,6 +73,10 @@ pub struct TransactionRequest {
 	/// EIP-2930 access list
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub access_list: Option<AccessList>,
+
+	/// EIP-7702 authorization list
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub authorization_list: Option<AuthorizationList>,
 }
 
 impl TransactionRequest {
@@ -142,6 +148,12 @@ impl TransactionRequest {
 		self
 	}
 
+	/// Sets the authorization list for the transaction.
+	pub fn authorization_list(mut self, authorization_list: AuthorizationList) -> Self {
+		self.authorization_list = Some(authorization_list);
+		self
+	}
+
 	/// Returns the configured fee cap, if any.
 	///
 	/// The returns `gas_price` (legacy) if set or `max_fee_per_gas` (EIP1559)
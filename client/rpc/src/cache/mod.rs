@@ -24,7 +24,7 @@ use std::{
 	sync::{Arc, Mutex},
 };
 
-use ethereum::BlockV2 as EthereumBlock;
+use ethereum::BlockV3 as EthereumBlock;
 use ethereum_types::U256;
 use futures::StreamExt;
 use tokio::sync::{mpsc, oneshot};
@@ -40,7 +40,7 @@ use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto
 // Frontier
 use fc_rpc_core::types::*;
 use fc_storage::StorageOverride;
-use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
+use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatusV2};
 
 use self::lru_cache::LRUCacheByteLimited;
 
@@ -58,11 +58,11 @@ enum EthBlockDataCacheMessage<B: BlockT> {
 
 	RequestCurrentTransactionStatuses {
 		block_hash: B::Hash,
-		response_tx: oneshot::Sender<Option<Vec<TransactionStatus>>>,
+		response_tx: oneshot::Sender<Option<Vec<TransactionStatusV2>>>,
 	},
 	FetchedCurrentTransactionStatuses {
 		block_hash: B::Hash,
-		statuses: Option<Vec<TransactionStatus>>,
+		statuses: Option<Vec<TransactionStatusV2>>,
 	},
 }
 
@@ -90,7 +90,7 @@ impl<B: BlockT> EthBlockDataCacheTask<B> {
 				blocks_cache_max_size as u64,
 				prometheus_registry.clone(),
 			);
-			let mut statuses_cache = LRUCacheByteLimited::<B::Hash, Vec<TransactionStatus>>::new(
+			let mut statuses_cache = LRUCacheByteLimited::<B::Hash, Vec<TransactionStatusV2>>::new(
 				"statuses_cache",
 				statuses_cache_max_size as u64,
 				prometheus_registry,
@@ -99,7 +99,7 @@ impl<B: BlockT> EthBlockDataCacheTask<B> {
 			let mut awaiting_blocks =
 				HashMap::<B::Hash, Vec<oneshot::Sender<Option<EthereumBlock>>>>::new();
 			let mut awaiting_statuses =
-				HashMap::<B::Hash, Vec<oneshot::Sender<Option<Vec<TransactionStatus>>>>>::new();
+				HashMap::<B::Hash, Vec<oneshot::Sender<Option<Vec<TransactionStatusV2>>>>>::new();
 
 			// Handle all incoming messages.
 			// Exits when there are no more senders.
@@ -230,7 +230,7 @@ impl<B: BlockT> EthBlockDataCacheTask<B> {
 	pub async fn current_transaction_statuses(
 		&self,
 		block_hash: B::Hash,
-	) -> Option<Vec<TransactionStatus>> {
+	) -> Option<Vec<TransactionStatusV2>> {
 		let (response_tx, response_rx) = oneshot::channel();
 
 		self.0
@@ -334,16 +334,21 @@ where
 					.enumerate()
 					.map(|(i, receipt)| TransactionHelper {
 						gas_used: match receipt {
-							ethereum::ReceiptV3::Legacy(d) | ethereum::ReceiptV3::EIP2930(d) | ethereum::ReceiptV3::EIP1559(d) => used_gas(d.used_gas, &mut previous_cumulative_gas),
+							ethereum::ReceiptV4::Legacy(d) | ethereum::ReceiptV4::EIP2930(d) | ethereum::ReceiptV4::EIP1559(d) | ethereum::ReceiptV4::EIP7702(d) => used_gas(d.used_gas, &mut previous_cumulative_gas),
 						},
 						effective_reward: match block.transactions.get(i) {
-							Some(ethereum::TransactionV2::Legacy(t)) => {
+							Some(ethereum::TransactionV3::Legacy(t)) => {
 								UniqueSaturatedInto::<u64>::unique_saturated_into(t.gas_price.saturating_sub(base_fee))
 							}
-							Some(ethereum::TransactionV2::EIP2930(t)) => {
+							Some(ethereum::TransactionV3::EIP2930(t)) => {
 								UniqueSaturatedInto::<u64>::unique_saturated_into(t.gas_price.saturating_sub(base_fee))
 							}
-							Some(ethereum::TransactionV2::EIP1559(t)) => UniqueSaturatedInto::<u64>::unique_saturated_into(
+							Some(ethereum::TransactionV3::EIP1559(t)) => UniqueSaturatedInto::<u64>::unique_saturated_into(
+									t
+										.max_priority_fee_per_gas
+										.min(t.max_fee_per_gas.saturating_sub(base_fee))
+							),
+							Some(ethereum::TransactionV3::EIP7702(t)) => UniqueSaturatedInto::<u64>::unique_saturated_into(
 									t
 										.max_priority_fee_per_gas
 										.min(t.max_fee_per_gas.saturating_sub(base_fee))
@@ -16,23 +16,34 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{marker::PhantomData, sync::Arc};
+use std::{collections::BTreeMap, marker::PhantomData, sync::Arc};
 
 use ethereum::EnvelopedEncodable;
-use ethereum_types::H256;
+use ethereum_types::{H160, H256, U256, U64};
+use evm::{ExitError, ExitReason};
 use jsonrpsee::core::{async_trait, RpcResult};
 use rlp::Encodable;
 // Substrate
-use sc_client_api::backend::{Backend, StorageProvider};
-use sp_api::ProvideRuntimeApi;
+use sc_client_api::{
+	backend::{Backend, StorageProvider},
+	BlockBackend,
+};
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_runtime::traits::Block as BlockT;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 // Frontier
 use fc_rpc_core::{types::*, DebugApiServer};
 use fc_storage::StorageOverride;
-use fp_rpc::EthereumRuntimeRPCApi;
+use fp_evm::{
+	CallTracerConfig, CallType, PrestateTracerConfig, StructLoggerConfig,
+	TracerConfig as RuntimeTracerConfig,
+};
+use fp_rpc::{DebugRuntimeApi, EthereumRuntimeRPCApi};
 
-use crate::{cache::EthBlockDataCacheTask, frontier_backend_client, internal_err};
+use crate::{
+	cache::EthBlockDataCacheTask, eth::fee_details, frontier_backend_client, internal_err,
+	revert::RevertReason,
+};
 
 /// Debug API implementation.
 pub struct Debug<B: BlockT, C, BE> {
@@ -59,7 +70,7 @@ impl<B: BlockT, C, BE> Debug<B, C, BE> {
 		}
 	}
 
-	async fn block_by(&self, number: BlockNumberOrHash) -> RpcResult<Option<ethereum::BlockV2>>
+	async fn substrate_hash_by(&self, number: BlockNumberOrHash) -> RpcResult<Option<B::Hash>>
 	where
 		C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
 		BE: Backend<B>,
@@ -79,6 +90,18 @@ impl<B: BlockT, C, BE> Debug<B, C, BE> {
 			.client
 			.expect_block_hash_from_id(&id)
 			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
+		Ok(Some(substrate_hash))
+	}
+
+	async fn block_by(&self, number: BlockNumberOrHash) -> RpcResult<Option<ethereum::BlockV3>>
+	where
+		C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+		BE: Backend<B>,
+	{
+		let substrate_hash = match self.substrate_hash_by(number).await? {
+			Some(hash) => hash,
+			None => return Ok(None),
+		};
 		let block = self.block_data_cache.current_block(substrate_hash).await;
 		Ok(block)
 	}
@@ -86,7 +109,7 @@ impl<B: BlockT, C, BE> Debug<B, C, BE> {
 	async fn transaction_by(
 		&self,
 		transaction_hash: H256,
-	) -> RpcResult<Option<ethereum::TransactionV2>>
+	) -> RpcResult<Option<ethereum::TransactionV3>>
 	where
 		C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
 		BE: Backend<B>,
@@ -125,39 +148,313 @@ impl<B: BlockT, C, BE> Debug<B, C, BE> {
 	async fn receipts_by(
 		&self,
 		number: BlockNumberOrHash,
-	) -> RpcResult<Option<Vec<ethereum::ReceiptV3>>>
+	) -> RpcResult<Option<Vec<ethereum::ReceiptV4>>>
 	where
 		C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
 		BE: Backend<B>,
 	{
-		let id = match frontier_backend_client::native_block_id::<B, C>(
-			self.client.as_ref(),
-			self.backend.as_ref(),
-			Some(number),
-		)
-		.await?
-		{
-			Some(id) => id,
+		let substrate_hash = match self.substrate_hash_by(number).await? {
+			Some(hash) => hash,
 			None => return Ok(None),
 		};
 
-		let substrate_hash = self
-			.client
-			.expect_block_hash_from_id(&id)
-			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
-
 		// TODO: use data cache in the future
 		let receipts = self.storage_override.current_receipts(substrate_hash);
 		Ok(receipts)
 	}
+
+	/// Header and extrinsics of the substrate block, along with the hash of its parent.
+	fn substrate_block(
+		&self,
+		substrate_hash: B::Hash,
+	) -> RpcResult<(B::Hash, B::Header, Vec<B::Extrinsic>)>
+	where
+		C: ProvideRuntimeApi<B> + BlockBackend<B>,
+		C::Api: DebugRuntimeApi<B>,
+	{
+		let block = self
+			.client
+			.block(substrate_hash)
+			.map_err(|err| internal_err(format!("{:?}", err)))?
+			.ok_or_else(|| internal_err("Block not found"))?
+			.block;
+		let (header, extrinsics) = block.deconstruct();
+		let parent_hash = *header.parent_hash();
+		self.ensure_debug_api(parent_hash)?;
+		Ok((parent_hash, header, extrinsics))
+	}
+
+	/// Ensure the runtime of the given block supports tracing.
+	fn ensure_debug_api(&self, substrate_hash: B::Hash) -> RpcResult<()>
+	where
+		C: ProvideRuntimeApi<B>,
+		C::Api: DebugRuntimeApi<B>,
+	{
+		let has_api = self
+			.client
+			.runtime_api()
+			.has_api::<dyn DebugRuntimeApi<B>>(substrate_hash)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?;
+		if !has_api {
+			return Err(internal_err(
+				"`DebugRuntimeApi` is not supported by the runtime",
+			));
+		}
+		Ok(())
+	}
+
+	async fn trace_block_by(
+		&self,
+		number: BlockNumberOrHash,
+		params: Option<TraceParams>,
+	) -> RpcResult<Vec<BlockTransactionTrace>>
+	where
+		C: ProvideRuntimeApi<B> + BlockBackend<B>,
+		C::Api: DebugRuntimeApi<B>,
+		C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+		BE: Backend<B>,
+	{
+		let tracer = tracer_config(params)?;
+		let substrate_hash = self
+			.substrate_hash_by(number)
+			.await?
+			.ok_or_else(|| internal_err("Block not found"))?;
+		let transaction_hashes = self
+			.block_data_cache
+			.current_block(substrate_hash)
+			.await
+			.map(|block| {
+				block
+					.transactions
+					.iter()
+					.map(|t| t.hash())
+					.collect::<Vec<_>>()
+			})
+			.unwrap_or_default();
+
+		let (parent_hash, header, extrinsics) = self.substrate_block(substrate_hash)?;
+		let traces = self
+			.client
+			.runtime_api()
+			.trace_block(parent_hash, &header, extrinsics, tracer)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			.map_err(|err| internal_err(format!("failed to trace block: {err:?}")))?;
+
+		Ok(transaction_hashes
+			.into_iter()
+			.zip(traces)
+			.map(|(tx_hash, trace)| BlockTransactionTrace {
+				tx_hash,
+				result: transaction_trace(trace),
+			})
+			.collect())
+	}
+}
+
+/// Build the runtime tracer requested by the `debug_trace*` parameters.
+fn tracer_config(params: Option<TraceParams>) -> RpcResult<RuntimeTracerConfig> {
+	let params = params.unwrap_or_default();
+	match params.tracer.as_deref() {
+		None => Ok(RuntimeTracerConfig::StructLogger(StructLoggerConfig {
+			disable_stack: params.disable_stack,
+			disable_memory: params.disable_memory,
+			disable_storage: params.disable_storage,
+		})),
+		Some("callTracer") => Ok(RuntimeTracerConfig::CallTracer(CallTracerConfig {
+			only_top_call: params.tracer_config.only_top_call,
+			with_log: params.tracer_config.with_log,
+		})),
+		Some("prestateTracer") => Ok(RuntimeTracerConfig::PrestateTracer(PrestateTracerConfig {
+			diff_mode: params.tracer_config.diff_mode,
+		})),
+		Some(tracer) => Err(internal_err(format!("tracer {tracer} is not supported"))),
+	}
+}
+
+fn transaction_trace(trace: fp_evm::TransactionTrace) -> TransactionTrace {
+	match trace {
+		fp_evm::TransactionTrace::StructLogger(trace) => {
+			TransactionTrace::StructLogger(StructLoggerResult {
+				gas: trace.gas,
+				failed: trace.failed,
+				return_value: hex::encode(trace.return_value),
+				struct_logs: trace.struct_logs.into_iter().map(struct_log).collect(),
+			})
+		}
+		fp_evm::TransactionTrace::CallTracer(frame) => {
+			TransactionTrace::CallTracer(call_frame(frame))
+		}
+		fp_evm::TransactionTrace::PrestateTracer(trace) => {
+			TransactionTrace::PrestateTracer(prestate_result(trace))
+		}
+	}
+}
+
+fn call_frame(frame: fp_evm::CallFrame) -> CallFrame {
+	let call_type = match frame.call_type {
+		CallType::Call => "CALL",
+		CallType::CallCode => "CALLCODE",
+		CallType::DelegateCall => "DELEGATECALL",
+		CallType::StaticCall => "STATICCALL",
+		CallType::Create => "CREATE",
+		CallType::Create2 => "CREATE2",
+		CallType::SelfDestruct => "SELFDESTRUCT",
+	};
+	let revert_reason = match frame.exit_reason {
+		Some(ExitReason::Revert(_)) => revert_reason(&frame.output),
+		_ => None,
+	};
+	CallFrame {
+		call_type: call_type.to_string(),
+		from: frame.from,
+		to: Some(frame.to),
+		value: frame.value,
+		gas: U64::from(frame.gas),
+		gas_used: U64::from(frame.gas_used),
+		input: Bytes(frame.input),
+		output: (!frame.output.is_empty()).then_some(Bytes(frame.output)),
+		error: frame.exit_reason.as_ref().and_then(exit_error),
+		revert_reason,
+		logs: frame
+			.logs
+			.into_iter()
+			.map(|log| CallLog {
+				address: log.address,
+				topics: log.topics,
+				data: Bytes(log.data),
+				position: U64::from(log.position),
+			})
+			.collect(),
+		calls: frame.calls.into_iter().map(call_frame).collect(),
+	}
+}
+
+/// Error message of a failed call, worded as geth does.
+fn exit_error(reason: &ExitReason) -> Option<String> {
+	let message = match reason {
+		ExitReason::Succeed(_) => return None,
+		ExitReason::Revert(_) => "execution reverted",
+		ExitReason::Error(err) => match err {
+			ExitError::StackUnderflow => "stack underflow",
+			ExitError::StackOverflow => "stack overflow",
+			ExitError::InvalidJump => "invalid jump destination",
+			ExitError::InvalidRange => "return data out of bounds",
+			ExitError::DesignatedInvalid => "invalid opcode: INVALID",
+			ExitError::CallTooDeep => "max call depth exceeded",
+			ExitError::CreateCollision => "contract address collision",
+			ExitError::CreateContractLimit => "max code size exceeded",
+			ExitError::InvalidCode(_) => "invalid code: must not begin with 0xef",
+			ExitError::OutOfGas => "out of gas",
+			ExitError::OutOfFund => "insufficient balance for transfer",
+			ExitError::Other(message) => return Some(message.to_string()),
+			err => return Some(format!("{err:?}")),
+		},
+		ExitReason::Fatal(err) => return Some(format!("evm fatal: {err:?}")),
+	};
+	Some(message.to_string())
+}
+
+/// Message of a revert with a solidity `Error(string)` or `Panic(uint256)`.
+fn revert_reason(output: &[u8]) -> Option<String> {
+	RevertReason::decode(output).map(|reason| reason.to_string())
+}
+
+fn prestate_account(state: fp_evm::AccountState) -> PrestateAccount {
+	PrestateAccount {
+		balance: Some(state.balance),
+		nonce: (!state.nonce.is_zero()).then(|| state.nonce.low_u64()),
+		code: (!state.code.is_empty()).then_some(Bytes(state.code)),
+		storage: (!state.storage.is_empty()).then_some(state.storage),
+	}
+}
+
+/// Convert the prestate trace, keeping in diff mode only the accounts and fields that changed.
+fn prestate_result(trace: fp_evm::PrestateTrace) -> PrestateResult {
+	let Some(mut post_state) = trace.post else {
+		let pre = trace
+			.pre
+			.into_iter()
+			.map(|(address, state)| (address, prestate_account(state)))
+			.collect();
+		return PrestateResult::Prestate(pre);
+	};
+
+	let mut pre = BTreeMap::new();
+	let mut post = BTreeMap::new();
+	for (address, before) in trace.pre {
+		let after = post_state.remove(&address).unwrap_or_default();
+		if before == after {
+			continue;
+		}
+		let changed_slots = |from: &BTreeMap<H256, H256>, to: &BTreeMap<H256, H256>| {
+			from.iter()
+				.filter(|(slot, value)| !value.is_zero() && to.get(*slot) != Some(*value))
+				.map(|(slot, value)| (*slot, *value))
+				.collect::<BTreeMap<_, _>>()
+		};
+		let post_storage = changed_slots(&after.storage, &before.storage);
+		let after_account = PrestateAccount {
+			balance: (after.balance != before.balance).then_some(after.balance),
+			nonce: (after.nonce != before.nonce).then(|| after.nonce.low_u64()),
+			code: (after.code != before.code && !after.code.is_empty())
+				.then(|| Bytes(after.code.clone())),
+			storage: (!post_storage.is_empty()).then_some(post_storage),
+		};
+		if after_account != PrestateAccount::default() {
+			post.insert(address, after_account);
+		}
+
+		let existed =
+			!before.balance.is_zero() || !before.nonce.is_zero() || !before.code.is_empty();
+		if existed {
+			let storage = changed_slots(&before.storage, &after.storage);
+			pre.insert(
+				address,
+				prestate_account(fp_evm::AccountState { storage, ..before }),
+			);
+		}
+	}
+	PrestateResult::Diff { pre, post }
+}
+
+fn struct_log(log: fp_evm::StructLog) -> StructLog {
+	StructLog {
+		pc: log.pc,
+		op: opcode_name(log.op),
+		gas: log.gas,
+		gas_cost: log.gas_cost,
+		depth: log.depth,
+		stack: log.stack.map(|stack| {
+			stack
+				.into_iter()
+				.map(|word| U256::from_big_endian(word.as_bytes()))
+				.collect()
+		}),
+		memory: log.memory.map(|memory| {
+			memory
+				.chunks(32)
+				.map(|chunk| {
+					let mut word = [0u8; 32];
+					word[..chunk.len()].copy_from_slice(chunk);
+					hex::encode(word)
+				})
+				.collect()
+		}),
+		storage: log.storage.map(|storage| {
+			storage
+				.into_iter()
+				.map(|(key, value)| (hex::encode(key), hex::encode(value)))
+				.collect()
+		}),
+	}
 }
 
 #[async_trait]
 impl<B, C, BE> DebugApiServer for Debug<B, C, BE>
 where
 	B: BlockT,
-	C: ProvideRuntimeApi<B>,
-	C::Api: EthereumRuntimeRPCApi<B>,
+	C: ProvideRuntimeApi<B> + BlockBackend<B>,
+	C::Api: EthereumRuntimeRPCApi<B> + DebugRuntimeApi<B>,
 	C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
 	BE: Backend<B> + 'static,
 {
@@ -191,4 +488,209 @@ where
 		// We can simply return empty array for this API.
 		Ok(vec![])
 	}
+
+	async fn trace_transaction(
+		&self,
+		transaction_hash: H256,
+		params: Option<TraceParams>,
+	) -> RpcResult<TransactionTrace> {
+		let tracer = tracer_config(params)?;
+		let eth_block_hash = match frontier_backend_client::load_transactions::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			transaction_hash,
+			true,
+		)
+		.await?
+		{
+			Some((hash, _)) => hash,
+			None => return Err(internal_err("Transaction not found")),
+		};
+		let substrate_hash = frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			eth_block_hash,
+		)
+		.await?
+		.ok_or_else(|| internal_err("Block not found"))?;
+
+		let (parent_hash, header, extrinsics) = self.substrate_block(substrate_hash)?;
+		let trace = self
+			.client
+			.runtime_api()
+			.trace_transaction(parent_hash, &header, extrinsics, transaction_hash, tracer)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			.map_err(|err| internal_err(format!("failed to trace transaction: {err:?}")))?;
+		Ok(transaction_trace(trace))
+	}
+
+	async fn trace_block_by_number(
+		&self,
+		number: BlockNumberOrHash,
+		params: Option<TraceParams>,
+	) -> RpcResult<Vec<BlockTransactionTrace>> {
+		self.trace_block_by(number, params).await
+	}
+
+	async fn trace_block_by_hash(
+		&self,
+		hash: H256,
+		params: Option<TraceParams>,
+	) -> RpcResult<Vec<BlockTransactionTrace>> {
+		self.trace_block_by(
+			BlockNumberOrHash::Hash {
+				hash,
+				require_canonical: false,
+			},
+			params,
+		)
+		.await
+	}
+
+	async fn trace_call(
+		&self,
+		request: TransactionRequest,
+		number: Option<BlockNumberOrHash>,
+		params: Option<TraceParams>,
+	) -> RpcResult<TransactionTrace> {
+		let tracer = tracer_config(params)?;
+		let substrate_hash = self
+			.substrate_hash_by(number.unwrap_or(BlockNumberOrHash::Latest))
+			.await?
+			.ok_or_else(|| internal_err("Block not found"))?;
+		self.ensure_debug_api(substrate_hash)?;
+
+		let TransactionRequest {
+			from,
+			to,
+			gas_price,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			gas,
+			value,
+			data,
+			nonce,
+			access_list,
+			authorization_list,
+			..
+		} = request;
+		let details = fee_details(gas_price, max_fee_per_gas, max_priority_fee_per_gas)?;
+		// Use the given gas limit or the one of the block.
+		let gas_limit = match gas {
+			Some(gas) => gas,
+			None => {
+				self.storage_override
+					.current_block(substrate_hash)
+					.ok_or_else(|| internal_err("block unavailable, cannot query gas limit"))?
+					.header
+					.gas_limit
+			}
+		};
+		let access_list = access_list.map(|list| {
+			list.into_iter()
+				.map(|item| (item.address, item.storage_keys))
+				.collect::<Vec<(H160, Vec<H256>)>>()
+		});
+		let authorization_list = authorization_list.map(|list| {
+			list.into_iter()
+				.map(Into::into)
+				.collect::<Vec<ethereum::AuthorizationListItem>>()
+		});
+
+		let from = from.unwrap_or_default();
+		let data = data.into_bytes().map(|d| d.into_vec()).unwrap_or_default();
+		let value = value.unwrap_or_default();
+		let trace = self
+			.client
+			.runtime_api()
+			.trace_call(
+				substrate_hash,
+				from,
+				to,
+				data,
+				value,
+				gas_limit,
+				details.max_fee_per_gas,
+				details.max_priority_fee_per_gas,
+				nonce,
+				access_list,
+				authorization_list,
+				tracer,
+				None,
+			)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			.map_err(|err| internal_err(format!("failed to trace call: {err:?}")))?;
+		Ok(transaction_trace(trace))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn revert_reason_decodes_error_string() {
+		let output = hex::decode(
+			"08c379a0\
+			 0000000000000000000000000000000000000000000000000000000000000020\
+			 0000000000000000000000000000000000000000000000000000000000000004\
+			 6e6f706500000000000000000000000000000000000000000000000000000000",
+		)
+		.unwrap();
+		assert_eq!(revert_reason(&output), Some("nope".to_string()));
+		assert_eq!(revert_reason(&output[..67]), None);
+		assert_eq!(revert_reason(&[0u8; 100]), None);
+	}
+
+	#[test]
+	fn prestate_diff_keeps_changed_fields() {
+		let sender = H160::repeat_byte(0xaa);
+		let untouched = H160::repeat_byte(0xbb);
+		let created = H160::repeat_byte(0xcc);
+		let slot = H256::repeat_byte(1);
+		let account = |balance: u64, nonce: u64| fp_evm::AccountState {
+			balance: balance.into(),
+			nonce: nonce.into(),
+			..Default::default()
+		};
+		let trace = fp_evm::PrestateTrace {
+			pre: BTreeMap::from([
+				(sender, account(10, 1)),
+				(untouched, account(5, 0)),
+				(created, account(0, 0)),
+			]),
+			post: Some(BTreeMap::from([
+				(sender, account(7, 2)),
+				(untouched, account(5, 0)),
+				(
+					created,
+					fp_evm::AccountState {
+						code: vec![0x00],
+						storage: BTreeMap::from([(slot, H256::repeat_byte(2))]),
+						..account(3, 1)
+					},
+				),
+			])),
+		};
+
+		let PrestateResult::Diff { pre, post } = prestate_result(trace) else {
+			panic!("diff mode result expected");
+		};
+		assert_eq!(pre.keys().collect::<Vec<_>>(), vec![&sender]);
+		assert_eq!(pre[&sender].balance, Some(U256::from(10)));
+		assert_eq!(
+			post[&sender],
+			PrestateAccount {
+				balance: Some(U256::from(7)),
+				nonce: Some(2),
+				..Default::default()
+			}
+		);
+		assert!(!post.contains_key(&untouched));
+		assert_eq!(post[&created].code, Some(Bytes(vec![0x00])));
+		assert_eq!(
+			post[&created].storage,
+			Some(BTreeMap::from([(slot, H256::repeat_byte(2))]))
+		);
+	}
 }
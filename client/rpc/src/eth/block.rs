@@ -24,7 +24,7 @@ use jsonrpsee::core::RpcResult;
 use sc_client_api::backend::{Backend, StorageProvider};
 use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::InPoolTransaction;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::hashing::keccak_256;
 use sp_runtime::traits::Block as BlockT;
@@ -161,9 +161,29 @@ where
 						.collect::<Vec<<B as BlockT>::Extrinsic>>(),
 				);
 
-				let (block, statuses) = api
-					.pending_block(best_hash, xts)
-					.map_err(|_| internal_err(format!("Runtime access error at {}", best_hash)))?;
+				let api_version = if let Ok(Some(api_version)) =
+					api.api_version::<dyn EthereumRuntimeRPCApi<B>>(best_hash)
+				{
+					api_version
+				} else {
+					return Err(internal_err("failed to retrieve Runtime Api version"));
+				};
+				let (block, statuses) = if api_version < 9 {
+					#[allow(deprecated)]
+					let (block, statuses) = api
+						.pending_block_before_version_9(best_hash, xts)
+						.map_err(|_| {
+							internal_err(format!("Runtime access error at {}", best_hash))
+						})?;
+					(
+						block,
+						statuses.map(|statuses| statuses.into_iter().map(Into::into).collect()),
+					)
+				} else {
+					api.pending_block(best_hash, xts).map_err(|_| {
+						internal_err(format!("Runtime access error at {}", best_hash))
+					})?
+				};
 
 				let base_fee = api.gas_price(best_hash).ok();
 
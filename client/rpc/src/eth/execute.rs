@@ -33,7 +33,7 @@ use sp_inherents::CreateInherentDataProviders;
 use sp_io::hashing::{blake2_128, twox_128};
 use sp_runtime::{
 	traits::{Block as BlockT, HashingFor},
-	DispatchError, SaturatedConversion,
+	DispatchError,
 };
 use sp_state_machine::OverlayedChanges;
 // Frontier
@@ -45,8 +45,13 @@ use fp_storage::constants::{EVM_ACCOUNT_CODES, EVM_ACCOUNT_STORAGES, PALLET_EVM}
 use crate::{
 	eth::{Eth, EthConfig},
 	frontier_backend_client, internal_err,
+	revert::RevertDecoder,
 };
 
+/// Maximum number of executions of `eth_createAccessList`, after which the access list of the last
+/// execution is returned even if it is still changing.
+const MAX_ACCESS_LIST_ITERATIONS: usize = 10;
+
 /// Allow to adapt a request for `estimate_gas`.
 /// Can be used to estimate gas of some contracts using a different function
 /// in the case the normal gas estimation doesn't work.
@@ -81,6 +86,7 @@ where
 		request: TransactionRequest,
 		number_or_hash: Option<BlockNumberOrHash>,
 		state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<Bytes> {
 		let TransactionRequest {
 			from,
@@ -93,6 +99,7 @@ where
 			data,
 			nonce,
 			access_list,
+			authorization_list,
 			..
 		} = request;
 
@@ -134,6 +141,7 @@ where
 		} else {
 			return Err(internal_err("failed to retrieve Runtime Api version"));
 		};
+		let block_overrides = runtime_block_overrides(block_overrides, api_version)?;
 
 		let block = if api_version > 1 {
 			api.current_block(substrate_hash)
@@ -191,7 +199,11 @@ where
 					.map_err(|err| internal_err(format!("runtime error: {err}")))?
 					.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
-					error_on_execution_failure(&info.exit_reason, &info.value)?;
+					error_on_execution_failure(
+						&self.revert_decoder,
+						&info.exit_reason,
+						&info.value,
+					)?;
 					Ok(Bytes(info.value))
 				} else if api_version >= 2 && api_version < 4 {
 					// Post-london
@@ -211,11 +223,15 @@ where
 					.map_err(|err| internal_err(format!("runtime error: {err}")))?
 					.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
-					error_on_execution_failure(&info.exit_reason, &info.value)?;
+					error_on_execution_failure(
+						&self.revert_decoder,
+						&info.exit_reason,
+						&info.value,
+					)?;
 					Ok(Bytes(info.value))
-				} else if api_version == 4 || api_version == 5 {
+				} else if api_version >= 4 {
 					// Post-london + access list support
-					let encoded_params = Encode::encode(&(
+					let mut encoded_params = Encode::encode(&(
 						&from.unwrap_or_default(),
 						&to,
 						&data,
@@ -233,6 +249,18 @@ where
 								.collect::<Vec<(sp_core::H160, Vec<H256>)>>(),
 						),
 					));
+					if api_version >= 6 {
+						// Authorization list support
+						encoded_params.extend(Encode::encode(&authorization_list.map(|list| {
+							list.into_iter()
+								.map(Into::into)
+								.collect::<Vec<ethereum::AuthorizationListItem>>()
+						})));
+					}
+					if api_version >= 7 {
+						// Block overrides support
+						encoded_params.extend(Encode::encode(&block_overrides));
+					}
 					let overlayed_changes = self.create_overrides_overlay(
 						substrate_hash,
 						api_version,
@@ -265,9 +293,13 @@ where
 							.map_err(|err| internal_err(format!("runtime error: {err}")))?
 							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
-						error_on_execution_failure(&info.exit_reason, &info.value)?;
+						error_on_execution_failure(
+							&self.revert_decoder,
+							&info.exit_reason,
+							&info.value,
+						)?;
 						info.value
-					} else if api_version == 5 {
+					} else if api_version >= 5 {
 						let info = self
 							.client
 							.call_api_at(params)
@@ -284,7 +316,11 @@ where
 							.map_err(|err| internal_err(format!("runtime error: {err}")))?
 							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
-						error_on_execution_failure(&info.exit_reason, &info.value)?;
+						error_on_execution_failure(
+							&self.revert_decoder,
+							&info.exit_reason,
+							&info.value,
+						)?;
 						info.value
 					} else {
 						unreachable!("invalid version");
@@ -312,7 +348,7 @@ where
 					.map_err(|err| internal_err(format!("runtime error: {err}")))?
 					.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
-					error_on_execution_failure(&info.exit_reason, &[])?;
+					error_on_execution_failure(&self.revert_decoder, &info.exit_reason, &[])?;
 
 					let code = api
 						.account_code_at(substrate_hash, info.value)
@@ -335,7 +371,7 @@ where
 					.map_err(|err| internal_err(format!("runtime error: {err}")))?
 					.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
-					error_on_execution_failure(&info.exit_reason, &[])?;
+					error_on_execution_failure(&self.revert_decoder, &info.exit_reason, &[])?;
 
 					let code = api
 						.account_code_at(substrate_hash, info.value)
@@ -365,15 +401,45 @@ where
 					.map_err(|err| internal_err(format!("runtime error: {err}")))?
 					.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
-					error_on_execution_failure(&info.exit_reason, &[])?;
+					error_on_execution_failure(&self.revert_decoder, &info.exit_reason, &[])?;
 
 					let code = api
 						.account_code_at(substrate_hash, info.value)
 						.map_err(|err| internal_err(format!("runtime error: {err}")))?;
 					Ok(Bytes(code))
-				} else if api_version == 5 {
+				} else if api_version >= 5 && api_version < 7 {
 					// Post-london + access list support
 					let access_list = access_list.unwrap_or_default();
+					#[allow(deprecated)]
+					let info = api.create_before_version_7(
+						substrate_hash,
+						from.unwrap_or_default(),
+						data,
+						value.unwrap_or_default(),
+						gas_limit,
+						max_fee_per_gas,
+						max_priority_fee_per_gas,
+						nonce,
+						false,
+						Some(
+							access_list
+								.into_iter()
+								.map(|item| (item.address, item.storage_keys))
+								.collect(),
+						),
+					)
+					.map_err(|err| internal_err(format!("runtime error: {err}")))?
+					.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
+
+					error_on_execution_failure(&self.revert_decoder, &info.exit_reason, &[])?;
+
+					let code = api
+						.account_code_at(substrate_hash, info.value)
+						.map_err(|err| internal_err(format!("runtime error: {err}")))?;
+					Ok(Bytes(code))
+				} else if api_version >= 7 {
+					// Post-london + access list + block overrides support
+					let access_list = access_list.unwrap_or_default();
 					let info = api
 						.create(
 							substrate_hash,
@@ -391,11 +457,12 @@ where
 									.map(|item| (item.address, item.storage_keys))
 									.collect(),
 							),
+							block_overrides,
 						)
 						.map_err(|err| internal_err(format!("runtime error: {err}")))?
 						.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
-					error_on_execution_failure(&info.exit_reason, &[])?;
+					error_on_execution_failure(&self.revert_decoder, &info.exit_reason, &[])?;
 
 					let code = api
 						.account_code_at(substrate_hash, info.value)
@@ -412,6 +479,7 @@ where
 		&self,
 		request: TransactionRequest,
 		number_or_hash: Option<BlockNumberOrHash>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<U256> {
 		let client = Arc::clone(&self.client);
 		let block_data_cache = Arc::clone(&self.block_data_cache);
@@ -533,6 +601,17 @@ where
 			}
 		}
 
+		let api_version = if let Ok(Some(api_version)) =
+			client
+				.runtime_api()
+				.api_version::<dyn EthereumRuntimeRPCApi<B>>(substrate_hash)
+		{
+			api_version
+		} else {
+			return Err(internal_err("failed to retrieve Runtime Api version"));
+		};
+		let block_overrides = runtime_block_overrides(block_overrides, api_version)?;
+
 		struct ExecutableResult {
 			data: Vec<u8>,
 			exit_reason: ExitReason,
@@ -562,6 +641,7 @@ where
 					data,
 					nonce,
 					access_list,
+					authorization_list,
 					..
 				} = request;
 
@@ -635,9 +715,63 @@ where
 							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
 							(info.exit_reason, info.value, info.used_gas)
-						} else {
+						} else if api_version == 5 {
 							// Post-london + access list support
 							let access_list = access_list.unwrap_or_default();
+							#[allow(deprecated)]
+							let info = api.call_before_version_6(
+								substrate_hash,
+								from.unwrap_or_default(),
+								to,
+								data,
+								value.unwrap_or_default(),
+								gas_limit,
+								max_fee_per_gas,
+								max_priority_fee_per_gas,
+								nonce,
+								estimate_mode,
+								Some(
+									access_list
+										.into_iter()
+										.map(|item| (item.address, item.storage_keys))
+										.collect(),
+								),
+							)
+							.map_err(|err| internal_err(format!("runtime error: {err}")))?
+							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
+
+							(info.exit_reason, info.value, info.used_gas.effective)
+						} else if api_version == 6 {
+							// Post-london + access list + authorization list support
+							let access_list = access_list.unwrap_or_default();
+							#[allow(deprecated)]
+							let info = api.call_before_version_7(
+								substrate_hash,
+								from.unwrap_or_default(),
+								to,
+								data,
+								value.unwrap_or_default(),
+								gas_limit,
+								max_fee_per_gas,
+								max_priority_fee_per_gas,
+								nonce,
+								estimate_mode,
+								Some(
+									access_list
+										.into_iter()
+										.map(|item| (item.address, item.storage_keys))
+										.collect(),
+								),
+								authorization_list
+									.map(|list| list.into_iter().map(Into::into).collect()),
+							)
+							.map_err(|err| internal_err(format!("runtime error: {err}")))?
+							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
+
+							(info.exit_reason, info.value, info.used_gas.effective)
+						} else {
+							// Post-london + access list + authorization list + block overrides support
+							let access_list = access_list.unwrap_or_default();
 							let info = api.call(
 								substrate_hash,
 								from.unwrap_or_default(),
@@ -655,6 +789,9 @@ where
 										.map(|item| (item.address, item.storage_keys))
 										.collect(),
 								),
+								authorization_list
+									.map(|list| list.into_iter().map(Into::into).collect()),
+								block_overrides.clone(),
 							)
 							.map_err(|err| internal_err(format!("runtime error: {err}")))?
 							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
@@ -723,9 +860,34 @@ where
 							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
 
 							(info.exit_reason, Vec::new(), info.used_gas)
-						} else {
+						} else if api_version < 7 {
 							// Post-london + access list support
 							let access_list = access_list.unwrap_or_default();
+							#[allow(deprecated)]
+							let info = api.create_before_version_7(
+								substrate_hash,
+								from.unwrap_or_default(),
+								data,
+								value.unwrap_or_default(),
+								gas_limit,
+								max_fee_per_gas,
+								max_priority_fee_per_gas,
+								nonce,
+								estimate_mode,
+								Some(
+									access_list
+										.into_iter()
+										.map(|item| (item.address, item.storage_keys))
+										.collect(),
+								),
+							)
+							.map_err(|err| internal_err(format!("runtime error: {err}")))?
+							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
+
+							(info.exit_reason, Vec::new(), info.used_gas.effective)
+						} else {
+							// Post-london + access list + block overrides support
+							let access_list = access_list.unwrap_or_default();
 							let info = api.create(
 								substrate_hash,
 								from.unwrap_or_default(),
@@ -742,6 +904,7 @@ where
 										.map(|item| (item.address, item.storage_keys))
 										.collect(),
 								),
+								block_overrides.clone(),
 							)
 							.map_err(|err| internal_err(format!("runtime error: {err}")))?
 							.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
@@ -756,15 +919,6 @@ where
 					used_gas,
 				})
 			};
-		let api_version = if let Ok(Some(api_version)) =
-			client
-				.runtime_api()
-				.api_version::<dyn EthereumRuntimeRPCApi<B>>(substrate_hash)
-		{
-			api_version
-		} else {
-			return Err(internal_err("failed to retrieve Runtime Api version"));
-		};
 
 		// Verify that the transaction succeed with the highest capacity
 		let cap = highest;
@@ -814,14 +968,18 @@ where
 							)))
 						}
 						// The execution has been done with block gas limit, so it is not a lack of gas from the user.
-						other => error_on_execution_failure(&other, &data)?,
+						other => error_on_execution_failure(&self.revert_decoder, &other, &data)?,
 					}
 				} else {
 					// The execution has already been done with block gas limit, so it is not a lack of gas from the user.
-					error_on_execution_failure(&ExitReason::Revert(revert), &data)?
+					error_on_execution_failure(
+						&self.revert_decoder,
+						&ExitReason::Revert(revert),
+						&data,
+					)?
 				}
 			}
-			other => error_on_execution_failure(&other, &data)?,
+			other => error_on_execution_failure(&self.revert_decoder, &other, &data)?,
 		};
 
 		#[cfg(not(feature = "rpc-binary-search-estimate"))]
@@ -867,7 +1025,7 @@ where
 					| ExitReason::Error(ExitError::InvalidCode(_)) => {
 						lowest = mid;
 					}
-					other => error_on_execution_failure(&other, &data)?,
+					other => error_on_execution_failure(&self.revert_decoder, &other, &data)?,
 				}
 				mid = (highest + lowest) / 2;
 			}
@@ -876,6 +1034,152 @@ where
 		}
 	}
 
+	pub async fn create_access_list(
+		&self,
+		request: TransactionRequest,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<AccessListResult> {
+		let TransactionRequest {
+			from,
+			to,
+			gas_price,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			gas,
+			value,
+			data,
+			nonce,
+			access_list,
+			authorization_list,
+			..
+		} = request;
+
+		let details = fee_details(gas_price, max_fee_per_gas, max_priority_fee_per_gas)?;
+
+		let id = frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			number_or_hash,
+		)
+		.await?;
+		// A new ApiRef instance is used per execution, so that the executions do not see the
+		// changes made by the previous ones.
+		let id = &id;
+		let runtime_api = move || async move {
+			match id {
+				Some(id) => {
+					let hash = self.client.expect_block_hash_from_id(id).map_err(|_| {
+						crate::err(CALL_EXECUTION_FAILED_CODE, "header not found", None)
+					})?;
+					Ok((hash, self.client.runtime_api()))
+				}
+				None => {
+					// Not mapped in the db, assume pending.
+					self.pending_runtime_api().await.map_err(|err| {
+						internal_err(format!("Create pending runtime api error: {err}"))
+					})
+				}
+			}
+		};
+
+		let (substrate_hash, api) = runtime_api().await?;
+		match api.api_version::<dyn EthereumRuntimeRPCApi<B>>(substrate_hash) {
+			Ok(Some(api_version)) if api_version >= 8 => (),
+			Ok(Some(_)) => {
+				return Err(internal_err(
+					"eth_createAccessList is not supported by the runtime api version",
+				))
+			}
+			_ => return Err(internal_err("failed to retrieve Runtime Api version")),
+		}
+
+		let block_gas_limit = api
+			.current_block(substrate_hash)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			.ok_or_else(|| internal_err("block unavailable, cannot query gas limit"))?
+			.header
+			.gas_limit;
+		let max_gas_limit = block_gas_limit * self.execute_gas_limit_multiplier;
+		// use given gas limit or query current block's limit
+		let gas_limit = match gas {
+			Some(amount) => {
+				if amount > max_gas_limit {
+					return Err(internal_err(format!(
+						"provided gas limit is too high (can be up to {}x the block gas limit)",
+						self.execute_gas_limit_multiplier
+					)));
+				}
+				amount
+			}
+			None => match api.gas_limit_multiplier_support(substrate_hash) {
+				Ok(_) => max_gas_limit,
+				_ => block_gas_limit,
+			},
+		};
+
+		let from = from.unwrap_or_default();
+		let data = data.into_bytes().map(|d| d.into_vec()).unwrap_or_default();
+		let authorization_list = authorization_list.map(|list| {
+			list.into_iter()
+				.map(Into::into)
+				.collect::<Vec<ethereum::AuthorizationListItem>>()
+		});
+		let mut access_list = access_list
+			.unwrap_or_default()
+			.into_iter()
+			.map(|item| (item.address, item.storage_keys))
+			.collect::<Vec<(H160, Vec<H256>)>>();
+
+		// As geth does, execute with the access list until it stops changing. The runtime keeps
+		// the given entries in the list, so it can only grow. The list returned is always the one
+		// of the last execution, so that the gas used matches it.
+		let mut first_api = Some(api);
+		let mut iteration = 0;
+		let (access_list, info) = loop {
+			iteration += 1;
+			let (substrate_hash, api) = match first_api.take() {
+				Some(api) => (substrate_hash, api),
+				None => runtime_api().await?,
+			};
+			let (recorded, info) = api
+				.create_access_list(
+					substrate_hash,
+					from,
+					to,
+					data.clone(),
+					value.unwrap_or_default(),
+					gas_limit,
+					details.max_fee_per_gas,
+					details.max_priority_fee_per_gas,
+					nonce,
+					Some(access_list.clone()),
+					authorization_list.clone(),
+					None,
+				)
+				.map_err(|err| internal_err(format!("runtime error: {err}")))?
+				.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
+
+			if recorded == access_list || iteration == MAX_ACCESS_LIST_ITERATIONS {
+				break (access_list, info);
+			}
+			access_list = recorded;
+		};
+
+		Ok(AccessListResult {
+			access_list: access_list
+				.into_iter()
+				.map(|(address, storage_keys)| ethereum::AccessListItem {
+					address,
+					storage_keys,
+				})
+				.collect(),
+			gas_used: info.used_gas.effective,
+			error: error_on_execution_failure(&self.revert_decoder, &info.exit_reason, &info.value)
+				.err()
+				.map(|err| err.message().to_string()),
+		})
+	}
+
 	/// Given an address mapped `CallStateOverride`, creates `OverlayedChanges` to be used for
 	/// `CallApiAt` eth_call.
 	fn create_overrides_overlay(
@@ -885,12 +1189,30 @@ where
 		state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
 	) -> RpcResult<OverlayedChanges<HashingFor<B>>> {
 		let mut overlayed_changes = OverlayedChanges::default();
+		self.apply_state_overrides(
+			&mut overlayed_changes,
+			block_hash,
+			api_version,
+			state_overrides,
+		)?;
+		Ok(overlayed_changes)
+	}
+
+	/// Given an address mapped `CallStateOverride`, applies the overrides on top of the changes
+	/// already made in `overlayed_changes`.
+	pub(crate) fn apply_state_overrides(
+		&self,
+		overlayed_changes: &mut OverlayedChanges<HashingFor<B>>,
+		block_hash: B::Hash,
+		api_version: u32,
+		state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
+	) -> RpcResult<()> {
 		if let Some(state_overrides) = state_overrides {
 			for (address, state_override) in state_overrides {
 				if EC::RuntimeStorageOverride::is_enabled() {
 					EC::RuntimeStorageOverride::set_overlayed_changes(
 						self.client.as_ref(),
-						&mut overlayed_changes,
+						overlayed_changes,
 						block_hash,
 						api_version,
 						address,
@@ -922,7 +1244,8 @@ where
 
 				// Use `state` first. If `stateDiff` is also present, it resolves consistently
 				if let Some(state) = &state_override.state {
-					// clear all storage
+					// clear all storage, including the slots only set in the overlay
+					overlayed_changes.clear_prefix(&account_storage_key);
 					if let Ok(all_keys) = self.client.storage_keys(
 						block_hash,
 						Some(&sp_storage::StorageKey(account_storage_key.clone())),
@@ -954,11 +1277,15 @@ where
 			}
 		}
 
-		Ok(overlayed_changes)
+		Ok(())
 	}
 }
 
-pub fn error_on_execution_failure(reason: &ExitReason, data: &[u8]) -> RpcResult<()> {
+pub fn error_on_execution_failure(
+	revert_decoder: &RevertDecoder,
+	reason: &ExitReason,
+	data: &[u8],
+) -> RpcResult<()> {
 	match reason {
 		ExitReason::Succeed(_) => Ok(()),
 		ExitReason::Error(err) => {
@@ -971,27 +1298,7 @@ pub fn error_on_execution_failure(reason: &ExitReason, data: &[u8]) -> RpcResult
 				&[],
 			))
 		}
-		ExitReason::Revert(_) => {
-			const LEN_START: usize = 36;
-			const MESSAGE_START: usize = 68;
-
-			let mut message = "VM Exception while processing transaction: revert".to_string();
-			// A minimum size of error function selector (4) + offset (32) + string length (32)
-			// should contain a utf-8 encoded revert reason.
-			if data.len() > MESSAGE_START {
-				let message_len =
-					U256::from(&data[LEN_START..MESSAGE_START]).saturated_into::<usize>();
-				let message_end = MESSAGE_START.saturating_add(message_len);
-
-				if data.len() >= message_end {
-					let body: &[u8] = &data[MESSAGE_START..message_end];
-					if let Ok(reason) = std::str::from_utf8(body) {
-						message = format!("{message} {reason}");
-					}
-				}
-			}
-			Err(crate::internal_err_with_data(message, data))
-		}
+		ExitReason::Revert(_) => Err(revert_decoder.error(data)),
 		ExitReason::Fatal(err) => Err(crate::internal_err_with_data(
 			format!("evm fatal: {err:?}"),
 			&[],
@@ -999,14 +1306,14 @@ pub fn error_on_execution_failure(reason: &ExitReason, data: &[u8]) -> RpcResult
 	}
 }
 
-struct FeeDetails {
-	gas_price: Option<U256>,
-	max_fee_per_gas: Option<U256>,
-	max_priority_fee_per_gas: Option<U256>,
-	fee_cap: U256,
+pub(crate) struct FeeDetails {
+	pub(crate) gas_price: Option<U256>,
+	pub(crate) max_fee_per_gas: Option<U256>,
+	pub(crate) max_priority_fee_per_gas: Option<U256>,
+	pub(crate) fee_cap: U256,
 }
 
-fn fee_details(
+pub(crate) fn fee_details(
 	request_gas_price: Option<U256>,
 	request_max_fee_per_gas: Option<U256>,
 	request_priority_fee_per_gas: Option<U256>,
@@ -1050,3 +1357,33 @@ fn fee_details(
 		}),
 	}
 }
+
+/// Converts the block overrides of a request into the ones of the runtime API, which supports
+/// them since version 7.
+pub(crate) fn runtime_block_overrides(
+	block_overrides: Option<BlockOverrides>,
+	api_version: u32,
+) -> RpcResult<Option<fp_evm::BlockOverrides>> {
+	let Some(block_overrides) = block_overrides else {
+		return Ok(None);
+	};
+	if api_version < 7 {
+		return Err(internal_err("block overrides unsupported by the runtime"));
+	}
+	Ok(Some(fp_evm::BlockOverrides {
+		number: block_overrides.number,
+		timestamp: block_overrides.time.map(|time| time.as_u64()),
+		coinbase: block_overrides.fee_recipient,
+		base_fee: block_overrides.base_fee_per_gas,
+		gas_limit: block_overrides
+			.gas_limit
+			.map(|gas_limit| U256::from(gas_limit.as_u64())),
+		random: block_overrides.prev_randao,
+		block_hashes: block_overrides
+			.block_hash
+			.unwrap_or_default()
+			.into_iter()
+			.map(|(number, hash)| (U256::from(number.as_u64()), hash))
+			.collect(),
+	}))
+}
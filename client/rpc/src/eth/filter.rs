@@ -23,7 +23,7 @@ use std::{
 	time::{Duration, Instant},
 };
 
-use ethereum::BlockV2 as EthereumBlock;
+use ethereum::BlockV3 as EthereumBlock;
 use ethereum_types::{H256, U256};
 use jsonrpsee::core::{async_trait, RpcResult};
 // Substrate
@@ -39,7 +39,7 @@ use sp_runtime::{
 };
 // Frontier
 use fc_rpc_core::{types::*, EthFilterApiServer};
-use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
+use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatusV2};
 
 use crate::{cache::EthBlockDataCacheTask, frontier_backend_client, internal_err};
 
@@ -597,7 +597,8 @@ where
 		let time_fetch = timer_fetch.elapsed().as_millis();
 		let timer_post = Instant::now();
 
-		let mut statuses_cache: BTreeMap<B::Hash, Option<Vec<TransactionStatus>>> = BTreeMap::new();
+		let mut statuses_cache: BTreeMap<B::Hash, Option<Vec<TransactionStatusV2>>> =
+			BTreeMap::new();
 
 		for log in logs.iter() {
 			let substrate_hash = log.substrate_block_hash;
@@ -757,7 +758,7 @@ fn filter_block_logs<'a>(
 	ret: &'a mut Vec<Log>,
 	filter: &'a Filter,
 	block: EthereumBlock,
-	transaction_statuses: Vec<TransactionStatus>,
+	transaction_statuses: Vec<TransactionStatusV2>,
 ) -> &'a Vec<Log> {
 	let params = FilteredParams::new(Some(filter.clone()));
 	let mut block_log_index: u32 = 0;
@@ -24,17 +24,21 @@ mod filter;
 pub mod format;
 mod mining;
 pub mod pending;
+mod simulate;
 mod state;
 mod submit;
 mod transaction;
 
 use std::{collections::BTreeMap, marker::PhantomData, sync::Arc};
 
-use ethereum::{BlockV2 as EthereumBlock, TransactionV2 as EthereumTransaction};
+use ethereum::{BlockV3 as EthereumBlock, TransactionV3 as EthereumTransaction};
 use ethereum_types::{H160, H256, H64, U256, U64};
 use jsonrpsee::core::{async_trait, RpcResult};
 // Substrate
-use sc_client_api::backend::{Backend, StorageProvider};
+use sc_client_api::{
+	backend::{Backend, StorageProvider},
+	ProofProvider,
+};
 use sc_network_sync::SyncingService;
 use sc_transaction_pool::{ChainApi, Pool};
 use sc_transaction_pool_api::TransactionPool;
@@ -48,15 +52,16 @@ use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
 use fc_rpc_core::{types::*, EthApiServer};
 use fc_storage::StorageOverride;
 use fp_rpc::{
-	ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi,
-	RuntimeStorageOverride, TransactionStatus,
+	ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi, EthereumStateProofApi,
+	RuntimeStorageOverride, TransactionStatusV2,
 };
 
 use crate::{
 	cache::EthBlockDataCacheTask, frontier_backend_client, internal_err, public_key,
-	signer::EthSigner,
+	revert::RevertDecoder, signer::EthSigner,
 };
 
+pub(crate) use self::execute::fee_details;
 pub use self::{execute::EstimateGasAdapter, filter::EthFilter};
 
 // Configuration trait for RPC configuration.
@@ -87,10 +92,14 @@ pub struct Eth<B: BlockT, C, P, CT, BE, A: ChainApi, CIDP, EC> {
 	/// When using eth_call/eth_estimateGas, the maximum allowed gas limit will be
 	/// block.gas_limit * execute_gas_limit_multiplier
 	execute_gas_limit_multiplier: u64,
+	/// Maximum number of storage keys proven by an eth_getProof request.
+	max_proof_storage_keys: u32,
 	forced_parent_hashes: Option<BTreeMap<H256, H256>>,
 	/// Something that can create the inherent data providers for pending state.
 	pending_create_inherent_data_providers: CIDP,
 	pending_consensus_data_provider: Option<Box<dyn pending::ConsensusDataProvider<B>>>,
+	/// Decodes the output of the reverted eth_call/eth_estimateGas.
+	revert_decoder: Arc<RevertDecoder>,
 	_marker: PhantomData<(BE, EC)>,
 }
 
@@ -117,6 +126,7 @@ where
 		fee_history_cache: FeeHistoryCache,
 		fee_history_cache_limit: FeeHistoryCacheLimit,
 		execute_gas_limit_multiplier: u64,
+		max_proof_storage_keys: u32,
 		forced_parent_hashes: Option<BTreeMap<H256, H256>>,
 		pending_create_inherent_data_providers: CIDP,
 		pending_consensus_data_provider: Option<Box<dyn pending::ConsensusDataProvider<B>>>,
@@ -135,13 +145,21 @@ where
 			fee_history_cache,
 			fee_history_cache_limit,
 			execute_gas_limit_multiplier,
+			max_proof_storage_keys,
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
+			revert_decoder: Default::default(),
 			_marker: PhantomData,
 		}
 	}
 
+	/// Decode the custom errors of the ABIs registered in `revert_decoder` when a call reverts.
+	pub fn with_revert_decoder(mut self, revert_decoder: Arc<RevertDecoder>) -> Self {
+		self.revert_decoder = revert_decoder;
+		self
+	}
+
 	pub async fn block_info_by_number(
 		&self,
 		number_or_hash: BlockNumberOrHash,
@@ -268,9 +286,11 @@ where
 			fee_history_cache,
 			fee_history_cache_limit,
 			execute_gas_limit_multiplier,
+			max_proof_storage_keys,
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
+			revert_decoder,
 			_marker: _,
 		} = self;
 
@@ -288,9 +308,11 @@ where
 			fee_history_cache,
 			fee_history_cache_limit,
 			execute_gas_limit_multiplier,
+			max_proof_storage_keys,
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
+			revert_decoder,
 			_marker: PhantomData,
 		}
 	}
@@ -301,8 +323,11 @@ impl<B, C, P, CT, BE, A, CIDP, EC> EthApiServer for Eth<B, C, P, CT, BE, A, CIDP
 where
 	B: BlockT,
 	C: CallApiAt<B> + ProvideRuntimeApi<B>,
-	C::Api: BlockBuilderApi<B> + ConvertTransactionRuntimeApi<B> + EthereumRuntimeRPCApi<B>,
-	C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+	C::Api: BlockBuilderApi<B>
+		+ ConvertTransactionRuntimeApi<B>
+		+ EthereumRuntimeRPCApi<B>
+		+ EthereumStateProofApi<B>,
+	C: HeaderBackend<B> + ProofProvider<B> + StorageProvider<B, BE> + 'static,
 	BE: Backend<B> + 'static,
 	P: TransactionPool<Block = B> + 'static,
 	CT: ConvertTransaction<<B as BlockT>::Extrinsic> + Send + Sync + 'static,
@@ -463,6 +488,15 @@ where
 		self.code_at(address, number_or_hash).await
 	}
 
+	async fn proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<H256>,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<EthAccount> {
+		self.proof(address, storage_keys, number_or_hash).await
+	}
+
 	// ########################################################################
 	// Execute
 	// ########################################################################
@@ -472,16 +506,36 @@ where
 		request: TransactionRequest,
 		number_or_hash: Option<BlockNumberOrHash>,
 		state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<Bytes> {
-		self.call(request, number_or_hash, state_overrides).await
+		self.call(request, number_or_hash, state_overrides, block_overrides)
+			.await
 	}
 
 	async fn estimate_gas(
 		&self,
 		request: TransactionRequest,
 		number_or_hash: Option<BlockNumberOrHash>,
+		block_overrides: Option<BlockOverrides>,
 	) -> RpcResult<U256> {
-		self.estimate_gas(request, number_or_hash).await
+		self.estimate_gas(request, number_or_hash, block_overrides)
+			.await
+	}
+
+	async fn create_access_list(
+		&self,
+		request: TransactionRequest,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<AccessListResult> {
+		self.create_access_list(request, number_or_hash).await
+	}
+
+	async fn simulate_v1(
+		&self,
+		payload: SimulatePayload,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<Vec<SimulatedBlock>> {
+		self.simulate_v1(payload, number_or_hash).await
 	}
 
 	// ########################################################################
@@ -545,7 +599,7 @@ where
 
 fn rich_block_build(
 	block: EthereumBlock,
-	statuses: Vec<Option<TransactionStatus>>,
+	statuses: Vec<Option<TransactionStatusV2>>,
 	hash: Option<H256>,
 	full_transactions: bool,
 	base_fee: Option<U256>,
@@ -621,7 +675,7 @@ fn rich_block_build(
 fn transaction_build(
 	ethereum_transaction: &EthereumTransaction,
 	block: Option<&EthereumBlock>,
-	status: Option<&TransactionStatus>,
+	status: Option<&TransactionStatusV2>,
 	base_fee: Option<U256>,
 ) -> Transaction {
 	let pubkey = match public_key(ethereum_transaction) {
@@ -640,7 +694,8 @@ fn transaction_build(
 
 	let mut transaction: Transaction = Transaction::build_from(from, ethereum_transaction);
 
-	if let EthereumTransaction::EIP1559(_) = ethereum_transaction {
+	if let EthereumTransaction::EIP1559(_) | EthereumTransaction::EIP7702(_) = ethereum_transaction
+	{
 		if block.is_none() && status.is_none() {
 			// If transaction is not mined yet, gas price is considered just max fee per gas.
 		} else {
@@ -677,8 +732,8 @@ fn transaction_build(
 #[derive(Clone, Default)]
 pub struct BlockInfo<H> {
 	block: Option<EthereumBlock>,
-	receipts: Option<Vec<ethereum::ReceiptV3>>,
-	statuses: Option<Vec<TransactionStatus>>,
+	receipts: Option<Vec<ethereum::ReceiptV4>>,
+	statuses: Option<Vec<TransactionStatusV2>>,
 	substrate_hash: H,
 	is_eip1559: bool,
 	base_fee: U256,
@@ -687,8 +742,8 @@ pub struct BlockInfo<H> {
 impl<H> BlockInfo<H> {
 	pub fn new(
 		block: Option<EthereumBlock>,
-		receipts: Option<Vec<ethereum::ReceiptV3>>,
-		statuses: Option<Vec<TransactionStatus>>,
+		receipts: Option<Vec<ethereum::ReceiptV4>>,
+		statuses: Option<Vec<TransactionStatusV2>>,
 		substrate_hash: H,
 		is_eip1559: bool,
 		base_fee: U256,
@@ -0,0 +1,852 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{cell::RefCell, collections::BTreeMap};
+
+use ethereum::{BlockV3 as EthereumBlock, TransactionV3 as EthereumTransaction};
+use ethereum_types::{Bloom, BloomInput, H160, H256, H64, U256, U64};
+use evm::{ExitReason, ExitSucceed};
+use jsonrpsee::{core::RpcResult, types::error::CALL_EXECUTION_FAILED_CODE};
+use scale_codec::{Decode, Encode};
+// Substrate
+use sc_client_api::backend::{Backend, StorageProvider};
+use sc_transaction_pool::ChainApi;
+use sp_api::{ApiExt, CallApiAt, CallApiAtParams, CallContext, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder as BlockBuilderApi;
+use sp_blockchain::HeaderBackend;
+use sp_externalities::Extensions;
+use sp_inherents::CreateInherentDataProviders;
+use sp_runtime::{
+	traits::{Block as BlockT, HashingFor},
+	DispatchError,
+};
+use sp_state_machine::OverlayedChanges;
+// Frontier
+use fc_rpc_core::types::*;
+use fp_evm::{CallFrame, CallTracerConfig, ExecutionInfoV2, TracerConfig};
+use fp_rpc::{DebugRuntimeApi, EthereumRuntimeRPCApi, TransactionStatusV2};
+
+use crate::{
+	eth::{fee_details, rich_block_build, Eth, EthConfig},
+	frontier_backend_client, internal_err,
+};
+
+/// Maximum number of blocks, including the ones filling the gaps between the requested block
+/// numbers, simulated by a single request.
+const MAX_SIMULATED_BLOCKS: u64 = 256;
+/// Time between two simulated blocks when their timestamp is not overridden, in milliseconds.
+const BLOCK_TIME: u64 = 12_000;
+
+// Error codes of `eth_simulateV1`.
+const BLOCK_GAS_LIMIT_REACHED: i32 = -38015;
+const INVALID_BLOCK_NUMBER: i32 = -38020;
+const INVALID_BLOCK_TIMESTAMP: i32 = -38021;
+const TOO_MANY_BLOCKS: i32 = -38026;
+
+/// Address of the logs added for the value transfers with `traceTransfers`.
+const TRANSFER_LOG_ADDRESS: H160 = H160([0xee; 20]);
+/// `keccak256("Transfer(address,address,uint256)")`.
+const TRANSFER_TOPIC: H256 = H256([
+	0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+	0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+]);
+
+/// Context of a simulated block.
+struct BlockContext {
+	number: U256,
+	/// In milliseconds, like the timestamps of the Ethereum blocks built by `pallet_ethereum`.
+	timestamp: u64,
+	gas_limit: U256,
+	beneficiary: H160,
+	base_fee: U256,
+	mix_hash: H256,
+}
+
+/// Outcome of a simulated call.
+struct CallOutcome {
+	exit_reason: ExitReason,
+	return_data: Vec<u8>,
+	used_gas: U256,
+	logs: Vec<ethereum::Log>,
+	contract_address: Option<H160>,
+}
+
+impl<B, C, P, CT, BE, A, CIDP, EC> Eth<B, C, P, CT, BE, A, CIDP, EC>
+where
+	B: BlockT,
+	C: CallApiAt<B> + ProvideRuntimeApi<B>,
+	C::Api: BlockBuilderApi<B> + EthereumRuntimeRPCApi<B>,
+	C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+	BE: Backend<B> + 'static,
+	A: ChainApi<Block = B>,
+	CIDP: CreateInherentDataProviders<B, ()> + Send + 'static,
+	EC: EthConfig<B, C>,
+{
+	/// Simulate the blocks of `payload` on top of the given block.
+	///
+	/// All the blocks share the same overlay, so that the state overrides and the calls of a
+	/// block see the changes made by the previous ones. Runtimes supporting block overrides
+	/// execute the calls in the context of their simulated block, older ones in the context of
+	/// the base block and reject the blocks overriding it.
+	pub async fn simulate_v1(
+		&self,
+		payload: SimulatePayload,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<Vec<SimulatedBlock>> {
+		let SimulatePayload {
+			block_state_calls,
+			trace_transfers,
+			validation,
+			return_full_transactions,
+		} = payload;
+
+		if block_state_calls.len() as u64 > MAX_SIMULATED_BLOCKS {
+			return Err(crate::err(
+				TOO_MANY_BLOCKS,
+				format!("too many blocks, up to {MAX_SIMULATED_BLOCKS} can be simulated"),
+				None,
+			));
+		}
+
+		let (substrate_hash, api) = match frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			number_or_hash,
+		)
+		.await?
+		{
+			Some(id) => {
+				let hash = self.client.expect_block_hash_from_id(&id).map_err(|_| {
+					crate::err(CALL_EXECUTION_FAILED_CODE, "header not found", None)
+				})?;
+				(hash, self.client.runtime_api())
+			}
+			None => {
+				// Not mapped in the db, assume pending.
+				let (hash, api) = self.pending_runtime_api().await.map_err(|err| {
+					internal_err(format!("Create pending runtime api error: {err}"))
+				})?;
+				(hash, api)
+			}
+		};
+
+		let api_version = match api.api_version::<dyn EthereumRuntimeRPCApi<B>>(substrate_hash) {
+			Ok(Some(api_version)) if api_version >= 5 => api_version,
+			Ok(Some(_)) => {
+				return Err(internal_err(
+					"eth_simulateV1 is not supported by the runtime api version",
+				))
+			}
+			_ => return Err(internal_err("failed to retrieve Runtime Api version")),
+		};
+		if trace_transfers
+			&& !api
+				.has_api::<dyn DebugRuntimeApi<B>>(substrate_hash)
+				.unwrap_or(false)
+		{
+			return Err(internal_err(
+				"traceTransfers is not supported by the runtime",
+			));
+		}
+
+		let base_block = api
+			.current_block(substrate_hash)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			.ok_or_else(|| internal_err("block unavailable, cannot simulate on top of it"))?;
+		let chain_id = api
+			.chain_id(substrate_hash)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?;
+		let base_fee = api
+			.gas_price(substrate_hash)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?;
+
+		let overlayed_changes = RefCell::new(OverlayedChanges::default());
+		let mut parent = base_block.header;
+		let mut simulated_count = 0u64;
+		let mut block_hashes = BTreeMap::new();
+		let mut simulated_blocks = Vec::new();
+		for SimulateBlock {
+			block_overrides,
+			state_overrides,
+			calls,
+		} in block_state_calls
+		{
+			if block_overrides.is_some() && api_version < 7 {
+				return Err(internal_err(
+					"block overrides are not supported by the runtime api version",
+				));
+			}
+			let overrides = block_overrides.unwrap_or_default();
+			let number = overrides.number.unwrap_or(parent.number + 1);
+			if number <= parent.number {
+				return Err(crate::err(
+					INVALID_BLOCK_NUMBER,
+					format!(
+						"block numbers must be in order: {} <= {}",
+						number, parent.number
+					),
+					None,
+				));
+			}
+			if number - parent.number > U256::from(MAX_SIMULATED_BLOCKS - simulated_count) {
+				return Err(crate::err(
+					TOO_MANY_BLOCKS,
+					format!("too many blocks, up to {MAX_SIMULATED_BLOCKS} can be simulated"),
+					None,
+				));
+			}
+			let context_from = |parent: &ethereum::Header, number| BlockContext {
+				number,
+				timestamp: parent.timestamp.saturating_add(BLOCK_TIME),
+				gas_limit: parent.gas_limit,
+				beneficiary: parent.beneficiary,
+				base_fee: if validation { base_fee } else { U256::zero() },
+				mix_hash: H256::zero(),
+			};
+
+			// Fill the gap up to the requested number with empty blocks.
+			while parent.number + 1 < number {
+				let context = context_from(&parent, parent.number + 1);
+				let block = seal_block(&parent, &context, vec![], vec![]);
+				parent = block.header.clone();
+				block_hashes.insert(parent.number, parent.hash());
+				simulated_blocks.push(SimulatedBlock {
+					block: rich_block_build(
+						block,
+						vec![],
+						None,
+						return_full_transactions,
+						Some(context.base_fee),
+						false,
+					),
+					calls: vec![],
+				});
+				simulated_count += 1;
+			}
+
+			let mut context = context_from(&parent, number);
+			if let Some(time) = overrides.time {
+				context.timestamp = time.as_u64().saturating_mul(1000);
+			}
+			if context.timestamp <= parent.timestamp {
+				return Err(crate::err(
+					INVALID_BLOCK_TIMESTAMP,
+					format!(
+						"block timestamps must be in order: {} <= {}",
+						context.timestamp / 1000,
+						parent.timestamp / 1000
+					),
+					None,
+				));
+			}
+			if let Some(gas_limit) = overrides.gas_limit {
+				context.gas_limit = U256::from(gas_limit.as_u64());
+			}
+			if let Some(fee_recipient) = overrides.fee_recipient {
+				context.beneficiary = fee_recipient;
+			}
+			if let Some(base_fee_per_gas) = overrides.base_fee_per_gas {
+				context.base_fee = base_fee_per_gas;
+			}
+			if let Some(prev_randao) = overrides.prev_randao {
+				context.mix_hash = prev_randao;
+			}
+
+			// The calls see the simulated blocks as the previous blocks of the chain.
+			block_hashes.extend(
+				overrides
+					.block_hash
+					.unwrap_or_default()
+					.into_iter()
+					.map(|(number, hash)| (U256::from(number.as_u64()), hash)),
+			);
+			let runtime_block_overrides = (api_version >= 7).then(|| fp_evm::BlockOverrides {
+				number: Some(context.number),
+				timestamp: Some(context.timestamp / 1000),
+				coinbase: Some(context.beneficiary),
+				base_fee: Some(context.base_fee),
+				gas_limit: Some(context.gas_limit),
+				random: Some(context.mix_hash),
+				block_hashes: block_hashes.clone(),
+			});
+
+			self.apply_state_overrides(
+				&mut overlayed_changes.borrow_mut(),
+				substrate_hash,
+				api_version,
+				state_overrides,
+			)?;
+
+			let mut transactions = Vec::new();
+			let mut outcomes = Vec::new();
+			let mut cumulative_gas_used = U256::zero();
+			for request in calls {
+				let message = Option::<TransactionMessage>::from(request.clone())
+					.ok_or_else(|| internal_err("invalid transaction parameters"))?;
+				let TransactionRequest {
+					from,
+					to,
+					gas_price,
+					max_fee_per_gas,
+					max_priority_fee_per_gas,
+					gas,
+					value,
+					data,
+					nonce,
+					access_list,
+					authorization_list,
+					..
+				} = request;
+				let from = from.unwrap_or_default();
+				let value = value.unwrap_or_default();
+				let data = data.into_bytes().map(|d| d.into_vec()).unwrap_or_default();
+				let access_list = access_list.unwrap_or_default();
+
+				let remaining_gas = context.gas_limit.saturating_sub(cumulative_gas_used);
+				let gas_limit = match gas {
+					Some(gas) if gas > remaining_gas => {
+						return Err(crate::err(
+							BLOCK_GAS_LIMIT_REACHED,
+							format!(
+								"block gas limit reached: {gas} > {remaining_gas} remaining gas"
+							),
+							None,
+						))
+					}
+					Some(gas) => gas,
+					None => remaining_gas,
+				};
+
+				let (max_fee_per_gas, max_priority_fee_per_gas) = {
+					let details =
+						fee_details(gas_price, max_fee_per_gas, max_priority_fee_per_gas)?;
+					if validation {
+						// Validated calls pay their fees, at least the base fee by default.
+						(
+							Some(
+								details
+									.max_fee_per_gas
+									.or(details.gas_price)
+									.unwrap_or(context.base_fee),
+							),
+							Some(
+								details
+									.max_priority_fee_per_gas
+									.or(details.gas_price)
+									.unwrap_or_default(),
+							),
+						)
+					} else {
+						(details.max_fee_per_gas, details.max_priority_fee_per_gas)
+					}
+				};
+
+				let account_nonce = self
+					.call_runtime_api::<fp_evm::Account>(
+						substrate_hash,
+						&overlayed_changes,
+						"EthereumRuntimeRPCApi_account_basic",
+						from.encode(),
+					)?
+					.nonce;
+				// Unless validated, the calls are not bound to the nonce of the sender.
+				let runtime_nonce = if validation {
+					Some(nonce.unwrap_or(account_nonce))
+				} else {
+					nonce
+				};
+
+				let runtime_access_list = access_list
+					.iter()
+					.map(|item| (item.address, item.storage_keys.clone()))
+					.collect::<Vec<(H160, Vec<H256>)>>();
+				let runtime_authorization_list = authorization_list.map(|list| {
+					list.into_iter()
+						.map(Into::into)
+						.collect::<Vec<ethereum::AuthorizationListItem>>()
+				});
+
+				let outcome = if trace_transfers {
+					let arguments = (
+						&from,
+						&to,
+						&data,
+						&value,
+						&gas_limit,
+						&max_fee_per_gas,
+						&max_priority_fee_per_gas,
+						&runtime_nonce,
+						&Some(runtime_access_list),
+						&runtime_authorization_list,
+						&TracerConfig::CallTracer(CallTracerConfig {
+							only_top_call: false,
+							with_log: true,
+						}),
+						&runtime_block_overrides,
+					)
+						.encode();
+					let trace = self
+						.call_runtime_api::<Result<fp_evm::TransactionTrace, DispatchError>>(
+							substrate_hash,
+							&overlayed_changes,
+							"DebugRuntimeApi_trace_call",
+							arguments,
+						)?
+						.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
+					let fp_evm::TransactionTrace::CallTracer(frame) = trace else {
+						return Err(internal_err("unexpected trace returned by the runtime"));
+					};
+					let exit_reason = frame
+						.exit_reason
+						.clone()
+						.unwrap_or(ExitReason::Succeed(ExitSucceed::Stopped));
+					let mut logs = Vec::new();
+					collect_logs(&frame, &mut logs);
+					CallOutcome {
+						contract_address: (to.is_none() && exit_reason.is_succeed())
+							.then_some(frame.to),
+						exit_reason,
+						return_data: frame.output,
+						used_gas: U256::from(frame.gas_used),
+						logs,
+					}
+				} else if let Some(to) = to {
+					let mut arguments = (
+						&from,
+						&to,
+						&data,
+						&value,
+						&gas_limit,
+						&max_fee_per_gas,
+						&max_priority_fee_per_gas,
+						&runtime_nonce,
+						&false,
+						&Some(runtime_access_list),
+					)
+						.encode();
+					if api_version >= 6 {
+						arguments.extend(runtime_authorization_list.encode());
+					}
+					if api_version >= 7 {
+						arguments.extend(runtime_block_overrides.encode());
+					}
+					let info = self
+						.call_runtime_api::<Result<ExecutionInfoV2<Vec<u8>>, DispatchError>>(
+							substrate_hash,
+							&overlayed_changes,
+							"EthereumRuntimeRPCApi_call",
+							arguments,
+						)?
+						.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
+					CallOutcome {
+						logs: if info.exit_reason.is_succeed() {
+							info.logs
+						} else {
+							vec![]
+						},
+						exit_reason: info.exit_reason,
+						return_data: info.value,
+						used_gas: info.used_gas.standard,
+						contract_address: None,
+					}
+				} else {
+					let mut arguments = (
+						&from,
+						&data,
+						&value,
+						&gas_limit,
+						&max_fee_per_gas,
+						&max_priority_fee_per_gas,
+						&runtime_nonce,
+						&false,
+						&Some(runtime_access_list),
+					)
+						.encode();
+					if api_version >= 7 {
+						arguments.extend(runtime_block_overrides.encode());
+					}
+					let info = self
+						.call_runtime_api::<Result<ExecutionInfoV2<H160>, DispatchError>>(
+							substrate_hash,
+							&overlayed_changes,
+							"EthereumRuntimeRPCApi_create",
+							arguments,
+						)?
+						.map_err(|err| internal_err(format!("execution fatal: {err:?}")))?;
+					let succeed = info.exit_reason.is_succeed();
+					CallOutcome {
+						logs: if succeed { info.logs } else { vec![] },
+						exit_reason: info.exit_reason,
+						return_data: vec![],
+						used_gas: info.used_gas.standard,
+						contract_address: succeed.then_some(info.value),
+					}
+				};
+				// Creations return the code of the created contract.
+				let outcome = match outcome.contract_address {
+					Some(address) => CallOutcome {
+						return_data: self.call_runtime_api::<Vec<u8>>(
+							substrate_hash,
+							&overlayed_changes,
+							"EthereumRuntimeRPCApi_account_code_at",
+							address.encode(),
+						)?,
+						..outcome
+					},
+					None => outcome,
+				};
+				cumulative_gas_used = cumulative_gas_used.saturating_add(outcome.used_gas);
+
+				transactions.push(simulated_transaction(
+					message,
+					chain_id,
+					nonce.unwrap_or(account_nonce),
+					gas_limit,
+					max_fee_per_gas.unwrap_or_default(),
+					max_priority_fee_per_gas.unwrap_or_default(),
+				)?);
+				outcomes.push((from, to, outcome));
+			}
+
+			let mut receipts = Vec::new();
+			let mut statuses = Vec::new();
+			let mut cumulative_gas_used = U256::zero();
+			for (index, (transaction, (from, to, outcome))) in
+				transactions.iter().zip(&outcomes).enumerate()
+			{
+				cumulative_gas_used = cumulative_gas_used.saturating_add(outcome.used_gas);
+				let logs_bloom = logs_bloom(&outcome.logs);
+				let receipt = ethereum::EIP2930ReceiptData {
+					status_code: u8::from(outcome.exit_reason.is_succeed()),
+					used_gas: cumulative_gas_used,
+					logs_bloom,
+					logs: outcome.logs.clone(),
+				};
+				receipts.push(match transaction {
+					EthereumTransaction::Legacy(_) => ethereum::ReceiptV4::Legacy(receipt),
+					EthereumTransaction::EIP2930(_) => ethereum::ReceiptV4::EIP2930(receipt),
+					EthereumTransaction::EIP1559(_) => ethereum::ReceiptV4::EIP1559(receipt),
+					EthereumTransaction::EIP7702(_) => ethereum::ReceiptV4::EIP7702(receipt),
+				});
+				statuses.push(TransactionStatusV2 {
+					transaction_hash: transaction.hash(),
+					transaction_index: index as u32,
+					from: *from,
+					to: *to,
+					contract_address: outcome.contract_address,
+					logs: outcome.logs.clone(),
+					logs_bloom,
+					exit_reason: (!outcome.exit_reason.is_succeed())
+						.then(|| outcome.exit_reason.clone()),
+					revert_reason: outcome
+						.exit_reason
+						.is_revert()
+						.then(|| outcome.return_data.clone()),
+				});
+			}
+
+			let block = seal_block(&parent, &context, transactions, receipts);
+			let block_hash = block.header.hash();
+			let mut log_index = 0u32;
+			let calls = outcomes
+				.into_iter()
+				.zip(&statuses)
+				.map(|((_, _, outcome), status)| {
+					let logs = outcome
+						.logs
+						.into_iter()
+						.enumerate()
+						.map(|(transaction_log_index, log)| {
+							log_index += 1;
+							Log {
+								address: log.address,
+								topics: log.topics,
+								data: Bytes(log.data),
+								block_hash: Some(block_hash),
+								block_number: Some(context.number),
+								transaction_hash: Some(status.transaction_hash),
+								transaction_index: Some(U256::from(status.transaction_index)),
+								log_index: Some(U256::from(log_index - 1)),
+								transaction_log_index: Some(U256::from(transaction_log_index)),
+								removed: false,
+							}
+						})
+						.collect();
+					call_result(
+						outcome.exit_reason,
+						outcome.return_data,
+						outcome.used_gas,
+						logs,
+					)
+				})
+				.collect();
+
+			parent = block.header.clone();
+			block_hashes.insert(parent.number, block_hash);
+			simulated_blocks.push(SimulatedBlock {
+				block: rich_block_build(
+					block,
+					statuses.into_iter().map(Some).collect(),
+					Some(block_hash),
+					return_full_transactions,
+					Some(context.base_fee),
+					false,
+				),
+				calls,
+			});
+			simulated_count += 1;
+		}
+
+		Ok(simulated_blocks)
+	}
+
+	/// Call `function` of the runtime api on top of `overlayed_changes`, keeping the changes it
+	/// makes to the state.
+	fn call_runtime_api<R: Decode>(
+		&self,
+		at: B::Hash,
+		overlayed_changes: &RefCell<OverlayedChanges<HashingFor<B>>>,
+		function: &'static str,
+		arguments: Vec<u8>,
+	) -> RpcResult<R> {
+		self.client
+			.call_api_at(CallApiAtParams {
+				at,
+				function,
+				arguments,
+				overlayed_changes,
+				call_context: CallContext::Offchain,
+				recorder: &None,
+				extensions: &RefCell::new(Extensions::new()),
+			})
+			.and_then(|r| {
+				R::decode(&mut &r[..]).map_err(|error| {
+					sp_api::ApiError::FailedToDecodeReturnValue {
+						function,
+						error,
+						raw: r,
+					}
+				})
+			})
+			.map_err(|err| internal_err(format!("runtime error: {err}")))
+	}
+}
+
+/// Build a simulated transaction of the requested type. The transactions are not signed, so they
+/// carry a placeholder signature.
+fn simulated_transaction(
+	message: TransactionMessage,
+	chain_id: u64,
+	nonce: U256,
+	gas_limit: U256,
+	max_fee_per_gas: U256,
+	max_priority_fee_per_gas: U256,
+) -> RpcResult<EthereumTransaction> {
+	let r = H256::from_low_u64_be(1);
+	let s = H256::from_low_u64_be(1);
+	Ok(match message {
+		TransactionMessage::Legacy(m) => EthereumTransaction::Legacy(ethereum::LegacyTransaction {
+			nonce,
+			gas_price: m.gas_price,
+			gas_limit,
+			action: m.action,
+			value: m.value,
+			input: m.input,
+			signature: ethereum::TransactionSignature::new(2 * chain_id + 35, r, s)
+				.ok_or_else(|| internal_err("invalid placeholder signature"))?,
+		}),
+		TransactionMessage::EIP2930(m) => {
+			EthereumTransaction::EIP2930(ethereum::EIP2930Transaction {
+				chain_id,
+				nonce,
+				// The gas price is optional for access list transactions.
+				gas_price: m.gas_price.max(max_fee_per_gas),
+				gas_limit,
+				action: m.action,
+				value: m.value,
+				input: m.input,
+				access_list: m.access_list,
+				odd_y_parity: false,
+				r,
+				s,
+			})
+		}
+		TransactionMessage::EIP1559(m) => {
+			EthereumTransaction::EIP1559(ethereum::EIP1559Transaction {
+				chain_id,
+				nonce,
+				max_priority_fee_per_gas,
+				max_fee_per_gas,
+				gas_limit,
+				action: m.action,
+				value: m.value,
+				input: m.input,
+				access_list: m.access_list,
+				odd_y_parity: false,
+				r,
+				s,
+			})
+		}
+		TransactionMessage::EIP7702(m) => {
+			EthereumTransaction::EIP7702(ethereum::EIP7702Transaction {
+				chain_id,
+				nonce,
+				max_priority_fee_per_gas,
+				max_fee_per_gas,
+				gas_limit,
+				action: m.action,
+				value: m.value,
+				input: m.input,
+				access_list: m.access_list,
+				authorization_list: m.authorization_list,
+				odd_y_parity: false,
+				r,
+				s,
+			})
+		}
+	})
+}
+
+/// Build the simulated block on top of `parent`.
+fn seal_block(
+	parent: &ethereum::Header,
+	context: &BlockContext,
+	transactions: Vec<EthereumTransaction>,
+	receipts: Vec<ethereum::ReceiptV4>,
+) -> EthereumBlock {
+	let mut logs_bloom = Bloom::default();
+	let mut gas_used = U256::zero();
+	for receipt in &receipts {
+		let d = match receipt {
+			ethereum::ReceiptV4::Legacy(d)
+			| ethereum::ReceiptV4::EIP2930(d)
+			| ethereum::ReceiptV4::EIP1559(d)
+			| ethereum::ReceiptV4::EIP7702(d) => d,
+		};
+		logs_bloom.accrue_bloom(&d.logs_bloom);
+		gas_used = d.used_gas;
+	}
+	let receipts_root = ethereum::util::ordered_trie_root(
+		receipts.iter().map(ethereum::EnvelopedEncodable::encode),
+	);
+	let partial_header = ethereum::PartialHeader {
+		parent_hash: parent.hash(),
+		beneficiary: context.beneficiary,
+		// The state of the simulated blocks is not merkleized.
+		state_root: H256::zero(),
+		receipts_root,
+		logs_bloom,
+		difficulty: U256::zero(),
+		number: context.number,
+		gas_limit: context.gas_limit,
+		gas_used,
+		timestamp: context.timestamp,
+		extra_data: Vec::new(),
+		mix_hash: context.mix_hash,
+		nonce: H64::default(),
+	};
+	ethereum::Block::new(partial_header, transactions, vec![])
+}
+
+fn logs_bloom(logs: &[ethereum::Log]) -> Bloom {
+	let mut bloom = Bloom::default();
+	for log in logs {
+		bloom.accrue(BloomInput::Raw(&log.address[..]));
+		for topic in &log.topics {
+			bloom.accrue(BloomInput::Raw(&topic[..]));
+		}
+	}
+	bloom
+}
+
+/// Collect the logs of a successful call frame and of its successful sub calls, in execution
+/// order, along with a `Transfer` log for each value transfer.
+fn collect_logs(frame: &CallFrame, logs: &mut Vec<ethereum::Log>) {
+	// Self destructs have no exit reason, they cannot fail.
+	if !frame
+		.exit_reason
+		.as_ref()
+		.map_or(true, ExitReason::is_succeed)
+	{
+		return;
+	}
+
+	if let Some(value) = frame.value.filter(|value| !value.is_zero()) {
+		logs.push(ethereum::Log {
+			address: TRANSFER_LOG_ADDRESS,
+			topics: vec![TRANSFER_TOPIC, frame.from.into(), frame.to.into()],
+			data: {
+				let mut data = [0u8; 32];
+				value.to_big_endian(&mut data);
+				data.to_vec()
+			},
+		});
+	}
+
+	let mut calls = frame.calls.iter();
+	let mut position = 0;
+	for log in &frame.logs {
+		while position < log.position {
+			if let Some(call) = calls.next() {
+				collect_logs(call, logs);
+			}
+			position += 1;
+		}
+		logs.push(ethereum::Log {
+			address: log.address,
+			topics: log.topics.clone(),
+			data: log.data.clone(),
+		});
+	}
+	for call in calls {
+		collect_logs(call, logs);
+	}
+}
+
+fn call_result(
+	exit_reason: ExitReason,
+	return_data: Vec<u8>,
+	used_gas: U256,
+	logs: Vec<Log>,
+) -> SimulateCallResult {
+	let error = match exit_reason {
+		ExitReason::Succeed(_) => None,
+		ExitReason::Revert(_) => Some(SimulateCallError {
+			code: SimulateCallError::REVERTED,
+			message: "execution reverted".into(),
+			data: Some(Bytes(return_data.clone())),
+		}),
+		ExitReason::Error(err) => Some(SimulateCallError {
+			code: SimulateCallError::VM_ERROR,
+			message: format!("evm error: {err:?}"),
+			data: None,
+		}),
+		ExitReason::Fatal(err) => Some(SimulateCallError {
+			code: SimulateCallError::VM_ERROR,
+			message: format!("evm fatal: {err:?}"),
+			data: None,
+		}),
+	};
+	SimulateCallResult {
+		return_data: Bytes(return_data),
+		logs,
+		gas_used: U64::from(used_gas.low_u64()),
+		status: U64::from(u64::from(error.is_none())),
+		error,
+	}
+}
@@ -20,19 +20,26 @@ use ethereum_types::{H160, H256, U256};
 use jsonrpsee::core::RpcResult;
 use scale_codec::Encode;
 // Substrate
-use sc_client_api::backend::{Backend, StorageProvider};
+use sc_client_api::{
+	backend::{Backend, StorageProvider},
+	ProofProvider,
+};
 use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder as BlockBuilderApi;
 use sp_blockchain::HeaderBackend;
+use sp_core::hashing::keccak_256;
 use sp_inherents::CreateInherentDataProviders;
-use sp_runtime::traits::Block as BlockT;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 // Frontier
 use fc_rpc_core::types::*;
-use fp_rpc::EthereumRuntimeRPCApi;
+use fp_rpc::{EthereumRuntimeRPCApi, EthereumStateProofApi, RuntimeStorageOverride};
 
-use crate::{eth::Eth, frontier_backend_client, internal_err};
+use crate::{
+	eth::{Eth, EthConfig},
+	frontier_backend_client, internal_err,
+};
 
 impl<B, C, P, CT, BE, A, CIDP, EC> Eth<B, C, P, CT, BE, A, CIDP, EC>
 where
@@ -205,3 +212,148 @@ where
 		}
 	}
 }
+
+impl<B, C, P, CT, BE, A, CIDP, EC> Eth<B, C, P, CT, BE, A, CIDP, EC>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EthereumRuntimeRPCApi<B> + EthereumStateProofApi<B>,
+	C: HeaderBackend<B> + ProofProvider<B> + StorageProvider<B, BE> + 'static,
+	BE: Backend<B> + 'static,
+	A: ChainApi<Block = B>,
+	EC: EthConfig<B, C>,
+{
+	/// Account and storage values of an address, with their proofs.
+	///
+	/// Runtimes maintaining an Ethereum state trie return Merkle Patricia proofs against the
+	/// `state_root` of the Ethereum block. Otherwise the proofs are Substrate read proofs of the
+	/// storage entries holding the values, against the state root of the Substrate block, which is
+	/// returned as `storage_hash` as accounts have no storage trie of their own. Those proofs need
+	/// a `RuntimeStorageOverride` to locate the account of the address, and fail without one. See
+	/// `fp_proof` for their verification.
+	pub async fn proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<H256>,
+		number_or_hash: Option<BlockNumberOrHash>,
+	) -> RpcResult<EthAccount> {
+		if storage_keys.len() > self.max_proof_storage_keys as usize {
+			return Err(internal_err(format!(
+				"too many storage keys (maximum {})",
+				self.max_proof_storage_keys
+			)));
+		}
+
+		// Proofs need a persisted state, so the pending block is served from the latest one.
+		let number_or_hash = match number_or_hash.unwrap_or(BlockNumberOrHash::Latest) {
+			BlockNumberOrHash::Pending => BlockNumberOrHash::Latest,
+			number_or_hash => number_or_hash,
+		};
+		let id = frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number_or_hash),
+		)
+		.await?
+		.ok_or_else(|| internal_err("Block not found"))?;
+		let substrate_hash = self
+			.client
+			.expect_block_hash_from_id(&id)
+			.map_err(|_| internal_err(format!("Expect block number from id: {id}")))?;
+
+		let api = self.client.runtime_api();
+		if api
+			.has_api::<dyn EthereumStateProofApi<B>>(substrate_hash)
+			.unwrap_or(false)
+		{
+			if let Some(proof) = api
+				.account_proof(substrate_hash, address, storage_keys.clone())
+				.map_err(|err| internal_err(format!("Failed to generate proof: {err:?}")))?
+			{
+				return Ok(EthAccount {
+					address,
+					balance: proof.balance,
+					nonce: proof.nonce,
+					code_hash: proof.code_hash,
+					storage_hash: proof.storage_hash,
+					account_proof: proof.account_proof.into_iter().map(Bytes::new).collect(),
+					storage_proof: proof
+						.storage_proof
+						.into_iter()
+						.map(|storage| StorageProof {
+							key: U256::from_big_endian(storage.key.as_bytes()),
+							value: storage.value,
+							proof: storage.proof.into_iter().map(Bytes::new).collect(),
+						})
+						.collect(),
+				});
+			}
+		}
+
+		if !EC::RuntimeStorageOverride::is_enabled() {
+			return Err(internal_err(
+				"Proofs are not supported: the runtime has no Ethereum state trie, nor a storage override",
+			));
+		}
+
+		let state_root = self
+			.client
+			.header(substrate_hash)
+			.map_err(|err| internal_err(format!("Fetch header failed: {err}")))?
+			.map(|header| H256::from_slice(header.state_root().as_ref()))
+			.ok_or_else(|| internal_err("Block header not found"))?;
+		let account = api
+			.account_basic(substrate_hash, address)
+			.map_err(|err| internal_err(format!("Fetch account failed: {err}")))?;
+		let code = self
+			.storage_override
+			.account_code_at(substrate_hash, address)
+			.unwrap_or_default();
+
+		let account_id = EC::RuntimeStorageOverride::into_account_id_bytes(address);
+		let account_proof = self.read_proof(
+			substrate_hash,
+			&[
+				fp_proof::account_code_key(address),
+				fp_proof::system_account_key(&account_id),
+			],
+		)?;
+
+		let storage_proof = storage_keys
+			.into_iter()
+			.map(|key| {
+				let index = U256::from_big_endian(key.as_bytes());
+				let value = self
+					.storage_override
+					.account_storage_at(substrate_hash, address, index)
+					.unwrap_or_default();
+				Ok(StorageProof {
+					key: index,
+					value: U256::from_big_endian(value.as_bytes()),
+					proof: self.read_proof(
+						substrate_hash,
+						&[fp_proof::account_storage_key(address, key)],
+					)?,
+				})
+			})
+			.collect::<RpcResult<Vec<_>>>()?;
+
+		Ok(EthAccount {
+			address,
+			balance: account.balance,
+			nonce: account.nonce,
+			code_hash: H256(keccak_256(&code)),
+			storage_hash: state_root,
+			account_proof,
+			storage_proof,
+		})
+	}
+
+	fn read_proof(&self, substrate_hash: B::Hash, keys: &[Vec<u8>]) -> RpcResult<Vec<Bytes>> {
+		let proof = self
+			.client
+			.read_proof(substrate_hash, &mut keys.iter().map(|key| key.as_slice()))
+			.map_err(|err| internal_err(format!("Failed to generate proof: {err:?}")))?;
+		Ok(proof.into_iter_nodes().map(Bytes::new).collect())
+	}
+}
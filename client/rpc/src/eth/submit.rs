@@ -126,6 +126,15 @@ where
 				}
 				TransactionMessage::EIP1559(m)
 			}
+			Some(TransactionMessage::EIP7702(mut m)) => {
+				m.nonce = nonce;
+				m.chain_id = chain_id;
+				m.gas_limit = gas_limit;
+				if max_fee_per_gas.is_none() {
+					m.max_fee_per_gas = self.gas_price().unwrap_or_default();
+				}
+				TransactionMessage::EIP7702(m)
+			}
 			_ => return Err(internal_err("invalid transaction parameters")),
 		};
 
@@ -161,7 +170,7 @@ where
 			return Err(internal_err("transaction data is empty"));
 		}
 
-		let transaction: ethereum::TransactionV2 =
+		let transaction: ethereum::TransactionV3 =
 			match ethereum::EnvelopedDecodable::decode(&bytes) {
 				Ok(transaction) => transaction,
 				Err(_) => return Err(internal_err("decode transaction failed")),
@@ -181,7 +190,7 @@ where
 	fn convert_transaction(
 		&self,
 		block_hash: B::Hash,
-		transaction: ethereum::TransactionV2,
+		transaction: ethereum::TransactionV3,
 	) -> RpcResult<B::Extrinsic> {
 		let api_version = match self
 			.client
@@ -202,7 +211,7 @@ where
 				Err(_) => Err(internal_err("cannot access `ConvertTransactionRuntimeApi`")),
 			},
 			Some(1) => {
-				if let ethereum::TransactionV2::Legacy(legacy_transaction) = transaction {
+				if let ethereum::TransactionV3::Legacy(legacy_transaction) = transaction {
 					// To be compatible with runtimes that do not support transactions v2
 					#[allow(deprecated)]
 					match self
@@ -18,7 +18,7 @@
 
 use std::sync::Arc;
 
-use ethereum::TransactionV2 as EthereumTransaction;
+use ethereum::TransactionV3 as EthereumTransaction;
 use ethereum_types::{H256, U256, U64};
 use jsonrpsee::core::RpcResult;
 // Substrate
@@ -225,12 +225,12 @@ where
 					if !block_info.is_eip1559 {
 						// Pre-london frontier update stored receipts require cumulative gas calculation.
 						match receipt {
-							ethereum::ReceiptV3::Legacy(ref d) => {
+							ethereum::ReceiptV4::Legacy(ref d) => {
 								let index = core::cmp::min(receipts.len(), index + 1);
 								let cumulative_gas: u32 = receipts[..index]
 									.iter()
 									.map(|r| match r {
-										ethereum::ReceiptV3::Legacy(d) => Ok(d.used_gas.as_u32()),
+										ethereum::ReceiptV4::Legacy(d) => Ok(d.used_gas.as_u32()),
 										_ => Err(internal_err(format!(
 											"Unknown receipt for request {}",
 											hash
@@ -254,16 +254,18 @@ where
 						}
 					} else {
 						match receipt {
-							ethereum::ReceiptV3::Legacy(ref d)
-							| ethereum::ReceiptV3::EIP2930(ref d)
-							| ethereum::ReceiptV3::EIP1559(ref d) => {
+							ethereum::ReceiptV4::Legacy(ref d)
+							| ethereum::ReceiptV4::EIP2930(ref d)
+							| ethereum::ReceiptV4::EIP1559(ref d)
+							| ethereum::ReceiptV4::EIP7702(ref d) => {
 								let cumulative_gas = d.used_gas;
 								let gas_used = if index > 0 {
 									let previous_receipt = receipts[index - 1].clone();
 									let previous_gas_used = match previous_receipt {
-										ethereum::ReceiptV3::Legacy(d)
-										| ethereum::ReceiptV3::EIP2930(d)
-										| ethereum::ReceiptV3::EIP1559(d) => d.used_gas,
+										ethereum::ReceiptV4::Legacy(d)
+										| ethereum::ReceiptV4::EIP2930(d)
+										| ethereum::ReceiptV4::EIP1559(d)
+										| ethereum::ReceiptV4::EIP7702(d) => d.used_gas,
 									};
 									cumulative_gas.saturating_sub(previous_gas_used)
 								} else {
@@ -287,7 +289,16 @@ where
 				let effective_gas_price = match transaction {
 					EthereumTransaction::Legacy(t) => t.gas_price,
 					EthereumTransaction::EIP2930(t) => t.gas_price,
-					EthereumTransaction::EIP1559(t) => {
+					EthereumTransaction::EIP1559(ethereum::EIP1559Transaction {
+						max_priority_fee_per_gas,
+						max_fee_per_gas,
+						..
+					})
+					| EthereumTransaction::EIP7702(ethereum::EIP7702Transaction {
+						max_priority_fee_per_gas,
+						max_fee_per_gas,
+						..
+					}) => {
 						let parent_eth_hash = block.header.parent_hash;
 						let base_fee_block_substrate_hash = if parent_eth_hash.is_zero() {
 							substrate_hash
@@ -308,9 +319,9 @@ where
 							.runtime_api()
 							.gas_price(base_fee_block_substrate_hash)
 							.unwrap_or_default()
-							.checked_add(t.max_priority_fee_per_gas)
+							.checked_add(max_priority_fee_per_gas)
 							.unwrap_or_else(U256::max_value)
-							.min(t.max_fee_per_gas)
+							.min(max_fee_per_gas)
 					}
 				};
 
@@ -332,9 +343,10 @@ where
 								cumulative_receipts
 									.iter()
 									.map(|r| match r {
-										ethereum::ReceiptV3::Legacy(d)
-										| ethereum::ReceiptV3::EIP2930(d)
-										| ethereum::ReceiptV3::EIP1559(d) => d.logs.len() as u32,
+										ethereum::ReceiptV4::Legacy(d)
+										| ethereum::ReceiptV4::EIP2930(d)
+										| ethereum::ReceiptV4::EIP1559(d)
+										| ethereum::ReceiptV4::EIP7702(d) => d.logs.len() as u32,
 									})
 									.sum::<u32>(),
 							);
@@ -362,10 +374,12 @@ where
 					state_root: None,
 					effective_gas_price,
 					transaction_type: match receipt {
-						ethereum::ReceiptV3::Legacy(_) => U256::from(0),
-						ethereum::ReceiptV3::EIP2930(_) => U256::from(1),
-						ethereum::ReceiptV3::EIP1559(_) => U256::from(2),
+						ethereum::ReceiptV4::Legacy(_) => U256::from(0),
+						ethereum::ReceiptV4::EIP2930(_) => U256::from(1),
+						ethereum::ReceiptV4::EIP1559(_) => U256::from(2),
+						ethereum::ReceiptV4::EIP7702(_) => U256::from(4),
 					},
+					revert_reason: status.revert_reason.map(Bytes),
 				}));
 			}
 			_ => Ok(None),
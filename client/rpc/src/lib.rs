@@ -31,7 +31,9 @@ mod debug;
 mod eth;
 mod eth_pubsub;
 mod net;
+mod revert;
 mod signer;
+mod trace;
 #[cfg(feature = "txpool")]
 mod txpool;
 mod web3;
@@ -44,15 +46,17 @@ pub use self::{
 	eth::{format, pending, EstimateGasAdapter, Eth, EthConfig, EthFilter},
 	eth_pubsub::{EthPubSub, EthereumSubIdProvider},
 	net::Net,
+	revert::{RevertDecoder, RevertReason},
 	signer::{EthDevSigner, EthSigner},
+	trace::Trace,
 	web3::Web3,
 };
-pub use ethereum::TransactionV2 as EthereumTransaction;
+pub use ethereum::TransactionV3 as EthereumTransaction;
 #[cfg(feature = "txpool")]
 pub use fc_rpc_core::TxPoolApiServer;
 pub use fc_rpc_core::{
 	DebugApiServer, EthApiServer, EthFilterApiServer, EthPubSubApiServer, NetApiServer,
-	Web3ApiServer,
+	TraceApiServer, Web3ApiServer,
 };
 pub use fc_storage::{overrides::*, StorageOverrideHandler};
 
@@ -342,6 +346,12 @@ pub fn public_key(transaction: &EthereumTransaction) -> Result<[u8; 64], sp_io::
 			sig[64] = t.odd_y_parity as u8;
 			msg.copy_from_slice(&ethereum::EIP1559TransactionMessage::from(t.clone()).hash()[..]);
 		}
+		EthereumTransaction::EIP7702(t) => {
+			sig[0..32].copy_from_slice(&t.r[..]);
+			sig[32..64].copy_from_slice(&t.s[..]);
+			sig[64] = t.odd_y_parity as u8;
+			msg.copy_from_slice(&ethereum::EIP7702TransactionMessage::from(t.clone()).hash()[..]);
+		}
 	}
 	sp_io::crypto::secp256k1_ecdsa_recover(&sig, &msg)
 }
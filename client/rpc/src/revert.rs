@@ -0,0 +1,411 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Decoding of the output of reverted calls.
+
+use std::{collections::HashMap, fmt, path::Path};
+
+use ethereum_types::{H160, U256};
+use jsonrpsee::types::error::ErrorObjectOwned;
+use serde::Deserialize;
+// Substrate
+use sp_core::hashing::keccak_256;
+
+/// Error code of a reverted call (EIP-1474).
+pub const EXECUTION_REVERTED: i32 = 3;
+
+/// Selector of the solidity `Error(string)`.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// Selector of the solidity `Panic(uint256)`.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Reason of a revert, decoded from its output.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RevertReason {
+	/// `Error(string)`, as raised by `require` and `revert` with a message.
+	Error(String),
+	/// `Panic(uint256)`, as raised by failed assertions and checked arithmetic.
+	Panic(U256),
+	/// A custom error of a registered ABI.
+	Custom {
+		/// Signature of the error, such as `InsufficientBalance(uint256,uint256)`.
+		signature: String,
+		/// Name of the error.
+		name: String,
+		/// Decoded arguments, unless some of their types are not supported.
+		args: Option<Vec<String>>,
+	},
+}
+
+impl RevertReason {
+	/// Decode the `Error(string)` and `Panic(uint256)` reverts.
+	pub fn decode(output: &[u8]) -> Option<Self> {
+		let (selector, data) = split_selector(output)?;
+		match selector {
+			ERROR_SELECTOR => decode_dynamic(data, 0)
+				.and_then(|message| String::from_utf8(message.to_vec()).ok())
+				.map(Self::Error),
+			PANIC_SELECTOR => word(data, 0).map(|code| Self::Panic(U256::from_big_endian(code))),
+			_ => None,
+		}
+	}
+}
+
+impl fmt::Display for RevertReason {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Error(message) => f.write_str(message),
+			Self::Panic(code) => match panic_meaning(*code) {
+				Some(meaning) => f.write_str(meaning),
+				None => write!(f, "unknown panic code: {code:#x}"),
+			},
+			Self::Custom {
+				name,
+				args: Some(args),
+				..
+			} => write!(f, "{name}({})", args.join(", ")),
+			Self::Custom { signature, .. } => f.write_str(signature),
+		}
+	}
+}
+
+/// Meaning of the solidity panic codes, worded as geth does.
+fn panic_meaning(code: U256) -> Option<&'static str> {
+	if code > U256::from(u8::MAX) {
+		return None;
+	}
+	Some(match code.low_u32() {
+		0x00 => "generic panic",
+		0x01 => "assert(false)",
+		0x11 => "arithmetic underflow or overflow",
+		0x12 => "division or modulo by zero",
+		0x21 => "enum overflow",
+		0x22 => "invalid encoded storage byte array accessed",
+		0x31 => "out-of-bounds array access; popping on an empty array",
+		0x32 => "out-of-bounds access of an array or bytesN",
+		0x41 => "out of memory",
+		0x51 => "uninitialized function",
+		_ => return None,
+	})
+}
+
+/// A custom error declared in an ABI.
+#[derive(Clone, Debug)]
+struct CustomError {
+	name: String,
+	signature: String,
+	types: Vec<String>,
+}
+
+/// Entry of a JSON ABI.
+#[derive(Deserialize)]
+struct AbiEntry {
+	#[serde(rename = "type")]
+	kind: String,
+	#[serde(default)]
+	name: String,
+	#[serde(default)]
+	inputs: Vec<AbiParam>,
+}
+
+/// Parameter of a JSON ABI entry.
+#[derive(Deserialize)]
+struct AbiParam {
+	#[serde(rename = "type")]
+	kind: String,
+	#[serde(default)]
+	components: Vec<AbiParam>,
+}
+
+impl AbiParam {
+	/// Canonical type of the parameter, as used in signatures.
+	fn canonical_type(&self) -> String {
+		match self.kind.strip_prefix("tuple") {
+			Some(suffix) => {
+				let components = self
+					.components
+					.iter()
+					.map(AbiParam::canonical_type)
+					.collect::<Vec<_>>();
+				format!("({}){suffix}", components.join(","))
+			}
+			None => self.kind.clone(),
+		}
+	}
+}
+
+/// JSON ABI, either bare or in a compiler artifact.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Abi {
+	Entries(Vec<AbiEntry>),
+	Artifact { abi: Vec<AbiEntry> },
+}
+
+/// Decodes the output of reverted calls, including the custom errors of a registry of ABIs.
+#[derive(Clone, Debug, Default)]
+pub struct RevertDecoder {
+	errors: HashMap<[u8; 4], CustomError>,
+}
+
+impl RevertDecoder {
+	/// Register the custom errors of a JSON ABI, bare or in a compiler artifact, returning how
+	/// many it declares.
+	pub fn register_abi(&mut self, abi: &str) -> Result<usize, serde_json::Error> {
+		let entries = match serde_json::from_str(abi)? {
+			Abi::Entries(entries) => entries,
+			Abi::Artifact { abi } => abi,
+		};
+		let mut count = 0;
+		for entry in entries.into_iter().filter(|entry| entry.kind == "error") {
+			let types = entry
+				.inputs
+				.iter()
+				.map(AbiParam::canonical_type)
+				.collect::<Vec<_>>();
+			let signature = format!("{}({})", entry.name, types.join(","));
+			let hash = keccak_256(signature.as_bytes());
+			self.errors.insert(
+				[hash[0], hash[1], hash[2], hash[3]],
+				CustomError {
+					name: entry.name,
+					signature,
+					types,
+				},
+			);
+			count += 1;
+		}
+		Ok(count)
+	}
+
+	/// Register the custom errors of the JSON ABI in the file at `path`.
+	pub fn register_abi_file(&mut self, path: &Path) -> std::io::Result<usize> {
+		let abi = std::fs::read_to_string(path)?;
+		self.register_abi(&abi)
+			.map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+	}
+
+	/// Decode the output of a reverted call.
+	pub fn decode(&self, output: &[u8]) -> Option<RevertReason> {
+		if let Some(reason) = RevertReason::decode(output) {
+			return Some(reason);
+		}
+		let (selector, data) = split_selector(output)?;
+		let error = self.errors.get(&selector)?;
+		Some(RevertReason::Custom {
+			signature: error.signature.clone(),
+			name: error.name.clone(),
+			args: decode_args(&error.types, data),
+		})
+	}
+
+	/// EIP-1474 error of a reverted call, with the output of the call as data.
+	pub fn error(&self, output: &[u8]) -> ErrorObjectOwned {
+		let message = match self.decode(output) {
+			Some(reason) => format!("execution reverted: {reason}"),
+			None => "execution reverted".to_string(),
+		};
+		crate::err(EXECUTION_REVERTED, message, Some(output))
+	}
+}
+
+fn split_selector(output: &[u8]) -> Option<([u8; 4], &[u8])> {
+	let (selector, data) = output.split_first_chunk::<4>()?;
+	Some((*selector, data))
+}
+
+/// The ABI word at `offset` of `data`.
+fn word(data: &[u8], offset: usize) -> Option<&[u8]> {
+	data.get(offset..offset.checked_add(32)?)
+}
+
+/// The word at `offset` of `data` as a `usize`.
+fn word_as_usize(data: &[u8], offset: usize) -> Option<usize> {
+	let value = U256::from_big_endian(word(data, offset)?);
+	(value <= U256::from(usize::MAX)).then(|| value.as_usize())
+}
+
+/// The `bytes` or `string` whose offset is in the head word at `offset` of `data`.
+fn decode_dynamic(data: &[u8], offset: usize) -> Option<&[u8]> {
+	let start = word_as_usize(data, offset)?;
+	let len = word_as_usize(data, start)?;
+	let content = start.checked_add(32)?;
+	data.get(content..content.checked_add(len)?)
+}
+
+/// Decode the arguments of a custom error, or `None` if some of their types are not supported.
+fn decode_args(types: &[String], data: &[u8]) -> Option<Vec<String>> {
+	types
+		.iter()
+		.enumerate()
+		.map(|(index, kind)| decode_arg(kind, data, index * 32))
+		.collect()
+}
+
+fn decode_arg(kind: &str, data: &[u8], offset: usize) -> Option<String> {
+	match kind {
+		"address" => Some(format!(
+			"{:?}",
+			H160::from_slice(&word(data, offset)?[12..])
+		)),
+		"bool" => match U256::from_big_endian(word(data, offset)?) {
+			value if value.is_zero() => Some("false".to_string()),
+			value if value == U256::one() => Some("true".to_string()),
+			_ => None,
+		},
+		"string" => String::from_utf8(decode_dynamic(data, offset)?.to_vec())
+			.ok()
+			.map(|value| format!("{value:?}")),
+		"bytes" => Some(format!("0x{}", hex::encode(decode_dynamic(data, offset)?))),
+		_ => {
+			if let Some(size) = kind.strip_prefix("uint") {
+				valid_int_size(size)?;
+				Some(U256::from_big_endian(word(data, offset)?).to_string())
+			} else if let Some(size) = kind.strip_prefix("int") {
+				valid_int_size(size)?;
+				let value = U256::from_big_endian(word(data, offset)?);
+				if value.bit(255) {
+					// Two's complement of a negative value.
+					Some(format!("-{}", (!value).overflowing_add(U256::one()).0))
+				} else {
+					Some(value.to_string())
+				}
+			} else if let Some(size) = kind.strip_prefix("bytes") {
+				let size = size
+					.parse::<usize>()
+					.ok()
+					.filter(|size| (1..=32).contains(size))?;
+				Some(format!("0x{}", hex::encode(&word(data, offset)?[..size])))
+			} else {
+				None
+			}
+		}
+	}
+}
+
+/// Check the size of an `intN` or `uintN`, `N` being empty for 256.
+fn valid_int_size(size: &str) -> Option<()> {
+	if size.is_empty() {
+		return Some(());
+	}
+	let size = size.parse::<usize>().ok()?;
+	(size > 0 && size <= 256 && size % 8 == 0).then_some(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn words(words: &[&str]) -> Vec<u8> {
+		words
+			.iter()
+			.flat_map(|word| hex::decode(format!("{word:0>64}")).unwrap())
+			.collect()
+	}
+
+	#[test]
+	fn decodes_error_string() {
+		let mut output = ERROR_SELECTOR.to_vec();
+		output.extend(words(&[
+			"20",
+			"4",
+			"6e6f706500000000000000000000000000000000000000000000000000000000",
+		]));
+		assert_eq!(
+			RevertReason::decode(&output),
+			Some(RevertReason::Error("nope".to_string()))
+		);
+		assert_eq!(RevertReason::decode(&output[..67]), None);
+	}
+
+	#[test]
+	fn decodes_panic_codes() {
+		let mut output = PANIC_SELECTOR.to_vec();
+		output.extend(words(&["11"]));
+		let reason = RevertReason::decode(&output).unwrap();
+		assert_eq!(reason, RevertReason::Panic(U256::from(0x11)));
+		assert_eq!(reason.to_string(), "arithmetic underflow or overflow");
+		assert_eq!(
+			RevertReason::Panic(U256::from(0x99)).to_string(),
+			"unknown panic code: 0x99"
+		);
+	}
+
+	#[test]
+	fn decodes_registered_custom_errors() {
+		let mut decoder = RevertDecoder::default();
+		let count = decoder
+			.register_abi(
+				r#"{"abi": [
+					{"type": "function", "name": "transfer", "inputs": []},
+					{"type": "error", "name": "InsufficientBalance", "inputs": [
+						{"name": "account", "type": "address"},
+						{"name": "delta", "type": "int256"},
+						{"name": "note", "type": "string"}
+					]},
+					{"type": "error", "name": "Unsupported", "inputs": [
+						{"name": "pair", "type": "tuple", "components": [
+							{"name": "a", "type": "uint8"},
+							{"name": "b", "type": "bool"}
+						]}
+					]}
+				]}"#,
+			)
+			.unwrap();
+		assert_eq!(count, 2);
+
+		let mut output = keccak_256(b"InsufficientBalance(address,int256,string)")[..4].to_vec();
+		output.extend(words(&[
+			"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+			"fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffb",
+			"60",
+			"2",
+			"6869000000000000000000000000000000000000000000000000000000000000",
+		]));
+		assert_eq!(
+			decoder.decode(&output).unwrap().to_string(),
+			"InsufficientBalance(0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa, -5, \"hi\")"
+		);
+
+		let mut output = keccak_256(b"Unsupported((uint8,bool))")[..4].to_vec();
+		output.extend(words(&["1", "1"]));
+		assert_eq!(
+			decoder.decode(&output).unwrap().to_string(),
+			"Unsupported((uint8,bool))"
+		);
+
+		assert_eq!(decoder.decode(&[0u8; 36]), None);
+	}
+
+	#[test]
+	fn reverts_are_eip1474_errors() {
+		let decoder = RevertDecoder::default();
+		let mut output = PANIC_SELECTOR.to_vec();
+		output.extend(words(&["1"]));
+		let error = decoder.error(&output);
+		assert_eq!(error.code(), EXECUTION_REVERTED);
+		assert_eq!(error.message(), "execution reverted: assert(false)");
+		assert_eq!(
+			error.data().unwrap().get(),
+			format!("\"0x{}\"", hex::encode(&output))
+		);
+
+		let error = decoder.error(&[]);
+		assert_eq!(error.message(), "execution reverted");
+	}
+}
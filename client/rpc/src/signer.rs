@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use ethereum::TransactionV2 as EthereumTransaction;
+use ethereum::TransactionV3 as EthereumTransaction;
 use ethereum_types::{H160, H256};
 use jsonrpsee::types::ErrorObjectOwned;
 // Substrate
@@ -153,6 +153,30 @@ impl EthSigner for EthDevSigner {
 								s,
 							}));
 					}
+					TransactionMessage::EIP7702(m) => {
+						let signing_message = libsecp256k1::Message::parse_slice(&m.hash()[..])
+							.map_err(|_| internal_err("invalid signing message"))?;
+						let (signature, recid) = libsecp256k1::sign(&signing_message, secret);
+						let rs = signature.serialize();
+						let r = H256::from_slice(&rs[0..32]);
+						let s = H256::from_slice(&rs[32..64]);
+						transaction =
+							Some(EthereumTransaction::EIP7702(ethereum::EIP7702Transaction {
+								chain_id: m.chain_id,
+								nonce: m.nonce,
+								max_priority_fee_per_gas: m.max_priority_fee_per_gas,
+								max_fee_per_gas: m.max_fee_per_gas,
+								gas_limit: m.gas_limit,
+								action: m.action,
+								value: m.value,
+								input: m.input.clone(),
+								access_list: m.access_list,
+								authorization_list: m.authorization_list,
+								odd_y_parity: recid.serialize() != 0,
+								r,
+								s,
+							}));
+					}
 				}
 				break;
 			}
@@ -0,0 +1,459 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use ethereum_types::{H256, U256};
+use evm::{ExitError, ExitReason};
+use jsonrpsee::core::{async_trait, RpcResult};
+// Substrate
+use sc_client_api::{
+	backend::{Backend, StorageProvider},
+	BlockBackend,
+};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
+// Frontier
+use fc_api::FlatCallFrame;
+use fc_rpc_core::{types::*, TraceApiServer};
+use fp_evm::{CallTracerConfig, CallType, TracerConfig};
+use fp_rpc::{DebugRuntimeApi, EthereumRuntimeRPCApi};
+
+use crate::{cache::EthBlockDataCacheTask, frontier_backend_client, internal_err};
+
+/// Trace API implementation.
+pub struct Trace<B: BlockT, C, BE> {
+	client: Arc<C>,
+	backend: Arc<dyn fc_api::Backend<B>>,
+	block_data_cache: Arc<EthBlockDataCacheTask<B>>,
+	/// Maximum number of blocks searched by a `trace_filter` request.
+	max_block_range: u32,
+	/// Maximum number of traces returned by a `trace_filter` request.
+	max_count: u32,
+	_marker: PhantomData<BE>,
+}
+
+impl<B: BlockT, C, BE> Trace<B, C, BE> {
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<dyn fc_api::Backend<B>>,
+		block_data_cache: Arc<EthBlockDataCacheTask<B>>,
+		max_block_range: u32,
+		max_count: u32,
+	) -> Self {
+		Self {
+			client,
+			backend,
+			block_data_cache,
+			max_block_range,
+			max_count,
+			_marker: PhantomData,
+		}
+	}
+
+	async fn substrate_hash_by(&self, number: BlockNumberOrHash) -> RpcResult<Option<B::Hash>>
+	where
+		C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+		BE: Backend<B>,
+	{
+		let id = match frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number),
+		)
+		.await?
+		{
+			Some(id) => id,
+			None => return Ok(None),
+		};
+
+		let substrate_hash = self
+			.client
+			.expect_block_hash_from_id(&id)
+			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
+		Ok(Some(substrate_hash))
+	}
+
+	/// Number of the given block, the latest block when unset.
+	async fn block_number_by(&self, number: Option<BlockNumberOrHash>) -> RpcResult<u64>
+	where
+		C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+		BE: Backend<B>,
+	{
+		let id = frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			number,
+		)
+		.await?
+		.ok_or_else(|| internal_err("Block not found"))?;
+		let number = self
+			.client
+			.expect_block_number_from_id(&id)
+			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
+		Ok(UniqueSaturatedInto::<u64>::unique_saturated_into(number))
+	}
+
+	/// Header and extrinsics of the substrate block, along with the hash of its parent.
+	fn substrate_block(
+		&self,
+		substrate_hash: B::Hash,
+	) -> RpcResult<(B::Hash, B::Header, Vec<B::Extrinsic>)>
+	where
+		C: ProvideRuntimeApi<B> + BlockBackend<B>,
+		C::Api: DebugRuntimeApi<B>,
+	{
+		let block = self
+			.client
+			.block(substrate_hash)
+			.map_err(|err| internal_err(format!("{:?}", err)))?
+			.ok_or_else(|| internal_err("Block not found"))?
+			.block;
+		let (header, extrinsics) = block.deconstruct();
+		let parent_hash = *header.parent_hash();
+		let has_api = self
+			.client
+			.runtime_api()
+			.has_api::<dyn DebugRuntimeApi<B>>(parent_hash)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?;
+		if !has_api {
+			return Err(internal_err(
+				"`DebugRuntimeApi` is not supported by the runtime",
+			));
+		}
+		Ok((parent_hash, header, extrinsics))
+	}
+}
+
+/// Flatten the call trace of a transaction.
+fn localized_traces(
+	trace: fp_evm::TransactionTrace,
+	block: &ethereum::BlockV3,
+	transaction_hash: H256,
+	transaction_position: u32,
+) -> Vec<LocalizedTrace> {
+	let fp_evm::TransactionTrace::CallTracer(frame) = trace else {
+		return vec![];
+	};
+	FlatCallFrame::flatten(frame)
+		.into_iter()
+		.map(|trace| {
+			localized_trace(
+				trace,
+				block.header.hash(),
+				block.header.number.as_u64(),
+				transaction_hash,
+				transaction_position,
+			)
+		})
+		.collect()
+}
+
+fn localized_trace(
+	trace: FlatCallFrame,
+	block_hash: H256,
+	block_number: u64,
+	transaction_hash: H256,
+	transaction_position: u32,
+) -> LocalizedTrace {
+	let FlatCallFrame {
+		trace_address,
+		subtraces,
+		call,
+	} = trace;
+	let error = call.exit_reason.as_ref().and_then(exit_error);
+	let gas = U256::from(call.gas);
+	let gas_used = U256::from(call.gas_used);
+	let (trace_type, action, result) = match call.call_type {
+		CallType::Create | CallType::Create2 => (
+			TraceType::Create,
+			TraceAction::Create(CreateAction {
+				from: call.from,
+				value: call.value.unwrap_or_default(),
+				gas,
+				init: Bytes(call.input),
+				creation_method: match call.call_type {
+					CallType::Create2 => "create2",
+					_ => "create",
+				}
+				.to_string(),
+			}),
+			Some(TraceResult::Create {
+				gas_used,
+				code: Bytes(call.output),
+				address: call.to,
+			}),
+		),
+		CallType::SelfDestruct => (
+			TraceType::Suicide,
+			TraceAction::Suicide(SuicideAction {
+				address: call.from,
+				refund_address: call.to,
+				balance: call.value.unwrap_or_default(),
+			}),
+			None,
+		),
+		call_type => (
+			TraceType::Call,
+			TraceAction::Call(CallAction {
+				from: call.from,
+				to: call.to,
+				value: call.value.unwrap_or_default(),
+				gas,
+				input: Bytes(call.input),
+				call_type: match call_type {
+					CallType::CallCode => "callcode",
+					CallType::DelegateCall => "delegatecall",
+					CallType::StaticCall => "staticcall",
+					_ => "call",
+				}
+				.to_string(),
+			}),
+			Some(TraceResult::Call {
+				gas_used,
+				output: Bytes(call.output),
+			}),
+		),
+	};
+	LocalizedTrace {
+		action,
+		result: result.filter(|_| error.is_none()),
+		error,
+		subtraces,
+		trace_address,
+		block_hash,
+		block_number,
+		transaction_hash,
+		transaction_position,
+		trace_type,
+	}
+}
+
+/// Error message of a failed call, worded as OpenEthereum does.
+fn exit_error(reason: &ExitReason) -> Option<String> {
+	let message = match reason {
+		ExitReason::Succeed(_) => return None,
+		ExitReason::Revert(_) => "Reverted",
+		ExitReason::Error(err) => match err {
+			ExitError::OutOfGas => "Out of gas",
+			ExitError::InvalidJump => "Bad jump destination",
+			ExitError::DesignatedInvalid => "Bad instruction",
+			ExitError::StackUnderflow => "Stack underflow",
+			ExitError::StackOverflow | ExitError::CallTooDeep => "Out of stack",
+			ExitError::InvalidRange => "Out of bounds",
+			ExitError::Other(message) => return Some(message.to_string()),
+			err => return Some(format!("{err:?}")),
+		},
+		ExitReason::Fatal(_) => "Internal error",
+	};
+	Some(message.to_string())
+}
+
+#[async_trait]
+impl<B, C, BE> TraceApiServer for Trace<B, C, BE>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + BlockBackend<B>,
+	C::Api: EthereumRuntimeRPCApi<B> + DebugRuntimeApi<B>,
+	C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
+	BE: Backend<B> + 'static,
+{
+	async fn trace_block(
+		&self,
+		number: BlockNumberOrHash,
+	) -> RpcResult<Option<Vec<LocalizedTrace>>> {
+		let Some(substrate_hash) = self.substrate_hash_by(number).await? else {
+			return Ok(None);
+		};
+		let Some(block) = self.block_data_cache.current_block(substrate_hash).await else {
+			return Ok(None);
+		};
+
+		let (parent_hash, header, extrinsics) = self.substrate_block(substrate_hash)?;
+		let traces = self
+			.client
+			.runtime_api()
+			.trace_block(
+				parent_hash,
+				&header,
+				extrinsics,
+				TracerConfig::CallTracer(CallTracerConfig::default()),
+			)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			.map_err(|err| internal_err(format!("failed to trace block: {err:?}")))?;
+
+		Ok(Some(
+			block
+				.transactions
+				.iter()
+				.zip(traces)
+				.enumerate()
+				.flat_map(|(position, (transaction, trace))| {
+					localized_traces(trace, &block, transaction.hash(), position as u32)
+				})
+				.collect(),
+		))
+	}
+
+	async fn trace_transaction(&self, hash: H256) -> RpcResult<Option<Vec<LocalizedTrace>>> {
+		let (eth_block_hash, position) = match frontier_backend_client::load_transactions::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			hash,
+			true,
+		)
+		.await?
+		{
+			Some(transaction) => transaction,
+			None => return Ok(None),
+		};
+		let Some(substrate_hash) = frontier_backend_client::load_hash::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			eth_block_hash,
+		)
+		.await?
+		else {
+			return Ok(None);
+		};
+		let Some(block) = self.block_data_cache.current_block(substrate_hash).await else {
+			return Ok(None);
+		};
+
+		let (parent_hash, header, extrinsics) = self.substrate_block(substrate_hash)?;
+		let trace = self
+			.client
+			.runtime_api()
+			.trace_transaction(
+				parent_hash,
+				&header,
+				extrinsics,
+				hash,
+				TracerConfig::CallTracer(CallTracerConfig::default()),
+			)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?
+			.map_err(|err| internal_err(format!("failed to trace transaction: {err:?}")))?;
+		Ok(Some(localized_traces(trace, &block, hash, position)))
+	}
+
+	async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTrace>> {
+		let trace_indexer = self.backend.trace_indexer();
+		if !trace_indexer.is_indexed() {
+			return Err(internal_err(
+				"trace_filter requires the sql backend with trace indexing enabled",
+			));
+		}
+
+		let from_block = self.block_number_by(filter.from_block).await?;
+		let to_block = self.block_number_by(filter.to_block).await?;
+		if from_block > to_block {
+			return Err(internal_err("fromBlock cannot be greater than toBlock"));
+		}
+		if to_block - from_block >= u64::from(self.max_block_range) {
+			return Err(internal_err(format!(
+				"block range is too wide (maximum {})",
+				self.max_block_range
+			)));
+		}
+		let count = match filter.count {
+			Some(count) if count > self.max_count => {
+				return Err(internal_err(format!(
+					"count is too large (maximum {})",
+					self.max_count
+				)))
+			}
+			Some(count) => count,
+			// One more trace than allowed is fetched, to tell whether the limit is exceeded.
+			None => self.max_count.saturating_add(1),
+		};
+
+		let traces = trace_indexer
+			.filter_traces(
+				from_block,
+				to_block,
+				filter.from_address,
+				filter.to_address,
+				filter.after.unwrap_or_default(),
+				count,
+			)
+			.await
+			.map_err(internal_err)?;
+		if traces.len() > self.max_count as usize {
+			return Err(internal_err(format!(
+				"query returned more than {} results",
+				self.max_count
+			)));
+		}
+		Ok(traces
+			.into_iter()
+			.map(|trace| {
+				localized_trace(
+					trace.trace,
+					trace.ethereum_block_hash,
+					trace.block_number.into(),
+					trace.transaction_hash,
+					trace.transaction_index,
+				)
+			})
+			.collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use ethereum_types::H160;
+
+	#[test]
+	fn failed_call_has_no_result() {
+		let call = fp_evm::CallFrame {
+			call_type: CallType::DelegateCall,
+			from: H160::from_low_u64_be(1),
+			to: H160::from_low_u64_be(2),
+			value: None,
+			gas: 100,
+			gas_used: 100,
+			input: vec![],
+			output: vec![],
+			exit_reason: Some(ExitReason::Error(ExitError::OutOfGas)),
+			logs: vec![],
+			calls: vec![],
+		};
+		let trace = localized_trace(
+			FlatCallFrame {
+				trace_address: vec![0, 1],
+				subtraces: 0,
+				call,
+			},
+			H256::zero(),
+			1,
+			H256::zero(),
+			0,
+		);
+		assert_eq!(trace.trace_type, TraceType::Call);
+		assert_eq!(trace.error.as_deref(), Some("Out of gas"));
+		assert_eq!(trace.result, None);
+		let TraceAction::Call(action) = trace.action else {
+			panic!("expected a call action");
+		};
+		assert_eq!(action.call_type, "delegatecall");
+		assert_eq!(action.value, U256::zero());
+	}
+}
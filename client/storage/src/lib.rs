@@ -22,14 +22,14 @@ pub mod overrides;
 
 use std::sync::Arc;
 
-use ethereum::{BlockV2, ReceiptV3};
+use ethereum::{BlockV3, ReceiptV4};
 use ethereum_types::{Address, H256, U256};
 // Substrate
 use sc_client_api::{backend::Backend, StorageProvider};
 use sp_api::ProvideRuntimeApi;
 use sp_runtime::{traits::Block as BlockT, Permill};
 // Frontier
-use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
+use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatusV2};
 use fp_storage::EthereumStorageSchema;
 
 pub use self::overrides::*;
@@ -75,6 +75,9 @@ where
 			Some(EthereumStorageSchema::V3) => {
 				SchemaV3StorageOverrideRef::new(&self.querier).account_code_at(at, address)
 			}
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).account_code_at(at, address)
+			}
 			None => self.fallback.account_code_at(at, address),
 		}
 	}
@@ -87,11 +90,13 @@ where
 				.account_storage_at(at, address, index),
 			Some(EthereumStorageSchema::V3) => SchemaV3StorageOverrideRef::new(&self.querier)
 				.account_storage_at(at, address, index),
+			Some(EthereumStorageSchema::V4) => SchemaV4StorageOverrideRef::new(&self.querier)
+				.account_storage_at(at, address, index),
 			None => self.fallback.account_storage_at(at, address, index),
 		}
 	}
 
-	fn current_block(&self, at: B::Hash) -> Option<BlockV2> {
+	fn current_block(&self, at: B::Hash) -> Option<BlockV3> {
 		match self.querier.storage_schema(at) {
 			Some(EthereumStorageSchema::V1) => {
 				SchemaV1StorageOverrideRef::new(&self.querier).current_block(at)
@@ -102,11 +107,14 @@ where
 			Some(EthereumStorageSchema::V3) => {
 				SchemaV3StorageOverrideRef::new(&self.querier).current_block(at)
 			}
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).current_block(at)
+			}
 			None => self.fallback.current_block(at),
 		}
 	}
 
-	fn current_receipts(&self, at: B::Hash) -> Option<Vec<ReceiptV3>> {
+	fn current_receipts(&self, at: B::Hash) -> Option<Vec<ReceiptV4>> {
 		match self.querier.storage_schema(at) {
 			Some(EthereumStorageSchema::V1) => {
 				SchemaV1StorageOverrideRef::new(&self.querier).current_receipts(at)
@@ -117,11 +125,14 @@ where
 			Some(EthereumStorageSchema::V3) => {
 				SchemaV3StorageOverrideRef::new(&self.querier).current_receipts(at)
 			}
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).current_receipts(at)
+			}
 			None => self.fallback.current_receipts(at),
 		}
 	}
 
-	fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatus>> {
+	fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatusV2>> {
 		match self.querier.storage_schema(at) {
 			Some(EthereumStorageSchema::V1) => {
 				SchemaV1StorageOverrideRef::new(&self.querier).current_transaction_statuses(at)
@@ -132,6 +143,9 @@ where
 			Some(EthereumStorageSchema::V3) => {
 				SchemaV3StorageOverrideRef::new(&self.querier).current_transaction_statuses(at)
 			}
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).current_transaction_statuses(at)
+			}
 			None => self.fallback.current_transaction_statuses(at),
 		}
 	}
@@ -147,6 +161,9 @@ where
 			Some(EthereumStorageSchema::V3) => {
 				SchemaV3StorageOverrideRef::new(&self.querier).elasticity(at)
 			}
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).elasticity(at)
+			}
 			None => self.fallback.elasticity(at),
 		}
 	}
@@ -162,6 +179,9 @@ where
 			Some(EthereumStorageSchema::V3) => {
 				SchemaV3StorageOverrideRef::new(&self.querier).is_eip1559(at)
 			}
+			Some(EthereumStorageSchema::V4) => {
+				SchemaV4StorageOverrideRef::new(&self.querier).is_eip1559(at)
+			}
 			None => self.fallback.is_eip1559(at),
 		}
 	}
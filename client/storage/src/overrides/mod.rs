@@ -26,7 +26,7 @@ use sp_io::hashing::{blake2_128, twox_128};
 use sp_runtime::{traits::Block as BlockT, Permill};
 use sp_storage::StorageKey;
 // Frontier
-use fp_rpc::TransactionStatus;
+use fp_rpc::TransactionStatusV2;
 use fp_storage::{constants::*, EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA};
 
 mod runtime_api;
@@ -47,6 +47,10 @@ pub use self::{
 			SchemaStorageOverride as SchemaV3StorageOverride,
 			SchemaStorageOverrideRef as SchemaV3StorageOverrideRef,
 		},
+		v4::{
+			SchemaStorageOverride as SchemaV4StorageOverride,
+			SchemaStorageOverrideRef as SchemaV4StorageOverrideRef,
+		},
 	},
 };
 
@@ -58,11 +62,11 @@ pub trait StorageOverride<Block: BlockT>: Send + Sync {
 	fn account_storage_at(&self, at: Block::Hash, address: Address, index: U256) -> Option<H256>;
 
 	/// Return the current ethereum block.
-	fn current_block(&self, at: Block::Hash) -> Option<ethereum::BlockV2>;
+	fn current_block(&self, at: Block::Hash) -> Option<ethereum::BlockV3>;
 	/// Return the current ethereum transaction receipt.
-	fn current_receipts(&self, at: Block::Hash) -> Option<Vec<ethereum::ReceiptV3>>;
+	fn current_receipts(&self, at: Block::Hash) -> Option<Vec<ethereum::ReceiptV4>>;
 	/// Return the current ethereum transaction status.
-	fn current_transaction_statuses(&self, at: Block::Hash) -> Option<Vec<TransactionStatus>>;
+	fn current_transaction_statuses(&self, at: Block::Hash) -> Option<Vec<TransactionStatusV2>>;
 
 	/// Return the elasticity multiplier at the given post-eip1559 block.
 	fn elasticity(&self, at: Block::Hash) -> Option<Permill>;
@@ -143,9 +147,9 @@ where
 		self.query::<Vec<Receipt>>(at, &StorageKey(key))
 	}
 
-	pub fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatus>> {
+	pub fn current_transaction_statuses<Status: Decode>(&self, at: B::Hash) -> Option<Vec<Status>> {
 		let key = storage_prefix_build(PALLET_ETHEREUM, ETHEREUM_CURRENT_TRANSACTION_STATUSES);
-		self.query::<Vec<TransactionStatus>>(at, &StorageKey(key))
+		self.query::<Vec<Status>>(at, &StorageKey(key))
 	}
 
 	pub fn elasticity(&self, at: B::Hash) -> Option<Permill> {
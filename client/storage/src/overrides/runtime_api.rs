@@ -23,7 +23,7 @@ use ethereum_types::{Address, H256, U256};
 use sp_api::{ApiExt, ApiRef, ProvideRuntimeApi};
 use sp_runtime::{traits::Block as BlockT, Permill};
 // Frontier
-use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
+use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatusV2};
 
 use crate::overrides::StorageOverride;
 
@@ -82,20 +82,20 @@ where
 			.ok()
 	}
 
-	fn current_block(&self, block_hash: B::Hash) -> Option<ethereum::BlockV2> {
+	fn current_block(&self, block_hash: B::Hash) -> Option<ethereum::BlockV3> {
 		let api = self.client.runtime_api();
 
 		let api_version = Self::api_version(&api, block_hash)?;
 		if api_version == 1 {
 			#[allow(deprecated)]
 			let old_block = api.current_block_before_version_2(block_hash).ok()?;
-			old_block.map(|block| block.into())
+			old_block.map(|block| ethereum::BlockV2::from(block).into())
 		} else {
 			api.current_block(block_hash).ok()?
 		}
 	}
 
-	fn current_receipts(&self, block_hash: B::Hash) -> Option<Vec<ethereum::ReceiptV3>> {
+	fn current_receipts(&self, block_hash: B::Hash) -> Option<Vec<ethereum::ReceiptV4>> {
 		let api = self.client.runtime_api();
 
 		let api_version = Self::api_version(&api, block_hash)?;
@@ -106,7 +106,7 @@ where
 				receipts
 					.into_iter()
 					.map(|r| {
-						ethereum::ReceiptV3::Legacy(ethereum::EIP658ReceiptData {
+						ethereum::ReceiptV4::Legacy(ethereum::EIP658ReceiptData {
 							status_code: r.state_root.to_low_u64_be() as u8,
 							used_gas: r.used_gas,
 							logs_bloom: r.logs_bloom,
@@ -123,11 +123,22 @@ where
 		}
 	}
 
-	fn current_transaction_statuses(&self, block_hash: B::Hash) -> Option<Vec<TransactionStatus>> {
-		self.client
-			.runtime_api()
-			.current_transaction_statuses(block_hash)
-			.ok()?
+	fn current_transaction_statuses(
+		&self,
+		block_hash: B::Hash,
+	) -> Option<Vec<TransactionStatusV2>> {
+		let api = self.client.runtime_api();
+
+		let api_version = Self::api_version(&api, block_hash)?;
+		if api_version < 9 {
+			#[allow(deprecated)]
+			let old_statuses = api
+				.current_transaction_statuses_before_version_9(block_hash)
+				.ok()?;
+			old_statuses.map(|statuses| statuses.into_iter().map(Into::into).collect())
+		} else {
+			api.current_transaction_statuses(block_hash).ok()?
+		}
 	}
 
 	fn elasticity(&self, block_hash: B::Hash) -> Option<Permill> {
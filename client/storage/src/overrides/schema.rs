@@ -23,7 +23,7 @@ use ethereum_types::{Address, H256, U256};
 use sc_client_api::backend::{Backend, StorageProvider};
 use sp_runtime::{traits::Block as BlockT, Permill};
 // Frontier
-use fp_rpc::TransactionStatus;
+use fp_rpc::{TransactionStatus, TransactionStatusV2};
 
 use crate::overrides::{StorageOverride, StorageQuerier};
 
@@ -57,15 +57,15 @@ pub mod v1 {
 			SchemaStorageOverrideRef::new(&self.querier).account_storage_at(at, address, index)
 		}
 
-		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV2> {
+		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV3> {
 			SchemaStorageOverrideRef::new(&self.querier).current_block(at)
 		}
 
-		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV3>> {
+		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV4>> {
 			SchemaStorageOverrideRef::new(&self.querier).current_receipts(at)
 		}
 
-		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatus>> {
+		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatusV2>> {
 			SchemaStorageOverrideRef::new(&self.querier).current_transaction_statuses(at)
 		}
 
@@ -103,20 +103,20 @@ pub mod v1 {
 			self.querier.account_storage(at, address, index)
 		}
 
-		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV2> {
+		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV3> {
 			self.querier
 				.current_block::<ethereum::BlockV0>(at)
-				.map(Into::into)
+				.map(|block| ethereum::BlockV2::from(block).into())
 		}
 
-		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV3>> {
+		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV4>> {
 			self.querier
 				.current_receipts::<ethereum::ReceiptV0>(at)
 				.map(|receipts| {
 					receipts
 						.into_iter()
 						.map(|r| {
-							ethereum::ReceiptV3::Legacy(ethereum::EIP658ReceiptData {
+							ethereum::ReceiptV4::Legacy(ethereum::EIP658ReceiptData {
 								status_code: r.state_root.to_low_u64_be() as u8,
 								used_gas: r.used_gas,
 								logs_bloom: r.logs_bloom,
@@ -127,8 +127,10 @@ pub mod v1 {
 				})
 		}
 
-		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatus>> {
-			self.querier.current_transaction_statuses(at)
+		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatusV2>> {
+			self.querier
+				.current_transaction_statuses::<TransactionStatus>(at)
+				.map(|statuses| statuses.into_iter().map(Into::into).collect())
 		}
 
 		fn elasticity(&self, _at: B::Hash) -> Option<Permill> {
@@ -171,15 +173,15 @@ pub mod v2 {
 			SchemaStorageOverrideRef::new(&self.querier).account_storage_at(at, address, index)
 		}
 
-		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV2> {
+		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV3> {
 			SchemaStorageOverrideRef::new(&self.querier).current_block(at)
 		}
 
-		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV3>> {
+		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV4>> {
 			SchemaStorageOverrideRef::new(&self.querier).current_receipts(at)
 		}
 
-		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatus>> {
+		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatusV2>> {
 			SchemaStorageOverrideRef::new(&self.querier).current_transaction_statuses(at)
 		}
 
@@ -217,18 +219,18 @@ pub mod v2 {
 			self.querier.account_storage(at, address, index)
 		}
 
-		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV2> {
+		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV3> {
 			self.querier.current_block(at)
 		}
 
-		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV3>> {
+		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV4>> {
 			self.querier
 				.current_receipts::<ethereum::ReceiptV0>(at)
 				.map(|receipts| {
 					receipts
 						.into_iter()
 						.map(|r| {
-							ethereum::ReceiptV3::Legacy(ethereum::EIP658ReceiptData {
+							ethereum::ReceiptV4::Legacy(ethereum::EIP658ReceiptData {
 								status_code: r.state_root.to_low_u64_be() as u8,
 								used_gas: r.used_gas,
 								logs_bloom: r.logs_bloom,
@@ -239,8 +241,10 @@ pub mod v2 {
 				})
 		}
 
-		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatus>> {
-			self.querier.current_transaction_statuses(at)
+		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatusV2>> {
+			self.querier
+				.current_transaction_statuses::<TransactionStatus>(at)
+				.map(|statuses| statuses.into_iter().map(Into::into).collect())
 		}
 
 		fn elasticity(&self, at: B::Hash) -> Option<Permill> {
@@ -283,15 +287,15 @@ pub mod v3 {
 			SchemaStorageOverrideRef::new(&self.querier).account_storage_at(at, address, index)
 		}
 
-		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV2> {
+		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV3> {
 			SchemaStorageOverrideRef::new(&self.querier).current_block(at)
 		}
 
-		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV3>> {
+		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV4>> {
 			SchemaStorageOverrideRef::new(&self.querier).current_receipts(at)
 		}
 
-		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatus>> {
+		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatusV2>> {
 			SchemaStorageOverrideRef::new(&self.querier).current_transaction_statuses(at)
 		}
 
@@ -329,15 +333,115 @@ pub mod v3 {
 			self.querier.account_storage(at, address, index)
 		}
 
-		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV2> {
+		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV3> {
+			self.querier.current_block(at)
+		}
+
+		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV4>> {
+			self.querier.current_receipts::<ethereum::ReceiptV4>(at)
+		}
+
+		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatusV2>> {
+			self.querier
+				.current_transaction_statuses::<TransactionStatus>(at)
+				.map(|statuses| statuses.into_iter().map(Into::into).collect())
+		}
+
+		fn elasticity(&self, at: B::Hash) -> Option<Permill> {
+			self.querier.elasticity(at)
+		}
+
+		fn is_eip1559(&self, _at: B::Hash) -> bool {
+			true
+		}
+	}
+}
+
+pub mod v4 {
+	use super::*;
+
+	/// A storage override for runtimes that use schema v4.
+	#[derive(Clone)]
+	pub struct SchemaStorageOverride<B, C, BE> {
+		querier: StorageQuerier<B, C, BE>,
+	}
+
+	impl<B, C, BE> SchemaStorageOverride<B, C, BE> {
+		pub fn new(client: Arc<C>) -> Self {
+			let querier = StorageQuerier::new(client);
+			Self { querier }
+		}
+	}
+
+	impl<B, C, BE> StorageOverride<B> for SchemaStorageOverride<B, C, BE>
+	where
+		B: BlockT,
+		C: StorageProvider<B, BE> + Send + Sync,
+		BE: Backend<B>,
+	{
+		fn account_code_at(&self, at: B::Hash, address: Address) -> Option<Vec<u8>> {
+			SchemaStorageOverrideRef::new(&self.querier).account_code_at(at, address)
+		}
+
+		fn account_storage_at(&self, at: B::Hash, address: Address, index: U256) -> Option<H256> {
+			SchemaStorageOverrideRef::new(&self.querier).account_storage_at(at, address, index)
+		}
+
+		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV3> {
+			SchemaStorageOverrideRef::new(&self.querier).current_block(at)
+		}
+
+		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV4>> {
+			SchemaStorageOverrideRef::new(&self.querier).current_receipts(at)
+		}
+
+		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatusV2>> {
+			SchemaStorageOverrideRef::new(&self.querier).current_transaction_statuses(at)
+		}
+
+		fn elasticity(&self, at: B::Hash) -> Option<Permill> {
+			SchemaStorageOverrideRef::new(&self.querier).elasticity(at)
+		}
+
+		fn is_eip1559(&self, at: B::Hash) -> bool {
+			SchemaStorageOverrideRef::new(&self.querier).is_eip1559(at)
+		}
+	}
+
+	/// A storage override for runtimes that use schema v4.
+	pub struct SchemaStorageOverrideRef<'a, B, C, BE> {
+		querier: &'a StorageQuerier<B, C, BE>,
+	}
+
+	impl<'a, B, C, BE> SchemaStorageOverrideRef<'a, B, C, BE> {
+		pub fn new(querier: &'a StorageQuerier<B, C, BE>) -> Self {
+			Self { querier }
+		}
+	}
+
+	impl<'a, B, C, BE> StorageOverride<B> for SchemaStorageOverrideRef<'a, B, C, BE>
+	where
+		B: BlockT,
+		C: StorageProvider<B, BE> + Send + Sync,
+		BE: Backend<B>,
+	{
+		fn account_code_at(&self, at: B::Hash, address: Address) -> Option<Vec<u8>> {
+			self.querier.account_code(at, address)
+		}
+
+		fn account_storage_at(&self, at: B::Hash, address: Address, index: U256) -> Option<H256> {
+			self.querier.account_storage(at, address, index)
+		}
+
+		fn current_block(&self, at: B::Hash) -> Option<ethereum::BlockV3> {
 			self.querier.current_block(at)
 		}
 
-		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV3>> {
-			self.querier.current_receipts::<ethereum::ReceiptV3>(at)
+		fn current_receipts(&self, at: B::Hash) -> Option<Vec<ethereum::ReceiptV4>> {
+			self.querier.current_receipts::<ethereum::ReceiptV4>(at)
 		}
 
-		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatus>> {
+		fn current_transaction_statuses(&self, at: B::Hash) -> Option<Vec<TransactionStatusV2>> {
 			self.querier.current_transaction_statuses(at)
 		}
 
@@ -35,8 +35,8 @@ mod tests;
 use alloc::{vec, vec::Vec};
 use core::marker::PhantomData;
 pub use ethereum::{
-	AccessListItem, BlockV2 as Block, LegacyTransactionMessage, Log, ReceiptV3 as Receipt,
-	TransactionAction, TransactionV2 as Transaction,
+	AccessListItem, AuthorizationListItem, BlockV3 as Block, LegacyTransactionMessage, Log,
+	ReceiptV4 as Receipt, TransactionAction, TransactionV3 as Transaction,
 };
 use ethereum_types::{Bloom, BloomInput, H160, H256, H64, U256};
 use evm::ExitReason;
@@ -67,7 +67,7 @@ use fp_ethereum::ValidatedTransaction as ValidatedTransactionT;
 use fp_evm::{
 	CallOrCreateInfo, CheckEvmTransaction, CheckEvmTransactionConfig, TransactionValidationError,
 };
-pub use fp_rpc::TransactionStatus;
+pub use fp_rpc::{TransactionStatus, TransactionStatusV2};
 use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA};
 use pallet_evm::{BlockHashMapping, FeeCalculator, GasWeightMapping, Runner};
 
@@ -204,6 +204,8 @@ pub mod pallet {
 		type PostLogContent: Get<PostLogContent>;
 		/// The maximum length of the extra data in the Executed event.
 		type ExtraDataLength: Get<u32>;
+		/// The maximum length of the revert output recorded for a failed transaction.
+		type RevertReasonLength: Get<u32>;
 	}
 
 	pub mod config_preludes {
@@ -229,6 +231,7 @@ pub mod pallet {
 			type StateRoot = IntermediateStateRoot<Self::Version>;
 			type PostLogContent = PostBlockAndTxnHashes;
 			type ExtraDataLength = ConstU32<30>;
+			type RevertReasonLength = ConstU32<1024>;
 		}
 	}
 
@@ -291,7 +294,7 @@ pub mod pallet {
 		fn on_runtime_upgrade() -> Weight {
 			frame_support::storage::unhashed::put::<EthereumStorageSchema>(
 				PALLET_ETHEREUM_SCHEMA,
-				&EthereumStorageSchema::V3,
+				&EthereumStorageSchema::V4,
 			);
 
 			T::DbWeight::get().writes(1)
@@ -337,6 +340,7 @@ pub mod pallet {
 			transaction_hash: H256,
 			exit_reason: ExitReason,
 			extra_data: Vec<u8>,
+			revert_reason: Option<Vec<u8>>,
 		},
 	}
 
@@ -351,11 +355,11 @@ pub mod pallet {
 	/// Current building block's transactions and receipts.
 	#[pallet::storage]
 	pub type Pending<T: Config> =
-		StorageValue<_, Vec<(Transaction, TransactionStatus, Receipt)>, ValueQuery>;
+		StorageValue<_, Vec<(Transaction, TransactionStatusV2, Receipt)>, ValueQuery>;
 
 	/// The current Ethereum block.
 	#[pallet::storage]
-	pub type CurrentBlock<T: Config> = StorageValue<_, ethereum::BlockV2>;
+	pub type CurrentBlock<T: Config> = StorageValue<_, ethereum::BlockV3>;
 
 	/// The current Ethereum receipts.
 	#[pallet::storage]
@@ -363,7 +367,7 @@ pub mod pallet {
 
 	/// The current transaction statuses.
 	#[pallet::storage]
-	pub type CurrentTransactionStatuses<T: Config> = StorageValue<_, Vec<TransactionStatus>>;
+	pub type CurrentTransactionStatuses<T: Config> = StorageValue<_, Vec<TransactionStatusV2>>;
 
 	// Mapping for block number and hashes.
 	#[pallet::storage]
@@ -382,7 +386,7 @@ pub mod pallet {
 			<Pallet<T>>::store_block(None, U256::zero());
 			frame_support::storage::unhashed::put::<EthereumStorageSchema>(
 				PALLET_ETHEREUM_SCHEMA,
-				&EthereumStorageSchema::V3,
+				&EthereumStorageSchema::V4,
 			);
 		}
 	}
@@ -430,6 +434,14 @@ impl<T: Config> Pallet<T> {
 					&ethereum::EIP1559TransactionMessage::from(t.clone()).hash()[..],
 				);
 			}
+			Transaction::EIP7702(t) => {
+				sig[0..32].copy_from_slice(&t.r[..]);
+				sig[32..64].copy_from_slice(&t.s[..]);
+				sig[64] = t.odd_y_parity as u8;
+				msg.copy_from_slice(
+					&ethereum::EIP7702TransactionMessage::from(t.clone()).hash()[..],
+				);
+			}
 		}
 		let pubkey = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &msg).ok()?;
 		Some(H160::from(H256::from(sp_io::hashing::keccak_256(&pubkey))))
@@ -446,9 +458,10 @@ impl<T: Config> Pallet<T> {
 			statuses.push(status);
 			receipts.push(receipt.clone());
 			let (logs, used_gas) = match receipt {
-				Receipt::Legacy(d) | Receipt::EIP2930(d) | Receipt::EIP1559(d) => {
-					(d.logs.clone(), d.used_gas)
-				}
+				Receipt::Legacy(d)
+				| Receipt::EIP2930(d)
+				| Receipt::EIP1559(d)
+				| Receipt::EIP7702(d) => (d.logs.clone(), d.used_gas),
 			};
 			cumulative_gas_used = used_gas;
 			Self::logs_bloom(logs, &mut logs_bloom);
@@ -503,6 +516,34 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	fn failure_reason(reason: &ExitReason) -> Option<ExitReason> {
+		match reason {
+			ExitReason::Succeed(_) => None,
+			reason => Some(reason.clone()),
+		}
+	}
+
+	/// Extracts the message of an `Error(string)` revert output, falling back to the whole output.
+	fn revert_message(data: Vec<u8>) -> Vec<u8> {
+		const LEN_START: usize = 36;
+		const MESSAGE_START: usize = 68;
+
+		let data_len = data.len();
+		if data_len > MESSAGE_START {
+			let message_len = U256::from(&data[LEN_START..MESSAGE_START]).saturated_into::<usize>();
+			let message_end =
+				MESSAGE_START.saturating_add(message_len.min(T::ExtraDataLength::get() as usize));
+
+			if data_len >= message_end {
+				data[MESSAGE_START..message_end].to_vec()
+			} else {
+				data
+			}
+		} else {
+			data
+		}
+	}
+
 	fn logs_bloom(logs: Vec<Log>, bloom: &mut Bloom) {
 		for log in logs {
 			bloom.accrue(BloomInput::Raw(&log.address[..]));
@@ -527,7 +568,7 @@ impl<T: Config> Pallet<T> {
 
 		let _ = CheckEvmTransaction::<InvalidTransactionWrapper>::new(
 			CheckEvmTransactionConfig {
-				evm_config: T::config(),
+				evm_config: pallet_evm::Pallet::<T>::evm_config(),
 				block_gas_limit: T::BlockGasLimit::get(),
 				base_fee,
 				chain_id: T::ChainId::get(),
@@ -544,12 +585,13 @@ impl<T: Config> Pallet<T> {
 		.map_err(|e| e.0)?;
 
 		// EIP-3607: https://eips.ethereum.org/EIPS/eip-3607
-		// Do not allow transactions for which `tx.sender` has any code deployed.
+		// Do not allow transactions for which `tx.sender` has any code deployed. Accounts
+		// holding an EIP-7702 delegation designator are still externally owned.
 		//
 		// This check should be done on the transaction validation (here) **and**
 		// on transaction execution, otherwise a contract tx will be included in
 		// the mempool and pollute the mempool forever.
-		if !pallet_evm::AccountCodes::<T>::get(origin).is_empty() {
+		if pallet_evm::Pallet::<T>::has_contract_code(&origin) {
 			return Err(InvalidTransaction::BadSigner.into());
 		}
 
@@ -595,78 +637,54 @@ impl<T: Config> Pallet<T> {
 		source: H160,
 		transaction: Transaction,
 	) -> Result<(PostDispatchInfo, CallOrCreateInfo), DispatchErrorWithPostInfo> {
-		let (to, _, info) = Self::execute(source, &transaction, None)?;
+		let (to, _, info, output) = Self::execute_with_output(source, &transaction, None)?;
 
 		let pending = Pending::<T>::get();
 		let transaction_hash = transaction.hash();
 		let transaction_index = pending.len() as u32;
 
-		let (reason, status, weight_info, used_gas, dest, extra_data) = match info.clone() {
+		let (reason, logs, weight_info, used_gas, contract_address) = match info.clone() {
 			CallOrCreateInfo::Call(info) => (
-				info.exit_reason.clone(),
-				TransactionStatus {
-					transaction_hash,
-					transaction_index,
-					from: source,
-					to,
-					contract_address: None,
-					logs: info.logs.clone(),
-					logs_bloom: {
-						let mut bloom: Bloom = Bloom::default();
-						Self::logs_bloom(info.logs, &mut bloom);
-						bloom
-					},
-				},
+				info.exit_reason,
+				info.logs,
 				info.weight_info,
 				info.used_gas,
-				to,
-				match info.exit_reason {
-					ExitReason::Revert(_) => {
-						const LEN_START: usize = 36;
-						const MESSAGE_START: usize = 68;
-
-						let data = info.value;
-						let data_len = data.len();
-						if data_len > MESSAGE_START {
-							let message_len = U256::from(&data[LEN_START..MESSAGE_START])
-								.saturated_into::<usize>();
-							let message_end = MESSAGE_START.saturating_add(
-								message_len.min(T::ExtraDataLength::get() as usize),
-							);
-
-							if data_len >= message_end {
-								data[MESSAGE_START..message_end].to_vec()
-							} else {
-								data
-							}
-						} else {
-							data
-						}
-					}
-					_ => vec![],
-				},
+				None,
 			),
 			CallOrCreateInfo::Create(info) => (
 				info.exit_reason,
-				TransactionStatus {
-					transaction_hash,
-					transaction_index,
-					from: source,
-					to,
-					contract_address: Some(info.value),
-					logs: info.logs.clone(),
-					logs_bloom: {
-						let mut bloom: Bloom = Bloom::default();
-						Self::logs_bloom(info.logs, &mut bloom);
-						bloom
-					},
-				},
+				info.logs,
 				info.weight_info,
 				info.used_gas,
 				Some(info.value),
-				Vec::new(),
 			),
 		};
+		let dest = contract_address.or(to);
+
+		let (revert_reason, extra_data) = match reason {
+			ExitReason::Revert(_) => {
+				let mut revert_reason = output.clone();
+				revert_reason.truncate(T::RevertReasonLength::get() as usize);
+				(Some(revert_reason), Self::revert_message(output))
+			}
+			_ => (None, Vec::new()),
+		};
+
+		let status = TransactionStatusV2 {
+			transaction_hash,
+			transaction_index,
+			from: source,
+			to,
+			contract_address,
+			logs: logs.clone(),
+			logs_bloom: {
+				let mut bloom: Bloom = Bloom::default();
+				Self::logs_bloom(logs, &mut bloom);
+				bloom
+			},
+			exit_reason: Self::failure_reason(&reason),
+			revert_reason,
+		};
 
 		let receipt = {
 			let status_code: u8 = match reason {
@@ -677,9 +695,10 @@ impl<T: Config> Pallet<T> {
 			let logs = status.clone().logs;
 			let cumulative_gas_used = if let Some((_, _, receipt)) = pending.last() {
 				match receipt {
-					Receipt::Legacy(d) | Receipt::EIP2930(d) | Receipt::EIP1559(d) => {
-						d.used_gas.saturating_add(used_gas.effective)
-					}
+					Receipt::Legacy(d)
+					| Receipt::EIP2930(d)
+					| Receipt::EIP1559(d)
+					| Receipt::EIP7702(d) => d.used_gas.saturating_add(used_gas.effective),
 				}
 			} else {
 				used_gas.effective
@@ -703,9 +722,16 @@ impl<T: Config> Pallet<T> {
 					logs_bloom,
 					logs,
 				}),
+				Transaction::EIP7702(_) => Receipt::EIP7702(ethereum::EIP2930ReceiptData {
+					status_code,
+					used_gas: cumulative_gas_used,
+					logs_bloom,
+					logs,
+				}),
 			}
 		};
 
+		let revert_reason = status.revert_reason.clone();
 		Pending::<T>::append((transaction, status, receipt));
 
 		Self::deposit_event(Event::Executed {
@@ -714,6 +740,7 @@ impl<T: Config> Pallet<T> {
 			transaction_hash,
 			exit_reason: reason,
 			extra_data,
+			revert_reason,
 		});
 
 		Ok((
@@ -744,12 +771,41 @@ impl<T: Config> Pallet<T> {
 		<CurrentBlock<T>>::get().map(|block| block.header.hash())
 	}
 
+	/// Gas used by the last transaction applied in the current block.
+	pub fn last_transaction_gas_used() -> U256 {
+		let cumulative_gas_used = |receipt: &Receipt| match receipt {
+			Receipt::Legacy(d)
+			| Receipt::EIP2930(d)
+			| Receipt::EIP1559(d)
+			| Receipt::EIP7702(d) => d.used_gas,
+		};
+		match Pending::<T>::get().as_slice() {
+			[.., (_, _, previous), (_, _, last)] => {
+				cumulative_gas_used(last).saturating_sub(cumulative_gas_used(previous))
+			}
+			[(_, _, last)] => cumulative_gas_used(last),
+			[] => U256::zero(),
+		}
+	}
+
 	/// Execute an Ethereum transaction.
 	pub fn execute(
 		from: H160,
 		transaction: &Transaction,
 		config: Option<evm::Config>,
 	) -> Result<(Option<H160>, Option<H160>, CallOrCreateInfo), DispatchErrorWithPostInfo> {
+		Self::execute_with_output(from, transaction, config)
+			.map(|(to, contract_address, info, _)| (to, contract_address, info))
+	}
+
+	/// Execute an Ethereum transaction, also returning its output: the return data of a call, or
+	/// the output of the init code of a contract creation.
+	fn execute_with_output(
+		from: H160,
+		transaction: &Transaction,
+		config: Option<evm::Config>,
+	) -> Result<(Option<H160>, Option<H160>, CallOrCreateInfo, Vec<u8>), DispatchErrorWithPostInfo>
+	{
 		let transaction_data: TransactionData = transaction.into();
 		let (weight_limit, proof_size_base_cost) = Self::transaction_weight(&transaction_data);
 		let is_transactional = true;
@@ -812,6 +868,23 @@ impl<T: Config> Pallet<T> {
 						access_list,
 					)
 				}
+				Transaction::EIP7702(t) => {
+					let access_list: Vec<(H160, Vec<H256>)> = t
+						.access_list
+						.iter()
+						.map(|item| (item.address, item.storage_keys.clone()))
+						.collect();
+					(
+						t.input.clone(),
+						t.value,
+						t.gas_limit,
+						Some(t.max_fee_per_gas),
+						Some(t.max_priority_fee_per_gas),
+						Some(t.nonce),
+						t.action,
+						access_list,
+					)
+				}
 			}
 		};
 
@@ -827,11 +900,14 @@ impl<T: Config> Pallet<T> {
 					max_priority_fee_per_gas,
 					nonce,
 					access_list,
+					transaction_data.authorization_list.unwrap_or_default(),
 					is_transactional,
 					validate,
 					weight_limit,
 					proof_size_base_cost,
-					config.as_ref().unwrap_or_else(|| T::config()),
+					config
+						.as_ref()
+						.unwrap_or_else(pallet_evm::Pallet::<T>::evm_config),
 				) {
 					Ok(res) => res,
 					Err(e) => {
@@ -845,10 +921,11 @@ impl<T: Config> Pallet<T> {
 					}
 				};
 
-				Ok((Some(target), None, CallOrCreateInfo::Call(res)))
+				let output = res.value.clone();
+				Ok((Some(target), None, CallOrCreateInfo::Call(res), output))
 			}
 			ethereum::TransactionAction::Create => {
-				let res = match T::Runner::create(
+				let (res, output) = match T::Runner::create(
 					from,
 					input,
 					value,
@@ -861,7 +938,9 @@ impl<T: Config> Pallet<T> {
 					validate,
 					weight_limit,
 					proof_size_base_cost,
-					config.as_ref().unwrap_or_else(|| T::config()),
+					config
+						.as_ref()
+						.unwrap_or_else(pallet_evm::Pallet::<T>::evm_config),
 				) {
 					Ok(res) => res,
 					Err(e) => {
@@ -875,7 +954,7 @@ impl<T: Config> Pallet<T> {
 					}
 				};
 
-				Ok((None, Some(res.value), CallOrCreateInfo::Create(res)))
+				Ok((None, Some(res.value), CallOrCreateInfo::Create(res), output))
 			}
 		}
 	}
@@ -895,7 +974,7 @@ impl<T: Config> Pallet<T> {
 
 		let _ = CheckEvmTransaction::<InvalidTransactionWrapper>::new(
 			CheckEvmTransactionConfig {
-				evm_config: T::config(),
+				evm_config: pallet_evm::Pallet::<T>::evm_config(),
 				block_gas_limit: T::BlockGasLimit::get(),
 				base_fee,
 				chain_id: T::ChainId::get(),
@@ -1050,6 +1129,11 @@ impl From<TransactionValidationError> for InvalidTransactionWrapper {
 			TransactionValidationError::UnknownError => InvalidTransactionWrapper(
 				InvalidTransaction::Custom(TransactionValidationError::UnknownError as u8),
 			),
+			TransactionValidationError::InvalidAuthorizationList => {
+				InvalidTransactionWrapper(InvalidTransaction::Custom(
+					TransactionValidationError::InvalidAuthorizationList as u8,
+				))
+			}
 		}
 	}
 }
@@ -27,7 +27,7 @@ use sp_runtime::{
 	AccountId32, BuildStorage,
 };
 // Frontier
-use pallet_evm::{config_preludes::ChainId, AddressMapping};
+use pallet_evm::{config_preludes::ChainId, AddressMapping, EvmConfig};
 
 use super::*;
 
@@ -97,8 +97,14 @@ impl pallet_evm::Config for Test {
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type FindAuthor = FindAuthorTruncated;
 	type Timestamp = Timestamp;
+
+	fn config() -> &'static EvmConfig {
+		&PECTRA_CONFIG
+	}
 }
 
+static PECTRA_CONFIG: EvmConfig = EvmConfig::pectra();
+
 #[derive_impl(crate::config_preludes::TestDefaultConfig)]
 impl Config for Test {}
 
@@ -400,3 +406,86 @@ impl EIP1559UnsignedTransaction {
 		})
 	}
 }
+
+pub struct EIP7702UnsignedTransaction {
+	pub nonce: U256,
+	pub max_priority_fee_per_gas: U256,
+	pub max_fee_per_gas: U256,
+	pub gas_limit: U256,
+	pub destination: H160,
+	pub value: U256,
+	pub input: Vec<u8>,
+	pub authorization_list: Vec<ethereum::AuthorizationListItem>,
+}
+
+impl EIP7702UnsignedTransaction {
+	pub fn sign(&self, secret: &H256, chain_id: Option<u64>) -> Transaction {
+		let secret = {
+			let mut sk: [u8; 32] = [0u8; 32];
+			sk.copy_from_slice(&secret[0..]);
+			libsecp256k1::SecretKey::parse(&sk).unwrap()
+		};
+		let chain_id = chain_id.unwrap_or(ChainId::get());
+		let msg = ethereum::EIP7702TransactionMessage {
+			chain_id,
+			nonce: self.nonce,
+			max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+			max_fee_per_gas: self.max_fee_per_gas,
+			gas_limit: self.gas_limit,
+			action: TransactionAction::Call(self.destination),
+			value: self.value,
+			input: self.input.clone(),
+			access_list: vec![],
+			authorization_list: self.authorization_list.clone(),
+		};
+		let signing_message = libsecp256k1::Message::parse_slice(&msg.hash()[..]).unwrap();
+
+		let (signature, recid) = libsecp256k1::sign(&signing_message, &secret);
+		let rs = signature.serialize();
+		let r = H256::from_slice(&rs[0..32]);
+		let s = H256::from_slice(&rs[32..64]);
+		Transaction::EIP7702(ethereum::EIP7702Transaction {
+			chain_id: msg.chain_id,
+			nonce: msg.nonce,
+			max_priority_fee_per_gas: msg.max_priority_fee_per_gas,
+			max_fee_per_gas: msg.max_fee_per_gas,
+			gas_limit: msg.gas_limit,
+			action: msg.action,
+			value: msg.value,
+			input: msg.input.clone(),
+			access_list: msg.access_list,
+			authorization_list: msg.authorization_list,
+			odd_y_parity: recid.serialize() != 0,
+			r,
+			s,
+		})
+	}
+}
+
+/// Sign an EIP-7702 authorization delegating the code of the signer to `address`.
+pub fn sign_authorization(
+	secret: &H256,
+	chain_id: u64,
+	address: H160,
+	nonce: U256,
+) -> ethereum::AuthorizationListItem {
+	let secret = libsecp256k1::SecretKey::parse_slice(&secret[..]).unwrap();
+	let mut stream = RlpStream::new_list(3);
+	stream.append(&chain_id);
+	stream.append(&address);
+	stream.append(&nonce);
+	let mut message = vec![0x05];
+	message.extend_from_slice(&stream.out());
+	let signing_message = libsecp256k1::Message::parse_slice(&keccak_256(&message)).unwrap();
+
+	let (signature, recid) = libsecp256k1::sign(&signing_message, &secret);
+	let rs = signature.serialize();
+	ethereum::AuthorizationListItem {
+		chain_id,
+		address,
+		nonce,
+		y_parity: recid.serialize() != 0,
+		r: H256::from_slice(&rs[0..32]),
+		s: H256::from_slice(&rs[32..64]),
+	}
+}
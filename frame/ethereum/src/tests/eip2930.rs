@@ -376,6 +376,7 @@ fn event_extra_data_should_be_handle_properly() {
 			.unwrap(),
 			exit_reason: ExitReason::Succeed(ExitSucceed::Returned),
 			extra_data: vec![],
+			revert_reason: None,
 		}));
 
 		let t3 = EIP2930UnsignedTransaction {
@@ -399,6 +400,7 @@ fn event_extra_data_should_be_handle_properly() {
 			.unwrap(),
 			exit_reason: ExitReason::Revert(ExitRevert::Reverted),
 			extra_data: b"very_long_error_msg_that_we_ex".to_vec(),
+			revert_reason: Some(hex::decode(TEST_CONTRACT_REVERT_OUTPUT).unwrap()),
 		}));
 	});
 }
@@ -539,6 +541,7 @@ fn proof_size_base_cost_should_keep_the_same_in_execution_and_estimate() {
 			raw_tx.value,
 			Some(100),
 			vec![],
+			None,
 		);
 		assert_eq!(
 			estimate_tx_data.proof_size_base_cost(),
@@ -0,0 +1,164 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! EIP-7702 set-code transaction tests.
+
+use super::*;
+use evm::{ExitReason, ExitSucceed};
+use frame_support::traits::Get;
+
+// MSTORE(0, 42) RETURN(0, 32)
+const DELEGATE_CODE: &str = "602a60005260206000f3";
+
+fn delegate_address() -> H160 {
+	H160::repeat_byte(0x77)
+}
+
+fn eip7702_transaction(
+	account: &AccountInfo,
+	nonce: U256,
+	destination: H160,
+	authorization_list: Vec<ethereum::AuthorizationListItem>,
+) -> Transaction {
+	EIP7702UnsignedTransaction {
+		nonce,
+		max_priority_fee_per_gas: U256::from(1),
+		max_fee_per_gas: U256::from(1),
+		gas_limit: U256::from(0x100000),
+		destination,
+		value: U256::zero(),
+		input: Default::default(),
+		authorization_list,
+	}
+	.sign(&account.private_key, None)
+}
+
+#[test]
+fn transaction_should_delegate_authority_code() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		pallet_evm::Pallet::<Test>::create_account(
+			delegate_address(),
+			hex::decode(DELEGATE_CODE).unwrap(),
+		);
+		let authorization = sign_authorization(
+			&bob.private_key,
+			<Test as pallet_evm::Config>::ChainId::get(),
+			delegate_address(),
+			U256::zero(),
+		);
+		let t = eip7702_transaction(alice, U256::zero(), bob.address, vec![authorization]);
+
+		let (_, _, info) = Ethereum::execute(alice.address, &t, None).unwrap();
+		match info {
+			CallOrCreateInfo::Call(info) => {
+				assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+				assert_eq!(U256::from_big_endian(&info.value), U256::from(42));
+			}
+			CallOrCreateInfo::Create(_) => panic!("expected call info"),
+		}
+		assert_eq!(
+			pallet_evm::Pallet::<Test>::delegation(&bob.address),
+			Some(delegate_address())
+		);
+		assert_eq!(EVM::account_basic(&bob.address).0.nonce, U256::from(1));
+		assert_eq!(EVM::account_basic(&alice.address).0.nonce, U256::from(1));
+	});
+}
+
+#[test]
+fn self_sponsored_authorization_should_use_bumped_nonce() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		// The sender nonce is incremented before the authorizations are processed.
+		let authorization = sign_authorization(
+			&alice.private_key,
+			<Test as pallet_evm::Config>::ChainId::get(),
+			delegate_address(),
+			U256::from(1),
+		);
+		let t = eip7702_transaction(alice, U256::zero(), alice.address, vec![authorization]);
+
+		assert_ok!(Ethereum::execute(alice.address, &t, None));
+		assert_eq!(
+			pallet_evm::Pallet::<Test>::delegation(&alice.address),
+			Some(delegate_address())
+		);
+		assert_eq!(EVM::account_basic(&alice.address).0.nonce, U256::from(2));
+	});
+}
+
+#[test]
+fn transaction_with_empty_authorization_list_should_fail_in_block() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		let transaction = eip7702_transaction(alice, U256::zero(), bob.address, vec![]);
+
+		let call = crate::Call::<Test>::transact { transaction };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<_, _, SignedExtra, _> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Ethereum(call),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+		assert_err!(
+			extrinsic.apply::<Test>(&dispatch_info, 0),
+			TransactionValidityError::Invalid(InvalidTransaction::Custom(
+				fp_evm::TransactionValidationError::InvalidAuthorizationList as u8,
+			))
+		);
+	});
+}
+
+#[test]
+fn delegated_account_can_send_transactions() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		pallet_evm::Pallet::<Test>::set_delegation(alice.address, delegate_address());
+
+		let transaction = EIP1559UnsignedTransaction {
+			nonce: U256::zero(),
+			max_priority_fee_per_gas: U256::from(1),
+			max_fee_per_gas: U256::from(1),
+			gas_limit: U256::from(0x100000),
+			action: ethereum::TransactionAction::Call(bob.address),
+			value: U256::from(1),
+			input: Default::default(),
+		}
+		.sign(&alice.private_key, None);
+
+		let call = crate::Call::<Test>::transact { transaction };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<_, _, SignedExtra, _> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Ethereum(call),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+		assert_ok!(extrinsic.apply::<Test>(&dispatch_info, 0));
+	});
+}
@@ -376,6 +376,7 @@ fn event_extra_data_should_be_handle_properly() {
 			.unwrap(),
 			exit_reason: ExitReason::Succeed(ExitSucceed::Returned),
 			extra_data: vec![],
+			revert_reason: None,
 		}));
 
 		let t3 = LegacyUnsignedTransaction {
@@ -399,6 +400,60 @@ fn event_extra_data_should_be_handle_properly() {
 			.unwrap(),
 			exit_reason: ExitReason::Revert(ExitRevert::Reverted),
 			extra_data: b"very_long_error_msg_that_we_ex".to_vec(),
+			revert_reason: Some(hex::decode(TEST_CONTRACT_REVERT_OUTPUT).unwrap()),
+		}));
+
+		// the failure is recorded in the transaction status
+		let (_, status, _) = crate::Pending::<Test>::get().pop().unwrap();
+		assert_eq!(
+			status.exit_reason,
+			Some(ExitReason::Revert(ExitRevert::Reverted))
+		);
+		assert_eq!(
+			status.revert_reason,
+			Some(hex::decode(TEST_CONTRACT_REVERT_OUTPUT).unwrap())
+		);
+	});
+}
+
+#[test]
+fn reverted_create_records_revert_reason() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+
+		// MSTORE(0, 42) REVERT(0, 32)
+		let t = LegacyUnsignedTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(0x100000),
+			action: ethereum::TransactionAction::Create,
+			value: U256::zero(),
+			input: hex::decode("602a60005260206000fd").unwrap(),
+		}
+		.sign(&alice.private_key);
+		assert_ok!(Ethereum::apply_validated_transaction(alice.address, t));
+
+		let output = H256::from_low_u64_be(42).as_bytes().to_vec();
+		let (_, status, receipt) = crate::Pending::<Test>::get().pop().unwrap();
+		assert_eq!(
+			status.exit_reason,
+			Some(ExitReason::Revert(ExitRevert::Reverted))
+		);
+		assert_eq!(status.revert_reason, Some(output.clone()));
+		match receipt {
+			ethereum::ReceiptV4::Legacy(data) => assert_eq!(data.status_code, 0),
+			_ => panic!("expected a legacy receipt"),
+		}
+		System::assert_last_event(RuntimeEvent::Ethereum(Event::Executed {
+			from: alice.address,
+			to: status.contract_address.unwrap(),
+			transaction_hash: status.transaction_hash,
+			exit_reason: ExitReason::Revert(ExitRevert::Reverted),
+			extra_data: output.clone(),
+			revert_reason: Some(output),
 		}));
 	});
 }
@@ -539,6 +594,7 @@ fn proof_size_base_cost_should_keep_the_same_in_execution_and_estimate() {
 			raw_tx.value,
 			Some(100),
 			vec![],
+			None,
 		);
 		assert_eq!(
 			estimate_tx_data.proof_size_base_cost(),
@@ -31,6 +31,7 @@ use fp_self_contained::CheckedExtrinsic;
 
 mod eip1559;
 mod eip2930;
+mod eip7702;
 mod legacy;
 
 // This ERC-20 contract mints the maximum amount of tokens to the contract creator.
@@ -52,3 +53,6 @@ pub const ERC20_CONTRACT_BYTECODE: &str = include_str!("./res/erc20_contract_byt
 // 	   }
 // }
 pub const TEST_CONTRACT_CODE: &str = "608060405234801561001057600080fd5b50610129806100206000396000f3fe6080604052348015600f57600080fd5b506004361060325760003560e01c8063c2985578146037578063febb0f7e146055575b600080fd5b603d605d565b60405180821515815260200191505060405180910390f35b605b6066565b005b60006001905090565b600060bc576040517f08c379a00000000000000000000000000000000000000000000000000000000081526004018080602001828103825260358152602001806100bf6035913960400191505060405180910390fd5b56fe766572795f6c6f6e675f6572726f725f6d73675f746861745f77655f6578706563745f746f5f62655f7472696d6d65645f61776179a26469706673582212207af96dd688d3a3adc999c619e6073d5b6056c72c79ace04a90ea4835a77d179364736f6c634300060c0033";
+
+// The output of `Test.bar()`, the ABI-encoded `Error(string)` it reverts with.
+pub const TEST_CONTRACT_REVERT_OUTPUT: &str = "08c379a000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000035766572795f6c6f6e675f6572726f725f6d73675f746861745f77655f6578706563745f746f5f62655f7472696d6d65645f617761790000000000000000000000";
@@ -0,0 +1,432 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # EVM State Root Pallet
+//!
+//! Maintains an Ethereum Merkle Patricia trie of the EVM accounts alongside the Substrate state,
+//! so that Ethereum blocks carry an Ethereum `state_root` and accounts can be proven with
+//! Ethereum proofs.
+//!
+//! The pallet is notified of the accounts and slots changed by `pallet_evm` during the block and
+//! applies them to the trie once, when the block is finalized. To enable it in a runtime:
+//!
+//! - set `pallet_evm::Config::OnStateChange` to this pallet,
+//! - set `pallet_ethereum::Config::StateRoot` to [`EthereumStateRoot`],
+//! - implement `fp_rpc::EthereumStateProofApi` with [`Pallet::account_proof`].
+//!
+//! `pallet_evm` genesis accounts are committed by `pallet_ethereum` when it builds the genesis
+//! block, as long as it comes after `pallet_evm` in the runtime.
+//!
+//! Only the changes made through `pallet_evm` are reported to the pallet. The other changes to
+//! the EVM accounts, such as transfers with the balances pallet, are not tracked unless the
+//! runtime reports them by calling the [`pallet_evm::OnStateChange`] functions of this pallet.
+//! The trie then drifts from the actual state of these accounts, and [`Pallet::account_proof`]
+//! refuses to prove an account or slot whose committed value differs from its current one.
+//! Likewise, a chain adding the pallet to a live runtime has to report all of its existing
+//! accounts and slots.
+//!
+//! The trie is updated in `on_finalize`, which cannot return weight. Each change is thus charged
+//! [`Config::WeightPerChange`] by the EVM execution reporting it, and `on_initialize` reserves the
+//! fixed cost of the commit. Changes reported by the runtime itself must be charged by the
+//! runtime in the same way. The storage tries of the cleared accounts are removed in `on_idle`,
+//! within the weight left in the block.
+//!
+//! A trie which fails to be updated, which only happens when its nodes are corrupted, is left
+//! as is: the changes are dropped and the block keeps the previous state root.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use alloc::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	vec::Vec,
+};
+use core::marker::PhantomData;
+// Substrate
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	traits::Get,
+	weights::Weight,
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::DispatchError;
+// Frontier
+use fp_proof::mpt::{self, Account, NodeStore, EMPTY_ROOT};
+use fp_rpc::{EthereumAccountProof, EthereumStorageProof};
+use pallet_evm::{AccountStorages, OnStateChange};
+
+pub use self::pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_evm::Config {
+		/// Weight of recording one changed account or slot and applying it to the trie, which
+		/// takes a few node reads and writes per level of the trie.
+		#[pallet::constant]
+		type WeightPerChange: Get<Weight>;
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			// Checking for changes and updating the state root. The changes themselves are
+			// charged when they are reported.
+			T::DbWeight::get().reads_writes(4, 1)
+		}
+
+		fn on_finalize(_n: BlockNumberFor<T>) {
+			// Usually a no-op, `pallet_ethereum` committing the changes when it reads the state
+			// root of the block.
+			Self::commit();
+		}
+
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::release_pending_nodes(remaining_weight)
+		}
+	}
+
+	#[pallet::type_value]
+	pub fn DefaultStateRoot() -> H256 {
+		EMPTY_ROOT
+	}
+
+	/// Root of the Ethereum state trie, as of the last commit.
+	#[pallet::storage]
+	pub type StateRoot<T: Config> = StorageValue<_, H256, ValueQuery, DefaultStateRoot>;
+
+	/// Root of the storage trie of the accounts with a non-empty storage.
+	#[pallet::storage]
+	pub type StorageRoots<T: Config> = StorageMap<_, Blake2_128Concat, H160, H256, OptionQuery>;
+
+	/// Trie nodes by hash, along with their reference count.
+	#[pallet::storage]
+	pub type Nodes<T: Config> = StorageMap<_, Identity, H256, (u32, Vec<u8>), OptionQuery>;
+
+	/// Accounts changed since the last commit.
+	#[pallet::storage]
+	pub type ChangedAccounts<T: Config> = StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
+	/// Storage slots changed since the last commit.
+	#[pallet::storage]
+	pub type ChangedStorages<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, H160, Blake2_128Concat, H256, (), OptionQuery>;
+
+	/// Accounts whose storage was cleared since the last commit.
+	#[pallet::storage]
+	pub type ClearedStorages<T: Config> = StorageMap<_, Blake2_128Concat, H160, (), OptionQuery>;
+
+	/// Nodes of the removed storage tries still to be released, along with their children.
+	#[pallet::storage]
+	pub type PendingReleases<T: Config> = StorageValue<_, Vec<H256>, ValueQuery>;
+}
+
+/// Ethereum state root of the block, to be used as `pallet_ethereum::Config::StateRoot`.
+///
+/// Reading it commits the changes made so far in the block.
+pub struct EthereumStateRoot<T>(PhantomData<T>);
+
+impl<T: Config> Get<H256> for EthereumStateRoot<T> {
+	fn get() -> H256 {
+		Pallet::<T>::commit()
+	}
+}
+
+impl<T: Config> OnStateChange for Pallet<T> {
+	fn on_account_changed(address: H160) {
+		ChangedAccounts::<T>::insert(address, ());
+	}
+
+	fn on_storage_changed(address: H160, index: H256) {
+		ChangedStorages::<T>::insert(address, index, ());
+	}
+
+	fn on_storage_cleared(address: H160) {
+		// The slots changed so far are kept: being read from the cleared storage, they are
+		// simply not part of the new storage trie.
+		ClearedStorages::<T>::insert(address, ());
+	}
+
+	fn change_weight() -> Weight {
+		T::WeightPerChange::get()
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Apply the changes made since the last commit to the state trie, returning its new root.
+	///
+	/// On failure to update the trie, the changes are dropped and the previous root is kept.
+	pub fn commit() -> H256 {
+		let root = StateRoot::<T>::get();
+		let Some(changes) = Changes::take::<T>() else {
+			return root;
+		};
+
+		with_transaction(|| match Self::apply_changes(root, changes) {
+			Ok(state_root) => TransactionOutcome::Commit(Ok(state_root)),
+			Err(error) => {
+				log::error!(
+					target: "evm-state-root",
+					"Failed to update the Ethereum state trie, keeping the previous root: {error:?}",
+				);
+				TransactionOutcome::Rollback(Err(DispatchError::Other(
+					"Failed to update the Ethereum state trie",
+				)))
+			}
+		})
+		.unwrap_or(root)
+	}
+
+	/// Ethereum proof of an account and of the given storage slots against the committed state
+	/// root.
+	///
+	/// Returns `None` if the committed account or one of the slots differs from the current
+	/// state, as happens for the changes not reported to the pallet.
+	pub fn account_proof(address: H160, storage_keys: Vec<H256>) -> Option<EthereumAccountProof> {
+		let store = NodeStorage::<T>(PhantomData);
+		let root = StateRoot::<T>::get();
+		let key = mpt::account_key(address);
+		let account = match mpt::get(&store, root, &key).ok()? {
+			Some(account) => Account::decode(&account).ok()?,
+			None => Account::default(),
+		};
+		let (basic, _) = pallet_evm::Pallet::<T>::account_basic(&address);
+		if account.nonce != basic.nonce
+			|| account.balance != basic.balance
+			|| account.code_hash != pallet_evm::Pallet::<T>::account_code_metadata(address).hash
+		{
+			return None;
+		}
+
+		let mut storage_proof = Vec::with_capacity(storage_keys.len());
+		for index in storage_keys {
+			let key = mpt::storage_key(index);
+			let value = match mpt::get(&store, account.storage_root, &key).ok()? {
+				Some(value) => mpt::decode_storage_value(&value).ok()?,
+				None => U256::zero(),
+			};
+			let current = AccountStorages::<T>::get(address, index);
+			if value != U256::from_big_endian(current.as_bytes()) {
+				return None;
+			}
+			storage_proof.push(EthereumStorageProof {
+				key: index,
+				value,
+				proof: mpt::prove(&store, account.storage_root, &key).ok()?,
+			});
+		}
+		Some(EthereumAccountProof {
+			nonce: account.nonce,
+			balance: account.balance,
+			storage_hash: account.storage_root,
+			code_hash: account.code_hash,
+			account_proof: mpt::prove(&store, root, &key).ok()?,
+			storage_proof,
+		})
+	}
+
+	fn apply_changes(root: H256, changes: Changes) -> Result<H256, mpt::Error> {
+		let mut store = NodeStorage::<T>(PhantomData);
+		let Changes {
+			cleared,
+			slots,
+			mut accounts,
+		} = changes;
+
+		let mut pending = PendingReleases::<T>::get();
+		for address in cleared {
+			// The nodes of the storage trie are released in `on_idle`.
+			if let Some(storage_root) = StorageRoots::<T>::take(address) {
+				pending.push(storage_root);
+			}
+			accounts.insert(address);
+		}
+		PendingReleases::<T>::put(pending);
+
+		for (address, indexes) in slots {
+			let old_root = StorageRoots::<T>::get(address).unwrap_or(EMPTY_ROOT);
+			let mut storage_root = old_root;
+			for index in indexes {
+				let key = mpt::storage_key(index);
+				let value = AccountStorages::<T>::get(address, index);
+				let new_root = if value.is_zero() {
+					mpt::remove(&mut store, storage_root, &key)?
+				} else {
+					let value = mpt::encode_storage_value(U256::from_big_endian(value.as_bytes()));
+					mpt::insert(&mut store, storage_root, &key, value)?
+				};
+				mpt::release(&mut store, storage_root)?;
+				storage_root = new_root;
+			}
+			if storage_root == EMPTY_ROOT {
+				StorageRoots::<T>::remove(address);
+			} else {
+				StorageRoots::<T>::insert(address, storage_root);
+			}
+			accounts.insert(address);
+		}
+
+		let mut state_root = root;
+		for address in accounts {
+			let (basic, _) = pallet_evm::Pallet::<T>::account_basic(&address);
+			let account = Account {
+				nonce: basic.nonce,
+				balance: basic.balance,
+				storage_root: StorageRoots::<T>::get(address).unwrap_or(EMPTY_ROOT),
+				code_hash: pallet_evm::Pallet::<T>::account_code_metadata(address).hash,
+			};
+			let key = mpt::account_key(address);
+			// Empty accounts are not part of the state (EIP-161).
+			let new_root = if account.is_empty() {
+				mpt::remove(&mut store, state_root, &key)?
+			} else {
+				mpt::insert(&mut store, state_root, &key, account.encode())?
+			};
+			mpt::release(&mut store, state_root)?;
+			state_root = new_root;
+		}
+
+		StateRoot::<T>::put(state_root);
+		Ok(state_root)
+	}
+
+	/// Release the nodes of the removed storage tries within `remaining_weight`, returning the
+	/// weight used.
+	fn release_pending_nodes(remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let base_weight = db_weight.reads_writes(1, 1);
+		let node_weight = db_weight.reads_writes(1, 1);
+		if remaining_weight.any_lt(base_weight) {
+			return Weight::zero();
+		}
+		let mut pending = PendingReleases::<T>::get();
+		if pending.is_empty() {
+			return db_weight.reads(1);
+		}
+
+		let limit = remaining_weight
+			.saturating_sub(base_weight)
+			.checked_div_per_component(&node_weight)
+			.unwrap_or(u64::MAX);
+		let mut store = NodeStorage::<T>(PhantomData);
+		let released = match mpt::release_some(
+			&mut store,
+			&mut pending,
+			limit.try_into().unwrap_or(usize::MAX),
+		) {
+			Ok(released) => released as u64,
+			Err(error) => {
+				log::error!(
+					target: "evm-state-root",
+					"Failed to release the removed storage tries: {error:?}",
+				);
+				// The nodes left are unreachable and can only be leaked.
+				pending.clear();
+				limit
+			}
+		};
+		if pending.is_empty() {
+			PendingReleases::<T>::kill();
+		} else {
+			PendingReleases::<T>::put(pending);
+		}
+		base_weight.saturating_add(node_weight.saturating_mul(released))
+	}
+}
+
+/// Changes reported since the last commit.
+struct Changes {
+	cleared: BTreeSet<H160>,
+	slots: BTreeMap<H160, Vec<H256>>,
+	accounts: BTreeSet<H160>,
+}
+
+impl Changes {
+	/// Take the changes reported since the last commit, if any.
+	///
+	/// They are taken out of any storage transaction, so that a failed commit drops them.
+	fn take<T: Config>() -> Option<Self> {
+		let cleared: BTreeSet<_> = ClearedStorages::<T>::drain()
+			.map(|(address, ())| address)
+			.collect();
+		let mut slots = BTreeMap::<H160, Vec<H256>>::new();
+		for (address, index, ()) in ChangedStorages::<T>::drain() {
+			slots.entry(address).or_default().push(index);
+		}
+		let accounts: BTreeSet<_> = ChangedAccounts::<T>::drain()
+			.map(|(address, ())| address)
+			.collect();
+		if cleared.is_empty() && slots.is_empty() && accounts.is_empty() {
+			return None;
+		}
+		Some(Self {
+			cleared,
+			slots,
+			accounts,
+		})
+	}
+}
+
+/// Trie nodes kept in the pallet storage.
+struct NodeStorage<T>(PhantomData<T>);
+
+impl<T: Config> NodeStore for NodeStorage<T> {
+	fn get(&self, hash: &H256) -> Option<Vec<u8>> {
+		Nodes::<T>::get(hash).map(|(_, node)| node)
+	}
+
+	fn insert(&mut self, hash: H256, node: Vec<u8>) -> bool {
+		if Nodes::<T>::contains_key(hash) {
+			return false;
+		}
+		Nodes::<T>::insert(hash, (0, node));
+		true
+	}
+
+	fn retain(&mut self, hash: &H256) {
+		Nodes::<T>::mutate(hash, |entry| {
+			if let Some((references, _)) = entry {
+				*references += 1;
+			}
+		});
+	}
+
+	fn release(&mut self, hash: &H256) -> Option<Vec<u8>> {
+		Nodes::<T>::mutate_exists(hash, |entry| match entry {
+			Some((references, _)) if *references > 1 => {
+				*references -= 1;
+				None
+			}
+			_ => entry.take().map(|(_, node)| node),
+		})
+	}
+}
@@ -0,0 +1,258 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::traits::{Currency, Hooks};
+use sp_core::hashing::keccak_256;
+// Frontier
+use fp_proof::mpt::{verify_account_proof, verify_storage_proof, MemoryStore};
+use pallet_evm::{AddressMapping, GasWeightMapping, Runner};
+
+use super::*;
+use crate::mock::*;
+
+fn fund(address: H160, balance: u64) {
+	let account_id = <Test as pallet_evm::Config>::AddressMapping::into_account_id(address);
+	let _ = Balances::deposit_creating(&account_id, balance);
+	EVMStateRoot::on_account_changed(address);
+}
+
+fn set_storage(address: H160, index: H256, value: u64) {
+	if value == 0 {
+		AccountStorages::<Test>::remove(address, index);
+	} else {
+		AccountStorages::<Test>::insert(address, index, H256::from_low_u64_be(value));
+	}
+	EVMStateRoot::on_storage_changed(address, index);
+}
+
+/// Root of the trie holding exactly the given entries.
+fn trie_root(entries: &[([u8; 32], Vec<u8>)]) -> H256 {
+	let mut store = MemoryStore::default();
+	let mut root = EMPTY_ROOT;
+	for (key, value) in entries {
+		root = mpt::insert(&mut store, root, key, value.clone()).unwrap();
+	}
+	root
+}
+
+#[test]
+fn commits_accounts_and_storage() {
+	new_test_ext().execute_with(|| {
+		let alice = H160::repeat_byte(0x01);
+		let contract = H160::repeat_byte(0x02);
+		let code = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+		let slot = H256::repeat_byte(0x01);
+		let unset = H256::repeat_byte(0x02);
+
+		fund(alice, 1_000);
+		EVM::create_account(contract, code.clone());
+		set_storage(contract, slot, 42);
+		// Slots set back to zero are not part of the storage trie.
+		set_storage(contract, unset, 1);
+		set_storage(contract, unset, 0);
+
+		let root = EVMStateRoot::commit();
+
+		let storage_root = trie_root(&[(
+			mpt::storage_key(slot),
+			mpt::encode_storage_value(U256::from(42)),
+		)]);
+		let alice_account = Account {
+			balance: U256::from(1_000),
+			..Default::default()
+		};
+		let contract_account = Account {
+			storage_root,
+			code_hash: H256(keccak_256(&code)),
+			..Default::default()
+		};
+		assert_eq!(
+			root,
+			trie_root(&[
+				(mpt::account_key(alice), alice_account.encode()),
+				(mpt::account_key(contract), contract_account.encode()),
+			])
+		);
+		assert_eq!(StateRoot::<Test>::get(), root);
+
+		let proof = EVMStateRoot::account_proof(contract, vec![slot, unset]).expect("proof");
+		assert_eq!(proof.storage_hash, storage_root);
+		assert_eq!(
+			verify_account_proof(root, contract, &proof.account_proof),
+			Ok(Some(contract_account))
+		);
+		assert_eq!(proof.storage_proof[0].value, U256::from(42));
+		assert_eq!(
+			verify_storage_proof(storage_root, slot, &proof.storage_proof[0].proof),
+			Ok(U256::from(42))
+		);
+		assert_eq!(proof.storage_proof[1].value, U256::zero());
+		assert_eq!(
+			verify_storage_proof(storage_root, unset, &proof.storage_proof[1].proof),
+			Ok(U256::zero())
+		);
+
+		// Nothing changed since the last commit.
+		assert_eq!(ChangedAccounts::<Test>::iter().count(), 0);
+		assert_eq!(EVMStateRoot::commit(), root);
+	});
+}
+
+#[test]
+fn removed_accounts_leave_no_node_behind() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::repeat_byte(0x02);
+		EVM::create_account(contract, vec![0x60, 0x00, 0x60, 0x00, 0xf3]);
+		for slot in 0..20 {
+			set_storage(contract, H256::from_low_u64_be(slot), slot + 1);
+		}
+		assert_ne!(EVMStateRoot::commit(), EMPTY_ROOT);
+		assert!(StorageRoots::<Test>::contains_key(contract));
+
+		EVM::remove_created_account(&contract);
+
+		assert_eq!(EVMStateRoot::commit(), EMPTY_ROOT);
+		assert!(!StorageRoots::<Test>::contains_key(contract));
+		// The storage trie is released once there is weight left in a block.
+		assert_ne!(Nodes::<Test>::iter().count(), 0);
+		EVMStateRoot::on_idle(1, Weight::MAX);
+		assert_eq!(Nodes::<Test>::iter().count(), 0);
+		assert!(PendingReleases::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn storage_tries_are_released_within_the_idle_weight() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::repeat_byte(0x02);
+		for slot in 0..20 {
+			set_storage(contract, H256::from_low_u64_be(slot), slot + 1);
+		}
+		EVMStateRoot::commit();
+		EVMStateRoot::on_storage_cleared(contract);
+		EVMStateRoot::commit();
+
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+		let weight = db_weight.reads_writes(3, 3);
+		assert_eq!(EVMStateRoot::on_idle(1, weight), weight);
+		assert!(!PendingReleases::<Test>::get().is_empty());
+		assert_ne!(Nodes::<Test>::iter().count(), 0);
+
+		while !PendingReleases::<Test>::get().is_empty() {
+			EVMStateRoot::on_idle(1, weight);
+		}
+		assert_eq!(Nodes::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn state_root_is_committed_when_read() {
+	new_test_ext().execute_with(|| {
+		let alice = H160::repeat_byte(0x01);
+		fund(alice, 1_000);
+
+		let root = EthereumStateRoot::<Test>::get();
+		assert_ne!(root, EMPTY_ROOT);
+		assert_eq!(StateRoot::<Test>::get(), root);
+		assert_eq!(ChangedAccounts::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn state_changes_are_charged_to_evm_execution() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::repeat_byte(0x02);
+		// SSTORE(0, 1)
+		EVM::create_account(contract, vec![0x60, 0x01, 0x60, 0x00, 0x55]);
+
+		let gas_limit = 1_000_000;
+		let weight_limit =
+			<Test as pallet_evm::Config>::GasWeightMapping::gas_to_weight(gas_limit, true);
+		let info = <Test as pallet_evm::Config>::Runner::call(
+			H160::repeat_byte(0x01),
+			contract,
+			Vec::new(),
+			U256::zero(),
+			gas_limit,
+			None,
+			None,
+			None,
+			Vec::new(),
+			Vec::new(),
+			false,
+			false,
+			Some(weight_limit),
+			Some(0),
+			<Test as pallet_evm::Config>::config(),
+		)
+		.expect("call succeeds");
+
+		let proof_size_usage = info
+			.weight_info
+			.and_then(|weight_info| weight_info.proof_size_usage)
+			.expect("proof size usage");
+		assert!(proof_size_usage >= WeightPerChange::get().proof_size());
+		assert!(ChangedStorages::<Test>::contains_key(
+			contract,
+			H256::zero()
+		));
+	});
+}
+
+#[test]
+fn corrupted_trie_keeps_the_previous_root() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::repeat_byte(0x02);
+		for slot in 0..20 {
+			set_storage(contract, H256::from_low_u64_be(slot), slot + 1);
+		}
+		let root = EVMStateRoot::commit();
+		let storage_root = StorageRoots::<Test>::get(contract);
+
+		let _ = Nodes::<Test>::clear(u32::MAX, None);
+		set_storage(contract, H256::from_low_u64_be(0), 42);
+
+		assert_eq!(EVMStateRoot::commit(), root);
+		assert_eq!(StateRoot::<Test>::get(), root);
+		assert_eq!(StorageRoots::<Test>::get(contract), storage_root);
+		// The changes are dropped rather than failing every later commit.
+		assert_eq!(ChangedStorages::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn drifted_accounts_are_not_proven() {
+	new_test_ext().execute_with(|| {
+		let alice = H160::repeat_byte(0x01);
+		let contract = H160::repeat_byte(0x02);
+		let slot = H256::repeat_byte(0x01);
+		fund(alice, 1_000);
+		set_storage(contract, slot, 42);
+		EVMStateRoot::commit();
+		assert!(EVMStateRoot::account_proof(alice, Vec::new()).is_some());
+		assert!(EVMStateRoot::account_proof(contract, vec![slot]).is_some());
+
+		// Changes not reported to the pallet.
+		let account_id = <Test as pallet_evm::Config>::AddressMapping::into_account_id(alice);
+		let _ = Balances::deposit_creating(&account_id, 1);
+		AccountStorages::<Test>::insert(contract, slot, H256::from_low_u64_be(43));
+
+		assert!(EVMStateRoot::account_proof(alice, Vec::new()).is_none());
+		assert!(EVMStateRoot::account_proof(contract, Vec::new()).is_some());
+		assert!(EVMStateRoot::account_proof(contract, vec![slot]).is_none());
+	});
+}
@@ -0,0 +1,413 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ERC-20 precompile set for fungible assets. Every asset of a `fungibles` implementation (such
+//! as `pallet-assets`) is exposed as an ERC-20 token at an address derived from its asset id, so
+//! that it can be used by wallets and contracts as any other token.
+//!
+//! The precompile set is meant to be wrapped in a `PrecompileSetStartingWith` whose prefix
+//! matches the one used by the [`AddressToAssetId`] implementation.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+use core::marker::PhantomData;
+use fp_evm::{ExitError, PrecompileFailure};
+use frame_support::traits::{
+	fungibles::{self, approvals, metadata},
+	tokens::Preservation,
+	Get,
+};
+use pallet_evm::{AccountIdOf, AddressMapping};
+use precompile_utils::{prelude::*, EvmResult};
+use sp_core::{H160, U256};
+use sp_runtime::{
+	traits::{StaticLookup, Zero},
+	DispatchError, DispatchResult,
+};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Solidity selector of the Transfer log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_TRANSFER: [u8; 32] = keccak256!("Transfer(address,address,uint256)");
+
+/// Solidity selector of the Approval log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_APPROVAL: [u8; 32] = keccak256!("Approval(address,address,uint256)");
+
+// Maximum encoded sizes (key included) of the `pallet-assets` storage items read by the
+// precompile, used to account for the proof size of each read.
+/// Asset details (total supply).
+pub const ASSET_DETAILS_PROOF_SIZE: usize = 258;
+/// Asset account (balance).
+pub const ASSET_ACCOUNT_PROOF_SIZE: usize = 130;
+/// Approval (allowance).
+pub const ASSET_APPROVAL_PROOF_SIZE: usize = 176;
+/// Asset metadata (name, symbol and decimals).
+pub const ASSET_METADATA_PROOF_SIZE: usize = 216;
+
+pub type AssetIdOf<Runtime, Assets> = <Assets as fungibles::Inspect<AccountIdOf<Runtime>>>::AssetId;
+pub type BalanceOf<Runtime, Assets> = <Assets as fungibles::Inspect<AccountIdOf<Runtime>>>::Balance;
+
+/// Mapping between asset ids and the addresses of their ERC-20 precompile.
+pub trait AddressToAssetId<AssetId> {
+	/// The asset id whose precompile lives at `address`, if any.
+	fn address_to_asset_id(address: H160) -> Option<AssetId>;
+
+	/// The address of the precompile of `asset_id`.
+	fn asset_id_to_address(asset_id: AssetId) -> H160;
+}
+
+/// [`AddressToAssetId`] implementation storing the asset id big-endian in the last 16 bytes of
+/// the address, after a `Prefix` of at most 4 bytes. The bytes between the prefix and the asset
+/// id must be zero.
+pub struct AssetIdInAddress<Prefix, AssetId>(PhantomData<(Prefix, AssetId)>);
+
+impl<Prefix, AssetId> AddressToAssetId<AssetId> for AssetIdInAddress<Prefix, AssetId>
+where
+	Prefix: Get<&'static [u8]>,
+	AssetId: TryFrom<u128> + Into<u128>,
+{
+	fn address_to_asset_id(address: H160) -> Option<AssetId> {
+		let prefix = Prefix::get();
+		let bytes = address.as_bytes();
+		if prefix.len() > 4 || !bytes.starts_with(prefix) {
+			return None;
+		}
+		if bytes[prefix.len()..4].iter().any(|byte| *byte != 0) {
+			return None;
+		}
+
+		let mut asset_id = [0u8; 16];
+		asset_id.copy_from_slice(&bytes[4..]);
+		AssetId::try_from(u128::from_be_bytes(asset_id)).ok()
+	}
+
+	fn asset_id_to_address(asset_id: AssetId) -> H160 {
+		let mut address = [0u8; 20];
+		let prefix = Prefix::get();
+		address[..prefix.len()].copy_from_slice(prefix);
+		address[4..].copy_from_slice(&asset_id.into().to_be_bytes());
+		H160(address)
+	}
+}
+
+/// Revocation of approvals, which `fungibles::approvals` doesn't provide.
+pub trait CancelApproval<AccountId>: fungibles::Inspect<AccountId> {
+	/// Remove the approval given by `owner` to `delegate` over `asset`.
+	fn cancel_approval(
+		asset: Self::AssetId,
+		owner: &AccountId,
+		delegate: &AccountId,
+	) -> DispatchResult;
+}
+
+impl<T, I> CancelApproval<T::AccountId> for pallet_assets::Pallet<T, I>
+where
+	T: pallet_assets::Config<I>,
+	T::AssetIdParameter: From<T::AssetId>,
+	I: 'static,
+{
+	fn cancel_approval(
+		asset: T::AssetId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+	) -> DispatchResult {
+		pallet_assets::Pallet::<T, I>::cancel_approval(
+			frame_system::RawOrigin::Signed(owner.clone()).into(),
+			asset.into(),
+			T::Lookup::unlookup(delegate.clone()),
+		)
+	}
+}
+
+/// Precompile set exposing each asset of `Assets` as an ERC-20 token, at the address given by
+/// `AssetMapping`.
+///
+/// `approve` sets the allowance by approving the difference with the current one, as
+/// `fungibles::approvals` can only increase allowances. Lowering an allowance cancels the existing
+/// approval before approving the new amount.
+pub struct Erc20AssetsPrecompileSet<Runtime, Assets, AssetMapping>(
+	PhantomData<(Runtime, Assets, AssetMapping)>,
+);
+
+impl<Runtime, Assets, AssetMapping> Default
+	for Erc20AssetsPrecompileSet<Runtime, Assets, AssetMapping>
+{
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+#[precompile_utils::precompile]
+#[precompile::precompile_set]
+impl<Runtime, Assets, AssetMapping> Erc20AssetsPrecompileSet<Runtime, Assets, AssetMapping>
+where
+	Runtime: pallet_evm::Config,
+	Assets: fungibles::Mutate<AccountIdOf<Runtime>>
+		+ metadata::Inspect<AccountIdOf<Runtime>>
+		+ approvals::Mutate<AccountIdOf<Runtime>>
+		+ CancelApproval<AccountIdOf<Runtime>>,
+	BalanceOf<Runtime, Assets>: TryFrom<U256> + Into<U256>,
+	AssetMapping: AddressToAssetId<AssetIdOf<Runtime, Assets>>,
+{
+	/// Maps the address to the id of an existing asset.
+	#[precompile::discriminant]
+	fn discriminant(address: H160, gas: u64) -> DiscriminantResult<AssetIdOf<Runtime, Assets>> {
+		let extra_cost = RuntimeHelper::<Runtime>::db_read_gas_cost();
+		if gas < extra_cost {
+			return DiscriminantResult::OutOfGas;
+		}
+
+		match AssetMapping::address_to_asset_id(address) {
+			Some(asset_id) if Assets::asset_exists(asset_id.clone()) => {
+				DiscriminantResult::Some(asset_id, extra_cost)
+			}
+			_ => DiscriminantResult::None(extra_cost),
+		}
+	}
+
+	#[precompile::public("name()")]
+	#[precompile::view]
+	fn name(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<UnboundedString> {
+		handle.record_db_read::<Runtime>(ASSET_METADATA_PROOF_SIZE)?;
+
+		Ok(Assets::name(asset_id).into())
+	}
+
+	#[precompile::public("symbol()")]
+	#[precompile::view]
+	fn symbol(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<UnboundedString> {
+		handle.record_db_read::<Runtime>(ASSET_METADATA_PROOF_SIZE)?;
+
+		Ok(Assets::symbol(asset_id).into())
+	}
+
+	#[precompile::public("decimals()")]
+	#[precompile::view]
+	fn decimals(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<u8> {
+		handle.record_db_read::<Runtime>(ASSET_METADATA_PROOF_SIZE)?;
+
+		Ok(Assets::decimals(asset_id))
+	}
+
+	#[precompile::public("totalSupply()")]
+	#[precompile::view]
+	fn total_supply(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+	) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(ASSET_DETAILS_PROOF_SIZE)?;
+
+		Ok(Assets::total_issuance(asset_id).into())
+	}
+
+	#[precompile::public("balanceOf(address)")]
+	#[precompile::view]
+	fn balance_of(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+		who: Address,
+	) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(ASSET_ACCOUNT_PROOF_SIZE)?;
+
+		let who = Runtime::AddressMapping::into_account_id(who.into());
+		Ok(Assets::balance(asset_id, &who).into())
+	}
+
+	#[precompile::public("allowance(address,address)")]
+	#[precompile::view]
+	fn allowance(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		spender: Address,
+	) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(ASSET_APPROVAL_PROOF_SIZE)?;
+
+		let owner = Runtime::AddressMapping::into_account_id(owner.into());
+		let spender = Runtime::AddressMapping::into_account_id(spender.into());
+		Ok(Assets::allowance(asset_id, &owner, &spender).into())
+	}
+
+	#[precompile::public("approve(address,uint256)")]
+	fn approve(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+		spender: Address,
+		value: U256,
+	) -> EvmResult<bool> {
+		handle.record_log_costs_manual(3, 32)?;
+		// Approval, asset details and owner account (deposit) are read then written.
+		handle.record_db_read::<Runtime>(ASSET_APPROVAL_PROOF_SIZE)?;
+		handle.record_db_read::<Runtime>(ASSET_DETAILS_PROOF_SIZE)?;
+		handle.record_db_read::<Runtime>(ASSET_ACCOUNT_PROOF_SIZE)?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost().saturating_mul(3))?;
+
+		let amount = Self::u256_to_amount(value).in_field("value")?;
+		let caller = handle.context().caller;
+		let owner = Runtime::AddressMapping::into_account_id(caller);
+		let delegate = Runtime::AddressMapping::into_account_id(spender.into());
+
+		let current = Assets::allowance(asset_id.clone(), &owner, &delegate);
+		if amount < current {
+			// The approval is removed then created again, which writes the same items twice.
+			handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost().saturating_mul(3))?;
+			Assets::cancel_approval(asset_id.clone(), &owner, &delegate)
+				.map_err(Self::dispatch_error)?;
+			if !amount.is_zero() {
+				Assets::approve(asset_id, &owner, &delegate, amount)
+					.map_err(Self::dispatch_error)?;
+			}
+		} else if amount > current {
+			Assets::approve(asset_id, &owner, &delegate, amount - current)
+				.map_err(Self::dispatch_error)?;
+		}
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_APPROVAL,
+			caller,
+			H160::from(spender),
+			solidity::encode_event_data(value),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("transfer(address,uint256)")]
+	fn transfer(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+		to: Address,
+		value: U256,
+	) -> EvmResult<bool> {
+		handle.record_log_costs_manual(3, 32)?;
+		Self::record_transfer_cost(handle)?;
+
+		let amount = Self::u256_to_amount(value).in_field("value")?;
+		let from = handle.context().caller;
+		let to: H160 = to.into();
+
+		Assets::transfer(
+			asset_id,
+			&Runtime::AddressMapping::into_account_id(from),
+			&Runtime::AddressMapping::into_account_id(to),
+			amount,
+			Preservation::Expendable,
+		)
+		.map_err(Self::dispatch_error)?;
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_TRANSFER,
+			from,
+			to,
+			solidity::encode_event_data(value),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("transferFrom(address,address,uint256)")]
+	fn transfer_from(
+		asset_id: AssetIdOf<Runtime, Assets>,
+		handle: &mut impl PrecompileHandle,
+		from: Address,
+		to: Address,
+		value: U256,
+	) -> EvmResult<bool> {
+		handle.record_log_costs_manual(3, 32)?;
+		Self::record_transfer_cost(handle)?;
+
+		let amount = Self::u256_to_amount(value).in_field("value")?;
+		let caller = handle.context().caller;
+		let from: H160 = from.into();
+		let to: H160 = to.into();
+		let source = Runtime::AddressMapping::into_account_id(from);
+		let dest = Runtime::AddressMapping::into_account_id(to);
+
+		// Spending one's own tokens doesn't require an allowance.
+		let result = if caller == from {
+			Assets::transfer(asset_id, &source, &dest, amount, Preservation::Expendable).map(|_| ())
+		} else {
+			// The approval is read and written on top of the transfer.
+			handle.record_db_read::<Runtime>(ASSET_APPROVAL_PROOF_SIZE)?;
+			handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+
+			let delegate = Runtime::AddressMapping::into_account_id(caller);
+			Assets::transfer_from(asset_id, &source, &delegate, &dest, amount)
+		};
+		result.map_err(Self::dispatch_error)?;
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_TRANSFER,
+			from,
+			to,
+			solidity::encode_event_data(value),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	/// Record the cost of a transfer: asset details and both accounts are read then written.
+	fn record_transfer_cost(handle: &mut impl PrecompileHandle) -> EvmResult {
+		handle.record_db_read::<Runtime>(ASSET_DETAILS_PROOF_SIZE)?;
+		handle.record_db_read::<Runtime>(ASSET_ACCOUNT_PROOF_SIZE)?;
+		handle.record_db_read::<Runtime>(ASSET_ACCOUNT_PROOF_SIZE)?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost().saturating_mul(3))?;
+
+		Ok(())
+	}
+
+	fn u256_to_amount(value: U256) -> MayRevert<BalanceOf<Runtime, Assets>> {
+		value
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").into())
+	}
+
+	fn dispatch_error(error: DispatchError) -> PrecompileFailure {
+		TryDispatchError::Substrate(error).into()
+	}
+}
+
+impl<Runtime, Assets, AssetMapping> Erc20AssetsPrecompileSet<Runtime, Assets, AssetMapping>
+where
+	Runtime: pallet_evm::Config,
+	Assets: fungibles::Inspect<AccountIdOf<Runtime>>,
+	AssetMapping: AddressToAssetId<AssetIdOf<Runtime, Assets>>,
+{
+	/// The address of the ERC-20 precompile of `asset_id`.
+	pub fn asset_address(asset_id: AssetIdOf<Runtime, Assets>) -> H160 {
+		AssetMapping::asset_id_to_address(asset_id)
+	}
+}
@@ -0,0 +1,264 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{mock::*, AddressToAssetId, SELECTOR_LOG_APPROVAL, SELECTOR_LOG_TRANSFER};
+use precompile_utils::{prelude::*, testing::*};
+use sp_core::{H160, U256};
+
+const ASSET: AssetId = 7;
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn token() -> AddressInPrefixedSet {
+	AddressInPrefixedSet(ASSET_PRECOMPILE_PREFIX, ASSET.into())
+}
+
+fn ext() -> sp_io::TestExternalities {
+	ExtBuilder::default()
+		.with_asset(ASSET, "Test Token", "TEST", 12)
+		.with_balances(vec![(ASSET, Alice.into(), 1000)])
+		.build()
+}
+
+#[test]
+fn address_mapping_roundtrips() {
+	let address = AssetMapping::asset_id_to_address(ASSET);
+	assert_eq!(address, H160::from(token()));
+	assert_eq!(AssetMapping::address_to_asset_id(address), Some(ASSET));
+
+	// Wrong prefix, or non-zero bytes outside of the prefix and the asset id.
+	assert_eq!(
+		AssetMapping::address_to_asset_id(AddressInPrefixedSet(0xFFFFFFFE, 7).into()),
+		None
+	);
+	assert_eq!(
+		AssetMapping::address_to_asset_id(
+			AddressInPrefixedSet(ASSET_PRECOMPILE_PREFIX, 1 << 32).into()
+		),
+		None
+	);
+}
+
+#[test]
+fn unknown_asset_is_not_a_precompile() {
+	ext().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				AddressInPrefixedSet(ASSET_PRECOMPILE_PREFIX, 8),
+				PCall::total_supply {},
+			)
+			.execute_none();
+	})
+}
+
+#[test]
+fn metadata_and_supply() {
+	ext().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, token(), PCall::name {})
+			.expect_no_logs()
+			.execute_returns(UnboundedString::from("Test Token".as_bytes()));
+
+		precompiles()
+			.prepare_test(Alice, token(), PCall::symbol {})
+			.expect_no_logs()
+			.execute_returns(UnboundedString::from("TEST".as_bytes()));
+
+		precompiles()
+			.prepare_test(Alice, token(), PCall::decimals {})
+			.expect_no_logs()
+			.execute_returns(12u8);
+
+		precompiles()
+			.prepare_test(Alice, token(), PCall::total_supply {})
+			.expect_no_logs()
+			.execute_returns(U256::from(1000));
+	})
+}
+
+#[test]
+fn transfer_moves_balance_and_emits_log() {
+	ext().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				token(),
+				PCall::transfer {
+					to: Address(Bob.into()),
+					value: U256::from(400),
+				},
+			)
+			.expect_log(log3(
+				token(),
+				SELECTOR_LOG_TRANSFER,
+				H160::from(Alice),
+				H160::from(Bob),
+				solidity::encode_event_data(U256::from(400)),
+			))
+			.execute_returns(true);
+
+		for (who, balance) in [(Address(Alice.into()), 600), (Address(Bob.into()), 400)] {
+			precompiles()
+				.prepare_test(Alice, token(), PCall::balance_of { who })
+				.execute_returns(U256::from(balance));
+		}
+	})
+}
+
+#[test]
+fn transfer_above_balance_reverts() {
+	ext().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				token(),
+				PCall::transfer {
+					to: Address(Bob.into()),
+					value: U256::from(1001),
+				},
+			)
+			.execute_reverts(|output| output.starts_with(b"Dispatched call failed"));
+	})
+}
+
+#[test]
+fn approve_and_transfer_from() {
+	ext().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				token(),
+				PCall::approve {
+					spender: Address(Bob.into()),
+					value: U256::from(500),
+				},
+			)
+			.expect_log(log3(
+				token(),
+				SELECTOR_LOG_APPROVAL,
+				H160::from(Alice),
+				H160::from(Bob),
+				solidity::encode_event_data(U256::from(500)),
+			))
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				token(),
+				PCall::transfer_from {
+					from: Address(Alice.into()),
+					to: Address(Charlie.into()),
+					value: U256::from(300),
+				},
+			)
+			.expect_log(log3(
+				token(),
+				SELECTOR_LOG_TRANSFER,
+				H160::from(Alice),
+				H160::from(Charlie),
+				solidity::encode_event_data(U256::from(300)),
+			))
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				token(),
+				PCall::allowance {
+					owner: Address(Alice.into()),
+					spender: Address(Bob.into()),
+				},
+			)
+			.execute_returns(U256::from(200));
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				token(),
+				PCall::balance_of {
+					who: Address(Charlie.into()),
+				},
+			)
+			.execute_returns(U256::from(300));
+
+		// The remaining allowance doesn't cover the transfer.
+		precompiles()
+			.prepare_test(
+				Bob,
+				token(),
+				PCall::transfer_from {
+					from: Address(Alice.into()),
+					to: Address(Charlie.into()),
+					value: U256::from(300),
+				},
+			)
+			.execute_reverts(|output| output.starts_with(b"Dispatched call failed"));
+	})
+}
+
+#[test]
+fn approve_sets_allowance() {
+	ext().execute_with(|| {
+		for value in [100, 250, 50, 0] {
+			precompiles()
+				.prepare_test(
+					Alice,
+					token(),
+					PCall::approve {
+						spender: Address(Bob.into()),
+						value: U256::from(value),
+					},
+				)
+				.expect_log(log3(
+					token(),
+					SELECTOR_LOG_APPROVAL,
+					H160::from(Alice),
+					H160::from(Bob),
+					solidity::encode_event_data(U256::from(value)),
+				))
+				.execute_returns(true);
+
+			precompiles()
+				.prepare_test(
+					Alice,
+					token(),
+					PCall::allowance {
+						owner: Address(Alice.into()),
+						spender: Address(Bob.into()),
+					},
+				)
+				.execute_returns(U256::from(value));
+		}
+
+		// Nothing is left to spend once the allowance is lowered to zero.
+		precompiles()
+			.prepare_test(
+				Bob,
+				token(),
+				PCall::transfer_from {
+					from: Address(Alice.into()),
+					to: Address(Charlie.into()),
+					value: U256::from(1),
+				},
+			)
+			.execute_reverts(|output| output.starts_with(b"Dispatched call failed"));
+	})
+}
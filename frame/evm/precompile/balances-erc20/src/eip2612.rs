@@ -0,0 +1,184 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! EIP-2612 permits, allowing an owner to approve a spender with a signed message.
+
+use super::*;
+use alloc::vec::Vec;
+use frame_support::traits::{Get, Time};
+use sp_io::hashing::keccak_256;
+use sp_runtime::traits::UniqueSaturatedInto;
+
+/// EIP-712 type hash of the domain of the permits.
+pub const PERMIT_DOMAIN: [u8; 32] = keccak256!(
+	"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+);
+
+/// EIP-712 type hash of a permit.
+pub const PERMIT_TYPEHASH: [u8; 32] = keccak256!(
+	"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+);
+
+/// Proof size of a nonce: Blake2_128(16) + AccountId(20) + U256(32)
+pub const NONCE_PROOF_SIZE: usize = 68;
+
+/// Gas cost of the signature recovery, which is the cost of the ecrecover precompile.
+const ECRECOVER_GAS_COST: u64 = 3000;
+
+pub struct Eip2612<Runtime, Metadata>(PhantomData<(Runtime, Metadata)>);
+
+impl<Runtime, Metadata> Eip2612<Runtime, Metadata>
+where
+	Runtime: pallet_balances::Config
+		+ pallet_evm::Config<AccountProvider = pallet_evm::FrameSystemAccountProvider<Runtime>>,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_balances::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256>,
+	<Runtime as pallet_balances::Config>::Balance: TryFrom<U256>,
+	Metadata: Erc20Metadata,
+{
+	/// The EIP-712 domain separator of the precompile at `address`.
+	pub fn compute_domain_separator(address: H160) -> [u8; 32] {
+		let name: H256 = keccak_256(Metadata::name().as_bytes()).into();
+		let version: H256 = keccak256!("1").into();
+		let chain_id: U256 = Runtime::ChainId::get().into();
+
+		let domain_separator_inner = solidity::encode_arguments((
+			H256::from(PERMIT_DOMAIN),
+			name,
+			version,
+			chain_id,
+			Address(address),
+		));
+
+		keccak_256(&domain_separator_inner)
+	}
+
+	/// The EIP-712 digest signed by `owner` to approve `spender`.
+	pub fn generate_permit(
+		address: H160,
+		owner: H160,
+		spender: H160,
+		value: U256,
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		let domain_separator = Self::compute_domain_separator(address);
+
+		let permit_content = solidity::encode_arguments((
+			H256::from(PERMIT_TYPEHASH),
+			Address(owner),
+			Address(spender),
+			value,
+			nonce,
+			deadline,
+		));
+		let permit_content = keccak_256(&permit_content);
+
+		let mut pre_digest = Vec::with_capacity(2 + 32 + 32);
+		pre_digest.extend_from_slice(b"\x19\x01");
+		pre_digest.extend_from_slice(&domain_separator);
+		pre_digest.extend_from_slice(&permit_content);
+		keccak_256(&pre_digest)
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) fn permit(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		spender: Address,
+		value: U256,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+	) -> EvmResult {
+		handle.record_cost(ECRECOVER_GAS_COST)?;
+		// Nonce is read and written, allowance is written.
+		handle.record_db_read::<Runtime>(NONCE_PROOF_SIZE)?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost().saturating_mul(2))?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		let owner: H160 = owner.into();
+		let spender: H160 = spender.into();
+
+		// Deadline is in seconds, while the timestamp is in milliseconds.
+		let now: u128 = <Runtime as pallet_evm::Config>::Timestamp::now().unique_saturated_into();
+		if deadline < U256::from(now / 1000) {
+			return Err(revert("Permit expired"));
+		}
+
+		let owner_account = Runtime::AddressMapping::into_account_id(owner);
+		let nonce = NoncesStorage::<Runtime>::get(&owner_account);
+
+		let permit = Self::generate_permit(
+			handle.context().address,
+			owner,
+			spender,
+			value,
+			nonce,
+			deadline,
+		);
+
+		let mut sig = [0u8; 65];
+		sig[0..32].copy_from_slice(r.as_bytes());
+		sig[32..64].copy_from_slice(s.as_bytes());
+		sig[64] = v;
+
+		let signer = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &permit)
+			.map_err(|_| revert("Invalid permit"))?;
+		let signer = H160::from(H256::from(keccak_256(&signer)));
+
+		if signer == H160::zero() || signer != owner {
+			return Err(revert("Invalid permit"));
+		}
+
+		NoncesStorage::<Runtime>::insert(&owner_account, nonce + U256::one());
+
+		// Allowances above the maximum balance are equivalent to an infinite allowance.
+		let amount = value.try_into().unwrap_or_else(|_| Bounded::max_value());
+		ApprovesStorage::<Runtime>::insert(
+			owner_account,
+			Runtime::AddressMapping::into_account_id(spender),
+			amount,
+		);
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_APPROVAL,
+			owner,
+			spender,
+			solidity::encode_event_data(value),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	pub(crate) fn nonces(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(NONCE_PROOF_SIZE)?;
+
+		let owner = Runtime::AddressMapping::into_account_id(owner.into());
+		Ok(NoncesStorage::<Runtime>::get(owner))
+	}
+
+	pub(crate) fn domain_separator(handle: &mut impl PrecompileHandle) -> EvmResult<H256> {
+		Ok(Self::compute_domain_separator(handle.context().address).into())
+	}
+}
@@ -0,0 +1,335 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ERC-20 precompile for the native currency. It exposes the balances of
+//! `pallet_evm::Config::Currency` as an ERC-20 token supporting EIP-2612 permits, so contracts
+//! can use the native currency without a wrapping contract.
+//!
+//! Allowances and permit nonces are kept in the precompile's own storage, under the
+//! `Erc20Balances` prefix.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+use core::marker::PhantomData;
+use fp_evm::ACCOUNT_BASIC_PROOF_SIZE;
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	storage::types::{StorageDoubleMap, StorageMap, ValueQuery},
+	traits::{Currency, StorageInstance},
+	Blake2_128Concat,
+};
+use pallet_evm::{AccountIdOf, AddressMapping, BalanceOf};
+use precompile_utils::{prelude::*, EvmResult};
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::{Bounded, CheckedSub, Dispatchable, StaticLookup};
+
+pub mod eip2612;
+use eip2612::Eip2612;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Solidity selector of the Transfer log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_TRANSFER: [u8; 32] = keccak256!("Transfer(address,address,uint256)");
+
+/// Solidity selector of the Approval log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_APPROVAL: [u8; 32] = keccak256!("Approval(address,address,uint256)");
+
+/// Proof size of an approval:
+/// Blake2_128(16) + AccountId(20) + Blake2_128(16) + AccountId(20) + Balance(16)
+pub const APPROVAL_PROOF_SIZE: usize = 88;
+/// Proof size of the total issuance: Balance(16)
+pub const TOTAL_ISSUANCE_PROOF_SIZE: usize = 16;
+
+/// Storage prefix for allowances.
+pub struct ApprovesPrefix;
+
+impl StorageInstance for ApprovesPrefix {
+	const STORAGE_PREFIX: &'static str = "Approves";
+
+	fn pallet_prefix() -> &'static str {
+		"Erc20Balances"
+	}
+}
+
+/// Allowances, by owner then spender.
+pub type ApprovesStorage<Runtime> = StorageDoubleMap<
+	ApprovesPrefix,
+	Blake2_128Concat,
+	AccountIdOf<Runtime>,
+	Blake2_128Concat,
+	AccountIdOf<Runtime>,
+	BalanceOf<Runtime>,
+>;
+
+/// Storage prefix for permit nonces.
+pub struct NoncesPrefix;
+
+impl StorageInstance for NoncesPrefix {
+	const STORAGE_PREFIX: &'static str = "Nonces";
+
+	fn pallet_prefix() -> &'static str {
+		"Erc20Balances"
+	}
+}
+
+/// EIP-2612 permit nonces, by owner.
+pub type NoncesStorage<Runtime> =
+	StorageMap<NoncesPrefix, Blake2_128Concat, AccountIdOf<Runtime>, U256, ValueQuery>;
+
+/// Metadata of the native currency exposed as an ERC-20.
+pub trait Erc20Metadata {
+	/// Name of the token.
+	fn name() -> &'static str;
+
+	/// Symbol of the token.
+	fn symbol() -> &'static str;
+
+	/// Number of decimals of the token.
+	fn decimals() -> u8;
+}
+
+type CurrencyOf<Runtime> = <Runtime as pallet_evm::Config>::Currency;
+
+/// Precompile exposing the native currency as an ERC-20 token.
+pub struct Erc20BalancesPrecompile<Runtime, Metadata>(PhantomData<(Runtime, Metadata)>);
+
+#[precompile_utils::precompile]
+impl<Runtime, Metadata> Erc20BalancesPrecompile<Runtime, Metadata>
+where
+	Runtime: pallet_balances::Config
+		+ pallet_evm::Config<AccountProvider = pallet_evm::FrameSystemAccountProvider<Runtime>>,
+	Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_balances::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256>,
+	<Runtime as pallet_balances::Config>::Balance: TryFrom<U256>,
+	Metadata: Erc20Metadata,
+{
+	#[precompile::public("name()")]
+	#[precompile::view]
+	fn name(_handle: &mut impl PrecompileHandle) -> EvmResult<UnboundedString> {
+		Ok(Metadata::name().as_bytes().into())
+	}
+
+	#[precompile::public("symbol()")]
+	#[precompile::view]
+	fn symbol(_handle: &mut impl PrecompileHandle) -> EvmResult<UnboundedString> {
+		Ok(Metadata::symbol().as_bytes().into())
+	}
+
+	#[precompile::public("decimals()")]
+	#[precompile::view]
+	fn decimals(_handle: &mut impl PrecompileHandle) -> EvmResult<u8> {
+		Ok(Metadata::decimals())
+	}
+
+	#[precompile::public("totalSupply()")]
+	#[precompile::view]
+	fn total_supply(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(TOTAL_ISSUANCE_PROOF_SIZE)?;
+
+		Ok(<CurrencyOf<Runtime> as Currency<_>>::total_issuance().into())
+	}
+
+	#[precompile::public("balanceOf(address)")]
+	#[precompile::view]
+	fn balance_of(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+		// Proof: System::Account
+		handle.record_db_read::<Runtime>(ACCOUNT_BASIC_PROOF_SIZE as usize)?;
+
+		let owner = Runtime::AddressMapping::into_account_id(owner.into());
+		Ok(<CurrencyOf<Runtime> as Currency<_>>::free_balance(&owner).into())
+	}
+
+	#[precompile::public("allowance(address,address)")]
+	#[precompile::view]
+	fn allowance(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		spender: Address,
+	) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(APPROVAL_PROOF_SIZE)?;
+
+		let owner = Runtime::AddressMapping::into_account_id(owner.into());
+		let spender = Runtime::AddressMapping::into_account_id(spender.into());
+		Ok(ApprovesStorage::<Runtime>::get(owner, spender)
+			.unwrap_or_default()
+			.into())
+	}
+
+	#[precompile::public("approve(address,uint256)")]
+	fn approve(
+		handle: &mut impl PrecompileHandle,
+		spender: Address,
+		value: U256,
+	) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		let caller = handle.context().caller;
+		let spender: H160 = spender.into();
+		// Allowances above the maximum balance are equivalent to an infinite allowance.
+		let amount = value.try_into().unwrap_or_else(|_| Bounded::max_value());
+
+		ApprovesStorage::<Runtime>::insert(
+			Runtime::AddressMapping::into_account_id(caller),
+			Runtime::AddressMapping::into_account_id(spender),
+			amount,
+		);
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_APPROVAL,
+			caller,
+			spender,
+			solidity::encode_event_data(value),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("transfer(address,uint256)")]
+	fn transfer(handle: &mut impl PrecompileHandle, to: Address, value: U256) -> EvmResult<bool> {
+		handle.record_log_costs_manual(3, 32)?;
+
+		let from = handle.context().caller;
+		let to: H160 = to.into();
+		Self::dispatch_transfer(handle, from, to, value)?;
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_TRANSFER,
+			from,
+			to,
+			solidity::encode_event_data(value),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("transferFrom(address,address,uint256)")]
+	fn transfer_from(
+		handle: &mut impl PrecompileHandle,
+		from: Address,
+		to: Address,
+		value: U256,
+	) -> EvmResult<bool> {
+		handle.record_log_costs_manual(3, 32)?;
+
+		let caller = handle.context().caller;
+		let from: H160 = from.into();
+		let to: H160 = to.into();
+
+		// Spending one's own tokens doesn't require an allowance.
+		if caller != from {
+			handle.record_db_read::<Runtime>(APPROVAL_PROOF_SIZE)?;
+			handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+
+			let amount: BalanceOf<Runtime> = Self::u256_to_amount(value).in_field("value")?;
+
+			ApprovesStorage::<Runtime>::try_mutate(
+				Runtime::AddressMapping::into_account_id(from),
+				Runtime::AddressMapping::into_account_id(caller),
+				|entry| {
+					let allowed = entry.ok_or_else(|| revert("spender not allowed"))?;
+					let allowed = allowed
+						.checked_sub(&amount)
+						.ok_or_else(|| revert("trying to spend more than allowed"))?;
+					*entry = Some(allowed);
+					EvmResult::Ok(())
+				},
+			)?;
+		}
+
+		Self::dispatch_transfer(handle, from, to, value)?;
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_TRANSFER,
+			from,
+			to,
+			solidity::encode_event_data(value),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("permit(address,address,uint256,uint256,uint8,bytes32,bytes32)")]
+	#[allow(clippy::too_many_arguments)]
+	fn permit(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		spender: Address,
+		value: U256,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+	) -> EvmResult {
+		Eip2612::<Runtime, Metadata>::permit(handle, owner, spender, value, deadline, v, r, s)
+	}
+
+	#[precompile::public("nonces(address)")]
+	#[precompile::view]
+	fn nonces(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+		Eip2612::<Runtime, Metadata>::nonces(handle, owner)
+	}
+
+	#[precompile::public("DOMAIN_SEPARATOR()")]
+	#[precompile::view]
+	fn domain_separator(handle: &mut impl PrecompileHandle) -> EvmResult<H256> {
+		Eip2612::<Runtime, Metadata>::domain_separator(handle)
+	}
+
+	/// Transfer `value` from `from` to `to` by dispatching a balances transfer on behalf of
+	/// `from`, whose weight is charged to the precompile.
+	fn dispatch_transfer(
+		handle: &mut impl PrecompileHandle,
+		from: H160,
+		to: H160,
+		value: U256,
+	) -> EvmResult {
+		let value = Self::u256_to_amount(value).in_field("value")?;
+		let origin = Runtime::AddressMapping::into_account_id(from);
+		let dest = Runtime::AddressMapping::into_account_id(to);
+
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_balances::Call::<Runtime>::transfer_allow_death {
+				dest: Runtime::Lookup::unlookup(dest),
+				value,
+			},
+		)?;
+
+		Ok(())
+	}
+
+	fn u256_to_amount<Balance: TryFrom<U256>>(value: U256) -> MayRevert<Balance> {
+		value
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").into())
+	}
+}
@@ -0,0 +1,293 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{eip2612::Eip2612, mock::*, SELECTOR_LOG_APPROVAL, SELECTOR_LOG_TRANSFER};
+use precompile_utils::{prelude::*, testing::*};
+use sp_core::{H160, H256, U256};
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn ext() -> sp_io::TestExternalities {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 1000), (CryptoAlith.into(), 1000)])
+		.build()
+}
+
+// Sign a permit with the key of `CryptoAlith`, returning `(v, r, s)`.
+fn sign_permit(spender: H160, value: U256, nonce: U256, deadline: U256) -> (u8, H256, H256) {
+	let permit = Eip2612::<Runtime, NativeErc20Metadata>::generate_permit(
+		Precompile1.into(),
+		CryptoAlith.into(),
+		spender,
+		value,
+		nonce,
+		deadline,
+	);
+	let secret = libsecp256k1::SecretKey::parse(&alith_secret_key()).unwrap();
+	let (signature, recovery_id) =
+		libsecp256k1::sign(&libsecp256k1::Message::parse(&permit), &secret);
+	let signature = signature.serialize();
+
+	(
+		recovery_id.serialize() + 27,
+		H256::from_slice(&signature[..32]),
+		H256::from_slice(&signature[32..]),
+	)
+}
+
+#[test]
+fn metadata() {
+	ext().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::name {})
+			.expect_no_logs()
+			.execute_returns(UnboundedString::from("Mock token".as_bytes()));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::symbol {})
+			.expect_no_logs()
+			.execute_returns(UnboundedString::from("MOCK".as_bytes()));
+
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::decimals {})
+			.expect_no_logs()
+			.execute_returns(18u8);
+	})
+}
+
+#[test]
+fn total_supply_and_balance_of() {
+	ext().execute_with(|| {
+		precompiles()
+			.prepare_test(Alice, Precompile1, PCall::total_supply {})
+			.execute_returns(U256::from(2000));
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::balance_of {
+					owner: Address(Alice.into()),
+				},
+			)
+			.execute_returns(U256::from(1000));
+	})
+}
+
+#[test]
+fn transfer_moves_native_balance_and_emits_log() {
+	ext().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::transfer {
+					to: Address(Bob.into()),
+					value: U256::from(400),
+				},
+			)
+			.expect_log(log3(
+				Precompile1,
+				SELECTOR_LOG_TRANSFER,
+				H160::from(Alice),
+				H160::from(Bob),
+				solidity::encode_event_data(U256::from(400)),
+			))
+			.execute_returns(true);
+
+		assert_eq!(Balances::free_balance(AccountId::from(Alice)), 600);
+		assert_eq!(Balances::free_balance(AccountId::from(Bob)), 400);
+	})
+}
+
+#[test]
+fn approve_and_transfer_from() {
+	ext().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::approve {
+					spender: Address(Bob.into()),
+					value: U256::from(500),
+				},
+			)
+			.expect_log(log3(
+				Precompile1,
+				SELECTOR_LOG_APPROVAL,
+				H160::from(Alice),
+				H160::from(Bob),
+				solidity::encode_event_data(U256::from(500)),
+			))
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::transfer_from {
+					from: Address(Alice.into()),
+					to: Address(Charlie.into()),
+					value: U256::from(300),
+				},
+			)
+			.expect_log(log3(
+				Precompile1,
+				SELECTOR_LOG_TRANSFER,
+				H160::from(Alice),
+				H160::from(Charlie),
+				solidity::encode_event_data(U256::from(300)),
+			))
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::allowance {
+					owner: Address(Alice.into()),
+					spender: Address(Bob.into()),
+				},
+			)
+			.execute_returns(U256::from(200));
+		assert_eq!(Balances::free_balance(AccountId::from(Charlie)), 300);
+
+		precompiles()
+			.prepare_test(
+				Bob,
+				Precompile1,
+				PCall::transfer_from {
+					from: Address(Alice.into()),
+					to: Address(Charlie.into()),
+					value: U256::from(300),
+				},
+			)
+			.execute_reverts(|output| output == b"trying to spend more than allowed");
+	})
+}
+
+#[test]
+fn approve_max_is_infinite() {
+	ext().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::approve {
+					spender: Address(Bob.into()),
+					value: U256::MAX,
+				},
+			)
+			.execute_returns(true);
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::allowance {
+					owner: Address(Alice.into()),
+					spender: Address(Bob.into()),
+				},
+			)
+			.execute_returns(U256::from(u128::MAX));
+	})
+}
+
+#[test]
+fn permit_sets_allowance_once() {
+	ext().execute_with(|| {
+		let value = U256::from(500);
+		let deadline = U256::from(10);
+		let (v, r, s) = sign_permit(Bob.into(), value, U256::zero(), deadline);
+
+		let permit = || PCall::permit {
+			owner: Address(CryptoAlith.into()),
+			spender: Address(Bob.into()),
+			value,
+			deadline,
+			v,
+			r,
+			s,
+		};
+
+		precompiles()
+			.prepare_test(Charlie, Precompile1, permit())
+			.expect_log(log3(
+				Precompile1,
+				SELECTOR_LOG_APPROVAL,
+				H160::from(CryptoAlith),
+				H160::from(Bob),
+				solidity::encode_event_data(value),
+			))
+			.execute_returns(());
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				PCall::allowance {
+					owner: Address(CryptoAlith.into()),
+					spender: Address(Bob.into()),
+				},
+			)
+			.execute_returns(value);
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				PCall::nonces {
+					owner: Address(CryptoAlith.into()),
+				},
+			)
+			.execute_returns(U256::one());
+
+		// The nonce has been consumed.
+		precompiles()
+			.prepare_test(Charlie, Precompile1, permit())
+			.execute_reverts(|output| output == b"Invalid permit");
+	})
+}
+
+#[test]
+fn permit_expired() {
+	ext().execute_with(|| {
+		pallet_timestamp::Pallet::<Runtime>::set_timestamp(20_000);
+
+		let value = U256::from(500);
+		let deadline = U256::from(10);
+		let (v, r, s) = sign_permit(Bob.into(), value, U256::zero(), deadline);
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				PCall::permit {
+					owner: Address(CryptoAlith.into()),
+					spender: Address(Bob.into()),
+					value,
+					deadline,
+					v,
+					r,
+					s,
+				},
+			)
+			.execute_reverts(|output| output == b"Permit expired");
+	})
+}
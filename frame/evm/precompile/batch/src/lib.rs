@@ -0,0 +1,268 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Batch precompile, allowing an account to perform several calls in a single transaction
+//! without deploying a multicall contract. Subcalls are performed with the caller of the
+//! precompile as their caller, in one of three modes:
+//! - `batchSome`: failing subcalls are ignored.
+//! - `batchSomeUntilFailure`: the first failing subcall stops the batch, whose previous subcalls
+//!   are kept.
+//! - `batchAll`: any failing subcall reverts the whole batch.
+//!
+//! A `SubcallSucceeded(uint256)` or `SubcallFailed(uint256)` log is emitted for each subcall
+//! that is attempted.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{iter::repeat, marker::PhantomData};
+use fp_evm::{Context, ExitError, ExitReason, Log, PrecompileFailure, Transfer};
+use precompile_utils::{evm::costs::call_cost, prelude::*, EvmResult};
+use sp_core::{ConstU32, H160, U256};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+	BatchSome,             // = "batchSome(...)"
+	BatchSomeUntilFailure, // = "batchSomeUntilFailure(...)"
+	BatchAll,              // = "batchAll(...)"
+}
+
+pub const LOG_SUBCALL_SUCCEEDED: [u8; 32] = keccak256!("SubcallSucceeded(uint256)");
+pub const LOG_SUBCALL_FAILED: [u8; 32] = keccak256!("SubcallFailed(uint256)");
+pub const CALL_DATA_LIMIT: u32 = 2u32.pow(16);
+pub const ARRAY_LIMIT: u32 = 2u32.pow(9);
+
+type GetCallDataLimit = ConstU32<CALL_DATA_LIMIT>;
+type GetArrayLimit = ConstU32<ARRAY_LIMIT>;
+
+pub fn log_subcall_succeeded(address: impl Into<H160>, index: usize) -> Log {
+	log1(
+		address,
+		LOG_SUBCALL_SUCCEEDED,
+		solidity::encode_event_data(U256::from(index)),
+	)
+}
+
+pub fn log_subcall_failed(address: impl Into<H160>, index: usize) -> Log {
+	log1(
+		address,
+		LOG_SUBCALL_FAILED,
+		solidity::encode_event_data(U256::from(index)),
+	)
+}
+
+/// Batch precompile.
+///
+/// It must be registered with subcalls allowed (`SubcallWithMaxNesting`), and is meant to be
+/// callable by smart contracts (`CallableByContract`).
+#[derive(Debug, Clone)]
+pub struct BatchPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> BatchPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config,
+{
+	#[precompile::public("batchSome(address[],uint256[],bytes[],uint64[])")]
+	fn batch_some(
+		handle: &mut impl PrecompileHandle,
+		to: BoundedVec<Address, GetArrayLimit>,
+		value: BoundedVec<U256, GetArrayLimit>,
+		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
+		gas_limit: BoundedVec<u64, GetArrayLimit>,
+	) -> EvmResult {
+		Self::inner_batch(Mode::BatchSome, handle, to, value, call_data, gas_limit)
+	}
+
+	#[precompile::public("batchSomeUntilFailure(address[],uint256[],bytes[],uint64[])")]
+	fn batch_some_until_failure(
+		handle: &mut impl PrecompileHandle,
+		to: BoundedVec<Address, GetArrayLimit>,
+		value: BoundedVec<U256, GetArrayLimit>,
+		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
+		gas_limit: BoundedVec<u64, GetArrayLimit>,
+	) -> EvmResult {
+		Self::inner_batch(
+			Mode::BatchSomeUntilFailure,
+			handle,
+			to,
+			value,
+			call_data,
+			gas_limit,
+		)
+	}
+
+	#[precompile::public("batchAll(address[],uint256[],bytes[],uint64[])")]
+	fn batch_all(
+		handle: &mut impl PrecompileHandle,
+		to: BoundedVec<Address, GetArrayLimit>,
+		value: BoundedVec<U256, GetArrayLimit>,
+		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
+		gas_limit: BoundedVec<u64, GetArrayLimit>,
+	) -> EvmResult {
+		Self::inner_batch(Mode::BatchAll, handle, to, value, call_data, gas_limit)
+	}
+
+	/// Perform the subcalls. `value`, `call_data` and `gas_limit` can be shorter than `to`, in
+	/// which case the missing entries default to no value, no call data and all the remaining
+	/// gas (which is also the meaning of a gas limit of 0).
+	fn inner_batch(
+		mode: Mode,
+		handle: &mut impl PrecompileHandle,
+		to: BoundedVec<Address, GetArrayLimit>,
+		value: BoundedVec<U256, GetArrayLimit>,
+		call_data: BoundedVec<BoundedBytes<GetCallDataLimit>, GetArrayLimit>,
+		gas_limit: BoundedVec<u64, GetArrayLimit>,
+	) -> EvmResult {
+		let addresses = Vec::from(to).into_iter().enumerate();
+		let values = Vec::from(value).into_iter().map(Some).chain(repeat(None));
+		let calls_data = Vec::from(call_data)
+			.into_iter()
+			.map(|x| Some(x.into()))
+			.chain(repeat(None));
+		let gas_limits = Vec::from(gas_limit)
+			.into_iter()
+			.map(|x| if x == 0 { None } else { Some(x) })
+			.chain(repeat(None));
+
+		// Cost of a subcall log, which doesn't depend on the index.
+		let log_cost = log_subcall_failed(handle.code_address(), 0)
+			.compute_cost()
+			.map_err(|_| revert("Failed to compute log cost"))?;
+
+		for ((i, Address(address)), (value, (call_data, gas_limit))) in
+			addresses.zip(values.zip(calls_data.zip(gas_limits)))
+		{
+			let value = value.unwrap_or_default();
+			let call_data: Vec<u8> = call_data.unwrap_or_default();
+
+			let sub_context = Context {
+				caller: handle.context().caller,
+				address,
+				apparent_value: value,
+			};
+
+			let transfer = if value.is_zero() {
+				None
+			} else {
+				Some(Transfer {
+					source: handle.context().caller,
+					target: address,
+					value,
+				})
+			};
+
+			// Reserve enough gas to emit the log of the subcall, and stop there according to
+			// the mode if there is not enough.
+			let forwarded_gas = match (handle.remaining_gas().checked_sub(log_cost), mode) {
+				(Some(remaining), _) => remaining,
+				(None, Mode::BatchAll) => {
+					return Err(PrecompileFailure::Error {
+						exit_status: ExitError::OutOfGas,
+					})
+				}
+				(None, _) => return Ok(()),
+			};
+
+			// Cost of the call itself, which `handle.call` records as the CALL opcode would: it is
+			// only kept out of the gas forwarded to the subcall here.
+			let call_cost = call_cost(value, pallet_evm::Pallet::<Runtime>::evm_config());
+
+			// The subcall is skipped if its cost or its gas limit exceed the remaining gas.
+			let forwarded_gas =
+				forwarded_gas
+					.checked_sub(call_cost)
+					.and_then(|remaining| match gas_limit {
+						None => Some(remaining),
+						Some(limit) if limit <= remaining => Some(limit),
+						Some(_) => None,
+					});
+
+			let Some(forwarded_gas) = forwarded_gas else {
+				let log = log_subcall_failed(handle.code_address(), i);
+				handle.record_log_costs(&[&log])?;
+				log.record(handle)?;
+
+				match mode {
+					Mode::BatchAll => {
+						return Err(PrecompileFailure::Error {
+							exit_status: ExitError::OutOfGas,
+						})
+					}
+					Mode::BatchSomeUntilFailure => return Ok(()),
+					Mode::BatchSome => continue,
+				}
+			};
+
+			let (reason, output) = handle.call(
+				address,
+				transfer,
+				call_data,
+				Some(forwarded_gas),
+				false,
+				&sub_context,
+			);
+
+			// Enough gas has been reserved for the log.
+			let log = match reason {
+				ExitReason::Revert(_) | ExitReason::Error(_) => {
+					Some(log_subcall_failed(handle.code_address(), i))
+				}
+				ExitReason::Succeed(_) => Some(log_subcall_succeeded(handle.code_address(), i)),
+				ExitReason::Fatal(_) => None,
+			};
+			if let Some(log) = log {
+				handle.record_log_costs(&[&log])?;
+				log.record(handle)?;
+			}
+
+			match (mode, reason) {
+				// Fatal errors are always fatal.
+				(_, ExitReason::Fatal(exit_status)) => {
+					return Err(PrecompileFailure::Fatal { exit_status })
+				}
+
+				// BatchAll: reverts and errors are forwarded.
+				(Mode::BatchAll, ExitReason::Revert(exit_status)) => {
+					return Err(PrecompileFailure::Revert {
+						exit_status,
+						output,
+					})
+				}
+				(Mode::BatchAll, ExitReason::Error(exit_status)) => {
+					return Err(PrecompileFailure::Error { exit_status })
+				}
+
+				// BatchSomeUntilFailure: reverts and errors stop the batch, which still succeeds.
+				(Mode::BatchSomeUntilFailure, ExitReason::Revert(_) | ExitReason::Error(_)) => {
+					return Ok(())
+				}
+
+				// Success, or ignored revert or error.
+				_ => (),
+			}
+		}
+
+		Ok(())
+	}
+}
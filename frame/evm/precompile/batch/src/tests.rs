@@ -0,0 +1,217 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{log_subcall_failed, log_subcall_succeeded, mock::*};
+use fp_evm::ExitError;
+use precompile_utils::{evm::costs::call_cost, prelude::*, testing::*};
+use sp_core::{H160, U256};
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+fn batch_to(to: impl IntoIterator<Item = H160>) -> BoundedVec<Address, crate::GetArrayLimit> {
+	to.into_iter().map(Address).collect::<Vec<_>>().into()
+}
+
+// Subcalls to Bob succeed while subcalls to Charlie revert.
+fn bob_succeeds_charlie_reverts(subcall: Subcall) -> SubcallOutput {
+	if subcall.address == Bob.into() {
+		SubcallOutput::succeed()
+	} else if subcall.address == Charlie.into() {
+		SubcallOutput {
+			output: solidity::revert::revert_as_bytes("Charlie reverts"),
+			..SubcallOutput::revert()
+		}
+	} else {
+		panic!("unexpected subcall to {:?}", subcall.address)
+	}
+}
+
+#[test]
+fn batch_forwards_value_call_data_and_gas() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 1_000_000)])
+		.build()
+		.execute_with(|| {
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::batch_all {
+						to: batch_to([Bob.into(), Charlie.into()]),
+						value: vec![U256::from(42)].into(),
+						call_data: vec![BoundedBytes::from(b"data".as_slice())].into(),
+						gas_limit: vec![100_000].into(),
+					},
+				)
+				.with_subcall_handle(|subcall| {
+					assert_eq!(subcall.context.caller, Alice.into());
+					assert_eq!(subcall.context.address, subcall.address);
+					if subcall.address == Bob.into() {
+						let transfer = subcall.transfer.expect("value is transferred");
+						assert_eq!(transfer.source, Alice.into());
+						assert_eq!(transfer.target, Bob.into());
+						assert_eq!(transfer.value, U256::from(42));
+						assert_eq!(subcall.context.apparent_value, U256::from(42));
+						assert_eq!(subcall.input, b"data");
+						assert_eq!(subcall.target_gas, Some(100_000));
+					} else {
+						// Missing entries default to no value, no data and all the gas.
+						assert!(subcall.transfer.is_none());
+						assert!(subcall.input.is_empty());
+						assert!(subcall.target_gas.unwrap() > 100_000);
+					}
+					SubcallOutput::succeed()
+				})
+				.expect_log(log_subcall_succeeded(Precompile1, 0))
+				.expect_log(log_subcall_succeeded(Precompile1, 1))
+				.execute_returns(());
+		})
+}
+
+#[test]
+fn batch_charges_value_transfer_subcalls_once() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), 1_000_000)])
+		.build()
+		.execute_with(|| {
+			let log_cost = log_subcall_succeeded(Precompile1, 0)
+				.compute_cost()
+				.unwrap();
+			let call_cost = call_cost(U256::from(42), &fp_evm::Config::london());
+
+			precompiles()
+				.prepare_test(
+					Alice,
+					Precompile1,
+					PCall::batch_all {
+						to: batch_to([Bob.into()]),
+						value: vec![U256::from(42)].into(),
+						call_data: vec![].into(),
+						gas_limit: vec![].into(),
+					},
+				)
+				.with_subcall_handle(|_| SubcallOutput {
+					cost: 1_000,
+					..SubcallOutput::succeed()
+				})
+				.expect_cost(call_cost + 1_000 + log_cost)
+				.expect_log(log_subcall_succeeded(Precompile1, 0))
+				.execute_returns(());
+		})
+}
+
+#[test]
+fn batch_some_ignores_failures() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::batch_some {
+					to: batch_to([Charlie.into(), Bob.into()]),
+					value: vec![].into(),
+					call_data: vec![].into(),
+					gas_limit: vec![].into(),
+				},
+			)
+			.with_subcall_handle(bob_succeeds_charlie_reverts)
+			.expect_log(log_subcall_failed(Precompile1, 0))
+			.expect_log(log_subcall_succeeded(Precompile1, 1))
+			.execute_returns(());
+	})
+}
+
+#[test]
+fn batch_some_until_failure_stops_at_first_failure() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::batch_some_until_failure {
+					to: batch_to([Bob.into(), Charlie.into(), David.into()]),
+					value: vec![].into(),
+					call_data: vec![].into(),
+					gas_limit: vec![].into(),
+				},
+			)
+			// A subcall to David would panic.
+			.with_subcall_handle(bob_succeeds_charlie_reverts)
+			.expect_log(log_subcall_succeeded(Precompile1, 0))
+			.expect_log(log_subcall_failed(Precompile1, 1))
+			.execute_returns(());
+	})
+}
+
+#[test]
+fn batch_all_reverts_on_failure() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::batch_all {
+					to: batch_to([Bob.into(), Charlie.into(), David.into()]),
+					value: vec![].into(),
+					call_data: vec![].into(),
+					gas_limit: vec![].into(),
+				},
+			)
+			.with_subcall_handle(bob_succeeds_charlie_reverts)
+			.execute_reverts(|output| output == b"Charlie reverts");
+	})
+}
+
+#[test]
+fn subcall_with_gas_limit_above_remaining_gas_is_skipped() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::batch_some {
+					to: batch_to([Charlie.into(), Bob.into()]),
+					value: vec![].into(),
+					call_data: vec![].into(),
+					gas_limit: vec![200_000].into(),
+				},
+			)
+			.with_target_gas(Some(150_000))
+			// Charlie is not called, as its subcall would revert.
+			.with_subcall_handle(bob_succeeds_charlie_reverts)
+			.expect_log(log_subcall_failed(Precompile1, 0))
+			.expect_log(log_subcall_succeeded(Precompile1, 1))
+			.execute_returns(());
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				Precompile1,
+				PCall::batch_all {
+					to: batch_to([Charlie.into(), Bob.into()]),
+					value: vec![].into(),
+					call_data: vec![].into(),
+					gas_limit: vec![200_000].into(),
+				},
+			)
+			.with_target_gas(Some(150_000))
+			.with_subcall_handle(bob_succeeds_charlie_reverts)
+			.execute_error(ExitError::OutOfGas);
+	})
+}
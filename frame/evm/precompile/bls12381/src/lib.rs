@@ -217,6 +217,259 @@ fn decode_g2(input: &[u8], offset: usize) -> Result<G2Projective, PrecompileFail
 	}
 }
 
+/// Decode G1 and check that it lies in the prime order subgroup.
+fn decode_g1_in_subgroup(input: &[u8], offset: usize) -> Result<G1Projective, PrecompileFailure> {
+	let g1 = decode_g1(input, offset)?;
+	if !g1.into_affine().is_in_correct_subgroup_assuming_on_curve() {
+		return Err(PrecompileFailure::Error {
+			exit_status: ExitError::Other("g1 point is not on correct subgroup".into()),
+		});
+	}
+	Ok(g1)
+}
+
+/// Decode G2 and check that it lies in the prime order subgroup.
+fn decode_g2_in_subgroup(input: &[u8], offset: usize) -> Result<G2Projective, PrecompileFailure> {
+	let g2 = decode_g2(input, offset)?;
+	if !g2.into_affine().is_in_correct_subgroup_assuming_on_curve() {
+		return Err(PrecompileFailure::Error {
+			exit_status: ExitError::Other("g2 point is not on correct subgroup".into()),
+		});
+	}
+	Ok(g2)
+}
+
+/// Returns the gas required by a multi scalar multiplication of `k` pairs, given the cost of a
+/// single multiplication and the discount table (in thousandths) of the operation.
+fn msm_gas_cost(k: usize, mul_cost: u64, discount_table: &[u16]) -> u64 {
+	if k == 0 {
+		return 0;
+	}
+	// Pairs past the end of the table get the maximum discount
+	let discount = discount_table[k.min(discount_table.len()) - 1];
+	k as u64 * mul_cost * discount as u64 / 1_000
+}
+
+/// Compute `p_0 + p_1` for two G1 points encoded in `256` bytes.
+fn g1_add(input: &[u8]) -> Result<PrecompileOutput, PrecompileFailure> {
+	if input.len() != 256 {
+		return Err(PrecompileFailure::Error {
+			exit_status: ExitError::Other("invalid input length".into()),
+		});
+	}
+
+	// Decode G1 point p_0
+	let p0 = decode_g1(input, 0)?;
+	// Decode G1 point p_1
+	let p1 = decode_g1(input, 128)?;
+	// Compute r = p_0 + p_1
+	let r = p0 + p1;
+	// Encode the G1 point into 128 bytes output
+	let output = encode_g1(r.into_affine());
+
+	Ok(PrecompileOutput {
+		exit_status: ExitSucceed::Returned,
+		output: output.to_vec(),
+	})
+}
+
+/// Compute `e_0 * p_0 + ... + e_(k-1) * p_(k-1)` for `k` G1 point and scalar pairs encoded in
+/// `160*k` bytes, optionally requiring every point to be in the correct subgroup.
+fn g1_msm(input: &[u8], subgroup_check: bool) -> Result<PrecompileOutput, PrecompileFailure> {
+	if input.is_empty() || input.len() % 160 != 0 {
+		return Err(PrecompileFailure::Error {
+			exit_status: ExitError::Other("invalid input length".into()),
+		});
+	}
+	let k = input.len() / 160;
+
+	let mut points = Vec::new();
+	let mut scalars = Vec::new();
+	// Decode point scalar pairs
+	for idx in 0..k {
+		let offset = idx * 160;
+		// Decode G1 point
+		let p = if subgroup_check {
+			decode_g1_in_subgroup(input, offset)?
+		} else {
+			decode_g1(input, offset)?
+		};
+		// Decode scalar value
+		let scalar = decode_fr(input, offset + 128);
+		points.push(p.into_affine());
+		scalars.push(scalar);
+	}
+
+	// Compute r = e_0 * p_0 + e_1 * p_1 + ... + e_(k-1) * p_(k-1)
+	let r = G1Projective::msm(&points, &scalars).map_err(|_| PrecompileFailure::Error {
+		exit_status: ExitError::Other("MSM failed".into()),
+	})?;
+
+	// Encode the G1 point into 128 bytes output
+	let output = encode_g1(r.into_affine());
+	Ok(PrecompileOutput {
+		exit_status: ExitSucceed::Returned,
+		output: output.to_vec(),
+	})
+}
+
+/// Compute `p_0 + p_1` for two G2 points encoded in `512` bytes.
+fn g2_add(input: &[u8]) -> Result<PrecompileOutput, PrecompileFailure> {
+	if input.len() != 512 {
+		return Err(PrecompileFailure::Error {
+			exit_status: ExitError::Other("invalid input length".into()),
+		});
+	}
+
+	// Decode G2 point p_0
+	let p0 = decode_g2(input, 0)?;
+	// Decode G2 point p_1
+	let p1 = decode_g2(input, 256)?;
+	// Compute r = p_0 + p_1
+	let r = p0 + p1;
+	// Encode the G2 point into 256 bytes output
+	let output = encode_g2(r.into_affine());
+
+	Ok(PrecompileOutput {
+		exit_status: ExitSucceed::Returned,
+		output: output.to_vec(),
+	})
+}
+
+/// Compute `e_0 * p_0 + ... + e_(k-1) * p_(k-1)` for `k` G2 point and scalar pairs encoded in
+/// `288*k` bytes, optionally requiring every point to be in the correct subgroup.
+fn g2_msm(input: &[u8], subgroup_check: bool) -> Result<PrecompileOutput, PrecompileFailure> {
+	if input.is_empty() || input.len() % 288 != 0 {
+		return Err(PrecompileFailure::Error {
+			exit_status: ExitError::Other("invalid input length".into()),
+		});
+	}
+	let k = input.len() / 288;
+
+	let mut points = Vec::new();
+	let mut scalars = Vec::new();
+	// Decode point scalar pairs
+	for idx in 0..k {
+		let offset = idx * 288;
+		// Decode G2 point
+		let p = if subgroup_check {
+			decode_g2_in_subgroup(input, offset)?
+		} else {
+			decode_g2(input, offset)?
+		};
+		// Decode scalar value
+		let scalar = decode_fr(input, offset + 256);
+		points.push(p.into_affine());
+		scalars.push(scalar);
+	}
+
+	// Compute r = e_0 * p_0 + e_1 * p_1 + ... + e_(k-1) * p_(k-1)
+	let r = G2Projective::msm(&points, &scalars).map_err(|_| PrecompileFailure::Error {
+		exit_status: ExitError::Other("MSM failed".into()),
+	})?;
+
+	// Encode the G2 point to 256 bytes output
+	let output = encode_g2(r.into_affine());
+	Ok(PrecompileOutput {
+		exit_status: ExitSucceed::Returned,
+		output: output.to_vec(),
+	})
+}
+
+/// Check whether the product of the pairings of `k` G1 and G2 pairs encoded in `384*k` bytes is
+/// the multiplicative identity. The length must have been validated by the caller.
+fn pairing_check(input: &[u8]) -> Result<PrecompileOutput, PrecompileFailure> {
+	let k = input.len() / 384;
+
+	let mut a = Vec::new();
+	let mut b = Vec::new();
+	// Decode G1 G2 pairs
+	for idx in 0..k {
+		let offset = idx * 384;
+		// Decode G1 point
+		let g1 = decode_g1(input, offset)?;
+		// Decode G2 point
+		let g2 = decode_g2(input, offset + 128)?;
+
+		// 'point is on curve' check already done,
+		// Here we need to apply subgroup checks.
+		if !g1.into_affine().is_in_correct_subgroup_assuming_on_curve() {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("g1 point is not on correct subgroup".into()),
+			});
+		}
+		if !g2.into_affine().is_in_correct_subgroup_assuming_on_curve() {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("g2 point is not on correct subgroup".into()),
+			});
+		}
+
+		a.push(g1);
+		b.push(g2);
+	}
+
+	let mut output = [0u8; 32];
+	// Compute pairing and set the output
+	if Bls12_381::multi_pairing(a, b).is_zero() {
+		output[31] = 1;
+	}
+
+	Ok(PrecompileOutput {
+		exit_status: ExitSucceed::Returned,
+		output: output.to_vec(),
+	})
+}
+
+/// Map an element of the base field encoded in `64` bytes to a G1 point.
+fn map_fp_to_g1(input: &[u8]) -> Result<PrecompileOutput, PrecompileFailure> {
+	if input.len() != 64 {
+		return Err(PrecompileFailure::Error {
+			exit_status: ExitError::Other("invalid input length".into()),
+		});
+	}
+
+	let fq = read_fq(input, 0)?;
+	let g1 = match map_to_curve_g1(fq) {
+		Ok(point) => point.clear_cofactor(),
+		Err(_) => {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("map to curve failed".into()),
+			})
+		}
+	};
+
+	let output = encode_g1(g1);
+	Ok(PrecompileOutput {
+		exit_status: ExitSucceed::Returned,
+		output: output.to_vec(),
+	})
+}
+
+/// Map an element of the quadratic extension field encoded in `128` bytes to a G2 point.
+fn map_fp2_to_g2(input: &[u8]) -> Result<PrecompileOutput, PrecompileFailure> {
+	if input.len() != 128 {
+		return Err(PrecompileFailure::Error {
+			exit_status: ExitError::Other("invalid input length".into()),
+		});
+	}
+
+	let fq2 = read_fq2(input, 0)?;
+	let g2 = match map_to_curve_g2(fq2) {
+		Ok(point) => point.clear_cofactor(),
+		Err(_) => {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("map to curve failed".into()),
+			})
+		}
+	};
+
+	let output = encode_g2(g2);
+	Ok(PrecompileOutput {
+		exit_status: ExitSucceed::Returned,
+		output: output.to_vec(),
+	})
+}
+
 /// Bls12381 implements EIP-2537 G1Add precompile.
 pub struct Bls12381G1Add;
 
@@ -232,26 +485,7 @@ impl Precompile for Bls12381G1Add {
 	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
 		handle.record_cost(Bls12381G1Add::GAS_COST)?;
 
-		let input = handle.input();
-		if input.len() != 256 {
-			return Err(PrecompileFailure::Error {
-				exit_status: ExitError::Other("invalid input length".into()),
-			});
-		}
-
-		// Decode G1 point p_0
-		let p0 = decode_g1(input, 0)?;
-		// Decode G1 point p_1
-		let p1 = decode_g1(input, 128)?;
-		// Compute r = p_0 + p_1
-		let r = p0 + p1;
-		// Encode the G1 point into 128 bytes output
-		let output = encode_g1(r.into_affine());
-
-		Ok(PrecompileOutput {
-			exit_status: ExitSucceed::Returned,
-			output: output.to_vec(),
-		})
+		g1_add(handle.input())
 	}
 }
 
@@ -297,24 +531,15 @@ impl Precompile for Bls12381G1Mul {
 pub struct Bls12381G1MultiExp;
 
 impl Bls12381G1MultiExp {
-	const MULTIPLIER: u64 = 1_000;
-
 	/// Returns the gas required to execute the pre-compiled contract.
 	fn calculate_gas_cost(input_len: usize) -> u64 {
 		// Calculate G1 point, scalar value pair length
 		let k = input_len / 160;
-		if k == 0 {
-			return 0;
-		}
-		// Lookup discount value for G1 point, scalar value pair length
-		let d_len = BLS12381_MULTIEXP_DISCOUNT_TABLE.len();
-		let discount = if k <= d_len {
-			BLS12381_MULTIEXP_DISCOUNT_TABLE[k - 1]
-		} else {
-			BLS12381_MULTIEXP_DISCOUNT_TABLE[d_len - 1]
-		};
-		// Calculate gas and return the result
-		k as u64 * Bls12381G1Mul::GAS_COST * discount as u64 / Bls12381G1MultiExp::MULTIPLIER
+		msm_gas_cost(
+			k,
+			Bls12381G1Mul::GAS_COST,
+			&BLS12381_MULTIEXP_DISCOUNT_TABLE,
+		)
 	}
 }
 
@@ -326,41 +551,7 @@ impl Precompile for Bls12381G1MultiExp {
 		let gas_cost = Bls12381G1MultiExp::calculate_gas_cost(handle.input().len());
 		handle.record_cost(gas_cost)?;
 
-		let k = handle.input().len() / 160;
-		if handle.input().is_empty() || handle.input().len() % 160 != 0 {
-			return Err(PrecompileFailure::Error {
-				exit_status: ExitError::Other("invalid input length".into()),
-			});
-		}
-
-		let input = handle.input();
-
-		let mut points = Vec::new();
-		let mut scalars = Vec::new();
-		// Decode point scalar pairs
-		for idx in 0..k {
-			let offset = idx * 160;
-			// Decode G1 point
-			let p = decode_g1(input, offset)?;
-			// Decode scalar value
-			let scalar = decode_fr(input, offset + 128);
-			points.push(p.into_affine());
-			scalars.push(scalar);
-		}
-
-		// Compute r = e_0 * p_0 + e_1 * p_1 + ... + e_(k-1) * p_(k-1)
-		let r = G1Projective::msm(&points.to_vec(), &scalars.to_vec()).map_err(|_| {
-			PrecompileFailure::Error {
-				exit_status: ExitError::Other("MSM failed".into()),
-			}
-		})?;
-
-		// Encode the G1 point into 128 bytes output
-		let output = encode_g1(r.into_affine());
-		Ok(PrecompileOutput {
-			exit_status: ExitSucceed::Returned,
-			output: output.to_vec(),
-		})
+		g1_msm(handle.input(), false)
 	}
 }
 
@@ -379,26 +570,7 @@ impl Precompile for Bls12381G2Add {
 	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
 		handle.record_cost(Bls12381G2Add::GAS_COST)?;
 
-		let input = handle.input();
-		if input.len() != 512 {
-			return Err(PrecompileFailure::Error {
-				exit_status: ExitError::Other("invalid input length".into()),
-			});
-		}
-
-		// Decode G2 point p_0
-		let p0 = decode_g2(input, 0)?;
-		// Decode G2 point p_1
-		let p1 = decode_g2(input, 256)?;
-		// Compute r = p_0 + p_1
-		let r = p0 + p1;
-		// Encode the G2 point into 256 bytes output
-		let output = encode_g2(r.into_affine());
-
-		Ok(PrecompileOutput {
-			exit_status: ExitSucceed::Returned,
-			output: output.to_vec(),
-		})
+		g2_add(handle.input())
 	}
 }
 
@@ -444,24 +616,15 @@ impl Precompile for Bls12381G2Mul {
 pub struct Bls12381G2MultiExp;
 
 impl Bls12381G2MultiExp {
-	const MULTIPLIER: u64 = 1_000;
-
 	/// Returns the gas required to execute the pre-compiled contract.
 	fn calculate_gas_cost(input_len: usize) -> u64 {
 		// Calculate G2 point, scalar value pair length
 		let k = input_len / 288;
-		if k == 0 {
-			return 0;
-		}
-		// Lookup discount value for G2 point, scalar value pair length
-		let d_len = BLS12381_MULTIEXP_DISCOUNT_TABLE.len();
-		let discount = if k <= d_len {
-			BLS12381_MULTIEXP_DISCOUNT_TABLE[k - 1]
-		} else {
-			BLS12381_MULTIEXP_DISCOUNT_TABLE[d_len - 1]
-		};
-		// Calculate gas and return the result
-		k as u64 * Bls12381G2Mul::GAS_COST * discount as u64 / Bls12381G2MultiExp::MULTIPLIER
+		msm_gas_cost(
+			k,
+			Bls12381G2Mul::GAS_COST,
+			&BLS12381_MULTIEXP_DISCOUNT_TABLE,
+		)
 	}
 }
 
@@ -473,41 +636,7 @@ impl Precompile for Bls12381G2MultiExp {
 		let gas_cost = Bls12381G2MultiExp::calculate_gas_cost(handle.input().len());
 		handle.record_cost(gas_cost)?;
 
-		let k = handle.input().len() / 288;
-		if handle.input().is_empty() || handle.input().len() % 288 != 0 {
-			return Err(PrecompileFailure::Error {
-				exit_status: ExitError::Other("invalid input length".into()),
-			});
-		}
-
-		let input = handle.input();
-
-		let mut points = Vec::new();
-		let mut scalars = Vec::new();
-		// Decode point scalar pairs
-		for idx in 0..k {
-			let offset = idx * 288;
-			// Decode G2 point
-			let p = decode_g2(input, offset)?;
-			// Decode scalar value
-			let scalar = decode_fr(input, offset + 256);
-			points.push(p.into_affine());
-			scalars.push(scalar);
-		}
-
-		// Compute r = e_0 * p_0 + e_1 * p_1 + ... + e_(k-1) * p_(k-1)
-		let r = G2Projective::msm(&points.to_vec(), &scalars.to_vec()).map_err(|_| {
-			PrecompileFailure::Error {
-				exit_status: ExitError::Other("MSM failed".into()),
-			}
-		})?;
-
-		// Encode the G2 point to 256 bytes output
-		let output = encode_g2(r.into_affine());
-		Ok(PrecompileOutput {
-			exit_status: ExitSucceed::Returned,
-			output: output.to_vec(),
-		})
+		g2_msm(handle.input(), false)
 	}
 }
 
@@ -539,45 +668,7 @@ impl Precompile for Bls12381Pairing {
 
 		handle.record_cost(gas_cost)?;
 
-		let input = handle.input();
-
-		let mut a = Vec::new();
-		let mut b = Vec::new();
-		// Decode G1 G2 pairs
-		for idx in 0..k {
-			let offset = idx * 384;
-			// Decode G1 point
-			let g1 = decode_g1(input, offset)?;
-			// Decode G2 point
-			let g2 = decode_g2(input, offset + 128)?;
-
-			// 'point is on curve' check already done,
-			// Here we need to apply subgroup checks.
-			if !g1.into_affine().is_in_correct_subgroup_assuming_on_curve() {
-				return Err(PrecompileFailure::Error {
-					exit_status: ExitError::Other("g1 point is not on correct subgroup".into()),
-				});
-			}
-			if !g2.into_affine().is_in_correct_subgroup_assuming_on_curve() {
-				return Err(PrecompileFailure::Error {
-					exit_status: ExitError::Other("g2 point is not on correct subgroup".into()),
-				});
-			}
-
-			a.push(g1);
-			b.push(g2);
-		}
-
-		let mut output = [0u8; 32];
-		// Compute pairing and set the output
-		if Bls12_381::multi_pairing(a, b).is_zero() {
-			output[31] = 1;
-		}
-
-		Ok(PrecompileOutput {
-			exit_status: ExitSucceed::Returned,
-			output: output.to_vec(),
-		})
+		pairing_check(handle.input())
 	}
 }
 
@@ -595,28 +686,7 @@ impl Precompile for Bls12381MapG1 {
 	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
 		handle.record_cost(Bls12381MapG1::GAS_COST)?;
 
-		let input = handle.input();
-		if input.len() != 64 {
-			return Err(PrecompileFailure::Error {
-				exit_status: ExitError::Other("invalid input length".into()),
-			});
-		}
-
-		let fq = read_fq(input, 0)?;
-		let g1 = match map_to_curve_g1(fq) {
-			Ok(point) => point.clear_cofactor(),
-			Err(_) => {
-				return Err(PrecompileFailure::Error {
-					exit_status: ExitError::Other("map to curve failed".into()),
-				})
-			}
-		};
-
-		let output = encode_g1(g1);
-		Ok(PrecompileOutput {
-			exit_status: ExitSucceed::Returned,
-			output: output.to_vec(),
-		})
+		map_fp_to_g1(handle.input())
 	}
 }
 
@@ -634,30 +704,11 @@ impl Precompile for Bls12381MapG2 {
 	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
 		handle.record_cost(Bls12381MapG2::GAS_COST)?;
 
-		let input = handle.input();
-		if input.len() != 128 {
-			return Err(PrecompileFailure::Error {
-				exit_status: ExitError::Other("invalid input length".into()),
-			});
-		}
-
-		let fq2 = read_fq2(input, 0)?;
-		let g2 = match map_to_curve_g2(fq2) {
-			Ok(point) => point.clear_cofactor(),
-			Err(_) => {
-				return Err(PrecompileFailure::Error {
-					exit_status: ExitError::Other("map to curve failed".into()),
-				})
-			}
-		};
-
-		let output = encode_g2(g2);
-		Ok(PrecompileOutput {
-			exit_status: ExitSucceed::Returned,
-			output: output.to_vec(),
-		})
+		map_fp2_to_g2(handle.input())
 	}
 }
 
+pub mod prague;
+
 #[cfg(test)]
 mod tests;
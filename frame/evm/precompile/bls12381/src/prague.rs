@@ -0,0 +1,229 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! BLS12-381 precompiles as specified by the final EIP-2537, activated with the Prague hard fork.
+//!
+//! Compared to the earlier draft implemented at the crate root, the `G1Mul`/`G2Mul` calls are
+//! replaced by MSM calls handling the single pair case, gas costs and discount tables are
+//! repriced, and every point given to an MSM or to the pairing must be in the correct subgroup.
+//! The precompiles live at addresses `0x0b` to `0x11`, see [`addresses`].
+
+// Frontier
+use fp_evm::{ExitError, Precompile, PrecompileFailure, PrecompileHandle, PrecompileResult};
+
+use super::{
+	g1_add, g1_msm, g2_add, g2_msm, map_fp2_to_g2, map_fp_to_g1, msm_gas_cost, pairing_check,
+};
+
+/// Addresses of the precompiles, as assigned by EIP-2537.
+pub mod addresses {
+	pub const G1_ADD: u64 = 0x0b;
+	pub const G1_MSM: u64 = 0x0c;
+	pub const G2_ADD: u64 = 0x0d;
+	pub const G2_MSM: u64 = 0x0e;
+	pub const PAIRING: u64 = 0x0f;
+	pub const MAP_FP_TO_G1: u64 = 0x10;
+	pub const MAP_FP2_TO_G2: u64 = 0x11;
+}
+
+/// Gas discount table for BLS12-381 G1 multi scalar multiplication.
+const G1_MSM_DISCOUNT_TABLE: [u16; 128] = [
+	1000, 949, 848, 797, 764, 750, 738, 728, 719, 712, 705, 698, 692, 687, 682, 677, 673, 669, 665,
+	661, 658, 654, 651, 648, 645, 642, 640, 637, 635, 632, 630, 627, 625, 623, 621, 619, 617, 615,
+	613, 611, 609, 608, 606, 604, 603, 601, 599, 598, 596, 595, 593, 592, 591, 589, 588, 586, 585,
+	584, 582, 581, 580, 579, 577, 576, 575, 574, 573, 572, 570, 569, 568, 567, 566, 565, 564, 563,
+	562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 551, 550, 549, 548, 547, 547, 546, 545,
+	544, 543, 542, 541, 540, 540, 539, 538, 537, 536, 536, 535, 534, 533, 532, 532, 531, 530, 529,
+	528, 528, 527, 526, 525, 525, 524, 523, 522, 522, 521, 520, 520, 519,
+];
+
+/// Gas discount table for BLS12-381 G2 multi scalar multiplication.
+const G2_MSM_DISCOUNT_TABLE: [u16; 128] = [
+	1000, 1000, 923, 884, 855, 832, 812, 796, 782, 770, 759, 749, 740, 732, 724, 717, 711, 704,
+	699, 693, 688, 683, 679, 674, 670, 666, 663, 659, 655, 652, 649, 646, 643, 640, 637, 634, 632,
+	629, 627, 624, 622, 620, 618, 615, 613, 611, 609, 607, 606, 604, 602, 600, 598, 597, 595, 593,
+	592, 590, 589, 587, 586, 584, 583, 582, 580, 579, 578, 576, 575, 574, 573, 571, 570, 569, 568,
+	567, 566, 565, 563, 562, 561, 560, 559, 558, 557, 556, 555, 554, 553, 552, 552, 551, 550, 549,
+	548, 547, 546, 545, 545, 544, 543, 542, 541, 541, 540, 539, 538, 537, 537, 536, 535, 535, 534,
+	533, 532, 532, 531, 530, 530, 529, 528, 528, 527, 526, 526, 525, 524, 524,
+];
+
+/// Bls12381G1Add implements EIP-2537 G1ADD precompile.
+pub struct Bls12381G1Add;
+
+impl Bls12381G1Add {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const GAS_COST: u64 = 375;
+}
+
+impl Precompile for Bls12381G1Add {
+	/// Implements EIP-2537 G1ADD precompile.
+	/// > G1 addition call expects `256` bytes as an input that is interpreted as byte concatenation of two G1 points (`128` bytes each).
+	/// > Output is an encoding of addition operation result - single G1 point (`128` bytes).
+	/// > No subgroup check is performed.
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381G1Add::GAS_COST)?;
+
+		g1_add(handle.input())
+	}
+}
+
+/// Bls12381G1Msm implements EIP-2537 G1MSM precompile.
+pub struct Bls12381G1Msm;
+
+impl Bls12381G1Msm {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const MUL_GAS_COST: u64 = 12_000;
+
+	/// Returns the gas required to execute the pre-compiled contract.
+	fn calculate_gas_cost(input_len: usize) -> u64 {
+		// Calculate G1 point, scalar value pair length
+		let k = input_len / 160;
+		msm_gas_cost(k, Bls12381G1Msm::MUL_GAS_COST, &G1_MSM_DISCOUNT_TABLE)
+	}
+}
+
+impl Precompile for Bls12381G1Msm {
+	/// Implements EIP-2537 G1MSM precompile.
+	/// > G1 MSM call expects `160*k` (`k` being a positive integer) bytes as an input that is interpreted as byte concatenation of `k` slices each of them being a byte concatenation of encoding of G1 point (`128` bytes) and encoding of a scalar value (`32` bytes).
+	/// > Output is an encoding of MSM operation result - single G1 point (`128` bytes).
+	/// > Every point must be in the correct subgroup.
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let gas_cost = Bls12381G1Msm::calculate_gas_cost(handle.input().len());
+		handle.record_cost(gas_cost)?;
+
+		g1_msm(handle.input(), true)
+	}
+}
+
+/// Bls12381G2Add implements EIP-2537 G2ADD precompile.
+pub struct Bls12381G2Add;
+
+impl Bls12381G2Add {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const GAS_COST: u64 = 600;
+}
+
+impl Precompile for Bls12381G2Add {
+	/// Implements EIP-2537 G2ADD precompile.
+	/// > G2 addition call expects `512` bytes as an input that is interpreted as byte concatenation of two G2 points (`256` bytes each).
+	/// > Output is an encoding of addition operation result - single G2 point (`256` bytes).
+	/// > No subgroup check is performed.
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381G2Add::GAS_COST)?;
+
+		g2_add(handle.input())
+	}
+}
+
+/// Bls12381G2Msm implements EIP-2537 G2MSM precompile.
+pub struct Bls12381G2Msm;
+
+impl Bls12381G2Msm {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const MUL_GAS_COST: u64 = 22_500;
+
+	/// Returns the gas required to execute the pre-compiled contract.
+	fn calculate_gas_cost(input_len: usize) -> u64 {
+		// Calculate G2 point, scalar value pair length
+		let k = input_len / 288;
+		msm_gas_cost(k, Bls12381G2Msm::MUL_GAS_COST, &G2_MSM_DISCOUNT_TABLE)
+	}
+}
+
+impl Precompile for Bls12381G2Msm {
+	/// Implements EIP-2537 G2MSM precompile.
+	/// > G2 MSM call expects `288*k` (`k` being a positive integer) bytes as an input that is interpreted as byte concatenation of `k` slices each of them being a byte concatenation of encoding of G2 point (`256` bytes) and encoding of a scalar value (`32` bytes).
+	/// > Output is an encoding of MSM operation result - single G2 point (`256` bytes).
+	/// > Every point must be in the correct subgroup.
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let gas_cost = Bls12381G2Msm::calculate_gas_cost(handle.input().len());
+		handle.record_cost(gas_cost)?;
+
+		g2_msm(handle.input(), true)
+	}
+}
+
+/// Bls12381Pairing implements EIP-2537 PAIRING_CHECK precompile.
+pub struct Bls12381Pairing;
+
+impl Bls12381Pairing {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const BASE_GAS: u64 = 37_700;
+	const PER_PAIR_GAS: u64 = 32_600;
+}
+
+impl Precompile for Bls12381Pairing {
+	/// Implements EIP-2537 PAIRING_CHECK precompile.
+	/// > Pairing check call expects `384*k` (`k` being a positive integer) bytes as an inputs that is interpreted as byte concatenation of `k` slices. Each slice has the following structure:
+	/// > - `128` bytes of G1 point encoding
+	/// > - `256` bytes of G2 point encoding
+	/// > Output is a `32` bytes where last single byte is `0x01` if pairing result is equal to multiplicative identity in a pairing target field and `0x00` otherwise.
+	/// > Every point must be in the correct subgroup.
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		if handle.input().is_empty() || handle.input().len() % 384 != 0 {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("invalid input length".into()),
+			});
+		}
+
+		let k = handle.input().len() / 384;
+		let gas_cost: u64 = Bls12381Pairing::BASE_GAS + (k as u64 * Bls12381Pairing::PER_PAIR_GAS);
+
+		handle.record_cost(gas_cost)?;
+
+		pairing_check(handle.input())
+	}
+}
+
+/// Bls12381MapG1 implements EIP-2537 MAP_FP_TO_G1 precompile.
+pub struct Bls12381MapG1;
+
+impl Bls12381MapG1 {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const GAS_COST: u64 = 5_500;
+}
+
+impl Precompile for Bls12381MapG1 {
+	/// Implements EIP-2537 MAP_FP_TO_G1 precompile.
+	/// > Field-to-curve call expects `64` bytes as an input that is interpreted as an element of the base field.
+	/// > Output of this call is `128` bytes and is G1 point following respective encoding rules.
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381MapG1::GAS_COST)?;
+
+		map_fp_to_g1(handle.input())
+	}
+}
+
+/// Bls12381MapG2 implements EIP-2537 MAP_FP2_TO_G2 precompile.
+pub struct Bls12381MapG2;
+
+impl Bls12381MapG2 {
+	/// https://eips.ethereum.org/EIPS/eip-2537#gas-schedule
+	const GAS_COST: u64 = 23_800;
+}
+
+impl Precompile for Bls12381MapG2 {
+	/// Implements EIP-2537 MAP_FP2_TO_G2 precompile.
+	/// > Field-to-curve call expects `128` bytes as an input that is interpreted as an element of the quadratic extension field.
+	/// > Output of this call is `256` bytes and is G2 point following respective encoding rules.
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(Bls12381MapG2::GAS_COST)?;
+
+		map_fp2_to_g2(handle.input())
+	}
+}
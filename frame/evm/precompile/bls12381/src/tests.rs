@@ -53,3 +53,44 @@ fn process_consensus_failure_tests() -> Result<(), String> {
 	test_precompile_failure_test_vectors::<Bls12381MapG2>("../testdata/fail-bls12381MapG2.json")?;
 	Ok(())
 }
+
+#[test]
+fn process_prague_consensus_tests() -> Result<(), String> {
+	test_precompile_test_vectors::<prague::Bls12381G1Add>("../testdata/bls12381PragueG1Add.json")?;
+	test_precompile_test_vectors::<prague::Bls12381G1Msm>("../testdata/bls12381PragueG1Msm.json")?;
+	test_precompile_test_vectors::<prague::Bls12381G2Add>("../testdata/bls12381PragueG2Add.json")?;
+	test_precompile_test_vectors::<prague::Bls12381G2Msm>("../testdata/bls12381PragueG2Msm.json")?;
+	test_precompile_test_vectors::<prague::Bls12381Pairing>(
+		"../testdata/bls12381PraguePairing.json",
+	)?;
+	test_precompile_test_vectors::<prague::Bls12381MapG1>("../testdata/bls12381PragueMapG1.json")?;
+	test_precompile_test_vectors::<prague::Bls12381MapG2>("../testdata/bls12381PragueMapG2.json")?;
+	Ok(())
+}
+
+#[test]
+fn process_prague_consensus_failure_tests() -> Result<(), String> {
+	// Inputs rejected by the draft are rejected the same way by the final specification.
+	test_precompile_failure_test_vectors::<prague::Bls12381G1Add>(
+		"../testdata/fail-bls12381G1Add.json",
+	)?;
+	test_precompile_failure_test_vectors::<prague::Bls12381G1Msm>(
+		"../testdata/fail-bls12381PragueG1Msm.json",
+	)?;
+	test_precompile_failure_test_vectors::<prague::Bls12381G2Add>(
+		"../testdata/fail-bls12381G2Add.json",
+	)?;
+	test_precompile_failure_test_vectors::<prague::Bls12381G2Msm>(
+		"../testdata/fail-bls12381PragueG2Msm.json",
+	)?;
+	test_precompile_failure_test_vectors::<prague::Bls12381Pairing>(
+		"../testdata/fail-bls12381Pairing.json",
+	)?;
+	test_precompile_failure_test_vectors::<prague::Bls12381MapG1>(
+		"../testdata/fail-bls12381MapG1.json",
+	)?;
+	test_precompile_failure_test_vectors::<prague::Bls12381MapG2>(
+		"../testdata/fail-bls12381MapG2.json",
+	)?;
+	Ok(())
+}
@@ -75,6 +75,34 @@ fn read_point(input: &[u8], start_inx: usize) -> Result<bn::G1, PrecompileFailur
 	})
 }
 
+fn read_g2_point(input: &[u8], start_inx: usize) -> Result<bn::G2, PrecompileFailure> {
+	use bn::{AffineG2, Fq, Fq2, Group, G2};
+
+	// Coordinates are encoded as (imaginary, real) pairs
+	let mut buf = [[0u8; 32]; 4];
+	for (i, b) in buf.iter_mut().enumerate() {
+		read_input(input, b, start_inx + i * 32);
+	}
+	let [x_im, x_re, y_im, y_re] = buf.map(|b| {
+		Fq::from_slice(&b).map_err(|_| PrecompileFailure::Error {
+			exit_status: ExitError::Other("Invalid G2 point coordinate".into()),
+		})
+	});
+
+	let x = Fq2::new(x_re?, x_im?);
+	let y = Fq2::new(y_re?, y_im?);
+
+	Ok(if x.is_zero() && y.is_zero() {
+		G2::zero()
+	} else {
+		AffineG2::new(x, y)
+			.map_err(|_| PrecompileFailure::Error {
+				exit_status: ExitError::Other("Invalid G2 point - not on curve".into()),
+			})?
+			.into()
+	})
+}
+
 /// The Bn128Add builtin
 pub struct Bn128Add;
 
@@ -292,10 +320,91 @@ impl Precompile for Bn128Pairing {
 	}
 }
 
+/// The Bn128Groth16Verify builtin
+///
+/// Verifies a Groth16 proof against a verifying key, both given in the input as
+/// `alpha | beta | gamma | delta | IC[0..=n] | A | B | C | inputs[0..n]`, where `alpha`, `IC`,
+/// `A` and `C` are G1 points encoded as for `Bn128Add`, `beta`, `gamma`, `delta` and `B` are G2
+/// points encoded as for `Bn128Pairing`, and the `n` public inputs are scalar field elements.
+/// Returns `1` if the proof is valid and `0` otherwise.
+pub struct Bn128Groth16Verify;
+
+impl Bn128Groth16Verify {
+	/// Length of the verifying key and proof for a circuit without public inputs.
+	const BASE_INPUT_LEN: usize = 768;
+	/// Length added to the input by each public input (its `IC` point and its value).
+	const PER_PUBLIC_INPUT_LEN: usize = 96;
+
+	/// The verification is a product of 4 pairings, plus one multiplication and addition on
+	/// G1 per public input.
+	fn gas_cost(public_inputs: usize) -> u64 {
+		Bn128Pairing::BASE_GAS_COST
+			+ 4 * Bn128Pairing::GAS_COST_PER_PAIRING
+			+ public_inputs as u64 * (Bn128Mul::GAS_COST + Bn128Add::GAS_COST)
+	}
+}
+
+impl Precompile for Bn128Groth16Verify {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		use bn::{pairing_batch, Gt};
+
+		let len = handle.input().len();
+		if len < Self::BASE_INPUT_LEN
+			|| (len - Self::BASE_INPUT_LEN) % Self::PER_PUBLIC_INPUT_LEN > 0
+		{
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("bad groth16 input size".into()),
+			});
+		}
+		let public_inputs = (len - Self::BASE_INPUT_LEN) / Self::PER_PUBLIC_INPUT_LEN;
+
+		handle.record_cost(Self::gas_cost(public_inputs))?;
+
+		let input = handle.input();
+
+		// Verifying key
+		let alpha = read_point(input, 0)?;
+		let beta = read_g2_point(input, 64)?;
+		let gamma = read_g2_point(input, 192)?;
+		let delta = read_g2_point(input, 320)?;
+		let ic_offset = 448;
+
+		// Proof
+		let proof_offset = ic_offset + (public_inputs + 1) * 64;
+		let a = read_point(input, proof_offset)?;
+		let b = read_g2_point(input, proof_offset + 64)?;
+		let c = read_point(input, proof_offset + 192)?;
+
+		// vk_x = IC[0] + inputs[0] * IC[1] + ... + inputs[n - 1] * IC[n]
+		let inputs_offset = proof_offset + 256;
+		let mut vk_x = read_point(input, ic_offset)?;
+		for idx in 0..public_inputs {
+			let ic = read_point(input, ic_offset + (idx + 1) * 64)?;
+			let fr = read_fr(input, inputs_offset + idx * 32)?;
+			vk_x = vk_x + ic * fr;
+		}
+
+		// e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1
+		let mul = pairing_batch(&[(-a, b), (alpha, beta), (vk_x, gamma), (c, delta)]);
+
+		let mut buf = [0u8; 32];
+		if mul == Gt::one() {
+			buf[31] = 1;
+		}
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output: buf.to_vec(),
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use pallet_evm_test_vector_support::test_precompile_test_vectors;
+	use pallet_evm_test_vector_support::{
+		test_precompile_failure_test_vectors, test_precompile_test_vectors,
+	};
 
 	#[test]
 	fn process_consensus_tests_for_add() -> Result<(), String> {
@@ -314,4 +423,18 @@ mod tests {
 		test_precompile_test_vectors::<Bn128Pairing>("../testdata/common_bnpair.json")?;
 		Ok(())
 	}
+
+	#[test]
+	fn process_consensus_tests_for_groth16() -> Result<(), String> {
+		test_precompile_test_vectors::<Bn128Groth16Verify>("../testdata/groth16Verify.json")?;
+		Ok(())
+	}
+
+	#[test]
+	fn process_consensus_failure_tests_for_groth16() -> Result<(), String> {
+		test_precompile_failure_test_vectors::<Bn128Groth16Verify>(
+			"../testdata/fail-groth16Verify.json",
+		)?;
+		Ok(())
+	}
 }
@@ -0,0 +1,258 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Call permit precompile. It dispatches a call on behalf of an account that signed an EIP-712
+//! `CallPermit` message, so that a relayer can pay for the gas of the call. Each signer has a
+//! nonce, incremented on each dispatched permit, preventing permits from being replayed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use fp_evm::{Context, ExitReason, ExitRevert, PrecompileFailure, Transfer};
+use frame_support::{
+	storage::types::{StorageMap, ValueQuery},
+	traits::{Get, StorageInstance, Time},
+	Blake2_128Concat,
+};
+use precompile_utils::{evm::costs::call_cost, prelude::*, EvmResult};
+use sp_core::{ConstU32, H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::traits::UniqueSaturatedInto;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub const CALL_DATA_LIMIT: u32 = 2u32.pow(16);
+
+type GetCallDataLimit = ConstU32<CALL_DATA_LIMIT>;
+
+/// EIP-712 type hash of a call permit.
+pub const PERMIT_TYPEHASH: [u8; 32] = keccak256!(
+	"CallPermit(address from,address to,uint256 value,bytes data,uint64 gaslimit,uint256 nonce,uint256 deadline)"
+);
+
+/// EIP-712 type hash of the domain of the call permits.
+pub const PERMIT_DOMAIN: [u8; 32] = keccak256!(
+	"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+);
+
+/// Proof size of a nonce: Blake2_128(16) + H160(20) + U256(32)
+pub const NONCE_PROOF_SIZE: usize = 68;
+
+/// Proof size of the timestamp: Moment(8)
+pub const TIMESTAMP_PROOF_SIZE: usize = 8;
+
+/// Gas cost of the signature recovery, which is the cost of the ecrecover precompile.
+const ECRECOVER_GAS_COST: u64 = 3000;
+
+/// Storage prefix for nonces.
+pub struct NoncesPrefix;
+
+impl StorageInstance for NoncesPrefix {
+	const STORAGE_PREFIX: &'static str = "Nonces";
+
+	fn pallet_prefix() -> &'static str {
+		"PrecompileCallPermit"
+	}
+}
+
+/// Call permit nonces, by signer.
+pub type NoncesStorage = StorageMap<NoncesPrefix, Blake2_128Concat, H160, U256, ValueQuery>;
+
+/// Precompile dispatching calls signed as EIP-712 call permits.
+///
+/// It must be registered with subcalls allowed (`SubcallWithMaxNesting`).
+#[derive(Debug, Clone)]
+pub struct CallPermitPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> CallPermitPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config,
+{
+	/// The EIP-712 domain separator of the precompile at `address`.
+	pub fn compute_domain_separator(address: H160) -> [u8; 32] {
+		let name: H256 = keccak256!("Call Permit Precompile").into();
+		let version: H256 = keccak256!("1").into();
+		let chain_id: U256 = Runtime::ChainId::get().into();
+
+		let domain_separator_inner = solidity::encode_arguments((
+			H256::from(PERMIT_DOMAIN),
+			name,
+			version,
+			chain_id,
+			Address(address),
+		));
+
+		keccak_256(&domain_separator_inner)
+	}
+
+	/// The EIP-712 digest signed by `from` to allow the call.
+	#[allow(clippy::too_many_arguments)]
+	pub fn generate_permit(
+		address: H160,
+		from: H160,
+		to: H160,
+		value: U256,
+		data: &[u8],
+		gas_limit: u64,
+		nonce: U256,
+		deadline: U256,
+	) -> [u8; 32] {
+		let domain_separator = Self::compute_domain_separator(address);
+
+		let permit_content = solidity::encode_arguments((
+			H256::from(PERMIT_TYPEHASH),
+			Address(from),
+			Address(to),
+			value,
+			// Dynamic values are encoded as the hash of their content.
+			H256::from(keccak_256(data)),
+			gas_limit,
+			nonce,
+			deadline,
+		));
+		let permit_content = keccak_256(&permit_content);
+
+		let mut pre_digest = Vec::with_capacity(2 + 32 + 32);
+		pre_digest.extend_from_slice(b"\x19\x01");
+		pre_digest.extend_from_slice(&domain_separator);
+		pre_digest.extend_from_slice(&permit_content);
+		keccak_256(&pre_digest)
+	}
+
+	/// Cost of a dispatch, on top of the subcall: signature recovery and nonce write.
+	pub fn dispatch_inherent_cost() -> u64 {
+		ECRECOVER_GAS_COST.saturating_add(RuntimeHelper::<Runtime>::db_write_gas_cost())
+	}
+
+	/// Dispatch the call signed by `from`, which becomes the caller of the call. Returns the
+	/// output of the call, or reverts with it if the call reverts.
+	#[precompile::public(
+		"dispatch(address,address,uint256,bytes,uint64,uint256,uint8,bytes32,bytes32)"
+	)]
+	#[allow(clippy::too_many_arguments)]
+	fn dispatch(
+		handle: &mut impl PrecompileHandle,
+		from: Address,
+		to: Address,
+		value: U256,
+		data: BoundedBytes<GetCallDataLimit>,
+		gas_limit: u64,
+		deadline: U256,
+		v: u8,
+		r: H256,
+		s: H256,
+	) -> EvmResult<UnboundedBytes> {
+		handle.record_db_read::<Runtime>(TIMESTAMP_PROOF_SIZE)?;
+		handle.record_db_read::<Runtime>(NONCE_PROOF_SIZE)?;
+		handle.record_cost(Self::dispatch_inherent_cost())?;
+
+		let from: H160 = from.into();
+		let to: H160 = to.into();
+		let data: Vec<u8> = data.into();
+
+		// The gas limit signed by `from` must be available for the call, along with the cost of
+		// the call itself which `handle.call` records as the CALL opcode would.
+		let call_cost = call_cost(value, pallet_evm::Pallet::<Runtime>::evm_config());
+		let total_cost = gas_limit
+			.checked_add(call_cost)
+			.ok_or_else(|| revert("Call require too much gas (uint64 overflow)"))?;
+		if total_cost > handle.remaining_gas() {
+			return Err(revert("Gaslimit is too low to dispatch provided call"));
+		}
+
+		// Deadline is in seconds, while the timestamp is in milliseconds.
+		let now: u128 = <Runtime as pallet_evm::Config>::Timestamp::now().unique_saturated_into();
+		if deadline < U256::from(now / 1000) {
+			return Err(revert("Permit expired"));
+		}
+
+		let nonce = NoncesStorage::get(from);
+		let permit = Self::generate_permit(
+			handle.context().address,
+			from,
+			to,
+			value,
+			&data,
+			gas_limit,
+			nonce,
+			deadline,
+		);
+
+		let mut sig = [0u8; 65];
+		sig[0..32].copy_from_slice(r.as_bytes());
+		sig[32..64].copy_from_slice(s.as_bytes());
+		sig[64] = v;
+
+		let signer = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &permit)
+			.map_err(|_| revert("Invalid permit"))?;
+		let signer = H160::from(H256::from(keccak_256(&signer)));
+
+		if signer != from {
+			return Err(revert("Invalid permit"));
+		}
+
+		NoncesStorage::insert(from, nonce + U256::one());
+
+		let sub_context = Context {
+			caller: from,
+			address: to,
+			apparent_value: value,
+		};
+		let transfer = if value.is_zero() {
+			None
+		} else {
+			Some(Transfer {
+				source: from,
+				target: to,
+				value,
+			})
+		};
+
+		let (reason, output) =
+			handle.call(to, transfer, data, Some(gas_limit), false, &sub_context);
+
+		match reason {
+			ExitReason::Error(exit_status) => Err(PrecompileFailure::Error { exit_status }),
+			ExitReason::Fatal(exit_status) => Err(PrecompileFailure::Fatal { exit_status }),
+			ExitReason::Revert(_) => Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output,
+			}),
+			ExitReason::Succeed(_) => Ok(output.into()),
+		}
+	}
+
+	#[precompile::public("nonces(address)")]
+	#[precompile::view]
+	fn nonces(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(NONCE_PROOF_SIZE)?;
+
+		Ok(NoncesStorage::get(H160::from(owner)))
+	}
+
+	#[precompile::public("DOMAIN_SEPARATOR()")]
+	#[precompile::view]
+	fn domain_separator(handle: &mut impl PrecompileHandle) -> EvmResult<H256> {
+		Ok(Self::compute_domain_separator(handle.context().address).into())
+	}
+}
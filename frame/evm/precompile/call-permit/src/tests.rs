@@ -0,0 +1,252 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{mock::*, CallPermitPrecompile};
+use fp_evm::ExitError;
+use precompile_utils::{evm::costs::call_cost, prelude::*, testing::*};
+use sp_core::{H160, H256, U256};
+
+fn precompiles() -> Precompiles<Runtime> {
+	PrecompilesValue::get()
+}
+
+// Sign a call permit with the key of `CryptoAlith`, returning `(v, r, s)`.
+fn sign_permit(
+	to: H160,
+	value: U256,
+	data: &[u8],
+	gas_limit: u64,
+	nonce: U256,
+	deadline: U256,
+) -> (u8, H256, H256) {
+	let permit = CallPermitPrecompile::<Runtime>::generate_permit(
+		Precompile1.into(),
+		CryptoAlith.into(),
+		to,
+		value,
+		data,
+		gas_limit,
+		nonce,
+		deadline,
+	);
+	let secret = libsecp256k1::SecretKey::parse(&alith_secret_key()).unwrap();
+	let (signature, recovery_id) =
+		libsecp256k1::sign(&libsecp256k1::Message::parse(&permit), &secret);
+	let signature = signature.serialize();
+
+	(
+		recovery_id.serialize() + 27,
+		H256::from_slice(&signature[..32]),
+		H256::from_slice(&signature[32..]),
+	)
+}
+
+fn dispatch_call(value: U256, gas_limit: u64, deadline: U256) -> PCall {
+	let (v, r, s) = sign_permit(
+		Bob.into(),
+		value,
+		b"data",
+		gas_limit,
+		U256::zero(),
+		deadline,
+	);
+
+	PCall::dispatch {
+		from: Address(CryptoAlith.into()),
+		to: Address(Bob.into()),
+		value,
+		data: b"data".as_slice().into(),
+		gas_limit,
+		deadline,
+		v,
+		r,
+		s,
+	}
+}
+
+#[test]
+fn dispatch_calls_on_behalf_of_signer() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				dispatch_call(U256::from(42), 100_000, U256::from(10)),
+			)
+			.with_subcall_handle(|subcall| {
+				assert_eq!(subcall.address, Bob.into());
+				assert_eq!(subcall.context.caller, CryptoAlith.into());
+				assert_eq!(subcall.context.apparent_value, U256::from(42));
+				let transfer = subcall.transfer.expect("value is transferred");
+				assert_eq!(transfer.source, CryptoAlith.into());
+				assert_eq!(transfer.value, U256::from(42));
+				assert_eq!(subcall.input, b"data");
+				assert_eq!(subcall.target_gas, Some(100_000));
+
+				SubcallOutput {
+					output: b"output".to_vec(),
+					cost: 1_000,
+					..SubcallOutput::succeed()
+				}
+			})
+			// The call itself is charged once, along with the gas used by the subcall.
+			.expect_cost(
+				2 * RuntimeHelper::<Runtime>::db_read_gas_cost()
+					+ CallPermitPrecompile::<Runtime>::dispatch_inherent_cost()
+					+ call_cost(U256::from(42), &fp_evm::Config::london())
+					+ 1_000,
+			)
+			.execute_returns(UnboundedBytes::from(b"output".as_slice()));
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				PCall::nonces {
+					owner: Address(CryptoAlith.into()),
+				},
+			)
+			.execute_returns(U256::one());
+
+		// The nonce has been consumed.
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				dispatch_call(U256::from(42), 100_000, U256::from(10)),
+			)
+			.with_subcall_handle(|_| panic!("there should be no subcall"))
+			.execute_reverts(|output| output == b"Invalid permit");
+	})
+}
+
+#[test]
+fn dispatch_forwards_revert() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				dispatch_call(U256::from(0), 100_000, U256::from(10)),
+			)
+			.with_subcall_handle(|subcall| {
+				assert!(subcall.transfer.is_none());
+				SubcallOutput {
+					output: solidity::revert::revert_as_bytes("subcall reverted"),
+					..SubcallOutput::revert()
+				}
+			})
+			.execute_reverts(|output| output == b"subcall reverted");
+	})
+}
+
+#[test]
+fn dispatch_rejects_expired_permit() {
+	ExtBuilder::default().build().execute_with(|| {
+		pallet_timestamp::Pallet::<Runtime>::set_timestamp(20_000);
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				dispatch_call(U256::from(0), 100_000, U256::from(10)),
+			)
+			.with_subcall_handle(|_| panic!("there should be no subcall"))
+			.execute_reverts(|output| output == b"Permit expired");
+	})
+}
+
+#[test]
+fn dispatch_rejects_permit_of_another_signer() {
+	ExtBuilder::default().build().execute_with(|| {
+		let PCall::dispatch {
+			to,
+			value,
+			data,
+			gas_limit,
+			deadline,
+			v,
+			r,
+			s,
+			..
+		} = dispatch_call(U256::from(0), 100_000, U256::from(10))
+		else {
+			unreachable!()
+		};
+
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				PCall::dispatch {
+					from: Address(Alice.into()),
+					to,
+					value,
+					data,
+					gas_limit,
+					deadline,
+					v,
+					r,
+					s,
+				},
+			)
+			.with_subcall_handle(|_| panic!("there should be no subcall"))
+			.execute_reverts(|output| output == b"Invalid permit");
+	})
+}
+
+#[test]
+fn dispatch_requires_signed_gas_limit() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				dispatch_call(U256::from(0), 100_000, U256::from(10)),
+			)
+			.with_target_gas(Some(50_000))
+			.with_subcall_handle(|_| panic!("there should be no subcall"))
+			.execute_reverts(|output| output == b"Gaslimit is too low to dispatch provided call");
+
+		// Not even enough gas to verify the permit.
+		precompiles()
+			.prepare_test(
+				Charlie,
+				Precompile1,
+				dispatch_call(U256::from(0), 100_000, U256::from(10)),
+			)
+			.with_target_gas(Some(1_000))
+			.with_subcall_handle(|_| panic!("there should be no subcall"))
+			.execute_error(ExitError::OutOfGas);
+	})
+}
+
+#[test]
+fn domain_separator_depends_on_address() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(Charlie, Precompile1, PCall::domain_separator {})
+			.execute_returns(H256::from(
+				CallPermitPrecompile::<Runtime>::compute_domain_separator(Precompile1.into()),
+			));
+
+		assert_ne!(
+			CallPermitPrecompile::<Runtime>::compute_domain_separator(Precompile1.into()),
+			CallPermitPrecompile::<Runtime>::compute_domain_separator(Bob.into()),
+		);
+	})
+}
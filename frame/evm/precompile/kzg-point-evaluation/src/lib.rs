@@ -0,0 +1,124 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+
+use fp_evm::{ExitError, ExitSucceed, LinearCostPrecompile, PrecompileFailure};
+use kzg_rs::{Bytes32, Bytes48, KzgProof, KzgSettings};
+use once_cell::race::OnceBox;
+
+/// Version byte prefixing the hash of a KZG commitment.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// `U256(FIELD_ELEMENTS_PER_BLOB) ++ U256(BLS_MODULUS)`, returned on successful verification.
+pub const RETURN_VALUE: [u8; 64] = [
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00,
+	0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+	0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+
+/// Ethereum trusted setup, loaded on first use.
+static KZG_SETTINGS: OnceBox<KzgSettings> = OnceBox::new();
+
+/// The point evaluation precompile, as specified by EIP-4844.
+///
+/// Input is `versioned_hash (32) | z (32) | y (32) | commitment (48) | proof (48)`. It verifies
+/// that the polynomial committed to evaluates to `y` at `z`, using the Ethereum trusted setup.
+pub struct KzgPointEvaluation;
+
+impl LinearCostPrecompile for KzgPointEvaluation {
+	const BASE: u64 = 50000;
+	const WORD: u64 = 0;
+
+	fn execute(input: &[u8], _: u64) -> Result<(ExitSucceed, Vec<u8>), PrecompileFailure> {
+		if input.len() != 192 {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("invalid input length".into()),
+			});
+		}
+
+		let versioned_hash = &input[0..32];
+		let commitment = &input[96..144];
+		if versioned_hash != kzg_to_versioned_hash(commitment) {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("mismatched versioned hash".into()),
+			});
+		}
+
+		if !verify_kzg_proof(commitment, &input[32..64], &input[64..96], &input[144..192]) {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("error verifying kzg proof".into()),
+			});
+		}
+
+		Ok((ExitSucceed::Returned, RETURN_VALUE.to_vec()))
+	}
+}
+
+fn kzg_to_versioned_hash(commitment: &[u8]) -> [u8; 32] {
+	let mut hash = sp_io::hashing::sha2_256(commitment);
+	hash[0] = VERSIONED_HASH_VERSION_KZG;
+	hash
+}
+
+fn verify_kzg_proof(commitment: &[u8], z: &[u8], y: &[u8], proof: &[u8]) -> bool {
+	// Non-canonical field elements and invalid points are reported as errors, which we
+	// treat the same way as a proof that does not verify.
+	let (Ok(commitment), Ok(z), Ok(y), Ok(proof)) = (
+		Bytes48::from_slice(commitment),
+		Bytes32::from_slice(z),
+		Bytes32::from_slice(y),
+		Bytes48::from_slice(proof),
+	) else {
+		return false;
+	};
+	let Ok(settings) =
+		KZG_SETTINGS.get_or_try_init(|| KzgSettings::load_trusted_setup_file().map(Box::new))
+	else {
+		return false;
+	};
+
+	KzgProof::verify_kzg_proof(&commitment, &z, &y, &proof, settings).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_evm_test_vector_support::{
+		test_precompile_failure_test_vectors, test_precompile_test_vectors,
+	};
+
+	#[test]
+	fn process_consensus_tests() -> Result<(), String> {
+		test_precompile_test_vectors::<KzgPointEvaluation>("../testdata/pointEvaluation.json")?;
+		Ok(())
+	}
+
+	#[test]
+	fn process_consensus_failure_tests() -> Result<(), String> {
+		test_precompile_failure_test_vectors::<KzgPointEvaluation>(
+			"../testdata/fail-pointEvaluation.json",
+		)?;
+		Ok(())
+	}
+}
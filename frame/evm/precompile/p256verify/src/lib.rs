@@ -0,0 +1,87 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use fp_evm::{ExitSucceed, LinearCostPrecompile, PrecompileFailure};
+use p256::{
+	ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey},
+	EncodedPoint, FieldBytes,
+};
+
+/// secp256r1 (P-256) signature verification, as specified by RIP-7212.
+///
+/// Input is `hash (32) | r (32) | s (32) | x (32) | y (32)`. On success, the output is
+/// 32 bytes encoding `1`; on any failure (including malformed input) the output is empty.
+pub struct P256Verify;
+
+impl LinearCostPrecompile for P256Verify {
+	const BASE: u64 = 3450;
+	const WORD: u64 = 0;
+
+	fn execute(input: &[u8], _: u64) -> Result<(ExitSucceed, Vec<u8>), PrecompileFailure> {
+		let output = if verify(input) {
+			let mut buf = [0u8; 32];
+			buf[31] = 1;
+			buf.to_vec()
+		} else {
+			Vec::new()
+		};
+
+		Ok((ExitSucceed::Returned, output))
+	}
+}
+
+fn verify(input: &[u8]) -> bool {
+	if input.len() != 160 {
+		return false;
+	}
+
+	let hash = &input[0..32];
+	// Rejects `r` or `s` equal to zero or not lower than the curve order.
+	let Ok(signature) = Signature::from_slice(&input[32..96]) else {
+		return false;
+	};
+	// Rejects coordinates outside the field, points not on the curve and the identity.
+	let point = EncodedPoint::from_affine_coordinates(
+		FieldBytes::from_slice(&input[96..128]),
+		FieldBytes::from_slice(&input[128..160]),
+		false,
+	);
+	let Ok(public_key) = VerifyingKey::from_encoded_point(&point) else {
+		return false;
+	};
+
+	public_key.verify_prehash(hash, &signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_evm_test_vector_support::test_precompile_test_vectors;
+
+	#[test]
+	fn process_consensus_tests() -> Result<(), String> {
+		test_precompile_test_vectors::<P256Verify>("../testdata/p256Verify.json")?;
+		Ok(())
+	}
+}
@@ -0,0 +1,144 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+mod constants;
+
+use alloc::vec::Vec;
+use bn::Fr;
+use fp_evm::{ExitError, ExitSucceed, LinearCostPrecompile, PrecompileFailure};
+
+use crate::constants::*;
+
+/// Number of full rounds, split evenly before and after the partial rounds.
+const FULL_ROUNDS: usize = 8;
+
+/// Maximum number of inputs, the state width being the number of inputs plus one.
+pub const MAX_INPUTS: usize = 4;
+
+/// The Poseidon hash over the BN254 scalar field, compatible with circomlib's `Poseidon(n)`.
+///
+/// Input is between `1` and [`MAX_INPUTS`] field elements of `32` bytes each, encoded in
+/// big-endian. The width of the permutation is chosen from the number of inputs and the output
+/// is the first element of the resulting state.
+pub struct Poseidon;
+
+impl LinearCostPrecompile for Poseidon {
+	const BASE: u64 = 3_000;
+	const WORD: u64 = 1_000;
+
+	fn execute(input: &[u8], _: u64) -> Result<(ExitSucceed, Vec<u8>), PrecompileFailure> {
+		if input.is_empty() || input.len() % 32 != 0 || input.len() / 32 > MAX_INPUTS {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("invalid input length".into()),
+			});
+		}
+
+		let inputs = input
+			.chunks(32)
+			.map(Fr::from_slice)
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(|_| PrecompileFailure::Error {
+				exit_status: ExitError::Other("Invalid field element".into()),
+			})?;
+
+		let hash = match inputs.len() {
+			1 => permute(&inputs, &C_2, &M_2, 56),
+			2 => permute(&inputs, &C_3, &M_3, 57),
+			3 => permute(&inputs, &C_4, &M_4, 56),
+			_ => permute(&inputs, &C_5, &M_5, 60),
+		};
+
+		let mut buf = [0u8; 32];
+		hash.to_big_endian(&mut buf)
+			.map_err(|_| PrecompileFailure::Error {
+				exit_status: ExitError::Other("Cannot fail since buf is 32-byte length".into()),
+			})?;
+
+		Ok((ExitSucceed::Returned, buf.to_vec()))
+	}
+}
+
+/// Run the permutation over `[0, inputs..]` and return the first element of the state.
+fn permute<const T: usize>(
+	inputs: &[Fr],
+	round_constants: &[[u8; 32]],
+	mds: &[[[u8; 32]; T]; T],
+	partial_rounds: usize,
+) -> Fr {
+	// Constants are generated below the field modulus.
+	let fr = |bytes: &[u8; 32]| Fr::from_slice(bytes).unwrap_or_else(|_| Fr::zero());
+	let mds = mds.map(|row| row.map(|x| fr(&x)));
+
+	let mut state = [Fr::zero(); T];
+	state[1..].copy_from_slice(inputs);
+
+	for round in 0..FULL_ROUNDS + partial_rounds {
+		for (i, x) in state.iter_mut().enumerate() {
+			*x = *x + fr(&round_constants[round * T + i]);
+		}
+
+		let half = FULL_ROUNDS / 2;
+		if round < half || round >= half + partial_rounds {
+			for x in state.iter_mut() {
+				*x = sbox(*x);
+			}
+		} else {
+			state[0] = sbox(state[0]);
+		}
+
+		let mut mixed = [Fr::zero(); T];
+		for (i, out) in mixed.iter_mut().enumerate() {
+			for (m, x) in mds[i].iter().zip(state.iter()) {
+				*out = *out + *m * *x;
+			}
+		}
+		state = mixed;
+	}
+
+	state[0]
+}
+
+/// The `x^5` S-box.
+fn sbox(x: Fr) -> Fr {
+	let x2 = x * x;
+	x2 * x2 * x
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_evm_test_vector_support::{
+		test_precompile_failure_test_vectors, test_precompile_test_vectors,
+	};
+
+	#[test]
+	fn process_consensus_tests() -> Result<(), String> {
+		test_precompile_test_vectors::<Poseidon>("../testdata/poseidon.json")?;
+		Ok(())
+	}
+
+	#[test]
+	fn process_consensus_failure_tests() -> Result<(), String> {
+		test_precompile_failure_test_vectors::<Poseidon>("../testdata/fail-poseidon.json")?;
+		Ok(())
+	}
+}
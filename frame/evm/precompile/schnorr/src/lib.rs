@@ -0,0 +1,87 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use fp_evm::{ExitError, ExitSucceed, LinearCostPrecompile, PrecompileFailure};
+use k256::schnorr::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey};
+
+/// BIP-340 Schnorr signature verification over secp256k1, as used by Bitcoin Taproot.
+pub struct SchnorrVerify;
+
+impl LinearCostPrecompile for SchnorrVerify {
+	const BASE: u64 = 3000;
+	const WORD: u64 = 0;
+
+	fn execute(input: &[u8], _: u64) -> Result<(ExitSucceed, Vec<u8>), PrecompileFailure> {
+		if input.len() < 128 {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("input must contain 128 bytes".into()),
+			});
+		};
+
+		let mut i = [0u8; 128];
+		i[..128].copy_from_slice(&input[..128]);
+
+		let mut buf = [0u8; 4];
+
+		let msg = &i[0..32];
+		// x-only public key
+		let pk = VerifyingKey::from_bytes(&i[32..64]).map_err(|_| PrecompileFailure::Error {
+			exit_status: ExitError::Other("Public key recover failed".into()),
+		})?;
+		let sig = Signature::try_from(&i[64..128]).map_err(|_| PrecompileFailure::Error {
+			exit_status: ExitError::Other("Signature recover failed".into()),
+		})?;
+
+		// BIP-340 signs 32 byte messages as is, without hashing them first.
+		if pk.verify_prehash(msg, &sig).is_ok() {
+			buf[3] = 0u8;
+		} else {
+			buf[3] = 1u8;
+		};
+
+		Ok((ExitSucceed::Returned, buf.to_vec()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_evm_test_vector_support::{
+		test_precompile_failure_test_vectors, test_precompile_test_vectors,
+	};
+
+	#[test]
+	fn process_consensus_tests() -> Result<(), String> {
+		test_precompile_test_vectors::<SchnorrVerify>("../testdata/schnorrVerify.json")?;
+		Ok(())
+	}
+
+	#[test]
+	fn process_consensus_failure_tests() -> Result<(), String> {
+		test_precompile_failure_test_vectors::<SchnorrVerify>(
+			"../testdata/fail-schnorrVerify.json",
+		)?;
+		Ok(())
+	}
+}
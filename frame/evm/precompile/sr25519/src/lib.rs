@@ -0,0 +1,131 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use fp_evm::{ExitError, ExitSucceed, LinearCostPrecompile, PrecompileFailure};
+use sp_core::sr25519::{Public, Signature};
+
+pub struct Sr25519Verify;
+
+impl LinearCostPrecompile for Sr25519Verify {
+	const BASE: u64 = 3000;
+	const WORD: u64 = 12;
+
+	fn execute(input: &[u8], _: u64) -> Result<(ExitSucceed, Vec<u8>), PrecompileFailure> {
+		if input.len() < 96 {
+			return Err(PrecompileFailure::Error {
+				exit_status: ExitError::Other("input must contain at least 96 bytes".into()),
+			});
+		};
+
+		let mut pk = [0u8; 32];
+		pk.copy_from_slice(&input[0..32]);
+		let mut sig = [0u8; 64];
+		sig.copy_from_slice(&input[32..96]);
+		let msg = &input[96..];
+
+		let mut buf = [0u8; 4];
+
+		if sp_io::crypto::sr25519_verify(&Signature::from_raw(sig), msg, &Public::from_raw(pk)) {
+			buf[3] = 0u8;
+		} else {
+			buf[3] = 1u8;
+		};
+
+		Ok((ExitSucceed::Returned, buf.to_vec()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::{sr25519::Pair, Pair as _};
+
+	fn verify_input(public: &Public, signature: &Signature, msg: &[u8]) -> Vec<u8> {
+		// input is:
+		// 1) pubkey (32 bytes)
+		// 2) signature (64 bytes)
+		// 3) message (any length)
+		let mut input: Vec<u8> = Vec::with_capacity(96 + msg.len());
+		input.extend_from_slice(public.as_ref());
+		input.extend_from_slice(signature.as_ref());
+		input.extend_from_slice(msg);
+		input
+	}
+
+	#[test]
+	fn test_short_input() -> Result<(), PrecompileFailure> {
+		let input = [0u8; 95];
+		let cost: u64 = 1;
+
+		match Sr25519Verify::execute(&input, cost) {
+			Ok((_, _)) => {
+				panic!("Test not expected to pass");
+			}
+			Err(e) => {
+				assert_eq!(
+					e,
+					PrecompileFailure::Error {
+						exit_status: ExitError::Other(
+							"input must contain at least 96 bytes".into()
+						)
+					}
+				);
+				Ok(())
+			}
+		}
+	}
+
+	#[test]
+	fn test_verify() -> Result<(), PrecompileFailure> {
+		let pair = Pair::from_seed(&[7u8; 32]);
+		let public = pair.public();
+		let cost: u64 = 1;
+
+		for msg in [
+			&b""[..],
+			&b"abcdefghijklmnopqrstuvwxyz123456"[..],
+			&[42u8; 300][..],
+		] {
+			let signature = pair.sign(msg);
+
+			let (_, output) =
+				Sr25519Verify::execute(&verify_input(&public, &signature, msg), cost)?;
+			assert_eq!(output, [0u8, 0, 0, 0]);
+		}
+
+		// try again with a different message
+		let signature = pair.sign(b"abcdefghijklmnopqrstuvwxyz123456");
+		let input = verify_input(&public, &signature, b"BAD_MESSAGE_mnopqrstuvwxyz123456");
+		let (_, output) = Sr25519Verify::execute(&input, cost)?;
+		assert_eq!(output, [0u8, 0, 0, 1]); // non-zero indicates error (in our case, 1)
+
+		// and with a different signer
+		let other = Pair::from_seed(&[8u8; 32]).public();
+		let input = verify_input(&other, &signature, b"abcdefghijklmnopqrstuvwxyz123456");
+		let (_, output) = Sr25519Verify::execute(&input, cost)?;
+		assert_eq!(output, [0u8, 0, 0, 1]);
+
+		Ok(())
+	}
+}
@@ -16,7 +16,9 @@
 // limitations under the License.
 
 //! Storage cleaner precompile. This precompile is used to clean the storage entries of smart contract that
-//! has been marked as suicided (self-destructed).
+//! has been marked as suicided (self-destructed). Since EIP-6780 (Cancun), contracts are only destroyed
+//! in the transaction that created them and their storage is cleared right away, so only contracts
+//! self-destructed under earlier hard forks need this precompile.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
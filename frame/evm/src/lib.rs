@@ -48,7 +48,9 @@
 //! the exact same transaction / receipt format. However, given one Ethereum transaction and one Substrate account's
 //! private key, one should be able to convert any Ethereum transaction into a transaction compatible with this pallet.
 //!
-//! The gas configurations are configurable. Right now, a pre-defined London hard fork configuration option is provided.
+//! The gas configurations are configurable. Right now, the pre-defined Shanghai hard fork configuration is used by default, and runtimes opt into Cancun by overriding [`Config::config`].
+//! Hard forks can be scheduled by block number or timestamp through [`Config::fork_schedule`], so that
+//! historical blocks keep being executed with the rules that were active at the time.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -102,10 +104,10 @@ use sp_runtime::{
 use fp_account::AccountId20;
 use fp_evm::GenesisAccount;
 pub use fp_evm::{
-	Account, AccountProvider, CallInfo, CreateInfo, ExecutionInfoV2 as ExecutionInfo,
-	FeeCalculator, IsPrecompileResult, LinearCostPrecompile, Log, Precompile, PrecompileFailure,
-	PrecompileHandle, PrecompileOutput, PrecompileResult, PrecompileSet,
-	TransactionValidationError, Vicinity,
+	Account, AccountProvider, BlockOverrides, CallInfo, CreateInfo,
+	ExecutionInfoV2 as ExecutionInfo, FeeCalculator, IsPrecompileResult, LinearCostPrecompile, Log,
+	Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult,
+	PrecompileSet, TransactionValidationError, Vicinity,
 };
 
 pub use self::{
@@ -186,6 +188,10 @@ pub mod pallet {
 		#[pallet::no_default_bounds]
 		type OnCreate: OnCreate<Self>;
 
+		/// Called when the EVM state of an account changes, used to maintain a commitment to the
+		/// EVM state such as an Ethereum state trie.
+		type OnStateChange: OnStateChange;
+
 		/// Find author for the current block.
 		type FindAuthor: FindAuthor<H160>;
 
@@ -193,6 +199,10 @@ pub mod pallet {
 		type GasLimitPovSizeRatio: Get<u64>;
 
 		/// Define the quick clear limit of storage clearing when a contract suicides. Set to 0 to disable it.
+		///
+		/// It also bounds the storage cleared right away when a contract created in the same
+		/// transaction self-destructs (EIP-6780). Each `SELFDESTRUCT` is charged the weight of
+		/// clearing that many slots.
 		type SuicideQuickClearLimit: Get<u32>;
 
 		/// Get the timestamp for the current block.
@@ -203,9 +213,20 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 
 		/// EVM config used in the module.
+		///
+		/// Runtimes opt into later hard forks, such as Cancun, by overriding it or through
+		/// [`Config::fork_schedule`].
 		fn config() -> &'static EvmConfig {
 			&SHANGHAI_CONFIG
 		}
+
+		/// EVM hard fork schedule, as `(activation, config)` pairs in activation order.
+		///
+		/// The last entry whose activation has been reached is used. Before any entry is active,
+		/// the EVM config returned by [`Config::config`] is used.
+		fn fork_schedule() -> &'static [(ForkActivation, &'static EvmConfig)] {
+			&[]
+		}
 	}
 
 	pub mod config_preludes {
@@ -249,6 +270,7 @@ pub mod pallet {
 			type BlockGasLimit = BlockGasLimit;
 			type OnChargeTransaction = ();
 			type OnCreate = ();
+			type OnStateChange = ();
 			type FindAuthor = FindAuthorTruncated;
 			type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
 			type SuicideQuickClearLimit = SuicideQuickClearLimit;
@@ -298,6 +320,7 @@ pub mod pallet {
 				value,
 				ExistenceRequirement::AllowDeath,
 			)?;
+			T::OnStateChange::on_account_changed(address);
 
 			Ok(())
 		}
@@ -334,11 +357,12 @@ pub mod pallet {
 				max_priority_fee_per_gas,
 				nonce,
 				access_list,
+				Vec::new(),
 				is_transactional,
 				validate,
 				None,
 				None,
-				T::config(),
+				Self::evm_config(),
 			) {
 				Ok(info) => info,
 				Err(e) => {
@@ -413,9 +437,9 @@ pub mod pallet {
 				validate,
 				None,
 				None,
-				T::config(),
+				Self::evm_config(),
 			) {
-				Ok(info) => info,
+				Ok((info, _)) => info,
 				Err(e) => {
 					return Err(DispatchErrorWithPostInfo {
 						post_info: PostDispatchInfo {
@@ -501,9 +525,9 @@ pub mod pallet {
 				validate,
 				None,
 				None,
-				T::config(),
+				Self::evm_config(),
 			) {
-				Ok(info) => info,
+				Ok((info, _)) => info,
 				Err(e) => {
 					return Err(DispatchErrorWithPostInfo {
 						post_info: PostDispatchInfo {
@@ -597,6 +621,8 @@ pub mod pallet {
 		TransactionMustComeFromEOA,
 		/// Undefined error.
 		Undefined,
+		/// The EIP-7702 authorization list is invalid.
+		InvalidAuthorizationList,
 	}
 
 	impl<T> From<TransactionValidationError> for Error<T> {
@@ -613,6 +639,9 @@ pub mod pallet {
 				TransactionValidationError::InvalidChainId => Error::<T>::InvalidChainId,
 				TransactionValidationError::InvalidSignature => Error::<T>::InvalidSignature,
 				TransactionValidationError::UnknownError => Error::<T>::Undefined,
+				TransactionValidationError::InvalidAuthorizationList => {
+					Error::<T>::InvalidAuthorizationList
+				}
 			}
 		}
 	}
@@ -651,9 +680,11 @@ pub mod pallet {
 				);
 
 				Pallet::<T>::create_account(*address, account.code.clone());
+				T::OnStateChange::on_account_changed(*address);
 
 				for (index, value) in &account.storage {
 					<AccountStorages<T>>::insert(address, index, value);
+					T::OnStateChange::on_storage_changed(*address, *index);
 				}
 			}
 		}
@@ -708,6 +739,28 @@ impl CodeMetadata {
 	}
 }
 
+/// The outcome of a contract call made by another pallet through [`Pallet::call_contract`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractCallInfo {
+	/// Why the execution stopped.
+	pub exit_reason: ExitReason,
+	/// The returned data, or the revert output if the call reverted.
+	pub output: Vec<u8>,
+	/// The logs emitted during the call.
+	pub logs: Vec<Log>,
+	/// The gas used by the call.
+	pub used_gas: U256,
+	/// The weight consumed by the call, mapped from the used gas and the proof size.
+	pub weight: Weight,
+}
+
+impl ContractCallInfo {
+	/// Whether the call succeeded.
+	pub fn is_success(&self) -> bool {
+		self.exit_reason.is_succeed()
+	}
+}
+
 pub trait EnsureAddressOrigin<OuterOrigin> {
 	/// Success return type.
 	type Success;
@@ -901,7 +954,43 @@ where
 
 static SHANGHAI_CONFIG: EvmConfig = EvmConfig::shanghai();
 
+/// Activation condition of an entry in the EVM hard fork schedule.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ForkActivation {
+	/// Active from the given block number on.
+	Block(u64),
+	/// Active from the given block timestamp, in seconds, on.
+	Timestamp(u64),
+}
+
+impl ForkActivation {
+	/// Whether the fork is active at the given block number and timestamp (in seconds).
+	pub fn is_active(&self, block_number: u64, timestamp: u64) -> bool {
+		match self {
+			ForkActivation::Block(number) => block_number >= *number,
+			ForkActivation::Timestamp(time) => timestamp >= *time,
+		}
+	}
+}
+
 impl<T: Config> Pallet<T> {
+	/// Get the EVM config active at the given block number and timestamp (in seconds).
+	pub fn evm_config_at(block_number: u64, timestamp: u64) -> &'static EvmConfig {
+		T::fork_schedule()
+			.iter()
+			.rev()
+			.find(|(activation, _)| activation.is_active(block_number, timestamp))
+			.map(|(_, config)| *config)
+			.unwrap_or_else(T::config)
+	}
+
+	/// Get the EVM config active in the current block.
+	pub fn evm_config() -> &'static EvmConfig {
+		let block_number: u64 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+		let now: u64 = T::Timestamp::now().unique_saturated_into();
+		Self::evm_config_at(block_number, now / 1000)
+	}
+
 	/// Check whether an account is empty.
 	pub fn is_account_empty(address: &H160) -> bool {
 		let (account, _) = Self::account_basic(address);
@@ -939,6 +1028,8 @@ impl<T: Config> Pallet<T> {
 
 		<AccountCodes<T>>::remove(address);
 		<AccountCodesMetadata<T>>::remove(address);
+		T::OnStateChange::on_account_changed(*address);
+		T::OnStateChange::on_storage_cleared(*address);
 
 		if T::SuicideQuickClearLimit::get() > 0 {
 			#[allow(deprecated)]
@@ -956,6 +1047,33 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Remove an account that was created in the current transaction.
+	///
+	/// Following EIP-6780, only such accounts can be destroyed. Up to `SuicideQuickClearLimit`
+	/// slots of their storage are cleared right away. If some are left, a `Suicided` entry is
+	/// left behind for the storage cleaner, which also drops the account reference.
+	pub fn remove_created_account(address: &H160) {
+		let account_id = T::AddressMapping::into_account_id(*address);
+		let has_code = <AccountCodes<T>>::contains_key(address);
+
+		<AccountCodes<T>>::remove(address);
+		<AccountCodesMetadata<T>>::remove(address);
+		T::OnStateChange::on_account_changed(*address);
+		T::OnStateChange::on_storage_cleared(*address);
+
+		let result =
+			<AccountStorages<T>>::clear_prefix(address, T::SuicideQuickClearLimit::get(), None);
+		if result.maybe_cursor.is_some() {
+			<Suicided<T>>::insert(address, ());
+			// The storage cleaner drops a reference to the account once done.
+			if !has_code {
+				T::AccountProvider::create_account(&account_id);
+			}
+		} else if has_code {
+			T::AccountProvider::remove_account(&account_id);
+		}
+	}
+
 	/// Create an account.
 	pub fn create_account(address: H160, code: Vec<u8>) {
 		if <Suicided<T>>::contains_key(address) {
@@ -980,6 +1098,38 @@ impl<T: Config> Pallet<T> {
 		<AccountCodesMetadata<T>>::insert(address, meta);
 
 		<AccountCodes<T>>::insert(address, code);
+		T::OnStateChange::on_account_changed(address);
+	}
+
+	/// Install an EIP-7702 delegation designator pointing to `delegate` on `address`, or clear
+	/// the delegation if `delegate` is the zero address. Account storage is left untouched.
+	pub fn set_delegation(address: H160, delegate: H160) {
+		if delegate.is_zero() {
+			if <AccountCodes<T>>::contains_key(address) {
+				let account_id = T::AddressMapping::into_account_id(address);
+				T::AccountProvider::remove_account(&account_id);
+			}
+			<AccountCodes<T>>::remove(address);
+			<AccountCodesMetadata<T>>::remove(address);
+			T::OnStateChange::on_account_changed(address);
+		} else {
+			Self::create_account(address, fp_evm::delegation_designator(delegate));
+		}
+	}
+
+	/// Get the address an account delegates its code to (EIP-7702), if any.
+	pub fn delegation(address: &H160) -> Option<H160> {
+		if <AccountCodes<T>>::decode_len(address) != Some(fp_evm::DELEGATION_DESIGNATOR_LEN) {
+			return None;
+		}
+		fp_evm::delegated_address(&<AccountCodes<T>>::get(address))
+	}
+
+	/// Check whether an account has contract code deployed. Accounts delegating their code
+	/// (EIP-7702) are still considered externally owned.
+	pub fn has_contract_code(address: &H160) -> bool {
+		let code = <AccountCodes<T>>::get(address);
+		!code.is_empty() && fp_evm::delegated_address(&code).is_none()
 	}
 
 	/// Get the account metadata (hash and size) from storage if it exists,
@@ -1032,6 +1182,84 @@ impl<T: Config> Pallet<T> {
 
 		T::FindAuthor::find_author(pre_runtime_digests).unwrap_or_default()
 	}
+
+	/// The weight to reserve for a [`Pallet::call_contract`] with the given gas limit.
+	pub fn call_contract_weight(gas_limit: u64) -> Weight {
+		T::GasWeightMapping::gas_to_weight(gas_limit, true)
+	}
+
+	/// Call the contract at `target` with `source` as the caller, on behalf of another pallet.
+	///
+	/// The calling pallet should reserve [`Pallet::call_contract_weight`] for `gas_limit` and
+	/// account for the [`ContractCallInfo::weight`] actually consumed. A transactional call
+	/// behaves like an Ethereum transaction: `source` must be an externally owned account
+	/// (EIP-3607), its nonce is increased and it pays the current base fee for the gas used. A
+	/// non-transactional call is free and can be made from any address, e.g. one derived from a
+	/// pallet id.
+	///
+	/// The call is not reverted when the EVM execution fails, check
+	/// [`ContractCallInfo::exit_reason`].
+	pub fn call_contract(
+		source: H160,
+		target: H160,
+		input: Vec<u8>,
+		value: U256,
+		gas_limit: u64,
+		is_transactional: bool,
+	) -> Result<ContractCallInfo, DispatchErrorWithPostInfo> {
+		// Transactional calls are priced at the base fee, without tip.
+		let max_fee_per_gas = is_transactional.then(|| T::FeeCalculator::min_gas_price().0);
+
+		let info = T::Runner::call(
+			source,
+			target,
+			input,
+			value,
+			gas_limit,
+			max_fee_per_gas,
+			None,
+			None,
+			Vec::new(),
+			Vec::new(),
+			is_transactional,
+			false,
+			None,
+			None,
+			Self::evm_config(),
+		)
+		.map_err(|e| DispatchErrorWithPostInfo {
+			post_info: PostDispatchInfo {
+				actual_weight: Some(e.weight),
+				pays_fee: Pays::Yes,
+			},
+			error: e.error.into(),
+		})?;
+
+		match info.exit_reason {
+			ExitReason::Succeed(_) => {
+				Pallet::<T>::deposit_event(Event::<T>::Executed { address: target });
+			}
+			_ => {
+				Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed { address: target });
+			}
+		};
+
+		let mut weight = T::GasWeightMapping::gas_to_weight(
+			info.used_gas.standard.unique_saturated_into(),
+			true,
+		);
+		if let Some(proof_size_usage) = info.weight_info.and_then(|w| w.proof_size_usage) {
+			*weight.proof_size_mut() = proof_size_usage;
+		}
+
+		Ok(ContractCallInfo {
+			exit_reason: info.exit_reason,
+			output: info.value,
+			logs: info.logs,
+			used_gas: info.used_gas.standard,
+			weight,
+		})
+	}
 }
 
 /// Handle withdrawing, refunding and depositing of transaction fees.
@@ -1277,6 +1505,45 @@ impl<T> OnCreate<T> for Tuple {
 	}
 }
 
+/// Notified of the changes to the EVM state, as the addresses and slots touched by each block.
+///
+/// Changes made to the balance of an account outside of this pallet, such as a transfer with
+/// the balances pallet, are not reported and have to be notified by the runtime.
+pub trait OnStateChange {
+	/// The nonce, balance or code of an account may have changed.
+	fn on_account_changed(address: H160);
+	/// A storage slot of an account may have changed.
+	fn on_storage_changed(address: H160, index: H256);
+	/// All the storage of an account was cleared.
+	fn on_storage_cleared(address: H160);
+
+	/// Weight of handling one reported change, charged to the EVM execution that makes it.
+	fn change_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+#[impl_for_tuples(12)]
+impl OnStateChange for Tuple {
+	fn on_account_changed(address: H160) {
+		for_tuples!(#( Tuple::on_account_changed(address); )*)
+	}
+
+	fn on_storage_changed(address: H160, index: H256) {
+		for_tuples!(#( Tuple::on_storage_changed(address, index); )*)
+	}
+
+	fn on_storage_cleared(address: H160) {
+		for_tuples!(#( Tuple::on_storage_cleared(address); )*)
+	}
+
+	fn change_weight() -> Weight {
+		let mut weight = Weight::zero();
+		for_tuples!(#( weight = weight.saturating_add(Tuple::change_weight()); )*);
+		weight
+	}
+}
+
 /// EVM account provider based on the [`frame_system`] accounts.
 ///
 /// Uses standard Substrate accounts system to hold EVM accounts.
@@ -21,8 +21,8 @@ use frame_support::{derive_impl, parameter_types, weights::Weight};
 use sp_core::{H160, U256};
 
 use crate::{
-	FeeCalculator, IsPrecompileResult, Precompile, PrecompileHandle, PrecompileResult,
-	PrecompileSet,
+	EvmConfig, FeeCalculator, ForkActivation, IsPrecompileResult, Precompile, PrecompileHandle,
+	PrecompileResult, PrecompileSet,
 };
 
 frame_support::construct_runtime! {
@@ -62,6 +62,7 @@ impl pallet_timestamp::Config for Test {}
 
 parameter_types! {
 	pub MockPrecompiles: MockPrecompileSet = MockPrecompileSet;
+	pub static SuicideQuickClearLimit: u32 = 10;
 }
 
 #[derive_impl(crate::config_preludes::TestDefaultConfig)]
@@ -73,9 +74,28 @@ impl crate::Config for Test {
 	type PrecompilesType = MockPrecompileSet;
 	type PrecompilesValue = MockPrecompiles;
 	type Runner = crate::runner::stack::Runner<Self>;
+	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 	type Timestamp = Timestamp;
+
+	fn config() -> &'static EvmConfig {
+		&PECTRA_CONFIG
+	}
+
+	fn fork_schedule() -> &'static [(ForkActivation, &'static EvmConfig)] {
+		&FORK_SCHEDULE
+	}
 }
 
+pub static SHANGHAI_CONFIG: EvmConfig = EvmConfig::shanghai();
+pub static CANCUN_CONFIG: EvmConfig = EvmConfig::cancun();
+pub static PECTRA_CONFIG: EvmConfig = EvmConfig::pectra();
+
+/// Shanghai from genesis, Cancun from timestamp 1_000 on.
+static FORK_SCHEDULE: [(ForkActivation, &EvmConfig); 2] = [
+	(ForkActivation::Block(0), &SHANGHAI_CONFIG),
+	(ForkActivation::Timestamp(1_000), &CANCUN_CONFIG),
+];
+
 pub struct FixedGasPrice;
 impl FeeCalculator for FixedGasPrice {
 	fn min_gas_price() -> (U256, Weight) {
@@ -16,12 +16,71 @@
 // limitations under the License.
 
 pub mod stack;
+#[cfg(feature = "tracing")]
+pub mod tracing;
 
 use crate::{Config, Weight};
-use alloc::vec::Vec;
-use fp_evm::{CallInfo, CreateInfo};
+use alloc::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	vec::Vec,
+};
+use fp_evm::{
+	AuthorizationList, BlockOverrides, CallInfo, CreateInfo, IsPrecompileResult, PrecompileSet,
+};
+use frame_support::traits::Get;
 use sp_core::{H160, H256, U256};
 
+/// Addresses accessed by the EVM, along with their accessed storage slots.
+pub type Accesses = BTreeMap<H160, BTreeSet<H256>>;
+
+environmental::environmental!(block_overrides: BlockOverrides);
+environmental::environmental!(accesses: Accesses);
+
+/// Execute `f` with the block context seen by the EVM overridden by `overrides`.
+///
+/// Meant for non-transactional calls, such as the ones of `eth_call`, to execute as if in
+/// another block.
+pub fn with_block_overrides<R>(mut overrides: BlockOverrides, f: impl FnOnce() -> R) -> R {
+	block_overrides::using(&mut overrides, f)
+}
+
+/// Block context overrides of the current execution, if any.
+pub fn current_block_overrides() -> BlockOverrides {
+	block_overrides::with(|overrides| overrides.clone()).unwrap_or_default()
+}
+
+/// Execute `f`, recording the addresses and storage slots accessed by the EVM.
+///
+/// Meant for `eth_createAccessList`. The precompiles, which are always warm, are left out unless
+/// one of their storage slots was accessed. Only the accesses priced by EIP-2929 are recorded, so
+/// none are with a config predating Berlin.
+pub fn record_accesses<T: Config, R>(f: impl FnOnce() -> R) -> (R, Accesses) {
+	let mut recorded = Accesses::new();
+	let result = accesses::using(&mut recorded, f);
+	let precompiles = T::PrecompilesValue::get();
+	recorded.retain(|address, storage_keys| {
+		!storage_keys.is_empty()
+			|| !matches!(
+				precompiles.is_precompile(*address, u64::MAX),
+				IsPrecompileResult::Answer {
+					is_precompile: true,
+					..
+				}
+			)
+	});
+	(result, recorded)
+}
+
+/// Record an access to `address`, or to one of its storage slots, if recording.
+pub(crate) fn record_access(address: H160, index: Option<H256>) {
+	accesses::with(|accesses| {
+		let slots = accesses.entry(address).or_default();
+		if let Some(index) = index {
+			slots.insert(index);
+		}
+	});
+}
+
 #[derive(Debug)]
 pub struct RunnerError<E: Into<sp_runtime::DispatchError>> {
 	pub error: E,
@@ -41,6 +100,7 @@ pub trait Runner<T: Config> {
 		max_priority_fee_per_gas: Option<U256>,
 		nonce: Option<U256>,
 		access_list: Vec<(H160, Vec<H256>)>,
+		authorization_list: AuthorizationList,
 		is_transactional: bool,
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
@@ -57,6 +117,7 @@ pub trait Runner<T: Config> {
 		max_priority_fee_per_gas: Option<U256>,
 		nonce: Option<U256>,
 		access_list: Vec<(H160, Vec<H256>)>,
+		authorization_list: AuthorizationList,
 		is_transactional: bool,
 		validate: bool,
 		weight_limit: Option<Weight>,
@@ -64,6 +125,8 @@ pub trait Runner<T: Config> {
 		config: &evm::Config,
 	) -> Result<CallInfo, RunnerError<Self::Error>>;
 
+	/// Creates a contract, returning its execution info along with the output of the init code,
+	/// which holds the revert reason when the creation reverted.
 	fn create(
 		source: H160,
 		init: Vec<u8>,
@@ -78,7 +141,7 @@ pub trait Runner<T: Config> {
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
 		config: &evm::Config,
-	) -> Result<CreateInfo, RunnerError<Self::Error>>;
+	) -> Result<(CreateInfo, Vec<u8>), RunnerError<Self::Error>>;
 
 	fn create2(
 		source: H160,
@@ -95,5 +158,5 @@ pub trait Runner<T: Config> {
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
 		config: &evm::Config,
-	) -> Result<CreateInfo, RunnerError<Self::Error>>;
+	) -> Result<(CreateInfo, Vec<u8>), RunnerError<Self::Error>>;
 }
@@ -41,15 +41,16 @@ use sp_core::{H160, H256, U256};
 use sp_runtime::traits::UniqueSaturatedInto;
 // Frontier
 use fp_evm::{
-	AccessedStorage, CallInfo, CreateInfo, ExecutionInfoV2, IsPrecompileResult, Log, PrecompileSet,
-	Vicinity, WeightInfo, ACCOUNT_BASIC_PROOF_SIZE, ACCOUNT_CODES_METADATA_PROOF_SIZE,
-	ACCOUNT_STORAGE_PROOF_SIZE, IS_EMPTY_CHECK_PROOF_SIZE, WRITE_PROOF_SIZE,
+	AccessedStorage, AuthorizationList, CallInfo, CreateInfo, ExecutionInfoV2, IsPrecompileResult,
+	Log, PrecompileSet, Vicinity, WeightInfo, ACCOUNT_BASIC_PROOF_SIZE,
+	ACCOUNT_CODES_METADATA_PROOF_SIZE, ACCOUNT_STORAGE_PROOF_SIZE, DELEGATION_DESIGNATOR_LEN,
+	IS_EMPTY_CHECK_PROOF_SIZE, PER_AUTH_BASE_COST, PER_EMPTY_ACCOUNT_COST, WRITE_PROOF_SIZE,
 };
 
 use crate::{
 	runner::Runner as RunnerT, AccountCodes, AccountCodesMetadata, AccountProvider,
 	AccountStorages, AddressMapping, BalanceOf, BlockHashMapping, Config, Error, Event,
-	FeeCalculator, OnChargeEVMTransaction, OnCreate, Pallet, RunnerError,
+	FeeCalculator, OnChargeEVMTransaction, OnCreate, OnStateChange, Pallet, RunnerError,
 };
 
 #[cfg(feature = "forbid-evm-reentrancy")]
@@ -184,8 +185,9 @@ where
 		// we will skip the checks for the EIP-3607.
 		//
 		// EIP-3607: https://eips.ethereum.org/EIPS/eip-3607
-		// Do not allow transactions for which `tx.sender` has any code deployed.
-		if is_transactional && !<AccountCodes<T>>::get(source).is_empty() {
+		// Do not allow transactions for which `tx.sender` has any code deployed. Accounts holding
+		// an EIP-7702 delegation designator are still externally owned.
+		if is_transactional && Pallet::<T>::has_contract_code(&source) {
 			return Err(RunnerError {
 				error: Error::<T>::TransactionMustComeFromEOA,
 				weight,
@@ -232,11 +234,13 @@ where
 		// Deduct fee from the `source` account. Returns `None` if `total_fee` is Zero.
 		let fee = T::OnChargeTransaction::withdraw_fee(&source, total_fee)
 			.map_err(|e| RunnerError { error: e, weight })?;
+		T::OnStateChange::on_account_changed(source);
 
 		// Execute the EVM call.
 		let vicinity = Vicinity {
 			gas_price: base_fee,
 			origin: source,
+			block_overrides: super::current_block_overrides(),
 		};
 
 		let metadata = StackSubstateMetadata::new(gas_limit, config);
@@ -305,6 +309,7 @@ where
 			fee,
 		);
 		T::OnChargeTransaction::pay_priority_fee(actual_priority_fee);
+		T::OnStateChange::on_account_changed(Pallet::<T>::find_author());
 
 		let state = executor.into_state();
 
@@ -314,7 +319,13 @@ where
 				"Deleting account at {:?}",
 				address
 			);
-			Pallet::<T>::remove_account(address)
+			if state.substate.created(*address) {
+				// EIP-6780: the contract was created in this transaction, so all of its
+				// storage was written (and paid for) in this transaction too.
+				Pallet::<T>::remove_created_account(address)
+			} else {
+				Pallet::<T>::remove_account(address)
+			}
 		}
 
 		for log in &state.substate.logs {
@@ -347,6 +358,85 @@ where
 			logs: state.substate.logs,
 		})
 	}
+
+	/// Process an EIP-7702 authorization list ahead of the transaction call.
+	///
+	/// Invalid tuples are skipped as mandated by the EIP, so this only fails if the
+	/// transaction cannot pay for the authorizations.
+	fn apply_authorizations<'config>(
+		executor: &mut StackExecutor<
+			'config,
+			'_,
+			SubstrateStackState<'_, 'config, T>,
+			T::PrecompilesType,
+		>,
+		source: H160,
+		authorization_list: AuthorizationList,
+	) -> Result<(), ExitError> {
+		let state = executor.state_mut();
+		state
+			.metadata_mut()
+			.gasometer_mut()
+			.record_cost(PER_EMPTY_ACCOUNT_COST.saturating_mul(authorization_list.len() as u64))?;
+
+		for authorization in authorization_list {
+			if authorization.chain_id != 0 && authorization.chain_id != T::ChainId::get() {
+				continue;
+			}
+			let Some(authority) = authorization.authority else {
+				continue;
+			};
+			state.metadata_mut().access_address(authority);
+
+			state.record_external_operation(ExternalOperation::AddressCodeRead(authority))?;
+			if Pallet::<T>::has_contract_code(&authority) {
+				continue;
+			}
+
+			state.record_external_operation(ExternalOperation::AccountBasicRead)?;
+			let (account, _) = Pallet::<T>::account_basic(&authority);
+			// `transact_call` bumps the sender nonce after the authorizations are processed,
+			// while EIP-7702 processes them after the bump.
+			let expected_nonce = if authority == source {
+				account.nonce.saturating_add(U256::one())
+			} else {
+				account.nonce
+			};
+			if authorization.nonce != expected_nonce {
+				continue;
+			}
+
+			if !Pallet::<T>::is_account_empty(&authority) {
+				state
+					.metadata_mut()
+					.gasometer_mut()
+					.record_refund((PER_EMPTY_ACCOUNT_COST - PER_AUTH_BASE_COST) as i64)?;
+			}
+
+			state.record_external_operation(ExternalOperation::Write(U256::from(
+				DELEGATION_DESIGNATOR_LEN,
+			)))?;
+			Pallet::<T>::set_delegation(authority, authorization.address);
+			state.inc_nonce(authority)?;
+		}
+
+		Ok(())
+	}
+
+	/// Splits the output of the init code off the info of a contract creation.
+	fn split_create_output(info: ExecutionInfoV2<(H160, Vec<u8>)>) -> (CreateInfo, Vec<u8>) {
+		let (address, output) = info.value;
+		(
+			CreateInfo {
+				exit_reason: info.exit_reason,
+				value: address,
+				used_gas: info.used_gas,
+				weight_info: info.weight_info,
+				logs: info.logs,
+			},
+			output,
+		)
+	}
 }
 
 impl<T: Config> RunnerT<T> for Runner<T>
@@ -365,6 +455,7 @@ where
 		max_priority_fee_per_gas: Option<U256>,
 		nonce: Option<U256>,
 		access_list: Vec<(H160, Vec<H256>)>,
+		authorization_list: AuthorizationList,
 		is_transactional: bool,
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
@@ -393,6 +484,7 @@ where
 				max_priority_fee_per_gas,
 				value,
 				access_list,
+				authorization_list: (!authorization_list.is_empty()).then_some(authorization_list),
 			},
 			weight_limit,
 			proof_size_base_cost,
@@ -414,6 +506,7 @@ where
 		max_priority_fee_per_gas: Option<U256>,
 		nonce: Option<U256>,
 		access_list: Vec<(H160, Vec<H256>)>,
+		authorization_list: AuthorizationList,
 		is_transactional: bool,
 		validate: bool,
 		weight_limit: Option<Weight>,
@@ -431,6 +524,7 @@ where
 				max_priority_fee_per_gas,
 				nonce,
 				access_list.clone(),
+				authorization_list.clone(),
 				is_transactional,
 				weight_limit,
 				proof_size_base_cost,
@@ -449,7 +543,12 @@ where
 			is_transactional,
 			weight_limit,
 			proof_size_base_cost,
-			|executor| executor.transact_call(source, target, value, input, gas_limit, access_list),
+			|executor| {
+				if let Err(e) = Self::apply_authorizations(executor, source, authorization_list) {
+					return (e.into(), Vec::new());
+				}
+				executor.transact_call(source, target, value, input, gas_limit, access_list)
+			},
 		)
 	}
 
@@ -467,7 +566,7 @@ where
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
 		config: &evm::Config,
-	) -> Result<CreateInfo, RunnerError<Self::Error>> {
+	) -> Result<(CreateInfo, Vec<u8>), RunnerError<Self::Error>> {
 		if validate {
 			Self::validate(
 				source,
@@ -479,6 +578,7 @@ where
 				max_priority_fee_per_gas,
 				nonce,
 				access_list.clone(),
+				Vec::new(),
 				is_transactional,
 				weight_limit,
 				proof_size_base_cost,
@@ -500,11 +600,12 @@ where
 			|executor| {
 				let address = executor.create_address(evm::CreateScheme::Legacy { caller: source });
 				T::OnCreate::on_create(source, address);
-				let (reason, _) =
+				let (reason, output) =
 					executor.transact_create(source, value, init, gas_limit, access_list);
-				(reason, address)
+				(reason, (address, output))
 			},
 		)
+		.map(Self::split_create_output)
 	}
 
 	fn create2(
@@ -522,7 +623,7 @@ where
 		weight_limit: Option<Weight>,
 		proof_size_base_cost: Option<u64>,
 		config: &evm::Config,
-	) -> Result<CreateInfo, RunnerError<Self::Error>> {
+	) -> Result<(CreateInfo, Vec<u8>), RunnerError<Self::Error>> {
 		if validate {
 			Self::validate(
 				source,
@@ -534,6 +635,7 @@ where
 				max_priority_fee_per_gas,
 				nonce,
 				access_list.clone(),
+				Vec::new(),
 				is_transactional,
 				weight_limit,
 				proof_size_base_cost,
@@ -560,17 +662,20 @@ where
 					salt,
 				});
 				T::OnCreate::on_create(source, address);
-				let (reason, _) =
+				let (reason, output) =
 					executor.transact_create2(source, value, init, salt, gas_limit, access_list);
-				(reason, address)
+				(reason, (address, output))
 			},
 		)
+		.map(Self::split_create_output)
 	}
 }
 
 struct SubstrateStackSubstate<'config> {
 	metadata: StackSubstateMetadata<'config>,
 	deletes: BTreeSet<H160>,
+	creates: BTreeSet<H160>,
+	transient_storage: BTreeMap<(H160, H256), U256>,
 	logs: Vec<Log>,
 	parent: Option<Box<SubstrateStackSubstate<'config>>>,
 }
@@ -589,6 +694,8 @@ impl<'config> SubstrateStackSubstate<'config> {
 			metadata: self.metadata.spit_child(gas_limit, is_static),
 			parent: None,
 			deletes: BTreeSet::new(),
+			creates: BTreeSet::new(),
+			transient_storage: BTreeMap::new(),
 			logs: Vec::new(),
 		};
 		mem::swap(&mut entering, self);
@@ -605,6 +712,8 @@ impl<'config> SubstrateStackSubstate<'config> {
 		self.metadata.swallow_commit(exited.metadata)?;
 		self.logs.append(&mut exited.logs);
 		self.deletes.append(&mut exited.deletes);
+		self.creates.append(&mut exited.creates);
+		self.transient_storage.append(&mut exited.transient_storage);
 
 		sp_io::storage::commit_transaction();
 		Ok(())
@@ -644,6 +753,38 @@ impl<'config> SubstrateStackSubstate<'config> {
 		self.deletes.insert(address);
 	}
 
+	pub fn created(&self, address: H160) -> bool {
+		if self.creates.contains(&address) {
+			return true;
+		}
+
+		if let Some(parent) = self.parent.as_ref() {
+			return parent.created(address);
+		}
+
+		false
+	}
+
+	pub fn set_created(&mut self, address: H160) {
+		self.creates.insert(address);
+	}
+
+	pub fn known_transient_storage(&self, address: H160, index: H256) -> Option<U256> {
+		if let Some(value) = self.transient_storage.get(&(address, index)) {
+			return Some(*value);
+		}
+
+		if let Some(parent) = self.parent.as_ref() {
+			return parent.known_transient_storage(address, index);
+		}
+
+		None
+	}
+
+	pub fn set_transient_storage(&mut self, address: H160, index: H256, value: U256) {
+		self.transient_storage.insert((address, index), value);
+	}
+
 	pub fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) {
 		self.logs.push(Log {
 			address,
@@ -693,6 +834,8 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 			substate: SubstrateStackSubstate {
 				metadata,
 				deletes: BTreeSet::new(),
+				creates: BTreeSet::new(),
+				transient_storage: BTreeMap::new(),
 				logs: Vec::new(),
 				parent: None,
 			},
@@ -707,6 +850,27 @@ impl<'vicinity, 'config, T: Config> SubstrateStackState<'vicinity, 'config, T> {
 		self.weight_info
 	}
 
+	/// Charge the weight of reporting `changes` state changes to `T::OnStateChange`.
+	fn record_state_changes(&mut self, changes: u64) -> Result<(), ExitError> {
+		self.record_weight(T::OnStateChange::change_weight().saturating_mul(changes))
+	}
+
+	/// Charge the weight of clearing the storage of a destroyed account, which is bounded by
+	/// `T::SuicideQuickClearLimit`.
+	fn record_storage_clear(&mut self) -> Result<(), ExitError> {
+		self.record_weight(T::DbWeight::get().writes(T::SuicideQuickClearLimit::get().into()))
+	}
+
+	fn record_weight(&mut self, weight: Weight) -> Result<(), ExitError> {
+		match self.weight_info.as_mut() {
+			Some(weight_info) if !weight.is_zero() => {
+				weight_info.try_record_ref_time_or_fail(weight.ref_time())?;
+				weight_info.try_record_proof_size_or_fail(weight.proof_size())
+			}
+			_ => Ok(()),
+		}
+	}
+
 	pub fn recorded(&self) -> &Recorded {
 		&self.recorded
 	}
@@ -728,7 +892,9 @@ where
 	}
 
 	fn block_hash(&self, number: U256) -> H256 {
-		if number > U256::from(u32::MAX) {
+		if let Some(hash) = self.vicinity.block_overrides.block_hashes.get(&number) {
+			*hash
+		} else if number > U256::from(u32::MAX) {
 			H256::default()
 		} else {
 			T::BlockHashMapping::block_hash(number.as_u32())
@@ -736,15 +902,23 @@ where
 	}
 
 	fn block_number(&self) -> U256 {
-		let number: u128 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
-		U256::from(number)
+		self.vicinity.block_overrides.number.unwrap_or_else(|| {
+			let number: u128 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+			U256::from(number)
+		})
 	}
 
 	fn block_coinbase(&self) -> H160 {
-		Pallet::<T>::find_author()
+		self.vicinity
+			.block_overrides
+			.coinbase
+			.unwrap_or_else(Pallet::<T>::find_author)
 	}
 
 	fn block_timestamp(&self) -> U256 {
+		if let Some(timestamp) = self.vicinity.block_overrides.timestamp {
+			return U256::from(timestamp);
+		}
 		let now: u128 = T::Timestamp::now().unique_saturated_into();
 		U256::from(now / 1000)
 	}
@@ -754,16 +928,21 @@ where
 	}
 
 	fn block_randomness(&self) -> Option<H256> {
-		None
+		self.vicinity.block_overrides.random
 	}
 
 	fn block_gas_limit(&self) -> U256 {
-		T::BlockGasLimit::get()
+		self.vicinity
+			.block_overrides
+			.gas_limit
+			.unwrap_or_else(T::BlockGasLimit::get)
 	}
 
 	fn block_base_fee_per_gas(&self) -> U256 {
-		let (base_fee, _) = T::FeeCalculator::min_gas_price();
-		base_fee
+		self.vicinity.block_overrides.base_fee.unwrap_or_else(|| {
+			let (base_fee, _) = T::FeeCalculator::min_gas_price();
+			base_fee
+		})
 	}
 
 	fn chain_id(&self) -> U256 {
@@ -784,6 +963,8 @@ where
 	}
 
 	fn code(&self, address: H160) -> Vec<u8> {
+		// EIP-7702: this is the raw code, so EXTCODECOPY copies the delegation designator. The
+		// executor loads the code of the delegate for call frames through `get_authority_target`.
 		<AccountCodes<T>>::get(address)
 	}
 
@@ -841,7 +1022,8 @@ where
 	fn inc_nonce(&mut self, address: H160) -> Result<(), ExitError> {
 		let account_id = T::AddressMapping::into_account_id(address);
 		T::AccountProvider::inc_account_nonce(&account_id);
-		Ok(())
+		T::OnStateChange::on_account_changed(address);
+		self.record_state_changes(1)
 	}
 
 	fn set_storage(&mut self, address: H160, index: H256, value: H256) {
@@ -875,11 +1057,13 @@ where
 			);
 			<AccountStorages<T>>::insert(address, index, value);
 		}
+		T::OnStateChange::on_storage_changed(address, index);
 	}
 
 	fn reset_storage(&mut self, address: H160) {
 		#[allow(deprecated)]
 		let _ = <AccountStorages<T>>::remove_prefix(address, None);
+		T::OnStateChange::on_storage_cleared(address);
 	}
 
 	fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) {
@@ -890,6 +1074,26 @@ where
 		self.substate.set_deleted(address)
 	}
 
+	fn is_created(&self, address: H160) -> bool {
+		self.substate.created(address)
+	}
+
+	fn set_created(&mut self, address: H160) {
+		self.substate.set_created(address)
+	}
+
+	fn tload(&mut self, address: H160, index: H256) -> Result<U256, ExitError> {
+		Ok(self
+			.substate
+			.known_transient_storage(address, index)
+			.unwrap_or_default())
+	}
+
+	fn tstore(&mut self, address: H160, index: H256, value: U256) -> Result<(), ExitError> {
+		self.substate.set_transient_storage(address, index, value);
+		Ok(())
+	}
+
 	fn set_code(&mut self, address: H160, code: Vec<u8>) {
 		log::debug!(
 			target: "evm",
@@ -912,7 +1116,10 @@ where
 				.map_err(|_| ExitError::OutOfFund)?,
 			ExistenceRequirement::AllowDeath,
 		)
-		.map_err(|_| ExitError::OutOfFund)
+		.map_err(|_| ExitError::OutOfFund)?;
+		T::OnStateChange::on_account_changed(transfer.source);
+		T::OnStateChange::on_account_changed(transfer.target);
+		self.record_state_changes(2)
 	}
 
 	fn reset_balance(&mut self, _address: H160) {
@@ -932,11 +1139,13 @@ where
 	}
 
 	fn is_cold(&self, address: H160) -> bool {
+		super::record_access(address, None);
 		self.substate
 			.recursive_is_cold(&|a| a.accessed_addresses.contains(&address))
 	}
 
 	fn is_storage_cold(&self, address: H160, key: H256) -> bool {
+		super::record_access(address, Some(key));
 		self.substate
 			.recursive_is_cold(&|a: &Accessed| a.accessed_storage.contains(&(address, key)))
 	}
@@ -949,6 +1158,14 @@ where
 		<Pallet<T>>::account_code_metadata(address).hash
 	}
 
+	fn is_authority_cold(&mut self, address: H160) -> Option<bool> {
+		self.get_authority_target(address).map(|target| self.is_cold(target))
+	}
+
+	fn get_authority_target(&mut self, authority: H160) -> Option<H160> {
+		Pallet::<T>::delegation(&authority)
+	}
+
 	fn record_external_operation(&mut self, op: evm::ExternalOperation) -> Result<(), ExitError> {
 		let size_limit: u64 = self
 			.metadata()
@@ -964,35 +1181,39 @@ where
 					weight_info.try_record_proof_size_or_fail(ACCOUNT_BASIC_PROOF_SIZE)?
 				}
 				ExternalOperation::AddressCodeRead(address) => {
-					let maybe_record = !recorded.account_codes.contains(&address);
-					// Skip if the address has been already recorded this block
-					if maybe_record {
-						// First we record account emptiness check.
-						// Transfers to EOAs with standard 21_000 gas limit are able to
-						// pay for this pov size.
-						weight_info.try_record_proof_size_or_fail(IS_EMPTY_CHECK_PROOF_SIZE)?;
-						if <AccountCodes<T>>::decode_len(address).unwrap_or(0) == 0 {
-							return Ok(());
-						}
+					// Executing a delegating account also reads the code of its delegate (EIP-7702).
+					let delegate = Pallet::<T>::delegation(&address);
+					for address in core::iter::once(address).chain(delegate) {
+						let maybe_record = !recorded.account_codes.contains(&address);
+						// Skip if the address has been already recorded this block
+						if maybe_record {
+							// First we record account emptiness check.
+							// Transfers to EOAs with standard 21_000 gas limit are able to
+							// pay for this pov size.
+							weight_info.try_record_proof_size_or_fail(IS_EMPTY_CHECK_PROOF_SIZE)?;
+							if <AccountCodes<T>>::decode_len(address).unwrap_or(0) == 0 {
+								continue;
+							}
 
-						weight_info
-							.try_record_proof_size_or_fail(ACCOUNT_CODES_METADATA_PROOF_SIZE)?;
-						if let Some(meta) = <AccountCodesMetadata<T>>::get(address) {
-							weight_info.try_record_proof_size_or_fail(meta.size)?;
-						} else if let Some(remaining_proof_size) =
-							weight_info.remaining_proof_size()
-						{
-							let pre_size = remaining_proof_size.min(size_limit);
-							weight_info.try_record_proof_size_or_fail(pre_size)?;
-
-							let actual_size = Pallet::<T>::account_code_metadata(address).size;
-							if actual_size > pre_size {
-								return Err(ExitError::OutOfGas);
+							weight_info
+								.try_record_proof_size_or_fail(ACCOUNT_CODES_METADATA_PROOF_SIZE)?;
+							if let Some(meta) = <AccountCodesMetadata<T>>::get(address) {
+								weight_info.try_record_proof_size_or_fail(meta.size)?;
+							} else if let Some(remaining_proof_size) =
+								weight_info.remaining_proof_size()
+							{
+								let pre_size = remaining_proof_size.min(size_limit);
+								weight_info.try_record_proof_size_or_fail(pre_size)?;
+
+								let actual_size = Pallet::<T>::account_code_metadata(address).size;
+								if actual_size > pre_size {
+									return Err(ExitError::OutOfGas);
+								}
+								// Refund unused proof size
+								weight_info.refund_proof_size(pre_size.saturating_sub(actual_size));
 							}
-							// Refund unused proof size
-							weight_info.refund_proof_size(pre_size.saturating_sub(actual_size));
+							recorded.account_codes.push(address);
 						}
-						recorded.account_codes.push(address);
 					}
 				}
 				ExternalOperation::IsEmpty => {
@@ -1012,6 +1233,16 @@ where
 		_gas_cost: GasCost,
 		target: evm::gasometer::StorageTarget,
 	) -> Result<(), ExitError> {
+		// The slot written by SSTORE is reported to `T::OnStateChange`.
+		if opcode == Opcode::SSTORE {
+			self.record_state_changes(1)?;
+		}
+		// The storage of an account destroyed by SELFDESTRUCT is cleared at the end of the
+		// transaction.
+		if opcode == Opcode::SUICIDE {
+			self.record_storage_clear()?;
+		}
+
 		// If account code or storage slot is in the overlay it is already accounted for and early exit
 		let accessed_storage: Option<AccessedStorage> = match target {
 			StorageTarget::Address(address) => {
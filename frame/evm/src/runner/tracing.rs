@@ -0,0 +1,769 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! EVM event listeners used to trace transactions executed by the stack runner.
+
+use alloc::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	rc::Rc,
+	vec::Vec,
+};
+use core::cell::RefCell;
+use evm::{
+	gasometer::{
+		tracing::{Event as GasometerEvent, EventListener as GasometerListener},
+		Snapshot,
+	},
+	tracing::{Event as EvmEvent, EventListener as EvmListener},
+	Capture, CreateScheme, ExitReason, ExitSucceed, Opcode,
+};
+use evm_runtime::tracing::{Event as RuntimeEvent, EventListener as RuntimeListener};
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use sp_core::{H160, H256, U256};
+use sp_runtime::DispatchError;
+// Frontier
+use fp_evm::{
+	AccountState, CallFrame, CallLog, CallTracerConfig, CallType, PrestateTrace, StructLog,
+	StructLoggerConfig, StructLoggerTrace, TracerConfig, TransactionTrace,
+};
+
+use crate::{AccountCodes, AccountStorages, Config, Pallet};
+
+/// Largest log data recorded by the call tracer. Logging more cannot be paid for, the memory
+/// expansion alone costing more gas than any block can hold.
+const MAX_LOG_DATA_LEN: usize = 8 * 1024 * 1024;
+
+/// Receives the events emitted while a traced transaction is executed.
+pub trait Tracer {
+	fn on_evm_event(&mut self, _event: EvmEvent) {}
+	fn on_runtime_event(&mut self, _event: RuntimeEvent) {}
+	fn on_gasometer_event(&mut self, _event: GasometerEvent) {}
+}
+
+struct Listener<T>(Rc<RefCell<T>>);
+
+impl<T: Tracer> EvmListener for Listener<T> {
+	fn event(&mut self, event: EvmEvent) {
+		self.0.borrow_mut().on_evm_event(event)
+	}
+}
+
+impl<T: Tracer> RuntimeListener for Listener<T> {
+	fn event(&mut self, event: RuntimeEvent) {
+		self.0.borrow_mut().on_runtime_event(event)
+	}
+}
+
+impl<T: Tracer> GasometerListener for Listener<T> {
+	fn event(&mut self, event: GasometerEvent) {
+		self.0.borrow_mut().on_gasometer_event(event)
+	}
+}
+
+/// Execute `f` with `tracer` listening to all the EVM events it emits, and give the tracer back.
+pub fn using<T: Tracer + 'static, R>(tracer: T, f: impl FnOnce() -> R) -> (R, T) {
+	let tracer = Rc::new(RefCell::new(tracer));
+	let result = {
+		let mut evm_listener = Listener(tracer.clone());
+		let mut runtime_listener = Listener(tracer.clone());
+		let mut gasometer_listener = Listener(tracer.clone());
+		evm::tracing::using(&mut evm_listener, || {
+			evm_runtime::tracing::using(&mut runtime_listener, || {
+				evm::gasometer::tracing::using(&mut gasometer_listener, f)
+			})
+		})
+	};
+	let tracer = match Rc::try_unwrap(tracer) {
+		Ok(tracer) => tracer.into_inner(),
+		Err(_) => unreachable!("listeners are dropped once tracing is over; qed"),
+	};
+	(result, tracer)
+}
+
+/// Execute `f` with the tracer described by `config`, `gas_used` giving the gas used by the
+/// transaction from the result of `f`.
+///
+/// The prestate tracer needs to know the touched accounts before reading their state, so `f` is
+/// first executed in a storage transaction that is rolled back, then executed again untraced.
+pub fn trace<T: Config, R>(
+	config: TracerConfig,
+	mut f: impl FnMut() -> R,
+	gas_used: impl FnOnce(&R) -> u64,
+) -> (R, TransactionTrace) {
+	match config {
+		TracerConfig::StructLogger(config) => {
+			let (result, logger) = using(StructLogger::new(config), f);
+			let trace = logger.into_trace(gas_used(&result));
+			(result, TransactionTrace::StructLogger(trace))
+		}
+		TracerConfig::CallTracer(config) => {
+			let (result, tracer) = using(CallTracer::new(config), f);
+			let trace = tracer.into_trace(gas_used(&result));
+			(result, TransactionTrace::CallTracer(trace))
+		}
+		TracerConfig::PrestateTracer(config) => {
+			let (_, tracer) = using(PrestateTracer::default(), || {
+				with_transaction(|| {
+					f();
+					TransactionOutcome::Rollback(Ok::<(), DispatchError>(()))
+				})
+			});
+			let pre = tracer.state::<T>();
+			let result = f();
+			let post = config.diff_mode.then(|| tracer.state::<T>());
+			(
+				result,
+				TransactionTrace::PrestateTracer(PrestateTrace { pre, post }),
+			)
+		}
+	}
+}
+
+/// Geth's default tracer, logging the machine state before every opcode.
+pub struct StructLogger {
+	config: StructLoggerConfig,
+	depth: u32,
+	/// Address of a frame that was entered but did not execute any opcode yet.
+	entering: Option<H160>,
+	/// Opcode being executed, with the address of its frame.
+	step: Option<(H160, StructLog)>,
+	/// Whether the gas of the current step is still to be recorded.
+	awaiting_cost: bool,
+	storage: BTreeMap<H160, BTreeMap<H256, H256>>,
+	trace: StructLoggerTrace,
+}
+
+impl StructLogger {
+	pub fn new(config: StructLoggerConfig) -> Self {
+		Self {
+			config,
+			depth: 0,
+			entering: None,
+			step: None,
+			awaiting_cost: false,
+			storage: BTreeMap::new(),
+			trace: StructLoggerTrace::default(),
+		}
+	}
+
+	/// Consume the logger, `gas` being the gas used by the transaction.
+	pub fn into_trace(mut self, gas: u64) -> StructLoggerTrace {
+		self.finish_step();
+		self.trace.gas = gas;
+		self.trace
+	}
+
+	fn enter(&mut self, address: H160) {
+		self.entering = Some(address);
+	}
+
+	fn exit(&mut self, reason: &ExitReason, return_value: &[u8]) {
+		if self.depth == 0 {
+			self.trace.failed = !reason.is_succeed();
+			self.trace.return_value = return_value.to_vec();
+		}
+	}
+
+	fn finish_step(&mut self) {
+		self.awaiting_cost = false;
+		if let Some((address, mut log)) = self.step.take() {
+			let op = Opcode(log.op);
+			if !self.config.disable_storage && (op == Opcode::SLOAD || op == Opcode::SSTORE) {
+				log.storage = Some(self.storage.get(&address).cloned().unwrap_or_default());
+			}
+			self.trace.struct_logs.push(log);
+		}
+	}
+}
+
+impl Tracer for StructLogger {
+	fn on_evm_event(&mut self, event: EvmEvent) {
+		match event {
+			EvmEvent::TransactCall { address, .. }
+			| EvmEvent::TransactCreate { address, .. }
+			| EvmEvent::TransactCreate2 { address, .. }
+			| EvmEvent::Create { address, .. } => self.enter(address),
+			EvmEvent::Call { context, .. } => self.enter(context.address),
+			EvmEvent::Exit {
+				reason,
+				return_value,
+			} => {
+				// Frames that never reached the interpreter (precompiles, early failures).
+				if self.entering.take().is_some() {
+					self.exit(reason, return_value);
+				}
+			}
+			_ => {}
+		}
+	}
+
+	fn on_runtime_event(&mut self, event: RuntimeEvent) {
+		match event {
+			RuntimeEvent::Step {
+				context,
+				opcode,
+				position,
+				stack,
+				memory,
+			} => {
+				self.finish_step();
+				if let Some(address) = self.entering.take() {
+					if address == context.address {
+						self.depth += 1;
+					}
+				}
+				let log = StructLog {
+					pc: position.as_ref().map(|pc| *pc as u64).unwrap_or_default(),
+					op: opcode.as_u8(),
+					depth: self.depth,
+					stack: (!self.config.disable_stack).then(|| stack.data().clone()),
+					memory: (!self.config.disable_memory).then(|| memory.data().clone()),
+					..Default::default()
+				};
+				self.step = Some((context.address, log));
+				self.awaiting_cost = true;
+			}
+			RuntimeEvent::StepResult {
+				result,
+				return_value,
+			} => {
+				self.finish_step();
+				if let Err(Capture::Exit(reason)) = result {
+					if self.entering.take().is_none() {
+						self.depth = self.depth.saturating_sub(1);
+					}
+					self.exit(reason, return_value);
+				}
+			}
+			RuntimeEvent::SLoad {
+				address,
+				index,
+				value,
+			}
+			| RuntimeEvent::SStore {
+				address,
+				index,
+				value,
+			} => {
+				self.storage
+					.entry(address)
+					.or_default()
+					.insert(index, value);
+			}
+		}
+	}
+
+	fn on_gasometer_event(&mut self, event: GasometerEvent) {
+		if !self.awaiting_cost {
+			return;
+		}
+		let (cost, snapshot) = match event {
+			GasometerEvent::RecordCost { cost, snapshot } => (cost, snapshot),
+			GasometerEvent::RecordDynamicCost {
+				gas_cost,
+				memory_gas,
+				snapshot,
+				..
+			} => {
+				let memory_cost = snapshot
+					.map(|snapshot| memory_gas.saturating_sub(snapshot.memory_gas))
+					.unwrap_or_default();
+				(gas_cost.saturating_add(memory_cost), snapshot)
+			}
+			_ => return,
+		};
+		if let Some((_, log)) = self.step.as_mut() {
+			log.gas_cost = cost;
+			log.gas = snapshot
+				.map(|snapshot| {
+					snapshot
+						.gas_limit
+						.saturating_sub(snapshot.used_gas)
+						.saturating_sub(snapshot.memory_gas)
+				})
+				.unwrap_or_default();
+		}
+		self.awaiting_cost = false;
+	}
+}
+
+/// Gas left in the frame a gasometer snapshot was taken from.
+fn gas_left(snapshot: &Snapshot) -> u64 {
+	snapshot
+		.gas_limit
+		.saturating_sub(snapshot.used_gas)
+		.saturating_sub(snapshot.memory_gas)
+}
+
+struct OpenFrame {
+	frame: CallFrame,
+	/// Address of the executed context, which differs from `to` for delegate calls.
+	context: H160,
+	/// Whether the frame executed an opcode, frames that did not are closed by `Exit` events.
+	stepped: bool,
+	/// Whether the gas of the frame is known from its own gasometer.
+	metered: bool,
+	gas_left: u64,
+}
+
+/// Geth's `callTracer`, recording the tree of calls made by a transaction.
+pub struct CallTracer {
+	config: CallTracerConfig,
+	/// Open frames, innermost last.
+	stack: Vec<OpenFrame>,
+	/// Whether the transaction frame is still to be matched with its `Call` or `Create` event.
+	binding_root: bool,
+	/// Whether the next gasometer event is the gas given by a parent frame to the frame entered.
+	awaiting_charge: bool,
+	root: Option<CallFrame>,
+}
+
+impl CallTracer {
+	pub fn new(config: CallTracerConfig) -> Self {
+		Self {
+			config,
+			stack: Vec::new(),
+			binding_root: false,
+			awaiting_charge: false,
+			root: None,
+		}
+	}
+
+	/// Consume the tracer, `gas_used` being the gas used by the transaction.
+	pub fn into_trace(mut self, gas_used: u64) -> CallFrame {
+		while !self.stack.is_empty() {
+			self.close(None, Vec::new());
+		}
+		let mut root = self.root.unwrap_or_else(|| {
+			call_frame(CallType::Call, H160::zero(), H160::zero(), None, 0, &[])
+		});
+		root.gas_used = gas_used;
+		root
+	}
+
+	fn open(&mut self, frame: CallFrame, context: H160) {
+		let gas_left = frame.gas;
+		self.stack.push(OpenFrame {
+			frame,
+			context,
+			stepped: false,
+			metered: false,
+			gas_left,
+		});
+	}
+
+	fn open_root(&mut self, frame: CallFrame) {
+		let context = frame.to;
+		self.open(frame, context);
+		self.binding_root = true;
+	}
+
+	/// Enter a sub call, unless it is the one of the transaction frame.
+	fn enter(&mut self, frame: CallFrame, context: H160) {
+		self.awaiting_charge = true;
+		if !core::mem::take(&mut self.binding_root) {
+			self.open(frame, context);
+		}
+	}
+
+	fn close(&mut self, reason: Option<&ExitReason>, output: Vec<u8>) {
+		let Some(OpenFrame {
+			mut frame,
+			gas_left,
+			..
+		}) = self.stack.pop()
+		else {
+			return;
+		};
+		let reason = reason
+			.cloned()
+			.unwrap_or(ExitReason::Succeed(ExitSucceed::Stopped));
+		frame.gas_used = match reason {
+			ExitReason::Succeed(_) | ExitReason::Revert(_) => frame.gas.saturating_sub(gas_left),
+			ExitReason::Error(_) | ExitReason::Fatal(_) => frame.gas,
+		};
+		if !reason.is_succeed() {
+			clear_logs(&mut frame);
+		}
+		frame.output = output;
+		frame.exit_reason = Some(reason);
+		match self.stack.last_mut() {
+			Some(parent) if !self.config.only_top_call => parent.frame.calls.push(frame),
+			Some(_) => {}
+			None => self.root = Some(frame),
+		}
+	}
+
+	fn record_log(&mut self, address: H160, opcode: Opcode, stack: &[H256], memory: &[u8]) {
+		let topics = usize::from(opcode.as_u8() - Opcode::LOG0.as_u8());
+		if stack.len() < topics + 2 {
+			return;
+		}
+		let mut stack = stack.iter().rev();
+		let offset = U256::from_big_endian(&stack.next().expect("length checked above; qed")[..]);
+		let size = U256::from_big_endian(&stack.next().expect("length checked above; qed")[..]);
+		if size > U256::from(MAX_LOG_DATA_LEN) {
+			return;
+		}
+		let topics = stack.take(topics).copied().collect();
+		let mut data = alloc::vec![0u8; size.as_usize()];
+		if offset < U256::from(memory.len()) {
+			let offset = offset.as_usize();
+			let available = data.len().min(memory.len() - offset);
+			data[..available].copy_from_slice(&memory[offset..offset + available]);
+		}
+		let Some(top) = self.stack.last_mut() else {
+			return;
+		};
+		let position = top.frame.calls.len() as u32;
+		top.frame.logs.push(CallLog {
+			address,
+			topics,
+			data,
+			position,
+		});
+	}
+}
+
+fn call_frame(
+	call_type: CallType,
+	from: H160,
+	to: H160,
+	value: Option<U256>,
+	gas: u64,
+	input: &[u8],
+) -> CallFrame {
+	CallFrame {
+		call_type,
+		from,
+		to,
+		value,
+		gas,
+		gas_used: 0,
+		input: input.to_vec(),
+		output: Vec::new(),
+		exit_reason: None,
+		logs: Vec::new(),
+		calls: Vec::new(),
+	}
+}
+
+fn clear_logs(frame: &mut CallFrame) {
+	frame.logs.clear();
+	frame.calls.iter_mut().for_each(clear_logs);
+}
+
+impl Tracer for CallTracer {
+	fn on_evm_event(&mut self, event: EvmEvent) {
+		match event {
+			EvmEvent::TransactCall {
+				caller,
+				address,
+				value,
+				data,
+				gas_limit,
+			} => self.open_root(call_frame(
+				CallType::Call,
+				caller,
+				address,
+				Some(value),
+				gas_limit,
+				data,
+			)),
+			EvmEvent::TransactCreate {
+				caller,
+				value,
+				init_code,
+				gas_limit,
+				address,
+			} => self.open_root(call_frame(
+				CallType::Create,
+				caller,
+				address,
+				Some(value),
+				gas_limit,
+				init_code,
+			)),
+			EvmEvent::TransactCreate2 {
+				caller,
+				value,
+				init_code,
+				gas_limit,
+				address,
+				..
+			} => self.open_root(call_frame(
+				CallType::Create2,
+				caller,
+				address,
+				Some(value),
+				gas_limit,
+				init_code,
+			)),
+			EvmEvent::Call {
+				code_address,
+				transfer,
+				input,
+				target_gas,
+				is_static,
+				context,
+			} => {
+				let (call_type, value) = if is_static {
+					(CallType::StaticCall, None)
+				} else if context.address == code_address {
+					(CallType::Call, Some(context.apparent_value))
+				} else if transfer.is_some() {
+					(CallType::CallCode, Some(context.apparent_value))
+				} else {
+					(CallType::DelegateCall, None)
+				};
+				let from = match call_type {
+					CallType::DelegateCall => context.address,
+					_ => context.caller,
+				};
+				let gas = target_gas.unwrap_or_default();
+				let frame = call_frame(call_type, from, code_address, value, gas, input);
+				self.enter(frame, context.address);
+			}
+			EvmEvent::Create {
+				caller,
+				address,
+				scheme,
+				value,
+				init_code,
+				target_gas,
+			} => {
+				let call_type = match scheme {
+					CreateScheme::Create2 { .. } => CallType::Create2,
+					_ => CallType::Create,
+				};
+				let gas = target_gas.unwrap_or_default();
+				let frame = call_frame(call_type, caller, address, Some(value), gas, init_code);
+				self.enter(frame, address);
+			}
+			EvmEvent::Suicide {
+				address,
+				target,
+				balance,
+			} => {
+				if let Some(top) = self.stack.last_mut() {
+					if !self.config.only_top_call {
+						let frame = call_frame(
+							CallType::SelfDestruct,
+							address,
+							target,
+							Some(balance),
+							0,
+							&[],
+						);
+						top.frame.calls.push(frame);
+					}
+				}
+			}
+			EvmEvent::Exit {
+				reason,
+				return_value,
+			} => {
+				// Frames that never reached the interpreter (precompiles, early failures).
+				if self.stack.last().is_some_and(|top| !top.stepped) {
+					self.close(Some(reason), return_value.to_vec());
+				}
+			}
+			_ => {}
+		}
+	}
+
+	fn on_runtime_event(&mut self, event: RuntimeEvent) {
+		match event {
+			RuntimeEvent::Step {
+				context,
+				opcode,
+				stack,
+				memory,
+				..
+			} => {
+				// A frame that did not step nor exit, while its parent is stepping again.
+				while self.stack.len() > 1
+					&& self
+						.stack
+						.last()
+						.is_some_and(|top| !top.stepped && top.context != context.address)
+				{
+					self.close(None, Vec::new());
+				}
+				if let Some(top) = self.stack.last_mut() {
+					top.stepped = true;
+				}
+				if self.config.with_log
+					&& opcode.as_u8() >= Opcode::LOG0.as_u8()
+					&& opcode.as_u8() <= Opcode::LOG4.as_u8()
+				{
+					self.record_log(context.address, opcode, stack.data(), memory.data());
+				}
+			}
+			RuntimeEvent::StepResult {
+				result: Err(Capture::Exit(reason)),
+				return_value,
+			} => self.close(Some(reason), return_value.to_vec()),
+			_ => {}
+		}
+	}
+
+	fn on_gasometer_event(&mut self, event: GasometerEvent) {
+		if core::mem::take(&mut self.awaiting_charge) {
+			// Paid by the parent frame, the cost is the gas given to the frame entered.
+			if let (true, GasometerEvent::RecordCost { cost, .. }) = (self.stack.len() > 1, event) {
+				if let Some(top) = self.stack.last_mut() {
+					top.frame.gas = cost;
+					top.gas_left = cost;
+				}
+			}
+			return;
+		}
+		let Some(top) = self.stack.last_mut() else {
+			return;
+		};
+		let (gas_left, snapshot) = match event {
+			GasometerEvent::RecordCost {
+				cost,
+				snapshot: Some(snapshot),
+			} => (gas_left(&snapshot).saturating_sub(cost), snapshot),
+			GasometerEvent::RecordDynamicCost {
+				gas_cost,
+				memory_gas,
+				snapshot: Some(snapshot),
+				..
+			} => {
+				let memory_cost = memory_gas.saturating_sub(snapshot.memory_gas);
+				(
+					gas_left(&snapshot)
+						.saturating_sub(gas_cost)
+						.saturating_sub(memory_cost),
+					snapshot,
+				)
+			}
+			GasometerEvent::RecordStipend {
+				stipend,
+				snapshot: Some(snapshot),
+			} => (gas_left(&snapshot).saturating_add(stipend), snapshot),
+			_ => return,
+		};
+		// The gas given to a sub call includes the stipend of value transfers.
+		if !top.metered && self.stack.len() > 1 {
+			top.frame.gas = snapshot.gas_limit;
+		}
+		top.metered = true;
+		top.gas_left = gas_left;
+	}
+}
+
+/// Geth's `prestateTracer`, recording the accounts and storage slots touched by a transaction.
+#[derive(Default)]
+pub struct PrestateTracer {
+	accounts: BTreeSet<H160>,
+	storage: BTreeMap<H160, BTreeSet<H256>>,
+}
+
+impl PrestateTracer {
+	/// Current state of the touched accounts.
+	pub fn state<T: Config>(&self) -> BTreeMap<H160, AccountState> {
+		self.accounts
+			.iter()
+			.map(|address| {
+				let (account, _) = Pallet::<T>::account_basic(address);
+				let storage = self
+					.storage
+					.get(address)
+					.map(|slots| {
+						slots
+							.iter()
+							.map(|slot| (*slot, AccountStorages::<T>::get(address, slot)))
+							.collect()
+					})
+					.unwrap_or_default();
+				let state = AccountState {
+					balance: account.balance,
+					nonce: account.nonce,
+					code: AccountCodes::<T>::get(address),
+					storage,
+				};
+				(*address, state)
+			})
+			.collect()
+	}
+}
+
+impl Tracer for PrestateTracer {
+	fn on_evm_event(&mut self, event: EvmEvent) {
+		match event {
+			EvmEvent::TransactCall {
+				caller, address, ..
+			}
+			| EvmEvent::TransactCreate {
+				caller, address, ..
+			}
+			| EvmEvent::TransactCreate2 {
+				caller, address, ..
+			}
+			| EvmEvent::Create {
+				caller, address, ..
+			} => {
+				self.accounts.insert(caller);
+				self.accounts.insert(address);
+			}
+			EvmEvent::Call {
+				code_address,
+				context,
+				..
+			} => {
+				self.accounts.insert(code_address);
+				self.accounts.insert(context.address);
+			}
+			EvmEvent::Suicide {
+				address, target, ..
+			} => {
+				self.accounts.insert(address);
+				self.accounts.insert(target);
+			}
+			_ => {}
+		}
+	}
+
+	fn on_runtime_event(&mut self, event: RuntimeEvent) {
+		match event {
+			RuntimeEvent::Step { opcode, stack, .. } => {
+				if [
+					Opcode::BALANCE,
+					Opcode::EXTCODESIZE,
+					Opcode::EXTCODECOPY,
+					Opcode::EXTCODEHASH,
+				]
+				.contains(&opcode)
+				{
+					if let Some(word) = stack.data().last() {
+						self.accounts.insert(H160::from(*word));
+					}
+				}
+			}
+			RuntimeEvent::SLoad { address, index, .. }
+			| RuntimeEvent::SStore { address, index, .. } => {
+				self.accounts.insert(address);
+				self.storage.entry(address).or_default().insert(index);
+			}
+			_ => {}
+		}
+	}
+}
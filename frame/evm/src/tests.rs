@@ -22,7 +22,10 @@ use frame_support::{
 	traits::{LockIdentifier, LockableCurrency, WithdrawReasons},
 };
 use sp_runtime::BuildStorage;
-use std::{collections::BTreeMap, str::FromStr};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	str::FromStr,
+};
 
 mod proof_size_test {
 	use super::*;
@@ -104,6 +107,7 @@ mod proof_size_test {
 			Some(0),
 			&<Test as Config>::config().clone(),
 		)
+		.map(|(info, _)| info)
 	}
 
 	fn create_proof_size_test_contract(
@@ -125,6 +129,7 @@ mod proof_size_test {
 			Some(0),
 			&<Test as Config>::config().clone(),
 		)
+		.map(|(info, _)| info)
 	}
 
 	#[test]
@@ -221,6 +226,7 @@ mod proof_size_test {
 				None,
 				None,
 				Vec::new(),
+				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
@@ -276,6 +282,7 @@ mod proof_size_test {
 				None,
 				None,
 				Vec::new(),
+				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
@@ -330,6 +337,7 @@ mod proof_size_test {
 				None,
 				None,
 				Vec::new(),
+				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
@@ -379,6 +387,7 @@ mod proof_size_test {
 				None,
 				None,
 				Vec::new(),
+				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
@@ -432,6 +441,7 @@ mod proof_size_test {
 				None,
 				None,
 				Vec::new(),
+				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
@@ -500,6 +510,7 @@ mod proof_size_test {
 				None,
 				None,
 				Vec::new(),
+				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
@@ -558,6 +569,7 @@ mod proof_size_test {
 				None,
 				None,
 				Vec::new(),
+				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
@@ -599,6 +611,7 @@ mod proof_size_test {
 				None,
 				None,
 				Vec::new(),
+				Vec::new(),
 				true, // transactional
 				true, // must be validated
 				Some(weight_limit),
@@ -1061,6 +1074,7 @@ fn runner_non_transactional_calls_with_non_balance_accounts_is_ok_without_gas_pr
 			None,
 			None,
 			Vec::new(),
+			Vec::new(),
 			false, // non-transactional
 			true,  // must be validated
 			None,
@@ -1097,6 +1111,7 @@ fn runner_non_transactional_calls_with_non_balance_accounts_is_err_with_gas_pric
 			None,
 			None,
 			Vec::new(),
+			Vec::new(),
 			false, // non-transactional
 			true,  // must be validated
 			None,
@@ -1121,6 +1136,7 @@ fn runner_transactional_call_with_zero_gas_price_fails() {
 			None,
 			None,
 			Vec::new(),
+			Vec::new(),
 			true, // transactional
 			true, // must be validated
 			None,
@@ -1145,6 +1161,7 @@ fn runner_max_fee_per_gas_gte_max_priority_fee_per_gas() {
 			Some(U256::from(2_000_000_000)),
 			None,
 			Vec::new(),
+			Vec::new(),
 			true, // transactional
 			true, // must be validated
 			None,
@@ -1162,6 +1179,7 @@ fn runner_max_fee_per_gas_gte_max_priority_fee_per_gas() {
 			Some(U256::from(2_000_000_000)),
 			None,
 			Vec::new(),
+			Vec::new(),
 			false, // non-transactional
 			true,  // must be validated
 			None,
@@ -1187,6 +1205,7 @@ fn eip3607_transaction_from_contract() {
 			None,
 			None,
 			Vec::new(),
+			Vec::new(),
 			true,  // transactional
 			false, // not sure be validated
 			None,
@@ -1212,6 +1231,7 @@ fn eip3607_transaction_from_contract() {
 			None,
 			None,
 			Vec::new(),
+			Vec::new(),
 			false, // non-transactional
 			true,  // must be validated
 			None,
@@ -1258,3 +1278,629 @@ fn metadata_empty_dont_code_gets_cached() {
 		assert!(<AccountCodesMetadata<Test>>::get(address).is_none());
 	});
 }
+
+#[test]
+fn transient_storage_is_transaction_scoped() {
+	new_test_ext().execute_with(|| {
+		let address = H160::repeat_byte(0xbb);
+		// TLOAD(0) -> mem[0..32]; TSTORE(0, 42); TLOAD(0) -> mem[32..64]; RETURN(0, 64)
+		EVM::create_account(
+			address,
+			hex::decode("60005c600052602a60005d60005c60205260406000f3").unwrap(),
+		);
+
+		let mut expected = vec![0u8; 64];
+		expected[63] = 42;
+		for _ in 0..2 {
+			let info = <Test as Config>::Runner::call(
+				H160::default(),
+				address,
+				Vec::new(),
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				Vec::new(),
+				false, // non-transactional
+				true,  // must be validated
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+			.expect("call succeeds");
+			assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+			// The value stored in the previous call is not visible anymore.
+			assert_eq!(info.value, expected);
+		}
+		assert_eq!(
+			<AccountStorages<Test>>::iter_prefix(address).count(),
+			0,
+			"transient storage must not leak into persistent storage"
+		);
+	});
+}
+
+#[test]
+fn block_overrides_are_seen_by_the_evm() {
+	new_test_ext().execute_with(|| {
+		let address = H160::repeat_byte(0xbb);
+		// Return NUMBER, TIMESTAMP, COINBASE, BASEFEE, GASLIMIT, PREVRANDAO and BLOCKHASH(5).
+		EVM::create_account(
+			address,
+			hex::decode(
+				"4360005242602052416040524860605245608052\
+				 4460a05260054060c05260e06000f3",
+			)
+			.unwrap(),
+		);
+		let call = || {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				address,
+				Vec::new(),
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				Vec::new(),
+				false, // non-transactional
+				true,  // must be validated
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+			.expect("call succeeds")
+		};
+		let overrides = BlockOverrides {
+			number: Some(U256::from(100)),
+			timestamp: Some(1_000),
+			coinbase: Some(H160::repeat_byte(0xcc)),
+			base_fee: Some(U256::from(7)),
+			gas_limit: Some(U256::from(30_000_000)),
+			random: Some(H256::repeat_byte(0xdd)),
+			block_hashes: BTreeMap::from([(U256::from(5), H256::repeat_byte(0xee))]),
+		};
+
+		let info = crate::runner::with_block_overrides(overrides, call);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		let expected = [
+			H256::from_low_u64_be(100),
+			H256::from_low_u64_be(1_000),
+			H256::from(H160::repeat_byte(0xcc)),
+			H256::from_low_u64_be(7),
+			H256::from_low_u64_be(30_000_000),
+			H256::repeat_byte(0xdd),
+			H256::repeat_byte(0xee),
+		]
+		.map(|word| word.0)
+		.concat();
+		assert_eq!(info.value, expected);
+
+		// The overrides do not outlive `with_block_overrides`.
+		assert_ne!(call().value, expected);
+	});
+}
+
+#[test]
+fn accesses_are_recorded_without_precompiles() {
+	new_test_ext().execute_with(|| {
+		let address = H160::repeat_byte(0xbb);
+		let other = H160::repeat_byte(0xaa);
+		// SLOAD(2), BALANCE(0xaa..aa) and EXTCODESIZE(1), the latter being a precompile.
+		EVM::create_account(
+			address,
+			hex::decode(
+				"60025450\
+				 73aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa3150\
+				 60013b5000",
+			)
+			.unwrap(),
+		);
+
+		let (info, accesses) = crate::runner::record_accesses::<Test, _>(|| {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				address,
+				Vec::new(),
+				U256::zero(),
+				1000000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				Vec::new(),
+				false, // non-transactional
+				true,  // must be validated
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+			.expect("call succeeds")
+		});
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(
+			accesses.get(&address),
+			Some(&BTreeSet::from([H256::from_low_u64_be(2)]))
+		);
+		assert_eq!(accesses.get(&other), Some(&BTreeSet::new()));
+		assert!(!accesses.contains_key(&H160::from_low_u64_be(1)));
+	});
+}
+
+#[test]
+fn call_contract_returns_output_logs_and_weight() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let address = H160::repeat_byte(0xbb);
+		// MSTORE(0, 42); LOG1(0, 32, 1); RETURN(0, 32)
+		EVM::create_account(
+			address,
+			hex::decode("602a600052600160206000a160206000f3").unwrap(),
+		);
+
+		let info = EVM::call_contract(
+			H160::default(),
+			address,
+			Vec::new(),
+			U256::zero(),
+			1000000,
+			false,
+		)
+		.expect("call succeeds");
+
+		assert!(info.is_success());
+		assert_eq!(info.output, H256::from_low_u64_be(42).as_bytes());
+		assert_eq!(info.logs.len(), 1);
+		assert_eq!(info.logs[0].address, address);
+		assert_eq!(info.logs[0].topics, vec![H256::from_low_u64_be(1)]);
+		assert_eq!(
+			info.weight,
+			EVM::call_contract_weight(info.used_gas.as_u64())
+		);
+		System::assert_last_event(RuntimeEvent::EVM(crate::Event::<Test>::Executed {
+			address,
+		}));
+	});
+}
+
+#[test]
+fn transactional_call_contract_pays_base_fee() {
+	new_test_ext().execute_with(|| {
+		let source = H160::default();
+		let address = H160::repeat_byte(0xbb);
+		// MSTORE(0, 42); RETURN(0, 32)
+		EVM::create_account(address, hex::decode("602a60005260206000f3").unwrap());
+		let (before, _) = EVM::account_basic(&source);
+
+		let info = EVM::call_contract(source, address, Vec::new(), U256::zero(), 1000000, true)
+			.expect("call succeeds");
+
+		assert!(info.is_success());
+		assert_eq!(info.output, H256::from_low_u64_be(42).as_bytes());
+
+		let (base_fee, _) = <Test as Config>::FeeCalculator::min_gas_price();
+		let (after, _) = EVM::account_basic(&source);
+		assert_eq!(after.nonce, before.nonce + 1);
+		assert_eq!(after.balance, before.balance - info.used_gas * base_fee);
+	});
+}
+
+#[test]
+fn call_contract_from_contract_requires_non_transactional() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		let result = EVM::call_contract(
+			contract,
+			H160::repeat_byte(0xbb),
+			Vec::new(),
+			U256::zero(),
+			1000000,
+			true,
+		);
+		assert_eq!(
+			result.map_err(|e| e.error),
+			Err(Error::<Test>::TransactionMustComeFromEOA.into())
+		);
+
+		assert!(EVM::call_contract(
+			contract,
+			H160::repeat_byte(0xbb),
+			Vec::new(),
+			U256::zero(),
+			1000000,
+			false,
+		)
+		.is_ok());
+	});
+}
+
+#[test]
+fn selfdestruct_in_creation_transaction_clears_account() {
+	new_test_ext().execute_with(|| {
+		// SSTORE(0, 1); SELFDESTRUCT(CALLER)
+		let (info, _) = <Test as Config>::Runner::create(
+			H160::default(),
+			hex::decode("600160005533ff").unwrap(),
+			U256::zero(),
+			1000000,
+			None,
+			None,
+			None,
+			Vec::new(),
+			false, // non-transactional
+			true,  // must be validated
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		)
+		.expect("create succeeds");
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Suicided));
+
+		let address = info.value;
+		assert!(!EVM::is_account_suicided(&address));
+		assert_eq!(<AccountStorages<Test>>::iter_prefix(address).count(), 0);
+	});
+}
+
+#[test]
+fn selfdestruct_in_creation_transaction_clears_bounded_storage() {
+	new_test_ext().execute_with(|| {
+		SuicideQuickClearLimit::set(1);
+		// SSTORE(0, 1); SSTORE(1, 1); SELFDESTRUCT(CALLER)
+		let (info, _) = <Test as Config>::Runner::create(
+			H160::default(),
+			hex::decode("6001600055600160015533ff").unwrap(),
+			U256::zero(),
+			1000000,
+			None,
+			None,
+			None,
+			Vec::new(),
+			false, // non-transactional
+			true,  // must be validated
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		)
+		.expect("create succeeds");
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Suicided));
+
+		// The slot left over is for the storage cleaner.
+		let address = info.value;
+		assert!(EVM::is_account_suicided(&address));
+		assert_eq!(<AccountStorages<Test>>::iter_prefix(address).count(), 1);
+	});
+}
+
+#[test]
+fn reverted_create_returns_revert_output() {
+	new_test_ext().execute_with(|| {
+		// MSTORE(0, 42) REVERT(0, 32)
+		let (info, output) = <Test as Config>::Runner::create(
+			H160::default(),
+			hex::decode("602a60005260206000fd").unwrap(),
+			U256::zero(),
+			1000000,
+			None,
+			None,
+			None,
+			Vec::new(),
+			false, // non-transactional
+			true,  // must be validated
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		)
+		.expect("create succeeds");
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+		assert_eq!(U256::from_big_endian(&output), U256::from(42));
+	});
+}
+
+#[test]
+fn selfdestruct_of_existing_contract_keeps_account() {
+	new_test_ext().execute_with(|| {
+		let address = H160::repeat_byte(0xcc);
+		// SELFDESTRUCT(CALLER)
+		let code = hex::decode("33ff").unwrap();
+		EVM::create_account(address, code.clone());
+		<AccountStorages<Test>>::insert(address, H256::zero(), H256::repeat_byte(1));
+
+		let info = <Test as Config>::Runner::call(
+			H160::default(),
+			address,
+			Vec::new(),
+			U256::zero(),
+			1000000,
+			None,
+			None,
+			None,
+			Vec::new(),
+			Vec::new(),
+			false, // non-transactional
+			true,  // must be validated
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		)
+		.expect("call succeeds");
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Suicided));
+
+		// EIP-6780: the contract was not created in this transaction, so it is not destroyed.
+		assert!(!EVM::is_account_suicided(&address));
+		assert_eq!(<AccountCodes<Test>>::get(address), code);
+		assert_eq!(
+			<AccountStorages<Test>>::get(address, H256::zero()),
+			H256::repeat_byte(1)
+		);
+	});
+}
+
+#[test]
+fn fork_activation_works() {
+	assert!(!ForkActivation::Block(10).is_active(9, u64::MAX));
+	assert!(ForkActivation::Block(10).is_active(10, 0));
+	assert!(!ForkActivation::Timestamp(1_000).is_active(u64::MAX, 999));
+	assert!(ForkActivation::Timestamp(1_000).is_active(0, 1_000));
+}
+
+#[test]
+fn evm_config_follows_fork_schedule() {
+	new_test_ext().execute_with(|| {
+		assert!(core::ptr::eq(EVM::evm_config_at(0, 0), &SHANGHAI_CONFIG));
+		assert!(core::ptr::eq(EVM::evm_config_at(0, 999), &SHANGHAI_CONFIG));
+		assert!(core::ptr::eq(EVM::evm_config_at(0, 1_000), &CANCUN_CONFIG));
+
+		assert!(core::ptr::eq(EVM::evm_config(), &SHANGHAI_CONFIG));
+		// Timestamps are in milliseconds.
+		Timestamp::set_timestamp(1_000_000);
+		assert!(core::ptr::eq(EVM::evm_config(), &CANCUN_CONFIG));
+	});
+}
+
+fn call_with_authorizations(
+	target: H160,
+	authorization_list: fp_evm::AuthorizationList,
+) -> fp_evm::CallInfo {
+	<Test as Config>::Runner::call(
+		H160::default(),
+		target,
+		Vec::new(),
+		U256::zero(),
+		1000000,
+		None,
+		None,
+		None,
+		Vec::new(),
+		authorization_list,
+		false, // non-transactional
+		true,  // must be validated
+		None,
+		None,
+		&<Test as Config>::config().clone(),
+	)
+	.expect("call succeeds")
+}
+
+#[test]
+fn authorization_delegates_code() {
+	new_test_ext().execute_with(|| {
+		let authority = H160::repeat_byte(0xaa);
+		let delegate = H160::repeat_byte(0xbb);
+		// MSTORE(0, 42) RETURN(0, 32)
+		EVM::create_account(delegate, hex::decode("602a60005260206000f3").unwrap());
+
+		let info = call_with_authorizations(
+			authority,
+			vec![fp_evm::Authorization {
+				chain_id: 0,
+				address: delegate,
+				nonce: U256::zero(),
+				authority: Some(authority),
+			}],
+		);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(U256::from_big_endian(&info.value), U256::from(42));
+		assert_eq!(
+			<AccountCodes<Test>>::get(authority),
+			fp_evm::delegation_designator(delegate)
+		);
+		assert_eq!(EVM::delegation(&authority), Some(delegate));
+		assert!(!EVM::has_contract_code(&authority));
+		assert_eq!(EVM::account_basic(&authority).0.nonce, U256::one());
+
+		// The zero address clears the delegation.
+		let info = call_with_authorizations(
+			authority,
+			vec![fp_evm::Authorization {
+				chain_id: 0,
+				address: H160::zero(),
+				nonce: U256::one(),
+				authority: Some(authority),
+			}],
+		);
+		assert!(info.value.is_empty());
+		assert!(<AccountCodes<Test>>::get(authority).is_empty());
+		assert_eq!(EVM::account_basic(&authority).0.nonce, U256::from(2));
+	});
+}
+
+#[test]
+fn extcode_opcodes_read_delegation_designator() {
+	new_test_ext().execute_with(|| {
+		let authority = H160::repeat_byte(0xaa);
+		let delegate = H160::repeat_byte(0xbb);
+		let probe = H160::repeat_byte(0xcc);
+		// MSTORE(0, 42) RETURN(0, 32)
+		EVM::create_account(delegate, hex::decode("602a60005260206000f3").unwrap());
+		EVM::set_delegation(authority, delegate);
+
+		// MSTORE(0, EXTCODESIZE(authority))
+		// MSTORE(32, EXTCODEHASH(authority))
+		// EXTCODECOPY(authority, 64, 0, 23)
+		// RETURN(0, 96)
+		let target = hex::encode(authority);
+		let code = format!(
+			"73{target}3b600052\
+			73{target}3f602052\
+			60176000604073{target}3c\
+			60606000f3"
+		);
+		EVM::create_account(probe, hex::decode(code).unwrap());
+
+		let info = call_with_authorizations(probe, Vec::new());
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+
+		let designator = fp_evm::delegation_designator(delegate);
+		assert_eq!(
+			U256::from_big_endian(&info.value[..32]),
+			U256::from(designator.len())
+		);
+		assert_eq!(
+			H256::from_slice(&info.value[32..64]),
+			H256::from(sp_io::hashing::keccak_256(&designator))
+		);
+		assert_eq!(&info.value[64..64 + designator.len()], &designator[..]);
+
+		// Calling the authority still executes the code of the delegate.
+		let info = call_with_authorizations(authority, Vec::new());
+		assert_eq!(U256::from_big_endian(&info.value), U256::from(42));
+	});
+}
+
+#[test]
+fn invalid_authorizations_are_skipped() {
+	new_test_ext().execute_with(|| {
+		let authority = H160::repeat_byte(0xaa);
+		let contract = H160::repeat_byte(0xcc);
+		let delegate = H160::repeat_byte(0xbb);
+		EVM::create_account(contract, hex::decode("00").unwrap());
+
+		let authorization = fp_evm::Authorization {
+			chain_id: 0,
+			address: delegate,
+			nonce: U256::zero(),
+			authority: Some(authority),
+		};
+		let invalid = vec![
+			// Nonce mismatch.
+			fp_evm::Authorization {
+				nonce: U256::one(),
+				..authorization.clone()
+			},
+			// Another chain.
+			fp_evm::Authorization {
+				chain_id: u64::MAX,
+				..authorization.clone()
+			},
+			// Signature could not be recovered.
+			fp_evm::Authorization {
+				authority: None,
+				..authorization.clone()
+			},
+			// Authority is a contract.
+			fp_evm::Authorization {
+				authority: Some(contract),
+				..authorization
+			},
+		];
+
+		let info = call_with_authorizations(authority, invalid);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert!(<AccountCodes<Test>>::get(authority).is_empty());
+		assert_eq!(EVM::account_basic(&authority).0.nonce, U256::zero());
+		assert_eq!(<AccountCodes<Test>>::get(contract), vec![0x00]);
+	});
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn struct_logger_traces_opcodes() {
+	use crate::runner::tracing::{using, StructLogger};
+
+	new_test_ext().execute_with(|| {
+		let contract = H160::repeat_byte(0xbb);
+		// MSTORE(0, 42) RETURN(0, 32)
+		EVM::create_account(contract, hex::decode("602a60005260206000f3").unwrap());
+
+		let (info, logger) = using(StructLogger::new(Default::default()), || {
+			call_with_authorizations(contract, Vec::new())
+		});
+		let trace = logger.into_trace(0);
+		assert!(!trace.failed);
+		assert_eq!(trace.return_value, info.value);
+
+		let steps = trace
+			.struct_logs
+			.iter()
+			.map(|log| (log.pc, log.op, log.depth))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			steps,
+			vec![
+				(0, 0x60, 1),
+				(2, 0x60, 1),
+				(4, 0x52, 1),
+				(5, 0x60, 1),
+				(7, 0x60, 1),
+				(9, 0xf3, 1)
+			]
+		);
+		assert_eq!(trace.struct_logs[0].gas_cost, 3);
+		assert_eq!(
+			trace.struct_logs[1].stack,
+			Some(vec![H256::from_low_u64_be(42)])
+		);
+		assert_eq!(trace.struct_logs[5].memory.as_ref().map(Vec::len), Some(32));
+	});
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn call_tracer_records_sub_calls() {
+	use crate::runner::tracing::{using, CallTracer};
+	use fp_evm::{CallTracerConfig, CallType};
+
+	new_test_ext().execute_with(|| {
+		let contract = H160::repeat_byte(0xbb);
+		let callee = H160::repeat_byte(0xcc);
+		// CALL(0xffff, callee, 0, 0, 0, 0, 0) STOP
+		EVM::create_account(
+			contract,
+			hex::decode("6000600060006000600073cccccccccccccccccccccccccccccccccccccccc61fffff100")
+				.unwrap(),
+		);
+		// LOG0(0, 0) STOP
+		EVM::create_account(callee, hex::decode("60006000a000").unwrap());
+
+		let config = CallTracerConfig {
+			only_top_call: false,
+			with_log: true,
+		};
+		let (info, tracer) = using(CallTracer::new(config), || {
+			call_with_authorizations(contract, Vec::new())
+		});
+		assert!(info.exit_reason.is_succeed());
+		let trace = tracer.into_trace(21_000);
+		assert_eq!(trace.call_type, CallType::Call);
+		assert_eq!(trace.to, contract);
+		assert_eq!(trace.gas, 1000000);
+		assert_eq!(trace.gas_used, 21_000);
+		assert_eq!(trace.calls.len(), 1);
+
+		let sub_call = &trace.calls[0];
+		assert_eq!(sub_call.call_type, CallType::Call);
+		assert_eq!(sub_call.from, contract);
+		assert_eq!(sub_call.to, callee);
+		assert_eq!(sub_call.gas, 0xffff);
+		// PUSH1, PUSH1, LOG0
+		assert_eq!(sub_call.gas_used, 3 + 3 + 375);
+		assert_eq!(sub_call.logs.len(), 1);
+		assert_eq!(sub_call.logs[0].address, callee);
+		assert!(sub_call.logs[0].data.is_empty());
+	});
+}
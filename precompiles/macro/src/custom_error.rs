@@ -0,0 +1,106 @@
+// This file is part of Frontier.
+
+// Copyright (c) Moonsong Labs.
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use sp_crypto_hashing::keccak_256;
+use syn::{parse_macro_input, spanned::Spanned, ItemStruct, LitStr};
+
+pub fn main(attr: TokenStream, input: TokenStream) -> TokenStream {
+	let signature_lit = parse_macro_input!(attr as LitStr);
+	let item = parse_macro_input!(input as ItemStruct);
+	let ItemStruct {
+		ident,
+		generics,
+		fields,
+		..
+	} = &item;
+
+	if !generics.params.is_empty() {
+		return quote_spanned! { generics.span() =>
+			compile_error!("Custom errors cannot be generic");
+		}
+		.into();
+	}
+
+	let signature = signature_lit.value();
+	let Some((name, args)) = signature
+		.strip_suffix(')')
+		.and_then(|signature| signature.split_once('('))
+		.filter(|(name, _)| !name.is_empty())
+	else {
+		return quote_spanned! { signature_lit.span() =>
+			compile_error!("Error signature must have form \"Foo(arg1,arg2,...)\"");
+		}
+		.into();
+	};
+
+	let digest = keccak_256(signature.as_bytes());
+	let selector = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+
+	let fields_ty: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+	let fields_access: Vec<_> = fields
+		.iter()
+		.enumerate()
+		.map(|(i, f)| match &f.ident {
+			Some(ident) => quote!(#ident),
+			None => {
+				let index = syn::Index::from(i);
+				quote!(#index)
+			}
+		})
+		.collect();
+
+	let args_lit = LitStr::new(&format!("({args})"), signature_lit.span());
+	let test_name = format_ident!("__{}_test_solidity_error_signature", ident);
+	let error_name = format!("{name} error");
+
+	quote! {
+		#item
+
+		impl ::precompile_utils::solidity::revert::CustomError for #ident {
+			const SELECTOR: u32 = #selector;
+
+			fn to_encoded_bytes(self) -> ::precompile_utils::__alloc::vec::Vec<u8> {
+				::precompile_utils::solidity::encode_with_selector(
+					Self::SELECTOR,
+					(#(self.#fields_access,)*),
+				)
+			}
+		}
+
+		impl From<#ident> for ::fp_evm::PrecompileFailure {
+			fn from(error: #ident) -> Self {
+				::precompile_utils::solidity::revert::revert_with_error(error)
+			}
+		}
+
+		#[test]
+		#[allow(non_snake_case)]
+		fn #test_name() {
+			use ::precompile_utils::solidity::Codec;
+			assert_eq!(
+				#args_lit,
+				<(#(#fields_ty,)*)>::signature(),
+				"{} signature doesn't match (left: attribute, right: computed from Rust types)",
+				#error_name
+			);
+		}
+	}
+	.into()
+}
@@ -32,6 +32,10 @@ pub fn main(input: TokenStream) -> TokenStream {
 		..
 	} = parse_macro_input!(input as DeriveInput);
 
+	if let syn::Data::Enum(data) = data {
+		return derive_enum(ident, generics, data);
+	}
+
 	let syn::Data::Struct(syn::DataStruct {
 		fields: syn::Fields::Named(fields),
 		..
@@ -134,3 +138,79 @@ pub fn main(input: TokenStream) -> TokenStream {
 	}
 	.into()
 }
+
+/// Fieldless enums are encoded like Solidity enums, as the `uint8` index of the variant.
+fn derive_enum(ident: Ident, generics: syn::Generics, data: syn::DataEnum) -> TokenStream {
+	let variants = data.variants;
+
+	if variants.is_empty() {
+		return quote_spanned! { ident.span() =>
+			compile_error!("Codec can only be derived for enums with at least one variant");
+		}
+		.into();
+	}
+
+	if variants.len() > 256 {
+		return quote_spanned! { ident.span() =>
+			compile_error!("Codec can only be derived for enums with at most 256 variants");
+		}
+		.into();
+	}
+
+	if let Some(variant) = variants
+		.iter()
+		.find(|v| !matches!(v.fields, syn::Fields::Unit))
+	{
+		return quote_spanned! { variant.ident.span() =>
+			compile_error!("Codec can only be derived for enums without fields");
+		}
+		.into();
+	}
+
+	if let Some((_, discriminant)) = variants.iter().find_map(|v| v.discriminant.as_ref()) {
+		return quote_spanned! { discriminant.span() =>
+			compile_error!("Codec cannot be derived for enums with explicit discriminants, as \
+				Solidity encodes the index of the variant");
+		}
+		.into();
+	}
+
+	let variants_ident: Vec<_> = variants.iter().map(|v| &v.ident).collect();
+	let variants_index: Vec<_> = (0..variants.len()).map(|i| i as u8).collect();
+	let ident_lit = LitStr::new(&ident.to_string(), ident.span());
+
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	quote! {
+		impl #impl_generics ::precompile_utils::solidity::codec::Codec for #ident #ty_generics
+		#where_clause {
+			fn read(
+				reader: &mut ::precompile_utils::solidity::codec::Reader
+			) -> ::precompile_utils::solidity::revert::MayRevert<Self> {
+				match reader.read::<u8>()? {
+					#(#variants_index => Ok(Self::#variants_ident),)*
+					_ => Err(
+						::precompile_utils::solidity::revert::RevertReason::value_is_too_large(
+							#ident_lit
+						).into()
+					),
+				}
+			}
+
+			fn write(writer: &mut ::precompile_utils::solidity::codec::Writer, value: Self) {
+				let index: u8 = match value {
+					#(Self::#variants_ident => #variants_index,)*
+				};
+				::precompile_utils::solidity::codec::Codec::write(writer, index);
+			}
+
+			fn has_static_size() -> bool {
+				true
+			}
+
+			fn signature() -> String {
+				String::from("uint8")
+			}
+		}
+	}
+	.into()
+}
@@ -24,6 +24,7 @@ use quote::{quote, quote_spanned};
 use sp_crypto_hashing::keccak_256;
 use syn::{parse_macro_input, spanned::Spanned, Expr, Ident, ItemType, Lit, LitStr};
 
+mod custom_error;
 mod derive_codec;
 mod precompile;
 mod precompile_name_from_address;
@@ -72,6 +73,11 @@ pub fn precompile_name_from_address(attr: TokenStream, input: TokenStream) -> To
 	precompile_name_from_address::main(attr, input)
 }
 
+#[proc_macro_attribute]
+pub fn custom_error(attr: TokenStream, input: TokenStream) -> TokenStream {
+	custom_error::main(attr, input)
+}
+
 #[proc_macro_derive(Codec)]
 pub fn derive_codec(input: TokenStream) -> TokenStream {
 	derive_codec::main(input)
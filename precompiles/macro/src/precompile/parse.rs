@@ -277,6 +277,7 @@ impl Precompile {
 				}
 			};
 			let ty = input.ty.as_ref().clone();
+			self.check_const_parameter_usage(&ty)?;
 			self.check_type_parameter_usage(&ty)?;
 
 			arguments.push(Argument { ident, ty })
@@ -518,6 +519,23 @@ impl Precompile {
 
 		let local_args_type = format!("({}", split[1]); // add back initial parenthesis
 
+		// Solidity computes selectors from canonical types only, aliases would thus produce a
+		// selector that no caller uses.
+		for ty in local_args_type.split(['(', ')', ',', '[', ']']) {
+			let canonical = match ty.trim() {
+				"uint" => "uint256",
+				"int" => "int256",
+				"byte" => "bytes1",
+				_ => continue,
+			};
+
+			let msg = format!(
+				"Type `{}` is an alias and must be written `{canonical}` in the selector",
+				ty.trim()
+			);
+			return Err(syn::Error::new(signature_lit.span(), msg));
+		}
+
 		// If there are multiple public attributes we check that they all have
 		// the same type.
 		if let Some(ref args_type) = solidity_arguments_type {
@@ -544,6 +562,41 @@ impl Precompile {
 		Ok(selector)
 	}
 
+	/// Check that the provided type doesn't depend on one of the const parameters of the
+	/// precompile. Unlike length bounds, array lengths and `FixedBytes` sizes are part of the
+	/// Solidity type, so this check is never skipped.
+	fn check_const_parameter_usage(&self, ty: &syn::Type) -> syn::Result<()> {
+		fn find_ident(
+			tokens: proc_macro2::TokenStream,
+			idents: &[&syn::Ident],
+		) -> Option<syn::Ident> {
+			tokens.into_iter().find_map(|token| match token {
+				proc_macro2::TokenTree::Ident(ident) if idents.contains(&&ident) => Some(ident),
+				proc_macro2::TokenTree::Group(group) => find_ident(group.stream(), idents),
+				_ => None,
+			})
+		}
+
+		let const_params: Vec<_> = self
+			.generics
+			.params
+			.iter()
+			.filter_map(|param| match param {
+				syn::GenericParam::Const(syn::ConstParam { ident, .. }) => Some(ident),
+				_ => None,
+			})
+			.collect();
+
+		if let Some(ident) = find_ident(ty.to_token_stream(), &const_params) {
+			let msg = "impl const parameter is used in functions arguments. Arguments should not \
+			have a type depending on a const parameter, as array lengths and `FixedBytes` sizes \
+			change the Solidity type and thus the selector.";
+			return Err(syn::Error::new(ident.span(), msg));
+		}
+
+		Ok(())
+	}
+
 	/// Check that the provided type doesn't depend on one of the type parameters of the
 	/// precompile. Check is skipped if `test_concrete_types` attribute is used.
 	fn check_type_parameter_usage(&self, ty: &syn::Type) -> syn::Result<()> {
@@ -0,0 +1,28 @@
+// This file is part of Frontier.
+
+// Copyright (c) Moonsong Labs.
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+
+pub struct Precompile<const N: usize>;
+
+#[precompile_utils_macro::precompile]
+impl<const N: usize> Precompile<N> {
+	#[precompile::public("foo(uint32[2])")]
+	fn foo(handle: &mut impl PrecompileHandle, arg: [u32; N]) -> EvmResult {
+		Ok(())
+	}
+}
+
+fn main() {}
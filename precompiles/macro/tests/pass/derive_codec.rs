@@ -31,6 +31,13 @@ struct DynamicSize<T> {
 	array: Vec<T>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Codec)]
+enum Status {
+	Pending,
+	Active,
+	Closed,
+}
+
 fn main() {
 	// static
 	let static_size = StaticSize {
@@ -78,4 +85,19 @@ fn main() {
 	let mut reader = Reader::new(&bytes);
 	let dynamic_size_2: DynamicSize<u32> = reader.read().expect("to decode properly");
 	assert_eq!(dynamic_size_2, dynamic_size);
+
+	// enum
+	assert!(Status::has_static_size());
+	assert_eq!(&Status::signature(), "uint8");
+
+	let bytes = Writer::new().write(Status::Closed).build();
+	assert_eq!(bytes, Writer::new().write(2u8).build());
+
+	let mut reader = Reader::new(&bytes);
+	let status: Status = reader.read().expect("to decode properly");
+	assert_eq!(status, Status::Closed);
+
+	let bytes = Writer::new().write(3u8).build();
+	let mut reader = Reader::new(&bytes);
+	assert!(reader.read::<Status>().is_err());
 }
@@ -42,6 +42,12 @@ pub use fp_evm::Precompile;
 use fp_evm::PrecompileFailure;
 pub use precompile_utils_macro::{keccak256, precompile, precompile_name_from_address};
 
+/// Attributes usable outside of a `#[precompile]` impl block, sharing the `precompile::` prefix
+/// of the ones used inside it.
+pub mod precompile {
+	pub use precompile_utils_macro::custom_error as error;
+}
+
 /// Alias for Result returning an EVM precompile error.
 pub type EvmResult<T = ()> = Result<T, PrecompileFailure>;
 
@@ -52,6 +58,7 @@ pub mod prelude {
 				handle::PrecompileHandleExt,
 				logs::{log0, log1, log2, log3, log4, LogExt},
 			},
+			precompile,
 			precompile_set::DiscriminantResult,
 			solidity::{
 				// We export solidity itself to encourage using `solidity::Codec` to avoid confusion
@@ -65,12 +72,14 @@ pub mod prelude {
 					// Allow usage of Codec methods while not exporting the name directly.
 					Codec as _,
 					Convert,
+					FixedBytes,
 					UnboundedBytes,
 					UnboundedString,
+					I256,
 				},
 				revert::{
-					revert, BacktraceExt, InjectBacktrace, MayRevert, Revert, RevertExt,
-					RevertReason,
+					revert, revert_with_error, BacktraceExt, CustomError, InjectBacktrace,
+					MayRevert, Revert, RevertExt, RevertReason,
 				},
 			},
 			substrate::{RuntimeHelper, TryDispatchError},
@@ -78,6 +87,6 @@ pub mod prelude {
 		},
 		alloc::string::String,
 		pallet_evm::{PrecompileHandle, PrecompileOutput},
-		precompile_utils_macro::{keccak256, precompile},
+		precompile_utils_macro::keccak256,
 	};
 }
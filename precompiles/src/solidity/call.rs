@@ -0,0 +1,140 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for pallets calling Solidity contracts through [`pallet_evm::Pallet::call_contract`].
+
+use alloc::vec::Vec;
+
+use pallet_evm::{ContractCallInfo, Log};
+use sp_core::{H160, H256, U256};
+use sp_runtime::DispatchErrorWithPostInfo;
+
+use crate::solidity::{
+	codec::{decode_event_data, decode_return_value, encode_with_selector, Codec},
+	revert::MayRevert,
+};
+
+/// Error of a [`call`] to a Solidity function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CallError {
+	/// The call could not be executed at all.
+	Dispatch(DispatchErrorWithPostInfo),
+	/// The execution failed or reverted.
+	Failed(ContractCallInfo),
+	/// The returned data is not a valid encoding of the expected return type.
+	InvalidOutput(ContractCallInfo),
+}
+
+impl CallError {
+	/// The weight consumed by the failed call.
+	pub fn weight(&self) -> Option<sp_weights::Weight> {
+		match self {
+			Self::Dispatch(e) => e.post_info.actual_weight,
+			Self::Failed(info) | Self::InvalidOutput(info) => Some(info.weight),
+		}
+	}
+}
+
+/// Call the function with the given `selector` of the contract at `target`, with `args`
+/// ABI-encoded as its arguments, and decode its return value as `R`.
+///
+/// See [`pallet_evm::Pallet::call_contract`] for the meaning of the other parameters. The
+/// outcome of the call is returned alongside the decoded value so the caller can account for
+/// its weight and inspect its logs.
+pub fn call<Runtime, A, R>(
+	source: H160,
+	target: H160,
+	selector: u32,
+	args: A,
+	value: U256,
+	gas_limit: u64,
+	is_transactional: bool,
+) -> Result<(R, ContractCallInfo), CallError>
+where
+	Runtime: pallet_evm::Config,
+	A: Codec,
+	R: Codec,
+{
+	let info = pallet_evm::Pallet::<Runtime>::call_contract(
+		source,
+		target,
+		encode_with_selector(selector, args),
+		value,
+		gas_limit,
+		is_transactional,
+	)
+	.map_err(CallError::Dispatch)?;
+
+	if !info.is_success() {
+		return Err(CallError::Failed(info));
+	}
+
+	match decode_return_value(&info.output) {
+		Ok(value) => Ok((value, info)),
+		Err(_) => Err(CallError::InvalidOutput(info)),
+	}
+}
+
+/// Decode the data of the logs emitted by `address` whose first topic is `event`, in emission
+/// order. Fails if any of them is not a valid encoding of `T`.
+pub fn decode_events<T: Codec>(logs: &[Log], address: H160, event: H256) -> MayRevert<Vec<T>> {
+	logs.iter()
+		.filter(|log| log.address == address && log.topics.first() == Some(&event))
+		.map(|log| decode_event_data(&log.data))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::solidity::codec::encode_event_data;
+
+	#[test]
+	fn decode_events_filters_by_emitter_and_topic() {
+		let token = H160::repeat_byte(0x11);
+		let transfer = H256::repeat_byte(0x22);
+		let log = |address, topic, amount: u32| Log {
+			address,
+			topics: vec![topic, H256::zero()],
+			data: encode_event_data(U256::from(amount)),
+		};
+		let logs = vec![
+			log(token, transfer, 1),
+			log(H160::repeat_byte(0x33), transfer, 2),
+			log(token, H256::repeat_byte(0x44), 3),
+			log(token, transfer, 4),
+		];
+
+		assert_eq!(
+			decode_events::<U256>(&logs, token, transfer).unwrap(),
+			vec![U256::from(1), U256::from(4)]
+		);
+	}
+
+	#[test]
+	fn decode_events_fails_on_invalid_data() {
+		let token = H160::repeat_byte(0x11);
+		let transfer = H256::repeat_byte(0x22);
+		let logs = vec![Log {
+			address: token,
+			topics: vec![transfer],
+			data: vec![1, 2, 3],
+		}];
+
+		assert!(decode_events::<U256>(&logs, token, transfer).is_err());
+	}
+}
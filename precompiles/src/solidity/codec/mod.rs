@@ -32,7 +32,7 @@ use sp_core::{H256, U256};
 
 pub use alloc::string::String;
 pub use bytes::{BoundedBytes, BoundedString, UnboundedBytes, UnboundedString};
-pub use native::{Address, BoundedVec};
+pub use native::{Address, BoundedVec, FixedBytes, I256};
 
 // derive macro
 pub use precompile_utils_macro::Codec;
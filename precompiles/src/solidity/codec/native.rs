@@ -108,6 +108,68 @@ impl Codec for H256 {
 	}
 }
 
+/// The `bytes1` to `bytes32` types of Solidity.
+/// Values are left-aligned in their 32 bytes word, and the padding must be zeros when reading.
+/// `bytes32` can also be represented as `H256`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedBytes<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> From<[u8; N]> for FixedBytes<N> {
+	fn from(value: [u8; N]) -> Self {
+		FixedBytes(value)
+	}
+}
+
+impl<const N: usize> From<FixedBytes<N>> for [u8; N] {
+	fn from(value: FixedBytes<N>) -> Self {
+		value.0
+	}
+}
+
+macro_rules! impl_evmdata_for_fixed_bytes {
+	($($size:literal, )*) => {
+		$(
+			impl Codec for FixedBytes<$size> {
+				fn read(reader: &mut Reader) -> MayRevert<Self> {
+					let range = reader.move_cursor(32)?;
+
+					let data = reader
+						.input
+						.get(range)
+						.ok_or_else(|| RevertReason::read_out_of_bounds(Self::signature()))?;
+
+					if data[$size..].iter().any(|byte| *byte != 0) {
+						return Err(RevertReason::value_is_too_large(Self::signature()).into());
+					}
+
+					let mut value = [0u8; $size];
+					value.copy_from_slice(&data[..$size]);
+					Ok(FixedBytes(value))
+				}
+
+				fn write(writer: &mut Writer, value: Self) {
+					let mut buffer = [0u8; 32];
+					buffer[..$size].copy_from_slice(&value.0);
+					writer.data.extend_from_slice(&buffer);
+				}
+
+				fn has_static_size() -> bool {
+					true
+				}
+
+				fn signature() -> String {
+					alloc::format!("bytes{}", $size)
+				}
+			}
+		)*
+	};
+}
+
+impl_evmdata_for_fixed_bytes!(
+	1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+	27, 28, 29, 30, 31, 32,
+);
+
 /// The `address` type of Solidity.
 /// H160 could represent 2 types of data (bytes20 and address) that are not encoded the same way.
 /// To avoid issues writing H160 is thus not supported.
@@ -224,6 +286,103 @@ macro_rules! impl_evmdata_for_uints {
 
 impl_evmdata_for_uints!(u8, u16, u32, u64, u128,);
 
+/// The `int256` type of Solidity, stored in two's complement.
+/// Smaller signed integers are represented by the native `i8` to `i128` types.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct I256(pub U256);
+
+impl I256 {
+	pub fn is_negative(&self) -> bool {
+		self.0.bit(255)
+	}
+}
+
+impl Codec for I256 {
+	fn read(reader: &mut Reader) -> MayRevert<Self> {
+		let value = U256::read(reader).map_err(|_| RevertReason::read_out_of_bounds("int256"))?;
+
+		Ok(I256(value))
+	}
+
+	fn write(writer: &mut Writer, value: Self) {
+		U256::write(writer, value.0);
+	}
+
+	fn has_static_size() -> bool {
+		true
+	}
+
+	fn signature() -> String {
+		String::from("int256")
+	}
+}
+
+macro_rules! impl_evmdata_for_ints {
+	($($int:ty, )*) => {
+		$(
+			impl From<$int> for I256 {
+				fn from(value: $int) -> Self {
+					// Sign extend the value to 32 bytes.
+					let mut buffer = if value < 0 { [0xffu8; 32] } else { [0u8; 32] };
+					buffer[32 - core::mem::size_of::<$int>()..]
+						.copy_from_slice(&value.to_be_bytes());
+
+					I256(U256::from_big_endian(&buffer))
+				}
+			}
+
+			impl TryFrom<I256> for $int {
+				type Error = RevertReason;
+
+				fn try_from(value: I256) -> Result<Self, Self::Error> {
+					let mut buffer = [0u8; 32];
+					value.0.to_big_endian(&mut buffer);
+
+					// All the bytes above the value must be copies of its sign bit.
+					let (extension, bytes) = buffer.split_at(32 - core::mem::size_of::<$int>());
+					let fill = if bytes[0] & 0x80 == 0 { 0u8 } else { 0xffu8 };
+					if extension.iter().any(|byte| *byte != fill) {
+						return Err(RevertReason::value_is_too_large(<$int>::signature()));
+					}
+
+					let mut int_bytes = [0u8; core::mem::size_of::<$int>()];
+					int_bytes.copy_from_slice(bytes);
+					Ok(<$int>::from_be_bytes(int_bytes))
+				}
+			}
+
+			impl Codec for $int {
+				fn read(reader: &mut Reader) -> MayRevert<Self> {
+					let value256: I256 = reader.read()
+					.map_err(|_| RevertReason::read_out_of_bounds(
+						Self::signature()
+					))?;
+
+					value256
+						.try_into()
+						.map_err(|_| RevertReason::value_is_too_large(
+							Self::signature()
+						).into())
+				}
+
+				fn write(writer: &mut Writer, value: Self) {
+					I256::write(writer, value.into());
+				}
+
+				fn has_static_size() -> bool {
+					true
+				}
+
+				fn signature() -> String {
+					alloc::format!("int{}", core::mem::size_of::<Self>() * 8)
+				}
+			}
+		)*
+	};
+}
+
+impl_evmdata_for_ints!(i8, i16, i32, i64, i128,);
+
 impl Codec for bool {
 	fn read(reader: &mut Reader) -> MayRevert<Self> {
 		let h256 = H256::read(reader).map_err(|_| RevertReason::read_out_of_bounds("bool"))?;
@@ -275,6 +434,51 @@ impl<T: Codec> Codec for Vec<T> {
 	}
 }
 
+/// Fixed size arrays (`T[N]`) don't have a length prefix. Their items are encoded inline if `T`
+/// has a static size, otherwise the whole array is behind an offset like dynamic tuples.
+impl<T: Codec, const N: usize> Codec for [T; N] {
+	fn read(reader: &mut Reader) -> MayRevert<Self> {
+		let mut pointer_reader;
+		let inner_reader = if T::has_static_size() {
+			reader
+		} else {
+			pointer_reader = reader.read_pointer()?;
+			&mut pointer_reader
+		};
+
+		let mut array = Vec::with_capacity(N);
+		for i in 0..N {
+			array.push(inner_reader.read().in_array(i)?);
+		}
+
+		array
+			.try_into()
+			.map_err(|_| RevertReason::read_out_of_bounds("array content").into())
+	}
+
+	fn write(writer: &mut Writer, value: Self) {
+		if T::has_static_size() {
+			for inner in value {
+				T::write(writer, inner);
+			}
+		} else {
+			let mut inner_writer = Writer::new();
+			for inner in value {
+				T::write(&mut inner_writer, inner);
+			}
+			writer.write_pointer(inner_writer.build());
+		}
+	}
+
+	fn has_static_size() -> bool {
+		T::has_static_size()
+	}
+
+	fn signature() -> String {
+		alloc::format!("{}[{}]", T::signature(), N)
+	}
+}
+
 /// Wrapper around a Vec that provides a max length bound on read.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BoundedVec<T, S> {
@@ -380,3 +584,93 @@ impl<T, S> From<BoundedVec<T, S>> for Vec<T> {
 		value.inner
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn word(bytes: &[u8]) -> Vec<u8> {
+		let mut buffer = vec![0u8; 32];
+		buffer[32 - bytes.len()..].copy_from_slice(bytes);
+		buffer
+	}
+
+	#[test]
+	fn signed_ints_are_sign_extended() {
+		assert_eq!(Writer::new().write(-1i8).build(), vec![0xff; 32]);
+		assert_eq!(Writer::new().write(1i64).build(), word(&[1]));
+		assert_eq!(I256::from(-2i128), I256(U256::MAX - 1));
+		assert!(I256::from(i128::MIN).is_negative());
+
+		for value in [i32::MIN, -1, 0, 1, i32::MAX] {
+			let bytes = Writer::new().write(value).build();
+			assert_eq!(Reader::new(&bytes).read::<i32>().unwrap(), value);
+		}
+
+		assert_eq!(
+			Reader::new(&[0xff; 32]).read::<I256>().unwrap(),
+			I256::from(-1i8)
+		);
+		assert_eq!(&i16::signature(), "int16");
+		assert_eq!(&I256::signature(), "int256");
+	}
+
+	#[test]
+	fn signed_ints_reject_dirty_sign_extension() {
+		// 128 doesn't fit in an int8.
+		assert!(Reader::new(&word(&[0x80])).read::<i8>().is_err());
+
+		let mut bytes = vec![0xff; 32];
+		bytes[31] = 0x7f;
+		assert!(Reader::new(&bytes).read::<i8>().is_err());
+		assert_eq!(Reader::new(&bytes).read::<i16>().unwrap(), -129);
+	}
+
+	#[test]
+	fn fixed_bytes_are_left_aligned() {
+		let value = FixedBytes([1u8, 2, 3, 4]);
+		let bytes = Writer::new().write(value).build();
+
+		let mut expected = vec![0u8; 32];
+		expected[..4].copy_from_slice(&[1, 2, 3, 4]);
+		assert_eq!(bytes, expected);
+		assert_eq!(Reader::new(&bytes).read::<FixedBytes<4>>().unwrap(), value);
+		assert_eq!(&FixedBytes::<4>::signature(), "bytes4");
+
+		// Non-zero padding.
+		assert!(Reader::new(&bytes).read::<FixedBytes<3>>().is_err());
+	}
+
+	#[test]
+	fn static_arrays_are_encoded_inline() {
+		let value = [1u32, 2, 3];
+		let bytes = Writer::new().write(value).build();
+
+		assert_eq!(
+			bytes,
+			Writer::new().write(1u32).write(2u32).write(3u32).build()
+		);
+		assert_eq!(Reader::new(&bytes).read::<[u32; 3]>().unwrap(), value);
+		assert!(<[u32; 3]>::has_static_size());
+		assert_eq!(&<[u32; 3]>::signature(), "uint32[3]");
+		assert_eq!(&<Vec<[Address; 2]>>::signature(), "address[2][]");
+
+		assert!(Reader::new(&bytes[..64]).read::<[u32; 3]>().is_err());
+	}
+
+	#[test]
+	fn dynamic_arrays_are_encoded_like_tuples() {
+		let value = [vec![1u32], vec![2u32, 3]];
+		let bytes = Writer::new().write(value.clone()).build();
+
+		assert_eq!(
+			bytes,
+			Writer::new()
+				.write((value[0].clone(), value[1].clone()))
+				.build()
+		);
+		assert_eq!(Reader::new(&bytes).read::<[Vec<u32>; 2]>().unwrap(), value);
+		assert!(!<[Vec<u32>; 2]>::has_static_size());
+		assert_eq!(&<[Vec<u32>; 2]>::signature(), "uint32[][2]");
+	}
+}
@@ -41,6 +41,26 @@ pub fn revert_as_bytes(msg: impl Into<String>) -> Vec<u8> {
 	Revert::new(RevertReason::custom(msg)).to_encoded_bytes()
 }
 
+/// A Solidity custom error (`error Foo(uint256)`). Like a function call, it is encoded as the
+/// selector of its signature followed by its arguments, which allows callers to `catch` it.
+/// It should be implemented using the `#[precompile::error("Foo(uint256)")]` attribute.
+pub trait CustomError {
+	/// Selector of the error signature.
+	const SELECTOR: u32;
+
+	/// Encode the error into its revert output.
+	fn to_encoded_bytes(self) -> Vec<u8>;
+}
+
+/// Generate a revert from a Solidity custom error.
+/// Returns a `PrecompileFailure` that fits in an `EvmResult::Err`.
+pub fn revert_with_error(error: impl CustomError) -> PrecompileFailure {
+	PrecompileFailure::Revert {
+		exit_status: ExitRevert::Reverted,
+		output: error.to_encoded_bytes(),
+	}
+}
+
 /// Generic error to build abi-encoded revert output.
 /// See: https://docs.soliditylang.org/en/latest/control-structures.html?highlight=revert#revert
 pub const ERROR_SELECTOR: u32 = 0x08c379a0;
@@ -384,3 +404,71 @@ impl From<RevertReason> for PrecompileFailure {
 		Revert::new(err).into()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::solidity::codec::{Address, FixedBytes, UnboundedBytes, Writer};
+	use sp_core::{H160, U256};
+
+	#[crate::precompile::error("InsufficientBalance(uint256,uint256)")]
+	struct InsufficientBalance {
+		available: U256,
+		required: U256,
+	}
+
+	#[crate::precompile::error("CallFailed(address,bytes)")]
+	struct CallFailed(Address, UnboundedBytes);
+
+	#[crate::precompile::error("Unauthorized()")]
+	struct Unauthorized;
+
+	#[test]
+	fn custom_errors_are_encoded_with_selector() {
+		assert_eq!(
+			InsufficientBalance::SELECTOR,
+			u32::from_be_bytes(
+				crate::keccak256!("InsufficientBalance(uint256,uint256)")[..4]
+					.try_into()
+					.unwrap()
+			)
+		);
+
+		let failure: PrecompileFailure = InsufficientBalance {
+			available: 1.into(),
+			required: 2.into(),
+		}
+		.into();
+		assert_eq!(
+			failure,
+			PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: Writer::new_with_selector(InsufficientBalance::SELECTOR)
+					.write(U256::from(1))
+					.write(U256::from(2))
+					.build(),
+			}
+		);
+
+		assert_eq!(
+			Unauthorized.to_encoded_bytes(),
+			Unauthorized::SELECTOR.to_be_bytes().to_vec()
+		);
+	}
+
+	#[test]
+	fn dynamic_custom_errors_are_encoded_as_arguments() {
+		let address = Address(H160::repeat_byte(0x11));
+		let output = CallFailed(address, UnboundedBytes::from(&b"oops"[..])).to_encoded_bytes();
+
+		assert_eq!(
+			output,
+			Writer::new_with_selector(CallFailed::SELECTOR)
+				.write(address)
+				.write(U256::from(64)) // offset of bytes
+				.write(U256::from(4)) // length of bytes
+				.write(FixedBytes(*b"oops"))
+				.build()
+		);
+	}
+}
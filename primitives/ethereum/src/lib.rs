@@ -22,11 +22,11 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 pub use ethereum::{
-	AccessListItem, BlockV2 as Block, LegacyTransactionMessage, Log, ReceiptV3 as Receipt,
-	TransactionAction, TransactionV2 as Transaction,
+	AccessListItem, AuthorizationListItem, BlockV3 as Block, LegacyTransactionMessage, Log,
+	ReceiptV4 as Receipt, TransactionAction, TransactionV3 as Transaction,
 };
 use ethereum_types::{H160, H256, U256};
-use fp_evm::{CallOrCreateInfo, CheckEvmTransactionInput};
+use fp_evm::{Authorization, AuthorizationList, CallOrCreateInfo, CheckEvmTransactionInput};
 use frame_support::dispatch::{DispatchErrorWithPostInfo, PostDispatchInfo};
 use scale_codec::{Decode, Encode};
 
@@ -49,6 +49,7 @@ pub struct TransactionData {
 	pub value: U256,
 	pub chain_id: Option<u64>,
 	pub access_list: Vec<(H160, Vec<H256>)>,
+	pub authorization_list: Option<AuthorizationList>,
 }
 
 impl TransactionData {
@@ -64,6 +65,7 @@ impl TransactionData {
 		value: U256,
 		chain_id: Option<u64>,
 		access_list: Vec<(H160, Vec<H256>)>,
+		authorization_list: Option<AuthorizationList>,
 	) -> Self {
 		Self {
 			action,
@@ -76,6 +78,7 @@ impl TransactionData {
 			value,
 			chain_id,
 			access_list,
+			authorization_list,
 		}
 	}
 
@@ -109,6 +112,7 @@ impl From<TransactionData> for CheckEvmTransactionInput {
 			max_priority_fee_per_gas: t.max_priority_fee_per_gas,
 			value: t.value,
 			access_list: t.access_list,
+			authorization_list: t.authorization_list,
 		}
 	}
 }
@@ -127,6 +131,7 @@ impl From<&Transaction> for TransactionData {
 				value: t.value,
 				chain_id: t.signature.chain_id(),
 				access_list: Vec::new(),
+				authorization_list: None,
 			},
 			Transaction::EIP2930(t) => TransactionData {
 				action: t.action,
@@ -143,6 +148,7 @@ impl From<&Transaction> for TransactionData {
 					.iter()
 					.map(|d| (d.address, d.storage_keys.clone()))
 					.collect(),
+				authorization_list: None,
 			},
 			Transaction::EIP1559(t) => TransactionData {
 				action: t.action,
@@ -159,7 +165,70 @@ impl From<&Transaction> for TransactionData {
 					.iter()
 					.map(|d| (d.address, d.storage_keys.clone()))
 					.collect(),
+				authorization_list: None,
+			},
+			Transaction::EIP7702(t) => TransactionData {
+				action: t.action,
+				input: t.input.clone(),
+				nonce: t.nonce,
+				gas_limit: t.gas_limit,
+				gas_price: None,
+				max_fee_per_gas: Some(t.max_fee_per_gas),
+				max_priority_fee_per_gas: Some(t.max_priority_fee_per_gas),
+				value: t.value,
+				chain_id: Some(t.chain_id),
+				access_list: t
+					.access_list
+					.iter()
+					.map(|d| (d.address, d.storage_keys.clone()))
+					.collect(),
+				authorization_list: Some(recover_authorization_list(&t.authorization_list)),
 			},
 		}
 	}
 }
+
+/// Magic byte prepended to the signed EIP-7702 authorization message.
+const AUTHORIZATION_MAGIC: u8 = 0x05;
+
+/// Half of the secp256k1 curve order, the upper bound for `s` (EIP-2).
+const SECP256K1N_HALF: H256 = H256([
+	0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+	0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+]);
+
+/// Recover the authorities of an EIP-7702 authorization list.
+pub fn recover_authorization_list(items: &[AuthorizationListItem]) -> AuthorizationList {
+	items
+		.iter()
+		.map(|item| Authorization {
+			chain_id: item.chain_id,
+			address: item.address,
+			nonce: item.nonce,
+			authority: recover_authority(item),
+		})
+		.collect()
+}
+
+/// Recover the authority that signed an EIP-7702 authorization tuple, i.e. the signer of
+/// `keccak256(0x05 || rlp([chain_id, address, nonce]))`.
+pub fn recover_authority(item: &AuthorizationListItem) -> Option<H160> {
+	if item.s > SECP256K1N_HALF {
+		return None;
+	}
+
+	let mut stream = rlp::RlpStream::new_list(3);
+	stream.append(&item.chain_id);
+	stream.append(&item.address);
+	stream.append(&item.nonce);
+	let mut message = alloc::vec![AUTHORIZATION_MAGIC];
+	message.extend_from_slice(&stream.out());
+	let msg = sp_io::hashing::keccak_256(&message);
+
+	let mut sig = [0u8; 65];
+	sig[0..32].copy_from_slice(&item.r[..]);
+	sig[32..64].copy_from_slice(&item.s[..]);
+	sig[64] = item.y_parity as u8;
+	let pubkey = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &msg).ok()?;
+	Some(H160::from(H256::from(sp_io::hashing::keccak_256(&pubkey))))
+}
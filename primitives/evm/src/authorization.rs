@@ -0,0 +1,89 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! EIP-7702 set-code authorizations.
+
+use alloc::vec::Vec;
+use scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, U256};
+
+/// Intrinsic gas charged for every authorization in the list.
+pub const PER_EMPTY_ACCOUNT_COST: u64 = 25_000;
+/// Gas refunded when the authority account already exists.
+pub const PER_AUTH_BASE_COST: u64 = 12_500;
+/// Prefix of the code installed on a delegating account.
+pub const DELEGATION_DESIGNATOR_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+/// Length of the delegation designator, prefix included.
+pub const DELEGATION_DESIGNATOR_LEN: usize = 23;
+
+/// An authorization tuple whose signer has already been recovered.
+#[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Authorization {
+	/// Chain the authorization is valid on, `0` meaning any chain.
+	pub chain_id: u64,
+	/// Address whose code the authority delegates to.
+	pub address: H160,
+	/// Expected nonce of the authority.
+	pub nonce: U256,
+	/// Recovered signer, `None` if the signature is invalid.
+	pub authority: Option<H160>,
+}
+
+pub type AuthorizationList = Vec<Authorization>;
+
+/// Build the delegation designator `0xef0100 || address`.
+pub fn delegation_designator(address: H160) -> Vec<u8> {
+	let mut code = Vec::with_capacity(DELEGATION_DESIGNATOR_LEN);
+	code.extend_from_slice(&DELEGATION_DESIGNATOR_PREFIX);
+	code.extend_from_slice(address.as_bytes());
+	code
+}
+
+/// Return the delegated address if `code` is a delegation designator.
+pub fn delegated_address(code: &[u8]) -> Option<H160> {
+	match code.strip_prefix(&DELEGATION_DESIGNATOR_PREFIX[..]) {
+		Some(address) if address.len() == H160::len_bytes() => Some(H160::from_slice(address)),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn delegation_designator_roundtrip() {
+		let address = H160::repeat_byte(0x11);
+		let code = delegation_designator(address);
+		assert_eq!(code.len(), DELEGATION_DESIGNATOR_LEN);
+		assert_eq!(delegated_address(&code), Some(address));
+	}
+
+	#[test]
+	fn delegated_address_rejects_other_code() {
+		assert_eq!(delegated_address(&[]), None);
+		assert_eq!(delegated_address(&[0xef, 0x01, 0x00]), None);
+
+		let mut code = delegation_designator(H160::repeat_byte(0x11));
+		code[2] = 0x01;
+		assert_eq!(delegated_address(&code), None);
+	}
+}
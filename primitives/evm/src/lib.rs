@@ -21,7 +21,9 @@
 extern crate alloc;
 
 mod account_provider;
+mod authorization;
 mod precompile;
+mod tracing;
 mod validation;
 
 use alloc::{collections::BTreeMap, vec::Vec};
@@ -40,11 +42,21 @@ pub use evm::{
 
 pub use self::{
 	account_provider::AccountProvider,
+	authorization::{
+		delegated_address, delegation_designator, Authorization, AuthorizationList,
+		DELEGATION_DESIGNATOR_LEN, DELEGATION_DESIGNATOR_PREFIX, PER_AUTH_BASE_COST,
+		PER_EMPTY_ACCOUNT_COST,
+	},
 	precompile::{
 		Context, ExitError, ExitRevert, ExitSucceed, IsPrecompileResult, LinearCostPrecompile,
 		Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult,
 		PrecompileSet, Transfer,
 	},
+	tracing::{
+		AccountState, CallFrame, CallLog, CallTracerConfig, CallType, PrestateTrace,
+		PrestateTracerConfig, StructLog, StructLoggerConfig, StructLoggerTrace, TracerConfig,
+		TransactionTrace,
+	},
 	validation::{
 		CheckEvmTransaction, CheckEvmTransactionConfig, CheckEvmTransactionInput,
 		TransactionValidationError,
@@ -59,6 +71,29 @@ pub struct Vicinity {
 	pub gas_price: U256,
 	/// Origin of the transaction.
 	pub origin: H160,
+	/// Overrides of the block context seen by the transaction.
+	pub block_overrides: BlockOverrides,
+}
+
+/// Overrides of the block context seen by the EVM, used by non-transactional calls to execute
+/// as if in another block. `None` keeps the value of the current block.
+#[derive(Clone, Eq, PartialEq, Default, Debug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlockOverrides {
+	/// Block number.
+	pub number: Option<U256>,
+	/// Block timestamp, in seconds.
+	pub timestamp: Option<u64>,
+	/// Block author.
+	pub coinbase: Option<H160>,
+	/// Block base fee per gas.
+	pub base_fee: Option<U256>,
+	/// Block gas limit.
+	pub gas_limit: Option<U256>,
+	/// Block randomness.
+	pub random: Option<H256>,
+	/// Hashes of the previous blocks, by number.
+	pub block_hashes: BTreeMap<U256, H256>,
 }
 
 /// `System::Account` 16(hash) + 20 (key) + 72 (AccountInfo::max_encoded_len)
@@ -0,0 +1,176 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracers and execution traces exchanged between the runtime and the debug RPC.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use evm::ExitReason;
+use scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+
+/// Tracer to attach to the EVM while re-executing a transaction.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TracerConfig {
+	/// Geth's default opcode logger.
+	StructLogger(StructLoggerConfig),
+	/// Geth's `callTracer`.
+	CallTracer(CallTracerConfig),
+	/// Geth's `prestateTracer`.
+	PrestateTracer(PrestateTracerConfig),
+}
+
+/// Options of the struct logger, mirroring geth's `disable*` flags.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StructLoggerConfig {
+	pub disable_stack: bool,
+	pub disable_memory: bool,
+	pub disable_storage: bool,
+}
+
+/// Options of the call tracer.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CallTracerConfig {
+	/// Only record the top level call.
+	pub only_top_call: bool,
+	/// Record the logs emitted by each call.
+	pub with_log: bool,
+}
+
+/// Options of the prestate tracer.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PrestateTracerConfig {
+	/// Also return the state of the touched accounts after the transaction.
+	pub diff_mode: bool,
+}
+
+/// Trace of a single transaction, in the format of the requested tracer.
+#[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TransactionTrace {
+	StructLogger(StructLoggerTrace),
+	CallTracer(CallFrame),
+	PrestateTracer(PrestateTrace),
+}
+
+/// Output of the struct logger.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StructLoggerTrace {
+	/// Gas used by the transaction.
+	pub gas: u64,
+	/// Whether the top level call failed.
+	pub failed: bool,
+	/// Data returned by the top level call.
+	pub return_value: Vec<u8>,
+	/// One entry per executed opcode.
+	pub struct_logs: Vec<StructLog>,
+}
+
+/// State of the machine right before an opcode is executed.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StructLog {
+	/// Program counter.
+	pub pc: u64,
+	/// Executed opcode.
+	pub op: u8,
+	/// Gas left before executing the opcode.
+	pub gas: u64,
+	/// Gas charged for the opcode, memory expansion included.
+	pub gas_cost: u64,
+	/// Call depth, starting at 1.
+	pub depth: u32,
+	/// Stack, bottom first. `None` when disabled.
+	pub stack: Option<Vec<H256>>,
+	/// Memory content. `None` when disabled.
+	pub memory: Option<Vec<u8>>,
+	/// Storage slots accessed so far by the current contract, only set for `SLOAD` and `SSTORE`.
+	pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// Kind of call frame.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CallType {
+	Call,
+	CallCode,
+	DelegateCall,
+	StaticCall,
+	Create,
+	Create2,
+	SelfDestruct,
+}
+
+/// A call frame recorded by the call tracer.
+#[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CallFrame {
+	pub call_type: CallType,
+	pub from: H160,
+	pub to: H160,
+	/// Transferred value, `None` for delegate and static calls.
+	pub value: Option<U256>,
+	/// Gas available to the frame.
+	pub gas: u64,
+	pub gas_used: u64,
+	pub input: Vec<u8>,
+	pub output: Vec<u8>,
+	/// `None` for self destructs, which do not execute.
+	pub exit_reason: Option<ExitReason>,
+	/// Logs emitted by the frame itself, only recorded with `with_log`.
+	pub logs: Vec<CallLog>,
+	/// Frames of the sub calls, in execution order.
+	pub calls: Vec<CallFrame>,
+}
+
+/// A log recorded by the call tracer.
+#[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CallLog {
+	pub address: H160,
+	pub topics: Vec<H256>,
+	pub data: Vec<u8>,
+	/// Number of sub calls of the frame made before the log was emitted.
+	pub position: u32,
+}
+
+/// Output of the prestate tracer.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PrestateTrace {
+	/// State of the touched accounts before the transaction.
+	pub pre: BTreeMap<H160, AccountState>,
+	/// State of the same accounts after the transaction, only recorded in diff mode.
+	pub post: Option<BTreeMap<H160, AccountState>>,
+}
+
+/// State of an account, limited to the storage slots touched by the transaction.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccountState {
+	pub balance: U256,
+	pub nonce: U256,
+	pub code: Vec<u8>,
+	pub storage: BTreeMap<H256, H256>,
+}
@@ -22,6 +22,8 @@ pub use evm::backend::Basic as Account;
 use frame_support::{sp_runtime::traits::UniqueSaturatedInto, weights::Weight};
 use sp_core::{H160, H256, U256};
 
+use crate::{AuthorizationList, PER_EMPTY_ACCOUNT_COST};
+
 #[derive(Debug)]
 pub struct CheckEvmTransactionInput {
 	pub chain_id: Option<u64>,
@@ -34,6 +36,8 @@ pub struct CheckEvmTransactionInput {
 	pub max_priority_fee_per_gas: Option<U256>,
 	pub value: U256,
 	pub access_list: Vec<(H160, Vec<H256>)>,
+	/// EIP-7702 authorization list, `None` for other transaction types.
+	pub authorization_list: Option<AuthorizationList>,
 }
 
 #[derive(Debug)]
@@ -81,6 +85,8 @@ pub enum TransactionValidationError {
 	/// Unknown error
 	#[num_enum(default)]
 	UnknownError,
+	/// The authorization list is empty or used for contract creation
+	InvalidAuthorizationList,
 }
 
 impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config, E> {
@@ -200,6 +206,14 @@ impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config,
 	}
 
 	pub fn validate_common(&self) -> Result<&Self, E> {
+		if let Some(authorization_list) = &self.transaction.authorization_list {
+			// EIP-7702 transactions must call an existing address and carry at least
+			// one authorization.
+			if self.transaction.to.is_none() || authorization_list.is_empty() {
+				return Err(TransactionValidationError::InvalidAuthorizationList.into());
+			}
+		}
+
 		if self.config.is_transactional {
 			// Try to subtract the proof_size_base_cost from the Weight proof_size limit or fail.
 			// Validate the weight limit can afford recording the proof size cost.
@@ -234,6 +248,14 @@ impl<'config, E: From<TransactionValidationError>> CheckEvmTransaction<'config,
 				return Err(TransactionValidationError::GasLimitTooLow.into());
 			}
 
+			if let Some(authorization_list) = &self.transaction.authorization_list {
+				let authorization_cost =
+					PER_EMPTY_ACCOUNT_COST.saturating_mul(authorization_list.len() as u64);
+				if gasometer.record_cost(authorization_cost).is_err() {
+					return Err(TransactionValidationError::GasLimitTooLow.into());
+				}
+			}
+
 			// Transaction gas limit is within the upper bound block gas limit.
 			if self.transaction.gas_limit > self.config.block_gas_limit {
 				return Err(TransactionValidationError::GasLimitTooHigh.into());
@@ -261,6 +283,7 @@ mod tests {
 		InvalidChainId,
 		InvalidSignature,
 		UnknownError,
+		InvalidAuthorizationList,
 	}
 
 	static SHANGHAI_CONFIG: evm::Config = evm::Config::shanghai();
@@ -279,6 +302,9 @@ mod tests {
 				TransactionValidationError::InvalidChainId => TestError::InvalidChainId,
 				TransactionValidationError::InvalidSignature => TestError::InvalidSignature,
 				TransactionValidationError::UnknownError => TestError::UnknownError,
+				TransactionValidationError::InvalidAuthorizationList => {
+					TestError::InvalidAuthorizationList
+				}
 			}
 		}
 	}
@@ -297,6 +323,7 @@ mod tests {
 		pub value: U256,
 		pub weight_limit: Option<Weight>,
 		pub proof_size_base_cost: Option<u64>,
+		pub authorization_list: Option<AuthorizationList>,
 	}
 
 	impl Default for TestCase {
@@ -315,6 +342,7 @@ mod tests {
 				value: U256::from(1u8),
 				weight_limit: None,
 				proof_size_base_cost: None,
+				authorization_list: None,
 			}
 		}
 	}
@@ -334,6 +362,7 @@ mod tests {
 			value,
 			weight_limit,
 			proof_size_base_cost,
+			authorization_list,
 		} = input;
 		CheckEvmTransaction::<TestError>::new(
 			CheckEvmTransactionConfig {
@@ -354,6 +383,7 @@ mod tests {
 				max_priority_fee_per_gas,
 				value,
 				access_list: vec![],
+				authorization_list,
 			},
 			weight_limit,
 			proof_size_base_cost,
@@ -848,4 +878,54 @@ mod tests {
 		let res = test.with_base_fee();
 		assert!(res.is_ok());
 	}
+
+	fn authorization(nonce: u64) -> crate::Authorization {
+		crate::Authorization {
+			chain_id: 42u64,
+			address: H160::repeat_byte(0x11),
+			nonce: U256::from(nonce),
+			authority: Some(H160::repeat_byte(0x22)),
+		}
+	}
+
+	// An empty authorization list fails in pool and in block.
+	#[test]
+	fn validate_empty_authorization_list_fails() {
+		let who = Account {
+			balance: U256::from(1_000_000u128),
+			nonce: U256::zero(),
+		};
+		let test = test_env(TestCase {
+			gas_limit: U256::from(100_000u64),
+			authorization_list: Some(vec![]),
+			..Default::default()
+		});
+		let res = test.validate_in_pool_for(&who);
+		assert_eq!(res.unwrap_err(), TestError::InvalidAuthorizationList);
+		let res = test.validate_in_block_for(&who);
+		assert_eq!(res.unwrap_err(), TestError::InvalidAuthorizationList);
+	}
+
+	// Every authorization adds to the intrinsic gas of the transaction.
+	#[test]
+	fn validate_authorization_list_intrinsic_gas() {
+		let who = Account {
+			balance: U256::from(1_000_000u128),
+			nonce: U256::zero(),
+		};
+		let test = test_env(TestCase {
+			gas_limit: U256::from(21_000u64 + crate::PER_EMPTY_ACCOUNT_COST),
+			authorization_list: Some(vec![authorization(0)]),
+			..Default::default()
+		});
+		assert!(test.validate_in_block_for(&who).is_ok());
+
+		let test = test_env(TestCase {
+			gas_limit: U256::from(21_000u64 + crate::PER_EMPTY_ACCOUNT_COST),
+			authorization_list: Some(vec![authorization(0), authorization(1)]),
+			..Default::default()
+		});
+		let res = test.validate_in_block_for(&who);
+		assert_eq!(res.unwrap_err(), TestError::GasLimitTooLow);
+	}
 }
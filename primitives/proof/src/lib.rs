@@ -0,0 +1,273 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of the proofs returned by `eth_getProof`.
+//!
+//! Frontier keeps the EVM state in the Substrate state trie, so the proofs are Substrate read
+//! proofs against the block's state root rather than Merkle Patricia proofs: the account proof
+//! covers the `frame_system::Account` and `EVM::AccountCodes` entries of the account, and each
+//! storage proof covers an `EVM::AccountStorages` entry. The state root is returned as the
+//! `storageHash` of the account.
+//!
+//! The balance and nonce of the account are those `pallet_evm` derives from its
+//! `frame_system::Account` entry, which depends on the runtime: the nonce is the `AccountInfo`
+//! nonce, and the balance is what the account can spend while staying alive, which for
+//! `pallet_balances` is computed from the `free`, `reserved` and `frozen` balances and the
+//! existential deposit. Clients verify them with [`verify_account_basic`], given the
+//! `AccountInfo` type of the runtime and that derivation.
+//!
+//! Runtimes maintaining an Ethereum state trie alongside the Substrate state return Merkle
+//! Patricia proofs instead, which are verified with the [`mpt`] module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+pub mod mpt;
+
+use alloc::vec::Vec;
+use scale_codec::Decode;
+// Substrate
+use sp_core::{
+	hashing::{blake2_128, keccak_256, twox_128},
+	H160, H256, U256,
+};
+use sp_runtime::traits::BlakeTwo256;
+use sp_trie::{LayoutV1, StorageProof};
+// Frontier
+use fp_storage::constants::{EVM_ACCOUNT_CODES, EVM_ACCOUNT_STORAGES, PALLET_EVM};
+
+/// Proof verification error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+	/// The proof is incomplete or does not match the state root.
+	InvalidProof,
+	/// The proven value cannot be decoded.
+	InvalidValue,
+	/// The proven value differs from the expected one.
+	Mismatch,
+}
+
+/// Storage key of the `frame_system::Account` entry of an encoded account id.
+pub fn system_account_key(account_id: &[u8]) -> Vec<u8> {
+	let mut key = storage_prefix(b"System", b"Account");
+	key.extend(blake2_128_concat(account_id));
+	key
+}
+
+/// Storage key of the `EVM::AccountCodes` entry of an address.
+pub fn account_code_key(address: H160) -> Vec<u8> {
+	let mut key = storage_prefix(PALLET_EVM, EVM_ACCOUNT_CODES);
+	key.extend(blake2_128_concat(address.as_bytes()));
+	key
+}
+
+/// Storage key of the `EVM::AccountStorages` entry of a slot.
+pub fn account_storage_key(address: H160, index: H256) -> Vec<u8> {
+	let mut key = storage_prefix(PALLET_EVM, EVM_ACCOUNT_STORAGES);
+	key.extend(blake2_128_concat(address.as_bytes()));
+	key.extend(blake2_128_concat(index.as_bytes()));
+	key
+}
+
+fn storage_prefix(pallet: &[u8], storage: &[u8]) -> Vec<u8> {
+	[twox_128(pallet), twox_128(storage)].concat()
+}
+
+fn blake2_128_concat(bytes: &[u8]) -> Vec<u8> {
+	[&blake2_128(bytes)[..], bytes].concat()
+}
+
+/// Read the value of `key` out of a proof against `state_root`, `None` meaning the proof shows
+/// the key is not set.
+pub fn read_proof(
+	state_root: H256,
+	proof: &[Vec<u8>],
+	key: &[u8],
+) -> Result<Option<Vec<u8>>, Error> {
+	let db = StorageProof::new(proof.iter().cloned()).into_memory_db::<BlakeTwo256>();
+	sp_trie::read_trie_value::<LayoutV1<BlakeTwo256>, _>(&db, &state_root, key, None, None)
+		.map_err(|_| Error::InvalidProof)
+}
+
+/// Read the `frame_system::Account` entry of an account out of an account proof, decoded as the
+/// `AccountInfo` type of the runtime.
+pub fn read_system_account<AccountInfo: Decode>(
+	state_root: H256,
+	account_proof: &[Vec<u8>],
+	account_id: &[u8],
+) -> Result<Option<AccountInfo>, Error> {
+	read_proof(state_root, account_proof, &system_account_key(account_id))?
+		.map(|value| AccountInfo::decode(&mut &value[..]).map_err(|_| Error::InvalidValue))
+		.transpose()
+}
+
+/// Verify an account proof proves the nonce and balance of an account, as `basic` derives them
+/// from its `frame_system::Account` entry. Accounts without an entry have a zero nonce and balance.
+pub fn verify_account_basic<AccountInfo: Decode>(
+	state_root: H256,
+	account_proof: &[Vec<u8>],
+	account_id: &[u8],
+	nonce: U256,
+	balance: U256,
+	basic: impl FnOnce(&AccountInfo) -> (U256, U256),
+) -> Result<(), Error> {
+	let proven = read_system_account::<AccountInfo>(state_root, account_proof, account_id)?
+		.map_or((U256::zero(), U256::zero()), |info| basic(&info));
+	if proven != (nonce, balance) {
+		return Err(Error::Mismatch);
+	}
+	Ok(())
+}
+
+/// Verify an account proof proves the code hash of an address, accounts without code having the
+/// hash of the empty code.
+pub fn verify_code_hash(
+	state_root: H256,
+	account_proof: &[Vec<u8>],
+	address: H160,
+	code_hash: H256,
+) -> Result<(), Error> {
+	let code = match read_proof(state_root, account_proof, &account_code_key(address))? {
+		Some(value) => Vec::<u8>::decode(&mut &value[..]).map_err(|_| Error::InvalidValue)?,
+		None => Vec::new(),
+	};
+	if H256(keccak_256(&code)) != code_hash {
+		return Err(Error::Mismatch);
+	}
+	Ok(())
+}
+
+/// Verify a storage proof proves the value of a slot, unset slots having a zero value.
+pub fn verify_storage(
+	state_root: H256,
+	storage_proof: &[Vec<u8>],
+	address: H160,
+	key: H256,
+	value: U256,
+) -> Result<(), Error> {
+	let proven = match read_proof(
+		state_root,
+		storage_proof,
+		&account_storage_key(address, key),
+	)? {
+		Some(value) => H256::decode(&mut &value[..]).map_err(|_| Error::InvalidValue)?,
+		None => H256::zero(),
+	};
+	if U256::from_big_endian(proven.as_bytes()) != value {
+		return Err(Error::Mismatch);
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use scale_codec::Encode;
+	use sp_trie::{MemoryDB, TrieDBMutBuilder, TrieMut};
+
+	/// Build a state with the given entries, returning its root and all of its nodes.
+	fn state(entries: &[(Vec<u8>, Vec<u8>)]) -> (H256, Vec<Vec<u8>>) {
+		let mut db = MemoryDB::<BlakeTwo256>::default();
+		let mut root = H256::zero();
+		{
+			let mut trie =
+				TrieDBMutBuilder::<LayoutV1<BlakeTwo256>>::new(&mut db, &mut root).build();
+			for (key, value) in entries {
+				trie.insert(key, value).unwrap();
+			}
+		}
+		let nodes = db.drain().into_values().map(|(node, _)| node).collect();
+		(root, nodes)
+	}
+
+	#[test]
+	fn verifies_code_and_storage() {
+		let address = H160::repeat_byte(0x11);
+		let code = vec![0x60, 0x00, 0x60, 0x00, 0xf3];
+		let slot = H256::repeat_byte(0x01);
+		let value = H256::from_low_u64_be(42);
+		let (root, proof) = state(&[
+			(account_code_key(address), code.encode()),
+			(account_storage_key(address, slot), value.encode()),
+		]);
+
+		let code_hash = H256(keccak_256(&code));
+		assert_eq!(verify_code_hash(root, &proof, address, code_hash), Ok(()));
+		assert_eq!(
+			verify_code_hash(root, &proof, address, H256(keccak_256(&[]))),
+			Err(Error::Mismatch)
+		);
+		assert_eq!(
+			verify_storage(root, &proof, address, slot, U256::from(42)),
+			Ok(())
+		);
+		// Unset slots are proven to be zero.
+		assert_eq!(
+			verify_storage(root, &proof, address, H256::repeat_byte(0x02), U256::zero()),
+			Ok(())
+		);
+	}
+
+	#[test]
+	fn verifies_account_basic() {
+		// The nonce and free balance of a minimal `AccountInfo`.
+		let basic = |info: &(u32, u128)| (U256::from(info.0), U256::from(info.1));
+		let account_id = [0x11; 20];
+		let (root, proof) = state(&[(system_account_key(&account_id), (3u32, 1_000u128).encode())]);
+
+		assert_eq!(
+			verify_account_basic(
+				root,
+				&proof,
+				&account_id,
+				U256::from(3),
+				U256::from(1_000),
+				basic
+			),
+			Ok(())
+		);
+		assert_eq!(
+			verify_account_basic(
+				root,
+				&proof,
+				&account_id,
+				U256::from(3),
+				U256::from(999),
+				basic
+			),
+			Err(Error::Mismatch)
+		);
+		// Accounts without an entry are proven to be empty.
+		assert_eq!(
+			verify_account_basic(root, &proof, &[0x22; 20], U256::zero(), U256::zero(), basic),
+			Ok(())
+		);
+	}
+
+	#[test]
+	fn rejects_proof_of_another_root() {
+		let address = H160::repeat_byte(0x11);
+		let (_, proof) = state(&[(account_code_key(address), vec![0x01].encode())]);
+		assert_eq!(
+			read_proof(H256::repeat_byte(0xff), &proof, &account_code_key(address)),
+			Err(Error::InvalidProof)
+		);
+	}
+}
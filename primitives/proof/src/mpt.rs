@@ -0,0 +1,880 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ethereum Merkle Patricia trie over a reference counted node store.
+//!
+//! Nodes are stored by their Keccak hash. Nodes shorter than 32 bytes are embedded in their
+//! parent, except for roots which are always stored so that a trie is identified by its hash.
+//! A stored node holds a reference to each of the stored nodes it points to, and dropping the
+//! last reference to a node drops its own references, so that replacing a root by another one
+//! with [`NodeStore::retain`] and [`release`] keeps exactly the nodes of the live tries.
+
+use alloc::{boxed::Box, collections::btree_map::BTreeMap, vec, vec::Vec};
+use rlp::{DecoderError, Rlp, RlpStream};
+// Substrate
+use sp_core::{hashing::keccak_256, H160, H256, U256};
+
+/// Root of the empty trie, `keccak256(rlp(""))`.
+pub const EMPTY_ROOT: H256 = H256([
+	0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+	0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+]);
+
+/// Hash of the empty code, `keccak256("")`.
+pub const EMPTY_CODE_HASH: H256 = H256([
+	0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+	0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+]);
+
+/// Trie error.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+	/// A node is missing from the store.
+	MissingNode(H256),
+	/// A node cannot be decoded.
+	InvalidNode,
+}
+
+impl From<DecoderError> for Error {
+	fn from(_: DecoderError) -> Self {
+		Error::InvalidNode
+	}
+}
+
+/// Storage of the trie nodes.
+pub trait NodeStore {
+	/// Encoded node with the given hash.
+	fn get(&self, hash: &H256) -> Option<Vec<u8>>;
+	/// Store a node without any reference to it, returning `false` if it is already stored.
+	fn insert(&mut self, hash: H256, node: Vec<u8>) -> bool;
+	/// Add a reference to a stored node.
+	fn retain(&mut self, hash: &H256);
+	/// Drop a reference to a stored node, removing and returning it once unreferenced.
+	fn release(&mut self, hash: &H256) -> Option<Vec<u8>>;
+}
+
+/// In-memory node store.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore(BTreeMap<H256, (u32, Vec<u8>)>);
+
+impl MemoryStore {
+	/// Store holding the nodes of a proof.
+	pub fn from_proof(proof: &[Vec<u8>]) -> Self {
+		let mut store = Self::default();
+		for node in proof {
+			store.insert(H256(keccak_256(node)), node.clone());
+		}
+		store
+	}
+
+	/// Number of stored nodes.
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Whether no node is stored.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+}
+
+impl NodeStore for MemoryStore {
+	fn get(&self, hash: &H256) -> Option<Vec<u8>> {
+		self.0.get(hash).map(|(_, node)| node.clone())
+	}
+
+	fn insert(&mut self, hash: H256, node: Vec<u8>) -> bool {
+		if self.0.contains_key(&hash) {
+			return false;
+		}
+		self.0.insert(hash, (0, node));
+		true
+	}
+
+	fn retain(&mut self, hash: &H256) {
+		if let Some((references, _)) = self.0.get_mut(hash) {
+			*references += 1;
+		}
+	}
+
+	fn release(&mut self, hash: &H256) -> Option<Vec<u8>> {
+		let (references, _) = self.0.get_mut(hash)?;
+		if *references > 1 {
+			*references -= 1;
+			return None;
+		}
+		self.0.remove(hash).map(|(_, node)| node)
+	}
+}
+
+/// Reference to a child node.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Child {
+	Empty,
+	Hash(H256),
+	Inline(Vec<u8>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Node {
+	Empty,
+	Leaf(Vec<u8>, Vec<u8>),
+	Extension(Vec<u8>, Child),
+	Branch(Box<[Child; 16]>, Option<Vec<u8>>),
+}
+
+/// Value of `key` in the trie of `root`.
+pub fn get<S: NodeStore>(store: &S, root: H256, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+	let mut node = load(store, &root_child(root))?;
+	let mut path = &nibbles(key)[..];
+	loop {
+		node = match node {
+			Node::Empty => return Ok(None),
+			Node::Leaf(leaf_path, value) => {
+				return Ok((leaf_path == path).then_some(value));
+			}
+			Node::Extension(extension_path, child) => {
+				match path.strip_prefix(&extension_path[..]) {
+					Some(rest) => {
+						path = rest;
+						load(store, &child)?
+					}
+					None => return Ok(None),
+				}
+			}
+			Node::Branch(children, value) => match path.split_first() {
+				Some((index, rest)) => {
+					path = rest;
+					load(store, &children[*index as usize])?
+				}
+				None => return Ok(value),
+			},
+		};
+	}
+}
+
+/// Nodes proving the value, or the absence, of `key` in the trie of `root`.
+pub fn prove<S: NodeStore>(store: &S, root: H256, key: &[u8]) -> Result<Vec<Vec<u8>>, Error> {
+	let mut proof = Vec::new();
+	let mut child = root_child(root);
+	let mut path = &nibbles(key)[..];
+	loop {
+		if let Child::Hash(hash) = child {
+			proof.push(store.get(&hash).ok_or(Error::MissingNode(hash))?);
+		}
+		child = match load(store, &child)? {
+			Node::Empty | Node::Leaf(..) => return Ok(proof),
+			Node::Extension(extension_path, next) => match path.strip_prefix(&extension_path[..]) {
+				Some(rest) => {
+					path = rest;
+					next
+				}
+				None => return Ok(proof),
+			},
+			Node::Branch(mut children, _) => match path.split_first() {
+				Some((index, rest)) => {
+					path = rest;
+					core::mem::replace(&mut children[*index as usize], Child::Empty)
+				}
+				None => return Ok(proof),
+			},
+		};
+	}
+}
+
+/// Set `key` to `value` in the trie of `root`, returning the root of the new trie.
+///
+/// The old root keeps its reference, which the caller drops with [`release`] once it is not
+/// used anymore.
+pub fn insert<S: NodeStore>(
+	store: &mut S,
+	root: H256,
+	key: &[u8],
+	value: Vec<u8>,
+) -> Result<H256, Error> {
+	let node = load(store, &root_child(root))?;
+	let node = insert_at(store, node, &nibbles(key), value)?;
+	Ok(commit_root(store, &node))
+}
+
+/// Remove `key` from the trie of `root`, returning the root of the new trie.
+///
+/// As for [`insert`], the old root keeps its reference.
+pub fn remove<S: NodeStore>(store: &mut S, root: H256, key: &[u8]) -> Result<H256, Error> {
+	let node = load(store, &root_child(root))?;
+	match remove_at(store, node, &nibbles(key))? {
+		Some(node) => Ok(commit_root(store, &node)),
+		None => {
+			// The key is not set: keep the same trie, with a new reference.
+			if root != EMPTY_ROOT {
+				store.retain(&root);
+			}
+			Ok(root)
+		}
+	}
+}
+
+/// Drop a reference to the trie of `root`, removing the nodes not used by another trie.
+pub fn release<S: NodeStore>(store: &mut S, root: H256) -> Result<(), Error> {
+	if root == EMPTY_ROOT {
+		return Ok(());
+	}
+	let mut pending = vec![root];
+	release_some(store, &mut pending, usize::MAX)?;
+	Ok(())
+}
+
+/// Drop the references to at most `limit` of the `pending` nodes, as for [`release`].
+///
+/// The children of the removed nodes are pushed to `pending`, so that a trie can be released
+/// over several calls. Returns the number of nodes released.
+pub fn release_some<S: NodeStore>(
+	store: &mut S,
+	pending: &mut Vec<H256>,
+	limit: usize,
+) -> Result<usize, Error> {
+	let mut released = 0;
+	while released < limit {
+		let Some(hash) = pending.pop() else {
+			break;
+		};
+		released += 1;
+		if let Some(node) = store.release(&hash) {
+			pending.extend(hashed_children(&decode_node(&node)?));
+		}
+	}
+	Ok(released)
+}
+
+/// Value of `key` in the trie of `root`, read out of a proof of it.
+pub fn verify_proof(root: H256, key: &[u8], proof: &[Vec<u8>]) -> Result<Option<Vec<u8>>, Error> {
+	get(&MemoryStore::from_proof(proof), root, key)
+}
+
+/// Ethereum account, as stored in the state trie under the Keccak hash of its address.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Account {
+	pub nonce: U256,
+	pub balance: U256,
+	pub storage_root: H256,
+	pub code_hash: H256,
+}
+
+impl Default for Account {
+	fn default() -> Self {
+		Self {
+			nonce: U256::zero(),
+			balance: U256::zero(),
+			storage_root: EMPTY_ROOT,
+			code_hash: EMPTY_CODE_HASH,
+		}
+	}
+}
+
+impl Account {
+	/// Whether the account is empty in the sense of EIP-161, and so absent from the state trie.
+	pub fn is_empty(&self) -> bool {
+		self.nonce.is_zero()
+			&& self.balance.is_zero()
+			&& self.code_hash == EMPTY_CODE_HASH
+			&& self.storage_root == EMPTY_ROOT
+	}
+
+	/// RLP encoding of the account.
+	pub fn encode(&self) -> Vec<u8> {
+		let mut stream = RlpStream::new_list(4);
+		stream.append(&encode_scalar(self.nonce).as_slice());
+		stream.append(&encode_scalar(self.balance).as_slice());
+		stream.append(&self.storage_root.as_bytes());
+		stream.append(&self.code_hash.as_bytes());
+		stream.out().to_vec()
+	}
+
+	/// Decode an RLP encoded account.
+	pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+		let rlp = Rlp::new(bytes);
+		if rlp.item_count()? != 4 {
+			return Err(Error::InvalidNode);
+		}
+		Ok(Self {
+			nonce: decode_scalar(rlp.at(0)?.data()?)?,
+			balance: decode_scalar(rlp.at(1)?.data()?)?,
+			storage_root: decode_hash(rlp.at(2)?.data()?)?,
+			code_hash: decode_hash(rlp.at(3)?.data()?)?,
+		})
+	}
+}
+
+/// Key of an account in the state trie.
+pub fn account_key(address: H160) -> [u8; 32] {
+	keccak_256(address.as_bytes())
+}
+
+/// Key of a slot in a storage trie.
+pub fn storage_key(index: H256) -> [u8; 32] {
+	keccak_256(index.as_bytes())
+}
+
+/// RLP encoding of a storage value, as stored in a storage trie. Zero values are not stored.
+pub fn encode_storage_value(value: U256) -> Vec<u8> {
+	let mut stream = RlpStream::new();
+	stream.append(&encode_scalar(value).as_slice());
+	stream.out().to_vec()
+}
+
+/// Decode a storage value out of a storage trie.
+pub fn decode_storage_value(bytes: &[u8]) -> Result<U256, Error> {
+	decode_scalar(Rlp::new(bytes).data()?)
+}
+
+/// Read an account out of an account proof against a state root, `None` meaning the proof shows
+/// the account is empty.
+pub fn verify_account_proof(
+	state_root: H256,
+	address: H160,
+	proof: &[Vec<u8>],
+) -> Result<Option<Account>, crate::Error> {
+	verify_proof(state_root, &account_key(address), proof)
+		.map_err(|_| crate::Error::InvalidProof)?
+		.map(|value| Account::decode(&value).map_err(|_| crate::Error::InvalidValue))
+		.transpose()
+}
+
+/// Read a slot out of a storage proof against the storage root of an account, unset slots
+/// having a zero value.
+pub fn verify_storage_proof(
+	storage_root: H256,
+	index: H256,
+	proof: &[Vec<u8>],
+) -> Result<U256, crate::Error> {
+	match verify_proof(storage_root, &storage_key(index), proof)
+		.map_err(|_| crate::Error::InvalidProof)?
+	{
+		Some(value) => decode_storage_value(&value).map_err(|_| crate::Error::InvalidValue),
+		None => Ok(U256::zero()),
+	}
+}
+
+fn insert_at<S: NodeStore>(
+	store: &mut S,
+	node: Node,
+	path: &[u8],
+	value: Vec<u8>,
+) -> Result<Node, Error> {
+	Ok(match node {
+		Node::Empty => Node::Leaf(path.to_vec(), value),
+		Node::Leaf(leaf_path, leaf_value) => {
+			if leaf_path == path {
+				return Ok(Node::Leaf(leaf_path, value));
+			}
+			let common = common_prefix(&leaf_path, path);
+			let mut children = empty_children();
+			let mut branch_value = None;
+			for (rest, value) in [(&leaf_path[common..], leaf_value), (&path[common..], value)] {
+				match rest.split_first() {
+					Some((index, rest)) => {
+						children[*index as usize] =
+							commit(store, &Node::Leaf(rest.to_vec(), value));
+					}
+					None => branch_value = Some(value),
+				}
+			}
+			extend(store, &path[..common], Node::Branch(children, branch_value))
+		}
+		Node::Extension(extension_path, child) => {
+			let common = common_prefix(&extension_path, path);
+			if common == extension_path.len() {
+				let node = load(store, &child)?;
+				let node = insert_at(store, node, &path[common..], value)?;
+				return Ok(Node::Extension(extension_path, commit(store, &node)));
+			}
+			let mut children = empty_children();
+			let mut branch_value = None;
+			let (index, rest) = extension_path[common..]
+				.split_first()
+				.expect("the extension path is longer than the common prefix; qed");
+			children[*index as usize] = if rest.is_empty() {
+				child
+			} else {
+				commit(store, &Node::Extension(rest.to_vec(), child))
+			};
+			match path[common..].split_first() {
+				Some((index, rest)) => {
+					children[*index as usize] = commit(store, &Node::Leaf(rest.to_vec(), value));
+				}
+				None => branch_value = Some(value),
+			}
+			extend(store, &path[..common], Node::Branch(children, branch_value))
+		}
+		Node::Branch(mut children, branch_value) => match path.split_first() {
+			Some((index, rest)) => {
+				let child = &mut children[*index as usize];
+				let node = load(store, child)?;
+				let node = insert_at(store, node, rest, value)?;
+				*child = commit(store, &node);
+				Node::Branch(children, branch_value)
+			}
+			None => Node::Branch(children, Some(value)),
+		},
+	})
+}
+
+/// Remove `path` from `node`, returning `None` if it is not set.
+fn remove_at<S: NodeStore>(store: &mut S, node: Node, path: &[u8]) -> Result<Option<Node>, Error> {
+	Ok(match node {
+		Node::Empty => None,
+		Node::Leaf(leaf_path, _) => (leaf_path == path).then_some(Node::Empty),
+		Node::Extension(extension_path, child) => {
+			let Some(rest) = path.strip_prefix(&extension_path[..]) else {
+				return Ok(None);
+			};
+			let node = load(store, &child)?;
+			match remove_at(store, node, rest)? {
+				Some(node) => Some(prepend(store, &extension_path, node)),
+				None => None,
+			}
+		}
+		Node::Branch(mut children, branch_value) => match path.split_first() {
+			Some((index, rest)) => {
+				let child = &mut children[*index as usize];
+				let node = load(store, child)?;
+				match remove_at(store, node, rest)? {
+					Some(node) => {
+						*child = commit(store, &node);
+						Some(normalize_branch(store, children, branch_value)?)
+					}
+					None => None,
+				}
+			}
+			None => match branch_value {
+				Some(_) => Some(normalize_branch(store, children, None)?),
+				None => None,
+			},
+		},
+	})
+}
+
+/// Collapse a branch left with a single entry.
+fn normalize_branch<S: NodeStore>(
+	store: &mut S,
+	mut children: Box<[Child; 16]>,
+	value: Option<Vec<u8>>,
+) -> Result<Node, Error> {
+	let mut used = children
+		.iter()
+		.enumerate()
+		.filter(|(_, child)| **child != Child::Empty)
+		.map(|(index, _)| index);
+	let (first, second) = (used.next(), used.next());
+	Ok(match (first, second, value) {
+		(None, _, None) => Node::Empty,
+		(None, _, Some(value)) => Node::Leaf(Vec::new(), value),
+		(Some(index), None, None) => {
+			let child = core::mem::replace(&mut children[index], Child::Empty);
+			let node = load(store, &child)?;
+			prepend(store, &[index as u8], node)
+		}
+		(_, _, value) => Node::Branch(children, value),
+	})
+}
+
+/// Node reached through `path` then `node`.
+fn prepend<S: NodeStore>(store: &mut S, path: &[u8], node: Node) -> Node {
+	match node {
+		Node::Empty => Node::Empty,
+		Node::Leaf(rest, value) => Node::Leaf([path, &rest[..]].concat(), value),
+		Node::Extension(rest, child) => Node::Extension([path, &rest[..]].concat(), child),
+		node @ Node::Branch(..) => extend(store, path, node),
+	}
+}
+
+/// Branch reached through `path`.
+fn extend<S: NodeStore>(store: &mut S, path: &[u8], branch: Node) -> Node {
+	if path.is_empty() {
+		branch
+	} else {
+		Node::Extension(path.to_vec(), commit(store, &branch))
+	}
+}
+
+fn root_child(root: H256) -> Child {
+	if root == EMPTY_ROOT {
+		Child::Empty
+	} else {
+		Child::Hash(root)
+	}
+}
+
+fn load<S: NodeStore>(store: &S, child: &Child) -> Result<Node, Error> {
+	match child {
+		Child::Empty => Ok(Node::Empty),
+		Child::Hash(hash) => decode_node(&store.get(hash).ok_or(Error::MissingNode(*hash))?),
+		Child::Inline(node) => decode_node(node),
+	}
+}
+
+/// Store a node, returning the reference to use in its parent.
+fn commit<S: NodeStore>(store: &mut S, node: &Node) -> Child {
+	if *node == Node::Empty {
+		return Child::Empty;
+	}
+	let encoded = encode_node(node);
+	if encoded.len() < 32 {
+		return Child::Inline(encoded);
+	}
+	Child::Hash(store_node(store, node, encoded))
+}
+
+/// Store a root node and add a reference to it.
+fn commit_root<S: NodeStore>(store: &mut S, node: &Node) -> H256 {
+	if *node == Node::Empty {
+		return EMPTY_ROOT;
+	}
+	let root = store_node(store, node, encode_node(node));
+	store.retain(&root);
+	root
+}
+
+fn store_node<S: NodeStore>(store: &mut S, node: &Node, encoded: Vec<u8>) -> H256 {
+	let hash = H256(keccak_256(&encoded));
+	if store.insert(hash, encoded) {
+		for child in hashed_children(node) {
+			store.retain(&child);
+		}
+	}
+	hash
+}
+
+/// Stored nodes a node points to. Inline nodes are too short to point to any.
+fn hashed_children(node: &Node) -> Vec<H256> {
+	let hash = |child: &Child| match child {
+		Child::Hash(hash) => Some(*hash),
+		_ => None,
+	};
+	match node {
+		Node::Extension(_, child) => hash(child).into_iter().collect(),
+		Node::Branch(children, _) => children.iter().filter_map(hash).collect(),
+		Node::Empty | Node::Leaf(..) => Vec::new(),
+	}
+}
+
+fn empty_children() -> Box<[Child; 16]> {
+	Box::new(core::array::from_fn(|_| Child::Empty))
+}
+
+fn nibbles(key: &[u8]) -> Vec<u8> {
+	key.iter()
+		.flat_map(|byte| [byte >> 4, byte & 0x0f])
+		.collect()
+}
+
+fn common_prefix(a: &[u8], b: &[u8]) -> usize {
+	a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+/// Hex-prefix encoding of a path.
+fn encode_path(path: &[u8], leaf: bool) -> Vec<u8> {
+	let flag = if leaf { 0x20 } else { 0x00 };
+	let mut encoded = Vec::with_capacity(path.len() / 2 + 1);
+	let rest = if path.len() % 2 == 1 {
+		encoded.push(flag | 0x10 | path[0]);
+		&path[1..]
+	} else {
+		encoded.push(flag);
+		path
+	};
+	encoded.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+	encoded
+}
+
+/// Decode a hex-prefix encoded path, along with whether it is the path of a leaf.
+fn decode_path(encoded: &[u8]) -> Result<(Vec<u8>, bool), Error> {
+	let (first, rest) = encoded.split_first().ok_or(Error::InvalidNode)?;
+	let flag = first >> 4;
+	if flag > 3 {
+		return Err(Error::InvalidNode);
+	}
+	let mut path = Vec::with_capacity(rest.len() * 2 + 1);
+	if flag & 1 == 1 {
+		path.push(first & 0x0f);
+	}
+	path.extend(nibbles(rest));
+	Ok((path, flag & 2 == 2))
+}
+
+fn encode_node(node: &Node) -> Vec<u8> {
+	let mut stream = RlpStream::new();
+	match node {
+		Node::Empty => {
+			stream.append_empty_data();
+		}
+		Node::Leaf(path, value) => {
+			stream.begin_list(2);
+			stream.append(&encode_path(path, true).as_slice());
+			stream.append(&value.as_slice());
+		}
+		Node::Extension(path, child) => {
+			stream.begin_list(2);
+			stream.append(&encode_path(path, false).as_slice());
+			append_child(&mut stream, child);
+		}
+		Node::Branch(children, value) => {
+			stream.begin_list(17);
+			for child in children.iter() {
+				append_child(&mut stream, child);
+			}
+			match value {
+				Some(value) => stream.append(&value.as_slice()),
+				None => stream.append_empty_data(),
+			};
+		}
+	}
+	stream.out().to_vec()
+}
+
+fn append_child(stream: &mut RlpStream, child: &Child) {
+	match child {
+		Child::Empty => {
+			stream.append_empty_data();
+		}
+		Child::Hash(hash) => {
+			stream.append(&hash.as_bytes());
+		}
+		Child::Inline(node) => {
+			stream.append_raw(node, 1);
+		}
+	}
+}
+
+fn decode_node(encoded: &[u8]) -> Result<Node, Error> {
+	let rlp = Rlp::new(encoded);
+	if rlp.is_data() && rlp.is_empty() {
+		return Ok(Node::Empty);
+	}
+	match rlp.item_count()? {
+		2 => {
+			let (path, leaf) = decode_path(rlp.at(0)?.data()?)?;
+			if leaf {
+				Ok(Node::Leaf(path, rlp.at(1)?.data()?.to_vec()))
+			} else {
+				Ok(Node::Extension(path, decode_child(&rlp.at(1)?)?))
+			}
+		}
+		17 => {
+			let mut children = empty_children();
+			for (index, child) in children.iter_mut().enumerate() {
+				*child = decode_child(&rlp.at(index)?)?;
+			}
+			let value = rlp.at(16)?.data()?;
+			Ok(Node::Branch(
+				children,
+				(!value.is_empty()).then(|| value.to_vec()),
+			))
+		}
+		_ => Err(Error::InvalidNode),
+	}
+}
+
+fn decode_child(rlp: &Rlp) -> Result<Child, Error> {
+	if rlp.is_list() {
+		return Ok(Child::Inline(rlp.as_raw().to_vec()));
+	}
+	match rlp.data()? {
+		[] => Ok(Child::Empty),
+		hash if hash.len() == 32 => Ok(Child::Hash(H256::from_slice(hash))),
+		_ => Err(Error::InvalidNode),
+	}
+}
+
+fn encode_scalar(value: U256) -> Vec<u8> {
+	let mut bytes = [0u8; 32];
+	value.to_big_endian(&mut bytes);
+	let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(32);
+	bytes[start..].to_vec()
+}
+
+fn decode_scalar(bytes: &[u8]) -> Result<U256, Error> {
+	if bytes.len() > 32 || bytes.first() == Some(&0) {
+		return Err(Error::InvalidNode);
+	}
+	Ok(U256::from_big_endian(bytes))
+}
+
+fn decode_hash(bytes: &[u8]) -> Result<H256, Error> {
+	if bytes.len() != 32 {
+		return Err(Error::InvalidNode);
+	}
+	Ok(H256::from_slice(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn build(entries: &[(&[u8], &[u8])]) -> (MemoryStore, H256) {
+		let mut store = MemoryStore::default();
+		let mut root = EMPTY_ROOT;
+		for (key, value) in entries {
+			let new_root = insert(&mut store, root, key, value.to_vec()).unwrap();
+			release(&mut store, root).unwrap();
+			root = new_root;
+		}
+		(store, root)
+	}
+
+	#[test]
+	fn empty_root_matches_ethereum() {
+		assert_eq!(EMPTY_ROOT, H256(keccak_256(&[0x80])));
+		assert_eq!(EMPTY_CODE_HASH, H256(keccak_256(&[])));
+	}
+
+	#[test]
+	fn root_matches_ethereum_test_vector() {
+		let (_, root) = build(&[
+			(b"doe", b"reindeer"),
+			(b"dog", b"puppy"),
+			(b"dogglesworth", b"cat"),
+		]);
+		assert_eq!(
+			root,
+			H256(hex_literal::hex!(
+				"8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3"
+			))
+		);
+	}
+
+	#[test]
+	fn root_does_not_depend_on_history() {
+		let (_, root) = build(&[(b"dog", b"puppy"), (b"horse", b"stallion")]);
+		let (mut store, with_removed) = build(&[
+			(b"horse", b"stallion"),
+			(b"doge", b"coin"),
+			(b"dog", b"puppy"),
+		]);
+		let removed = remove(&mut store, with_removed, b"doge").unwrap();
+		release(&mut store, with_removed).unwrap();
+		assert_eq!(removed, root);
+	}
+
+	#[test]
+	fn removing_all_keys_empties_the_store() {
+		let keys: Vec<[u8; 32]> = (0u8..40).map(|i| keccak_256(&[i])).collect();
+		let mut store = MemoryStore::default();
+		let mut root = EMPTY_ROOT;
+		for key in &keys {
+			let new_root = insert(&mut store, root, key, vec![1; 40]).unwrap();
+			release(&mut store, root).unwrap();
+			root = new_root;
+		}
+		for key in &keys {
+			let new_root = remove(&mut store, root, key).unwrap();
+			release(&mut store, root).unwrap();
+			root = new_root;
+		}
+		assert_eq!(root, EMPTY_ROOT);
+		assert!(store.is_empty());
+	}
+
+	#[test]
+	fn releases_a_trie_over_several_calls() {
+		let keys: Vec<[u8; 32]> = (0u8..40).map(|i| keccak_256(&[i])).collect();
+		let entries: Vec<(&[u8], &[u8])> =
+			keys.iter().map(|key| (&key[..], &[1u8; 40][..])).collect();
+		let (mut store, root) = build(&entries);
+		let mut pending = vec![root];
+		let mut calls = 0;
+		while !pending.is_empty() {
+			assert_eq!(
+				release_some(&mut store, &mut pending, 4).map(|n| n <= 4),
+				Ok(true)
+			);
+			calls += 1;
+		}
+		assert!(calls > 1);
+		assert!(store.is_empty());
+	}
+
+	#[test]
+	fn shared_tries_keep_their_nodes() {
+		let (mut store, root) = build(&[(b"dog", b"puppy"), (b"horse", b"stallion")]);
+		// A second trie with the same content shares all of its nodes.
+		store.retain(&root);
+		let other = insert(&mut store, root, b"horse", b"pony".to_vec()).unwrap();
+		release(&mut store, root).unwrap();
+		assert_eq!(
+			get(&store, root, b"horse").unwrap(),
+			Some(b"stallion".to_vec())
+		);
+		assert_eq!(
+			get(&store, other, b"horse").unwrap(),
+			Some(b"pony".to_vec())
+		);
+	}
+
+	#[test]
+	fn proves_accounts_and_absence() {
+		let address = H160::repeat_byte(0x11);
+		let account = Account {
+			nonce: U256::from(1),
+			balance: U256::from(1_000_000_000u64),
+			..Default::default()
+		};
+		let mut store = MemoryStore::default();
+		let root = insert(
+			&mut store,
+			EMPTY_ROOT,
+			&account_key(address),
+			account.encode(),
+		)
+		.unwrap();
+
+		let proof = prove(&store, root, &account_key(address)).unwrap();
+		assert_eq!(
+			verify_account_proof(root, address, &proof),
+			Ok(Some(account))
+		);
+
+		let absent = H160::repeat_byte(0x22);
+		let proof = prove(&store, root, &account_key(absent)).unwrap();
+		assert_eq!(verify_account_proof(root, absent, &proof), Ok(None));
+		assert_eq!(
+			verify_account_proof(H256::repeat_byte(0xff), absent, &proof),
+			Err(crate::Error::InvalidProof)
+		);
+	}
+
+	#[test]
+	fn proves_storage() {
+		let index = H256::repeat_byte(0x01);
+		let mut store = MemoryStore::default();
+		let root = insert(
+			&mut store,
+			EMPTY_ROOT,
+			&storage_key(index),
+			encode_storage_value(U256::from(42)),
+		)
+		.unwrap();
+		let proof = prove(&store, root, &storage_key(index)).unwrap();
+		assert_eq!(
+			verify_storage_proof(root, index, &proof),
+			Ok(U256::from(42))
+		);
+		let unset = H256::repeat_byte(0x02);
+		let proof = prove(&store, root, &storage_key(unset)).unwrap();
+		assert_eq!(verify_storage_proof(root, unset, &proof), Ok(U256::zero()));
+	}
+}
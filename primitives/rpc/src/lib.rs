@@ -45,6 +45,71 @@ pub struct TransactionStatus {
 	pub logs_bloom: Bloom,
 }
 
+/// Transaction status that also records why a failed transaction failed.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct TransactionStatusV2 {
+	pub transaction_hash: H256,
+	pub transaction_index: u32,
+	pub from: Address,
+	pub to: Option<Address>,
+	pub contract_address: Option<Address>,
+	pub logs: Vec<Log>,
+	pub logs_bloom: Bloom,
+	/// The exit reason of a failed transaction, `None` if it succeeded.
+	pub exit_reason: Option<fp_evm::ExitReason>,
+	/// The (possibly truncated) output of a reverted call.
+	pub revert_reason: Option<Vec<u8>>,
+}
+
+impl From<TransactionStatus> for TransactionStatusV2 {
+	fn from(status: TransactionStatus) -> Self {
+		Self {
+			transaction_hash: status.transaction_hash,
+			transaction_index: status.transaction_index,
+			from: status.from,
+			to: status.to,
+			contract_address: status.contract_address,
+			logs: status.logs,
+			logs_bloom: status.logs_bloom,
+			exit_reason: None,
+			revert_reason: None,
+		}
+	}
+}
+
+impl From<TransactionStatusV2> for TransactionStatus {
+	fn from(status: TransactionStatusV2) -> Self {
+		Self {
+			transaction_hash: status.transaction_hash,
+			transaction_index: status.transaction_index,
+			from: status.from,
+			to: status.to,
+			contract_address: status.contract_address,
+			logs: status.logs,
+			logs_bloom: status.logs_bloom,
+		}
+	}
+}
+
+/// Ethereum Merkle Patricia proof of an account and of some of its storage slots.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct EthereumAccountProof {
+	pub nonce: U256,
+	pub balance: U256,
+	pub storage_hash: H256,
+	pub code_hash: H256,
+	pub account_proof: Vec<Vec<u8>>,
+	pub storage_proof: Vec<EthereumStorageProof>,
+}
+
+/// Ethereum Merkle Patricia proof of a storage slot.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct EthereumStorageProof {
+	pub key: H256,
+	pub value: U256,
+	pub proof: Vec<Vec<u8>>,
+}
+
 pub trait RuntimeStorageOverride<B: BlockT, C>: Send + Sync {
 	fn is_enabled() -> bool;
 
@@ -84,7 +149,7 @@ impl<B: BlockT, C> RuntimeStorageOverride<B, C> for () {
 
 sp_api::decl_runtime_apis! {
 	/// API necessary for Ethereum-compatibility layer.
-	#[api_version(5)]
+	#[api_version(9)]
 	pub trait EthereumRuntimeRPCApi {
 		/// Returns runtime defined pallet_evm::ChainId.
 		fn chain_id() -> u64;
@@ -141,6 +206,7 @@ sp_api::decl_runtime_apis! {
 			estimate: bool,
 			access_list: Option<Vec<(Address, Vec<H256>)>>,
 		) -> Result<fp_evm::ExecutionInfo::<Vec<u8>>, sp_runtime::DispatchError>;
+		#[changed_in(6)]
 		fn call(
 			from: Address,
 			to: Address,
@@ -153,6 +219,34 @@ sp_api::decl_runtime_apis! {
 			estimate: bool,
 			access_list: Option<Vec<(Address, Vec<H256>)>>,
 		) -> Result<fp_evm::ExecutionInfoV2::<Vec<u8>>, sp_runtime::DispatchError>;
+		#[changed_in(7)]
+		fn call(
+			from: Address,
+			to: Address,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(Address, Vec<H256>)>>,
+			authorization_list: Option<Vec<ethereum::AuthorizationListItem>>,
+		) -> Result<fp_evm::ExecutionInfoV2::<Vec<u8>>, sp_runtime::DispatchError>;
+		fn call(
+			from: Address,
+			to: Address,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(Address, Vec<H256>)>>,
+			authorization_list: Option<Vec<ethereum::AuthorizationListItem>>,
+			block_overrides: Option<fp_evm::BlockOverrides>,
+		) -> Result<fp_evm::ExecutionInfoV2::<Vec<u8>>, sp_runtime::DispatchError>;
 
 		/// Returns a frame_ethereum::create response.
 		#[changed_in(2)]
@@ -188,6 +282,18 @@ sp_api::decl_runtime_apis! {
 			estimate: bool,
 			access_list: Option<Vec<(Address, Vec<H256>)>>,
 		) -> Result<fp_evm::ExecutionInfo::<Address>, sp_runtime::DispatchError>;
+		#[changed_in(7)]
+		fn create(
+			from: Address,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(Address, Vec<H256>)>>,
+		) -> Result<fp_evm::ExecutionInfoV2::<Address>, sp_runtime::DispatchError>;
 		fn create(
 			from: Address,
 			data: Vec<u8>,
@@ -198,22 +304,26 @@ sp_api::decl_runtime_apis! {
 			nonce: Option<U256>,
 			estimate: bool,
 			access_list: Option<Vec<(Address, Vec<H256>)>>,
+			block_overrides: Option<fp_evm::BlockOverrides>,
 		) -> Result<fp_evm::ExecutionInfoV2::<Address>, sp_runtime::DispatchError>;
 
 		/// Return the current block. Legacy.
 		#[changed_in(2)]
 		fn current_block() -> Option<ethereum::BlockV0>;
 		/// Return the current block.
-		fn current_block() -> Option<ethereum::BlockV2>;
+		fn current_block() -> Option<ethereum::BlockV3>;
 
 		/// Return the current receipt.
 		#[changed_in(4)]
 		fn current_receipts() -> Option<Vec<ethereum::ReceiptV0>>;
 		/// Return the current receipt.
-		fn current_receipts() -> Option<Vec<ethereum::ReceiptV3>>;
+		fn current_receipts() -> Option<Vec<ethereum::ReceiptV4>>;
 
 		/// Return the current transaction status.
+		#[changed_in(9)]
 		fn current_transaction_statuses() -> Option<Vec<TransactionStatus>>;
+		/// Return the current transaction status.
+		fn current_transaction_statuses() -> Option<Vec<TransactionStatusV2>>;
 
 		/// Return all the current data for a block in a single runtime call. Legacy.
 		#[changed_in(2)]
@@ -229,11 +339,17 @@ sp_api::decl_runtime_apis! {
 			Option<Vec<ethereum::ReceiptV0>>,
 			Option<Vec<TransactionStatus>>
 		);
+		#[changed_in(9)]
 		fn current_all() -> (
-			Option<ethereum::BlockV2>,
-			Option<Vec<ethereum::ReceiptV3>>,
+			Option<ethereum::BlockV3>,
+			Option<Vec<ethereum::ReceiptV4>>,
 			Option<Vec<TransactionStatus>>
 		);
+		fn current_all() -> (
+			Option<ethereum::BlockV3>,
+			Option<Vec<ethereum::ReceiptV4>>,
+			Option<Vec<TransactionStatusV2>>
+		);
 
 		/// Receives a `Vec<OpaqueExtrinsic>` and filters all the ethereum transactions. Legacy.
 		#[changed_in(2)]
@@ -243,7 +359,7 @@ sp_api::decl_runtime_apis! {
 		/// Receives a `Vec<OpaqueExtrinsic>` and filters all the ethereum transactions.
 		fn extrinsic_filter(
 			xts: Vec<<Block as BlockT>::Extrinsic>,
-		) -> Vec<ethereum::TransactionV2>;
+		) -> Vec<ethereum::TransactionV3>;
 
 		/// Return the elasticity multiplier.
 		fn elasticity() -> Option<Permill>;
@@ -253,29 +369,97 @@ sp_api::decl_runtime_apis! {
 		fn gas_limit_multiplier_support();
 
 		/// Return the pending block.
+		#[changed_in(9)]
 		fn pending_block(
 			xts: Vec<<Block as BlockT>::Extrinsic>,
-		) -> (Option<ethereum::BlockV2>, Option<Vec<TransactionStatus>>);
+		) -> (Option<ethereum::BlockV3>, Option<Vec<TransactionStatus>>);
+		/// Return the pending block.
+		fn pending_block(
+			xts: Vec<<Block as BlockT>::Extrinsic>,
+		) -> (Option<ethereum::BlockV3>, Option<Vec<TransactionStatusV2>>);
 		/// Initialize the pending block.
 		/// The behavior should be the same as the runtime api Core_initialize_block but
 		/// for a "pending" block.
 		/// If your project don't need to have a different behavior to initialize "pending" blocks,
 		/// you can copy your Core_initialize_block implementation.
 		fn initialize_pending_block(header: &<Block as BlockT>::Header);
+
+		/// Execute a call, or a contract creation when `to` is `None`, with `access_list` applied
+		/// and return the EIP-2930 access list of what it accessed, along with its execution info.
+		/// The sender, the recipient and the precompiles are left out of the list.
+		fn create_access_list(
+			from: Address,
+			to: Option<Address>,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(Address, Vec<H256>)>>,
+			authorization_list: Option<Vec<ethereum::AuthorizationListItem>>,
+			block_overrides: Option<fp_evm::BlockOverrides>,
+		) -> Result<
+			(Vec<(Address, Vec<H256>)>, fp_evm::ExecutionInfoV2::<Vec<u8>>),
+			sp_runtime::DispatchError,
+		>;
 	}
 
 	#[api_version(2)]
 	pub trait ConvertTransactionRuntimeApi {
-		fn convert_transaction(transaction: ethereum::TransactionV2) -> <Block as BlockT>::Extrinsic;
+		fn convert_transaction(transaction: ethereum::TransactionV3) -> <Block as BlockT>::Extrinsic;
 		#[changed_in(2)]
 		fn convert_transaction(transaction: ethereum::TransactionV0) -> <Block as BlockT>::Extrinsic;
 	}
+
+	pub trait EthereumStateProofApi {
+		/// Ethereum Merkle Patricia proof of an account and of the given storage slots against the
+		/// `state_root` of the Ethereum block, or `None` if the runtime does not maintain an
+		/// Ethereum state trie or the trie does not match the current state of the account.
+		fn account_proof(address: Address, storage_keys: Vec<H256>) -> Option<EthereumAccountProof>;
+	}
+
+	pub trait DebugRuntimeApi {
+		/// Initialize the block from `header`, apply `extrinsics` in order and return the trace of
+		/// the Ethereum transaction `transaction_hash` built by `tracer`.
+		fn trace_transaction(
+			header: &<Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			transaction_hash: H256,
+			tracer: fp_evm::TracerConfig,
+		) -> Result<fp_evm::TransactionTrace, sp_runtime::DispatchError>;
+
+		/// Initialize the block from `header`, apply `extrinsics` in order and return the traces of
+		/// all its Ethereum transactions built by `tracer`.
+		fn trace_block(
+			header: &<Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			tracer: fp_evm::TracerConfig,
+		) -> Result<Vec<fp_evm::TransactionTrace>, sp_runtime::DispatchError>;
+
+		/// Execute a call, or a contract creation when `to` is `None`, on top of the state of the
+		/// block and return its trace built by `tracer`.
+		fn trace_call(
+			from: Address,
+			to: Option<Address>,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(Address, Vec<H256>)>>,
+			authorization_list: Option<Vec<ethereum::AuthorizationListItem>>,
+			tracer: fp_evm::TracerConfig,
+			block_overrides: Option<fp_evm::BlockOverrides>,
+		) -> Result<fp_evm::TransactionTrace, sp_runtime::DispatchError>;
+	}
 }
 
 /// Fallback transaction converter when the `ConvertTransactionRuntimeApi` is not available. For almost all
 /// non-legacy cases, you can instantiate this type as `NoTransactionConverter`.
 pub trait ConvertTransaction<E> {
-	fn convert_transaction(&self, transaction: ethereum::TransactionV2) -> E;
+	fn convert_transaction(&self, transaction: ethereum::TransactionV3) -> E;
 }
 
 /// No fallback transaction converter is available.
@@ -285,7 +469,7 @@ pub enum NoTransactionConverter {}
 impl<E> ConvertTransaction<E> for NoTransactionConverter {
 	// `convert_transaction` is a method taking `&self` as a parameter, so it can only be called via an instance of type Self,
 	// so we are guaranteed at compile time that this method can never be called.
-	fn convert_transaction(&self, _transaction: ethereum::TransactionV2) -> E {
+	fn convert_transaction(&self, _transaction: ethereum::TransactionV3) -> E {
 		match *self {}
 	}
 }
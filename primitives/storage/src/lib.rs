@@ -57,4 +57,6 @@ pub enum EthereumStorageSchema {
 	V2,
 	#[codec(index = 3)]
 	V3,
+	#[codec(index = 4)]
+	V4,
 }
@@ -90,6 +90,27 @@ pub struct EthConfiguration {
 	/// Default value is 200MB.
 	#[arg(long, default_value = "209715200")]
 	pub frontier_sql_backend_cache_size: u64,
+
+	/// Index the calls of every transaction in the SQL backend, required by `trace_filter`.
+	#[arg(long)]
+	pub frontier_sql_backend_index_traces: bool,
+
+	/// Maximum number of blocks searched by a `trace_filter` request.
+	#[arg(long, default_value = "1000")]
+	pub max_trace_filter_block_range: u32,
+
+	/// Maximum number of traces returned by a `trace_filter` request.
+	#[arg(long, default_value = "500")]
+	pub max_trace_filter_count: u32,
+
+	/// Maximum number of storage keys proven by an `eth_getProof` request.
+	#[arg(long, default_value = "1024")]
+	pub max_proof_storage_keys: u32,
+
+	/// JSON ABIs, bare or in compiler artifacts, whose custom errors are decoded when an
+	/// eth_call/eth_estimateGas reverts.
+	#[arg(long)]
+	pub eth_revert_abi: Vec<PathBuf>,
 }
 
 pub struct FrontierPartialComponents {
@@ -113,6 +134,8 @@ pub trait EthCompatRuntimeApiCollection<Block: BlockT>:
 	sp_api::ApiExt<Block>
 	+ fp_rpc::ConvertTransactionRuntimeApi<Block>
 	+ fp_rpc::EthereumRuntimeRPCApi<Block>
+	+ fp_rpc::EthereumStateProofApi<Block>
+	+ fp_rpc::DebugRuntimeApi<Block>
 {
 }
 
@@ -121,7 +144,9 @@ where
 	Block: BlockT,
 	Api: sp_api::ApiExt<Block>
 		+ fp_rpc::ConvertTransactionRuntimeApi<Block>
-		+ fp_rpc::EthereumRuntimeRPCApi<Block>,
+		+ fp_rpc::EthereumRuntimeRPCApi<Block>
+		+ fp_rpc::EthereumStateProofApi<Block>
+		+ fp_rpc::DebugRuntimeApi<Block>,
 {
 }
 
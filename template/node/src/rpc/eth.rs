@@ -5,7 +5,7 @@ use jsonrpsee::RpcModule;
 use sc_client_api::{
 	backend::{Backend, StorageProvider},
 	client::BlockchainEvents,
-	AuxStore, UsageProvider,
+	AuxStore, BlockBackend, ProofProvider, UsageProvider,
 };
 use sc_network::service::traits::NetworkService;
 use sc_network_sync::SyncingService;
@@ -20,10 +20,13 @@ use sp_core::H256;
 use sp_inherents::CreateInherentDataProviders;
 use sp_runtime::traits::Block as BlockT;
 // Frontier
-pub use fc_rpc::{EthBlockDataCacheTask, EthConfig};
+pub use fc_rpc::{EthBlockDataCacheTask, EthConfig, RevertDecoder};
 pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
 use fc_storage::StorageOverride;
-use fp_rpc::{ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi};
+use fp_rpc::{
+	ConvertTransaction, ConvertTransactionRuntimeApi, DebugRuntimeApi, EthereumRuntimeRPCApi,
+	EthereumStateProofApi,
+};
 
 /// Extra dependencies for Ethereum compatibility.
 pub struct EthDeps<B: BlockT, C, P, A: ChainApi, CT, CIDP> {
@@ -53,6 +56,12 @@ pub struct EthDeps<B: BlockT, C, P, A: ChainApi, CT, CIDP> {
 	pub filter_pool: Option<FilterPool>,
 	/// Maximum number of logs in a query.
 	pub max_past_logs: u32,
+	/// Maximum number of blocks searched by a `trace_filter` request.
+	pub max_trace_filter_block_range: u32,
+	/// Maximum number of traces returned by a `trace_filter` request.
+	pub max_trace_filter_count: u32,
+	/// Maximum number of storage keys proven by an `eth_getProof` request.
+	pub max_proof_storage_keys: u32,
 	/// Fee history cache.
 	pub fee_history_cache: FeeHistoryCache,
 	/// Maximum fee history cache size.
@@ -64,6 +73,8 @@ pub struct EthDeps<B: BlockT, C, P, A: ChainApi, CT, CIDP> {
 	pub forced_parent_hashes: Option<BTreeMap<H256, H256>>,
 	/// Something that can create the inherent data providers for pending state
 	pub pending_create_inherent_data_providers: CIDP,
+	/// Decoder of the reverts of eth_call/eth_estimateGas.
+	pub revert_decoder: Arc<RevertDecoder>,
 }
 
 /// Instantiate Ethereum-compatible RPC extensions.
@@ -83,9 +94,12 @@ where
 	C::Api: AuraApi<B, AuraId>
 		+ BlockBuilderApi<B>
 		+ ConvertTransactionRuntimeApi<B>
-		+ EthereumRuntimeRPCApi<B>,
+		+ EthereumRuntimeRPCApi<B>
+		+ EthereumStateProofApi<B>
+		+ DebugRuntimeApi<B>,
 	C: HeaderBackend<B> + HeaderMetadata<B, Error = BlockChainError>,
 	C: BlockchainEvents<B> + AuxStore + UsageProvider<B> + StorageProvider<B, BE> + 'static,
+	C: BlockBackend<B> + ProofProvider<B>,
 	BE: Backend<B> + 'static,
 	P: TransactionPool<Block = B> + 'static,
 	A: ChainApi<Block = B> + 'static,
@@ -96,7 +110,7 @@ where
 	use fc_rpc::{
 		pending::AuraConsensusDataProvider, Debug, DebugApiServer, Eth, EthApiServer, EthDevSigner,
 		EthFilter, EthFilterApiServer, EthPubSub, EthPubSubApiServer, EthSigner, Net, NetApiServer,
-		Web3, Web3ApiServer,
+		Trace, TraceApiServer, Web3, Web3ApiServer,
 	};
 	#[cfg(feature = "txpool")]
 	use fc_rpc::{TxPool, TxPoolApiServer};
@@ -115,11 +129,15 @@ where
 		block_data_cache,
 		filter_pool,
 		max_past_logs,
+		max_trace_filter_block_range,
+		max_trace_filter_count,
+		max_proof_storage_keys,
 		fee_history_cache,
 		fee_history_cache_limit,
 		execute_gas_limit_multiplier,
 		forced_parent_hashes,
 		pending_create_inherent_data_providers,
+		revert_decoder,
 	} = deps;
 
 	let mut signers = Vec::new();
@@ -142,10 +160,12 @@ where
 			fee_history_cache,
 			fee_history_cache_limit,
 			execute_gas_limit_multiplier,
+			max_proof_storage_keys,
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			Some(Box::new(AuraConsensusDataProvider::new(client.clone()))),
 		)
+		.with_revert_decoder(revert_decoder)
 		.replace_config::<EC>()
 		.into_rpc(),
 	)?;
@@ -192,9 +212,20 @@ where
 	io.merge(
 		Debug::new(
 			client.clone(),
-			frontier_backend,
+			frontier_backend.clone(),
 			storage_override,
+			block_data_cache.clone(),
+		)
+		.into_rpc(),
+	)?;
+
+	io.merge(
+		Trace::new(
+			client.clone(),
+			frontier_backend,
 			block_data_cache,
+			max_trace_filter_block_range,
+			max_trace_filter_count,
 		)
 		.into_rpc(),
 	)?;
@@ -148,6 +148,7 @@ where
 					create_if_missing: true,
 					thread_count: eth_config.frontier_sql_backend_thread_count,
 					cache_size: eth_config.frontier_sql_backend_cache_size,
+					index_traces: eth_config.frontier_sql_backend_index_traces,
 				}),
 				eth_config.frontier_sql_backend_pool_size,
 				std::num::NonZeroU32::new(eth_config.frontier_sql_backend_num_ops_timeout),
@@ -411,7 +412,17 @@ where
 		let is_authority = role.is_authority();
 		let enable_dev_signer = eth_config.enable_dev_signer;
 		let max_past_logs = eth_config.max_past_logs;
+		let max_trace_filter_block_range = eth_config.max_trace_filter_block_range;
+		let max_trace_filter_count = eth_config.max_trace_filter_count;
+		let max_proof_storage_keys = eth_config.max_proof_storage_keys;
 		let execute_gas_limit_multiplier = eth_config.execute_gas_limit_multiplier;
+		let mut revert_decoder = fc_rpc::RevertDecoder::default();
+		for path in &eth_config.eth_revert_abi {
+			revert_decoder.register_abi_file(path).map_err(|err| {
+				ServiceError::Other(format!("Failed to load the ABI {}: {err}", path.display()))
+			})?;
+		}
+		let revert_decoder = Arc::new(revert_decoder);
 		let filter_pool = filter_pool.clone();
 		let frontier_backend = frontier_backend.clone();
 		let pubsub_notification_sinks = pubsub_notification_sinks.clone();
@@ -457,11 +468,15 @@ where
 				block_data_cache: block_data_cache.clone(),
 				filter_pool: filter_pool.clone(),
 				max_past_logs,
+				max_trace_filter_block_range,
+				max_trace_filter_count,
+				max_proof_storage_keys,
 				fee_history_cache: fee_history_cache.clone(),
 				fee_history_cache_limit,
 				execute_gas_limit_multiplier,
 				forced_parent_hashes: None,
 				pending_create_inherent_data_providers,
+				revert_decoder: revert_decoder.clone(),
 			};
 			let deps = crate::rpc::FullDeps {
 				client: client.clone(),
@@ -49,10 +49,11 @@ use sp_genesis_builder::PresetId;
 // Frontier
 use fp_account::EthereumSignature;
 use fp_evm::weight_per_gas;
-use fp_rpc::TransactionStatus;
+use fp_rpc::TransactionStatusV2;
 use pallet_ethereum::{Call::transact, PostLogContent, Transaction as EthereumTransaction};
 use pallet_evm::{
-	Account as EVMAccount, EnsureAccountId20, FeeCalculator, IdentityAddressMapping, Runner,
+	Account as EVMAccount, EnsureAccountId20, EvmConfig, FeeCalculator, IdentityAddressMapping,
+	Runner,
 };
 
 // A few exports that help ease life for downstream crates.
@@ -365,13 +366,20 @@ impl pallet_evm::Config for Runtime {
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
 	type OnCreate = ();
+	type OnStateChange = ();
 	type FindAuthor = FindAuthorTruncated<Aura>;
 	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
 	type SuicideQuickClearLimit = SuicideQuickClearLimit;
 	type Timestamp = Timestamp;
 	type WeightInfo = pallet_evm::weights::SubstrateWeight<Self>;
+
+	fn config() -> &'static EvmConfig {
+		&PECTRA_CONFIG
+	}
 }
 
+static PECTRA_CONFIG: EvmConfig = EvmConfig::pectra();
+
 parameter_types! {
 	pub const PostBlockAndTxnHashes: PostLogContent = PostLogContent::BlockAndTxnHashes;
 }
@@ -381,6 +389,7 @@ impl pallet_ethereum::Config for Runtime {
 	type StateRoot = pallet_ethereum::IntermediateStateRoot<Self::Version>;
 	type PostLogContent = PostBlockAndTxnHashes;
 	type ExtraDataLength = ConstU32<30>;
+	type RevertReasonLength = ConstU32<1024>;
 }
 
 parameter_types! {
@@ -578,6 +587,44 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 	}
 }
 
+/// Execute `f` with `tracer` attached to the EVM.
+///
+/// Tracing is only built with the `tracing` feature, meant for the runtime of tracing nodes and
+/// not for the on-chain one: without it, tracing requests fail.
+fn with_tracer<R>(
+	tracer: fp_evm::TracerConfig,
+	f: impl FnMut() -> R,
+	gas_used: impl FnOnce(&R) -> u64,
+) -> Result<(R, fp_evm::TransactionTrace), sp_runtime::DispatchError> {
+	#[cfg(feature = "tracing")]
+	{
+		Ok(pallet_evm::runner::tracing::trace::<Runtime, _>(
+			tracer, f, gas_used,
+		))
+	}
+	#[cfg(not(feature = "tracing"))]
+	{
+		let _ = (tracer, f, gas_used);
+		Err(sp_runtime::DispatchError::Other(
+			"Tracing is not enabled in this runtime",
+		))
+	}
+}
+
+/// Apply an Ethereum transaction extrinsic with `tracer` attached to the EVM.
+fn trace_extrinsic(
+	extrinsic: <Block as BlockT>::Extrinsic,
+	tracer: fp_evm::TracerConfig,
+) -> Result<fp_evm::TransactionTrace, sp_runtime::DispatchError> {
+	let (result, trace) = with_tracer(
+		tracer,
+		|| Executive::apply_extrinsic(extrinsic.clone()),
+		|_| Ethereum::last_transaction_gas_used().unique_saturated_into(),
+	)?;
+	result.map_err(|_| sp_runtime::DispatchError::Other("Invalid transaction"))?;
+	Ok(trace)
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benches {
 	frame_benchmarking::define_benchmarks!(
@@ -795,11 +842,13 @@ impl_runtime_apis! {
 			nonce: Option<U256>,
 			estimate: bool,
 			access_list: Option<Vec<(H160, Vec<H256>)>>,
+			authorization_list: Option<Vec<pallet_ethereum::AuthorizationListItem>>,
+			block_overrides: Option<pallet_evm::BlockOverrides>,
 		) -> Result<pallet_evm::CallInfo, sp_runtime::DispatchError> {
 			use pallet_evm::GasWeightMapping as _;
 
 			let config = if estimate {
-				let mut config = <Runtime as pallet_evm::Config>::config().clone();
+				let mut config = pallet_evm::Pallet::<Runtime>::evm_config().clone();
 				config.estimate = true;
 				Some(config)
 			} else {
@@ -827,6 +876,10 @@ impl_runtime_apis! {
 				estimated_transaction_len += access_list.encoded_size();
 			}
 
+			if authorization_list.is_some() {
+				estimated_transaction_len += authorization_list.encoded_size();
+			}
+
 
 			let gas_limit = if gas_limit > U256::from(u64::MAX) {
 				u64::MAX
@@ -846,22 +899,28 @@ impl_runtime_apis! {
 					_ => (None, None),
 				};
 
-			<Runtime as pallet_evm::Config>::Runner::call(
-				from,
-				to,
-				data,
-				value,
-				gas_limit.unique_saturated_into(),
-				max_fee_per_gas,
-				max_priority_fee_per_gas,
-				nonce,
-				access_list.unwrap_or_default(),
-				false,
-				true,
-				weight_limit,
-				proof_size_base_cost,
-				config.as_ref().unwrap_or(<Runtime as pallet_evm::Config>::config()),
-			).map_err(|err| err.error.into())
+			pallet_evm::runner::with_block_overrides(block_overrides.unwrap_or_default(), || {
+				<Runtime as pallet_evm::Config>::Runner::call(
+					from,
+					to,
+					data,
+					value,
+					gas_limit.unique_saturated_into(),
+					max_fee_per_gas,
+					max_priority_fee_per_gas,
+					nonce,
+					access_list.unwrap_or_default(),
+					authorization_list
+						.as_deref()
+						.map(fp_ethereum::recover_authorization_list)
+						.unwrap_or_default(),
+					false,
+					true,
+					weight_limit,
+					proof_size_base_cost,
+					config.as_ref().unwrap_or(pallet_evm::Pallet::<Runtime>::evm_config()),
+				)
+			}).map_err(|err| err.error.into())
 		}
 
 		fn create(
@@ -874,11 +933,12 @@ impl_runtime_apis! {
 			nonce: Option<U256>,
 			estimate: bool,
 			access_list: Option<Vec<(H160, Vec<H256>)>>,
+			block_overrides: Option<pallet_evm::BlockOverrides>,
 		) -> Result<pallet_evm::CreateInfo, sp_runtime::DispatchError> {
 			use pallet_evm::GasWeightMapping as _;
 
 			let config = if estimate {
-				let mut config = <Runtime as pallet_evm::Config>::config().clone();
+				let mut config = pallet_evm::Pallet::<Runtime>::evm_config().clone();
 				config.estimate = true;
 				Some(config)
 			} else {
@@ -925,24 +985,26 @@ impl_runtime_apis! {
 					_ => (None, None),
 				};
 
-			<Runtime as pallet_evm::Config>::Runner::create(
-				from,
-				data,
-				value,
-				gas_limit.unique_saturated_into(),
-				max_fee_per_gas,
-				max_priority_fee_per_gas,
-				nonce,
-				access_list.unwrap_or_default(),
-				false,
-				true,
-				weight_limit,
-				proof_size_base_cost,
-				config.as_ref().unwrap_or(<Runtime as pallet_evm::Config>::config()),
-			).map_err(|err| err.error.into())
+			pallet_evm::runner::with_block_overrides(block_overrides.unwrap_or_default(), || {
+				<Runtime as pallet_evm::Config>::Runner::create(
+					from,
+					data,
+					value,
+					gas_limit.unique_saturated_into(),
+					max_fee_per_gas,
+					max_priority_fee_per_gas,
+					nonce,
+					access_list.unwrap_or_default(),
+					false,
+					true,
+					weight_limit,
+					proof_size_base_cost,
+					config.as_ref().unwrap_or(pallet_evm::Pallet::<Runtime>::evm_config()),
+				)
+			}).map(|(info, _)| info).map_err(|err| err.error.into())
 		}
 
-		fn current_transaction_statuses() -> Option<Vec<TransactionStatus>> {
+		fn current_transaction_statuses() -> Option<Vec<TransactionStatusV2>> {
 			pallet_ethereum::CurrentTransactionStatuses::<Runtime>::get()
 		}
 
@@ -957,7 +1019,7 @@ impl_runtime_apis! {
 		fn current_all() -> (
 			Option<pallet_ethereum::Block>,
 			Option<Vec<pallet_ethereum::Receipt>>,
-			Option<Vec<TransactionStatus>>
+			Option<Vec<TransactionStatusV2>>
 		) {
 			(
 				pallet_ethereum::CurrentBlock::<Runtime>::get(),
@@ -983,7 +1045,7 @@ impl_runtime_apis! {
 
 		fn pending_block(
 			xts: Vec<<Block as BlockT>::Extrinsic>,
-		) -> (Option<pallet_ethereum::Block>, Option<Vec<TransactionStatus>>) {
+		) -> (Option<pallet_ethereum::Block>, Option<Vec<TransactionStatusV2>>) {
 			for ext in xts.into_iter() {
 				let _ = Executive::apply_extrinsic(ext);
 			}
@@ -999,6 +1061,81 @@ impl_runtime_apis! {
 		fn initialize_pending_block(header: &<Block as BlockT>::Header) {
 			Executive::initialize_block(header);
 		}
+
+		fn create_access_list(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+			authorization_list: Option<Vec<pallet_ethereum::AuthorizationListItem>>,
+			block_overrides: Option<pallet_evm::BlockOverrides>,
+		) -> Result<(Vec<(H160, Vec<H256>)>, pallet_evm::CallInfo), sp_runtime::DispatchError> {
+			use fp_rpc::runtime_decl_for_ethereum_runtime_rpc_api::EthereumRuntimeRPCApi;
+
+			let (result, mut accesses) = pallet_evm::runner::record_accesses::<Runtime, _>(|| {
+				match to {
+					Some(to) => <Runtime as EthereumRuntimeRPCApi<Block>>::call(
+						from,
+						to,
+						data,
+						value,
+						gas_limit,
+						max_fee_per_gas,
+						max_priority_fee_per_gas,
+						nonce,
+						false,
+						access_list.clone(),
+						authorization_list,
+						block_overrides,
+					)
+					.map(|info| (to, info)),
+					None => <Runtime as EthereumRuntimeRPCApi<Block>>::create(
+						from,
+						data,
+						value,
+						gas_limit,
+						max_fee_per_gas,
+						max_priority_fee_per_gas,
+						nonce,
+						false,
+						access_list.clone(),
+						block_overrides,
+					)
+					.map(|info| {
+						let info_without_address = pallet_evm::CallInfo {
+							exit_reason: info.exit_reason,
+							value: Vec::new(),
+							used_gas: info.used_gas,
+							weight_info: info.weight_info,
+							logs: info.logs,
+						};
+						(info.value, info_without_address)
+					}),
+				}
+			});
+			let (to, info) = result?;
+
+			// The given entries stay in the list. The sender and the recipient are warm anyway, so
+			// they are only listed for their storage keys.
+			for (address, storage_keys) in access_list.unwrap_or_default() {
+				accesses.entry(address).or_default().extend(storage_keys);
+			}
+			for address in [from, to] {
+				if accesses.get(&address).is_some_and(|storage_keys| storage_keys.is_empty()) {
+					accesses.remove(&address);
+				}
+			}
+			let access_list = accesses
+				.into_iter()
+				.map(|(address, storage_keys)| (address, storage_keys.into_iter().collect()))
+				.collect();
+			Ok((access_list, info))
+		}
 	}
 
 	impl fp_rpc::ConvertTransactionRuntimeApi<Block> for Runtime {
@@ -1009,6 +1146,119 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl fp_rpc::EthereumStateProofApi<Block> for Runtime {
+		fn account_proof(
+			_address: H160,
+			_storage_keys: Vec<H256>,
+		) -> Option<fp_rpc::EthereumAccountProof> {
+			// The template keeps the default `pallet_ethereum` state root. Runtimes using
+			// `pallet-evm-state-root` return `EVMStateRoot::account_proof(..)`.
+			None
+		}
+	}
+
+	impl fp_rpc::DebugRuntimeApi<Block> for Runtime {
+		fn trace_transaction(
+			header: &<Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			transaction_hash: H256,
+			tracer: fp_evm::TracerConfig,
+		) -> Result<fp_evm::TransactionTrace, sp_runtime::DispatchError> {
+			Executive::initialize_block(header);
+
+			for ext in extrinsics.into_iter() {
+				let is_traced = matches!(
+					&ext.0.function,
+					RuntimeCall::Ethereum(transact { transaction }) if transaction.hash() == transaction_hash
+				);
+				if is_traced {
+					return trace_extrinsic(ext, tracer);
+				}
+				let _ = Executive::apply_extrinsic(ext);
+			}
+
+			Err(sp_runtime::DispatchError::Other("Transaction not found in block"))
+		}
+
+		fn trace_block(
+			header: &<Block as BlockT>::Header,
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			tracer: fp_evm::TracerConfig,
+		) -> Result<Vec<fp_evm::TransactionTrace>, sp_runtime::DispatchError> {
+			Executive::initialize_block(header);
+
+			let mut traces = Vec::new();
+			for ext in extrinsics.into_iter() {
+				if matches!(&ext.0.function, RuntimeCall::Ethereum(transact { .. })) {
+					traces.push(trace_extrinsic(ext, tracer)?);
+				} else {
+					let _ = Executive::apply_extrinsic(ext);
+				}
+			}
+
+			Ok(traces)
+		}
+
+		fn trace_call(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+			authorization_list: Option<Vec<pallet_ethereum::AuthorizationListItem>>,
+			tracer: fp_evm::TracerConfig,
+			block_overrides: Option<pallet_evm::BlockOverrides>,
+		) -> Result<fp_evm::TransactionTrace, sp_runtime::DispatchError> {
+			use fp_rpc::runtime_decl_for_ethereum_runtime_rpc_api::EthereumRuntimeRPCApi;
+
+			let (result, trace) = with_tracer(
+				tracer,
+				|| match to {
+					Some(to) => <Runtime as EthereumRuntimeRPCApi<Block>>::call(
+						from,
+						to,
+						data.clone(),
+						value,
+						gas_limit,
+						max_fee_per_gas,
+						max_priority_fee_per_gas,
+						nonce,
+						false,
+						access_list.clone(),
+						authorization_list.clone(),
+						block_overrides.clone(),
+					)
+					.map(|info| info.used_gas.standard),
+					None => <Runtime as EthereumRuntimeRPCApi<Block>>::create(
+						from,
+						data.clone(),
+						value,
+						gas_limit,
+						max_fee_per_gas,
+						max_priority_fee_per_gas,
+						nonce,
+						false,
+						access_list.clone(),
+						block_overrides.clone(),
+					)
+					.map(|info| info.used_gas.standard),
+				},
+				|result| {
+					result
+						.as_ref()
+						.map(|used_gas| used_gas.unique_saturated_into())
+						.unwrap_or_default()
+				},
+			)?;
+			result?;
+			Ok(trace)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -39,7 +39,7 @@ describeWithFrontier("Frontier RPC (Revert Reason)", (context) => {
 			await contract.methods.max10(30).call();
 		} catch (error) {
 			expect(error.message).to.be.eq(
-				"Returned error: VM Exception while processing transaction: revert Value must not be greater than 10."
+				"Returned error: execution reverted: Value must not be greater than 10."
 			);
 		}
 	});